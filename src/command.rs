@@ -8,35 +8,68 @@ use crate::{
     resolve::{localize_iri, strict_resolve_iri},
 };
 
+use curie::PrefixMapping;
+
 use std::{
     fs::File,
-    io::{BufReader, Write},
+    io::{BufRead, BufReader, Write},
     path::Path,
 };
 
+/// Return the type of the resource at `path` from its extension.
+///
+/// With the `gzip` feature, a `.gz` suffix is ignored, so that
+/// `foo.owl.gz` is RDF.
 pub fn path_type(path: &Path) -> Option<ResourceType> {
     match path.extension().and_then(|s| s.to_str()) {
         Some("owx") => Some(ResourceType::OWX),
         Some("owl") => Some(ResourceType::RDF),
+        Some("nt") => Some(ResourceType::NTriples),
+        #[cfg(feature = "gzip")]
+        Some("gz") => path.file_stem().and_then(|s| path_type(Path::new(s))),
         _ => None,
     }
 }
 
+fn is_gzip(path: &Path) -> bool {
+    cfg!(feature = "gzip") && path.extension().map_or(false, |s| s == "gz")
+}
+
+/// Open the file at `path`, decompressing it if it is gzipped.
+#[cfg(feature = "gzip")]
+fn open_path(path: &Path) -> Result<Box<dyn BufRead>, HornedError> {
+    super::io::gzip::decompress(BufReader::new(File::open(path)?))
+}
+
+#[cfg(not(feature = "gzip"))]
+fn open_path(path: &Path) -> Result<Box<dyn BufRead>, HornedError> {
+    Ok(Box::new(BufReader::new(File::open(path)?)))
+}
+
 pub fn parse_path(
     path: &Path,
     config: ParserConfiguration
 ) -> Result<ParserOutput<RcStr, RcAnnotatedAxiom>, HornedError> {
     Ok(match path_type(path) {
         Some(ResourceType::OWX) => {
-            let file = File::open(&path)?;
-            let mut bufreader = BufReader::new(file);
+            let mut bufreader = open_path(path)?;
             super::io::owx::reader::read(&mut bufreader, config)?.into()
         }
+        // The closure reader reads imports by IRI, so cannot
+        // decompress the ontology itself
+        Some(ResourceType::RDF) if is_gzip(path) => {
+            let mut bufreader = open_path(path)?;
+            super::io::rdf::reader::read(&mut bufreader, config)?.into()
+        }
         Some(ResourceType::RDF) => {
             let b = Build::new();
             let iri = super::resolve::path_to_file_iri(&b, path);
             super::io::rdf::closure_reader::read(&iri, config)?.into()
         }
+        Some(ResourceType::NTriples) => {
+            let so = super::io::rdf::ntriples::read_ntriples(open_path(path)?, &Build::new())?;
+            (so, PrefixMapping::default()).into()
+        }
         None => {
             return Err(HornedError::CommandError(format!(
                 "Cannot parse a file of this format: {:?}",
@@ -51,8 +84,7 @@ pub fn parse_imports(
     path: &Path,
     config: ParserConfiguration
 ) -> Result<ParserOutput<RcStr, RcAnnotatedAxiom>, HornedError> {
-    let file = File::open(&path)?;
-    let mut bufreader = BufReader::new(file);
+    let mut bufreader = open_path(path)?;
     Ok(match path_type(path) {
         Some(ResourceType::OWX) => super::io::owx::reader::read(&mut bufreader, config)?.into(),
         Some(ResourceType::RDF) => {
@@ -61,6 +93,10 @@ pub fn parse_imports(
             p.parse_imports()?;
             p.as_ontology_and_incomplete()?.into()
         }
+        Some(ResourceType::NTriples) => {
+            let so = super::io::rdf::ntriples::read_ntriples(bufreader, &Build::new())?;
+            (so, PrefixMapping::default()).into()
+        }
         None => {
             return Err(HornedError::CommandError(format!(
                 "Cannot parse a file of this format: {:?}",
@@ -83,7 +119,7 @@ pub fn materialize_1<'a>(
     recurse: bool,
 ) -> Result<&'a mut Vec<IRI<RcStr>>, HornedError> {
     println!("Parsing: {}", input);
-    let amont: RcAxiomMappedOntology = parse_imports(Path::new(input), config)?.into();
+    let amont: RcAxiomMappedOntology = parse_imports(Path::new(input), config.clone())?.into();
     let import = amont.i().import();
 
     let b = Build::new_rc();
@@ -104,7 +140,7 @@ pub fn materialize_1<'a>(
                 println!("Already Present: {}", local);
             }
             if recurse {
-                materialize_1(&local, config, done, true)?;
+                materialize_1(&local, config.clone(), done, true)?;
             }
         } else {
             println!("Already materialized: {}", &i.0);
@@ -232,7 +268,8 @@ pub mod config {
     pub fn parser_config(matches: &ArgMatches) -> ParserConfiguration {
         ParserConfiguration{
             rdf: RDFParserConfiguration {
-                lax: !matches.get_one::<bool>("strict").unwrap_or(&false)
+                lax: !matches.get_one::<bool>("strict").unwrap_or(&false),
+                ..Default::default()
             },
             ..Default::default()
         }
@@ -6,6 +6,7 @@ use thiserror::Error;
 #[derive(Debug)]
 pub enum Location {
     BytePosition(usize),
+    Line(usize),
     Unknown
 }
 
@@ -19,6 +20,7 @@ impl Display for Location {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::BytePosition(u) => write!(f, "Byte Position: {}", u),
+            Self::Line(u) => write!(f, "Line: {}", u),
             Self::Unknown => write!(f, "Unknown")
         }
     }
@@ -40,6 +42,35 @@ pub enum HornedError {
     #[error("Validity Error: {0} at {1}")]
     ValidityError(String, Location),
 
+    /// Data has been given that is valid but uses a construct that
+    /// Horned-OWL does not support
+    #[error("Unsupported Construct: {0} at {1}")]
+    UnsupportedConstruct(String, Location),
+
+    /// A facet restriction uses a facet which is not one of those
+    /// defined by OWL
+    #[error("Unknown Facet: {0} at {1}")]
+    UnknownFacet(String, Location),
+
+    /// Elements are nested more deeply than the parser allows
+    #[error("Maximum nesting depth of {0} exceeded at {1}")]
+    DepthExceeded(usize, Location),
+
+    /// An RDF list is missing `rdf:first` or `rdf:rest`, does not
+    /// end in `rdf:nil`, or is cyclic
+    #[error("Malformed List: {0} at {1}")]
+    MalformedList(String, Location),
+
+    /// The facets of a datatype restriction admit no values or
+    /// contradict each other
+    #[error("Inconsistent Facets: {0}")]
+    InconsistentFacets(String),
+
+    /// A patch could not be applied, because it inserts an axiom
+    /// which is already present or removes one which is not
+    #[error("Patch Error: {0}")]
+    PatchError(String),
+
     /// A command has been given that is invalid
     #[error("Command Error: {0}")]
     CommandError(String),
@@ -75,3 +106,35 @@ impl From<rio_xml::RdfXmlError> for HornedError {
         Self::ParserError(e.into(), Location::Unknown)
     }
 }
+
+#[cfg(feature = "persist")]
+impl From<bincode::Error> for HornedError {
+    fn from(e: bincode::Error) -> Self {
+        match *e {
+            bincode::ErrorKind::Io(e) => Self::IOError(e),
+            e => Self::ParserError(Box::new(e), Location::Unknown),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate mktemp;
+
+    use self::mktemp::Temp;
+    use super::*;
+    use crate::command::parse_path;
+
+    #[test]
+    fn io_and_parse_errors() {
+        let dir = Temp::new_dir().unwrap();
+
+        let io = parse_path(&dir.as_ref().join("missing.owx"), Default::default());
+        assert!(matches!(io, Err(HornedError::IOError(_))));
+
+        let path = dir.as_ref().join("broken.owx");
+        std::fs::write(&path, "<Ontology><Declaration></Ontology>").unwrap();
+        let parse = parse_path(&path, Default::default());
+        assert!(matches!(parse, Err(HornedError::ParserError(_, _))));
+    }
+}
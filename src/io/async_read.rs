@@ -0,0 +1,76 @@
+//! Reading of ontologies from an asynchronous source
+
+//! # Overview
+//!
+//! The RDF/XML parser is synchronous, so it cannot consume an
+//! `AsyncBufRead` directly. Instead, `read_async` reads the whole of
+//! the input into memory, yielding to the executor while it waits
+//! for each chunk, and then parses it from the buffer. Only the
+//! parse itself blocks; for very large ontologies, consider moving
+//! the result of reading into `tokio::task::spawn_blocking` instead.
+use tokio::io::{AsyncBufRead, AsyncReadExt};
+
+use std::rc::Rc;
+
+use crate::error::HornedError;
+use crate::io::rdf::reader::read_with_build;
+use crate::io::ParserConfiguration;
+use crate::model::{AnnotatedAxiom, Build, ForIRI};
+use crate::ontology::set::SetOntology;
+
+/// Read an RDF/XML ontology from `bufread`.
+///
+/// Triples which cannot be parsed are ignored, as with
+/// [`read_with_build`](../rdf/reader/fn.read_with_build.html).
+///
+/// # Examples
+/// ```
+/// # use horned_owl::io::async_read::read_async;
+/// # use horned_owl::model::*;
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let ont_s = r#"<?xml version="1.0"?>
+/// <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+///          xmlns:owl="http://www.w3.org/2002/07/owl#">
+///     <owl:Ontology rdf:about="http://www.example.com/o"/>
+/// </rdf:RDF>"#;
+/// let b = Build::new_rc();
+/// let o = read_async(ont_s.as_bytes(), &b, Default::default()).await.unwrap();
+/// assert_eq!(o.id().iri, Some(b.iri("http://www.example.com/o")));
+/// # });
+/// ```
+pub async fn read_async<A: ForIRI, R: AsyncBufRead + Unpin>(
+    mut bufread: R,
+    build: &Build<A>,
+    config: ParserConfiguration,
+) -> Result<SetOntology<A>, HornedError> {
+    let mut buf = vec![];
+    bufread.read_to_end(&mut buf).await?;
+
+    let (o, _) =
+        read_with_build::<A, Rc<AnnotatedAxiom<A>>, _>(&mut buf.as_slice(), build, config)?;
+    Ok(o.into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::io::rdf::reader::read;
+    use crate::model::{Ontology, RcStr};
+
+    use tokio::io::BufReader;
+
+    #[tokio::test]
+    async fn test_read_async() {
+        let ont_s = include_str!("../ont/owl-rdf/class.owl");
+        let b = Build::new_rc();
+
+        // A small buffer, so that the input is read in many chunks
+        let r = BufReader::with_capacity(16, ont_s.as_bytes());
+        let o = read_async(r, &b, Default::default()).await.unwrap();
+
+        let (ont, _) = read(&mut ont_s.as_bytes(), Default::default()).unwrap();
+        let ont: SetOntology<RcStr> = ont.into();
+        assert!(o.id().iri.is_some());
+        assert_eq!(o, ont);
+    }
+}
@@ -0,0 +1,130 @@
+//! Conversion of XML documents to UTF-8 before parsing.
+//!
+//! Both XML readers expect UTF-8. A document may instead start with a
+//! byte order mark, be encoded in UTF-16, or declare ISO-8859-1 in
+//! its XML declaration; these are detected here and rewritten to
+//! UTF-8, with the declaration changed to match.
+use std::io::BufRead;
+use std::ops::Range;
+
+use crate::error::HornedError;
+
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+
+#[derive(Debug, PartialEq)]
+enum XmlEncoding {
+    Utf8,
+    Utf8Bom,
+    Utf16Le,
+    Utf16Be,
+    Latin1,
+}
+
+/// Return the position of the value of the `encoding` pseudo
+/// attribute in the XML declaration at the start of `head`, if there
+/// is one.
+fn declared_encoding(head: &[u8]) -> Option<Range<usize>> {
+    if !head.starts_with(b"<?xml") {
+        return None;
+    }
+    let end = head.windows(2).position(|w| w == b"?>")?;
+    let decl = &head[..end];
+    let start = decl.windows(8).position(|w| w == b"encoding")? + 8;
+    let eq = start + decl[start..].iter().position(|&c| c == b'=')?;
+    let open = eq + decl[eq..].iter().position(|&c| c == b'"' || c == b'\'')?;
+    let close = open + 1 + decl[open + 1..].iter().position(|&c| c == decl[open])?;
+    Some(open + 1..close)
+}
+
+fn xml_encoding(head: &[u8]) -> XmlEncoding {
+    match head {
+        [0xEF, 0xBB, 0xBF, ..] => XmlEncoding::Utf8Bom,
+        [0xFF, 0xFE, ..] | [b'<', 0, b'?', 0, ..] => XmlEncoding::Utf16Le,
+        [0xFE, 0xFF, ..] | [0, b'<', 0, b'?', ..] => XmlEncoding::Utf16Be,
+        _ => match declared_encoding(head).map(|r| head[r].to_ascii_lowercase()) {
+            Some(ref e)
+                if ["iso-8859-1", "iso_8859-1", "latin1", "latin-1", "l1"]
+                    .iter()
+                    .any(|l| l.as_bytes() == e.as_slice()) =>
+            {
+                XmlEncoding::Latin1
+            }
+            _ => XmlEncoding::Utf8,
+        },
+    }
+}
+
+fn utf16(bytes: &[u8], f: fn([u8; 2]) -> u16) -> String {
+    let units: Vec<u16> = bytes.chunks_exact(2).map(|c| f([c[0], c[1]])).collect();
+    let s = String::from_utf16_lossy(&units);
+    match s.strip_prefix('\u{FEFF}') {
+        Some(s) => s.to_string(),
+        None => s,
+    }
+}
+
+/// Convert the XML document in `bytes` to UTF-8.
+///
+/// Characters which cannot be decoded are replaced with U+FFFD.
+pub(crate) fn xml_to_utf8(bytes: Vec<u8>) -> Vec<u8> {
+    let mut s = match xml_encoding(&bytes) {
+        XmlEncoding::Utf8 => return bytes,
+        XmlEncoding::Utf8Bom => return bytes[UTF8_BOM.len()..].to_vec(),
+        XmlEncoding::Utf16Le => utf16(&bytes, u16::from_le_bytes),
+        XmlEncoding::Utf16Be => utf16(&bytes, u16::from_be_bytes),
+        XmlEncoding::Latin1 => bytes.iter().map(|&b| b as char).collect(),
+    };
+    if let Some(r) = declared_encoding(s.as_bytes()) {
+        s.replace_range(r, "UTF-8");
+    }
+    s.into_bytes()
+}
+
+/// Prepare `bufread` to be read as UTF-8 XML.
+///
+/// A UTF-8 byte order mark is consumed and `None` returned, as is
+/// `None` for a document which needs no conversion, so that it can
+/// still be streamed from `bufread`. Otherwise, the whole document
+/// is read and returned converted.
+pub(crate) fn utf8_xml<R: BufRead>(bufread: &mut R) -> Result<Option<Vec<u8>>, HornedError> {
+    match xml_encoding(bufread.fill_buf()?) {
+        XmlEncoding::Utf8 => Ok(None),
+        XmlEncoding::Utf8Bom => {
+            bufread.consume(UTF8_BOM.len());
+            Ok(None)
+        }
+        _ => {
+            let mut buf = vec![];
+            bufread.read_to_end(&mut buf)?;
+            Ok(Some(xml_to_utf8(buf)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_xml_to_utf8() {
+        let doc = "<?xml version=\"1.0\"?><a>é</a>";
+        let mut bom = UTF8_BOM.to_vec();
+        bom.extend_from_slice(doc.as_bytes());
+        assert_eq!(xml_to_utf8(bom), doc.as_bytes());
+
+        let mut le = vec![0xFF, 0xFE];
+        let mut be = vec![0xFE, 0xFF];
+        for u in doc.encode_utf16() {
+            le.extend_from_slice(&u.to_le_bytes());
+            be.extend_from_slice(&u.to_be_bytes());
+        }
+        assert_eq!(xml_to_utf8(le), doc.as_bytes());
+        assert_eq!(xml_to_utf8(be), doc.as_bytes());
+
+        let latin1 = b"<?xml version='1.0' encoding='ISO-8859-1'?><a>\xE9</a>".to_vec();
+        assert_eq!(
+            xml_to_utf8(latin1),
+            "<?xml version='1.0' encoding='UTF-8'?><a>é</a>".as_bytes()
+        );
+    }
+}
@@ -0,0 +1,96 @@
+//! Transparent reading of gzipped ontologies
+use std::io::{BufRead, BufReader};
+
+use flate2::bufread::GzDecoder;
+
+use crate::error::HornedError;
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Return a reader over the content of `bufread`, decompressing it
+/// if it starts with the gzip magic number.
+///
+/// The result can be passed to any of the parsers.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::io::gzip::decompress;
+/// # use std::io::Read;
+/// let mut plain = decompress("<Ontology/>".as_bytes()).unwrap();
+/// let mut s = String::new();
+/// plain.read_to_string(&mut s).unwrap();
+/// assert_eq!(s, "<Ontology/>");
+/// ```
+pub fn decompress<'a, R: BufRead + 'a>(
+    mut bufread: R,
+) -> Result<Box<dyn BufRead + 'a>, HornedError> {
+    if bufread.fill_buf()?.starts_with(&GZIP_MAGIC) {
+        Ok(Box::new(BufReader::new(GzDecoder::new(bufread))))
+    } else {
+        Ok(Box::new(bufread))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::command::{parse_path, path_type};
+    use crate::io::rdf::reader::read;
+    use crate::io::ResourceType;
+    use crate::model::{Ontology, RcStr};
+    use crate::ontology::set::SetOntology;
+
+    use flate2::{write::GzEncoder, Compression};
+    use std::io::Write;
+
+    #[test]
+    fn test_read_gzipped() {
+        let ont_s = include_str!("../ont/owl-rdf/class.owl");
+        let mut e = GzEncoder::new(Vec::new(), Compression::default());
+        e.write_all(ont_s.as_bytes()).unwrap();
+        let gz = e.finish().unwrap();
+        assert_ne!(gz.as_slice(), ont_s.as_bytes());
+
+        let (gz_ont, incomplete) =
+            read(&mut decompress(gz.as_slice()).unwrap(), Default::default()).unwrap();
+        assert!(incomplete.is_complete());
+
+        let (ont, _) = read(&mut ont_s.as_bytes(), Default::default()).unwrap();
+        let gz_ont: SetOntology<RcStr> = gz_ont.into();
+        let ont: SetOntology<RcStr> = ont.into();
+        assert_eq!(gz_ont, ont);
+    }
+
+    #[test]
+    fn test_parse_path() {
+        let dir = mktemp::Temp::new_dir().unwrap();
+        let path = dir.as_path().join("class.owl.gz");
+        let ont_s = include_str!("../ont/owl-rdf/class.owl");
+        let mut e = GzEncoder::new(
+            std::fs::File::create(&path).unwrap(),
+            Compression::default(),
+        );
+        e.write_all(ont_s.as_bytes()).unwrap();
+        e.finish().unwrap();
+
+        assert!(matches!(path_type(&path), Some(ResourceType::RDF)));
+        let gz_ont: SetOntology<RcStr> = parse_path(&path, Default::default()).unwrap().into();
+        let (ont, _) = read(&mut ont_s.as_bytes(), Default::default()).unwrap();
+        let ont: SetOntology<RcStr> = ont.into();
+        assert_eq!(gz_ont, ont);
+    }
+
+    #[test]
+    fn test_read_plain() {
+        let ont_s = include_str!("../ont/owl-rdf/class.owl");
+        let (ont, incomplete) = read(
+            &mut decompress(ont_s.as_bytes()).unwrap(),
+            Default::default(),
+        )
+        .unwrap();
+        assert!(incomplete.is_complete());
+
+        let ont: SetOntology<RcStr> = ont.into();
+        assert!(ont.id().iri.is_some());
+    }
+}
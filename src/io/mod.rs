@@ -1,20 +1,112 @@
 //! Parsers and Renders for OWL Ontologies
 
+#[cfg(feature = "tokio")]
+pub mod async_read;
+mod encoding;
+#[cfg(feature = "gzip")]
+pub mod gzip;
 pub mod owx;
 pub mod rdf;
 
+use std::io::{BufRead, Write};
+use std::sync::Arc;
+
 use curie::PrefixMapping;
+use rio_api::parser::TriplesParser;
 
+use self::encoding::utf8_xml;
 use self::rdf::reader::{IncompleteParse, RDFOntology};
+use crate::error::HornedError;
 use crate::ontology::indexed::ForIndex;
 use crate::{
-    model::ForIRI,
+    model::{Build, ForIRI, RcAnnotatedAxiom, RcStr},
     ontology::{axiom_mapped::AxiomMappedOntology, set::SetOntology},
 };
 
 pub enum ResourceType {
     OWX,
     RDF,
+    NTriples,
+}
+
+/// Convert a document from one format to another.
+///
+/// RDF/XML is converted to N-Triples one triple at a time, without
+/// building an ontology, so that large documents can be converted
+/// without holding them in memory. The triples are copied as they
+/// are, including any which do not form part of an OWL axiom, and
+/// blank nodes may be labelled differently from the output of
+/// [`write_ntriples`](rdf/ntriples/fn.write_ntriples.html).
+/// N-Triples is copied unchanged.
+///
+/// Every other conversion reads the whole document into an ontology
+/// before writing it. Writing RDF/XML always requires this, because
+/// the lists and nested blank nodes of an axiom can only be written
+/// once all of its triples are known.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::io::{convert, ResourceType};
+/// let rdfxml = r#"<?xml version="1.0"?>
+/// <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+///          xmlns:owl="http://www.w3.org/2002/07/owl#">
+///     <owl:Ontology rdf:about="http://www.example.com/o"/>
+/// </rdf:RDF>"#;
+///
+/// let mut nt = vec![];
+/// convert(rdfxml.as_bytes(), &mut nt, ResourceType::RDF, ResourceType::NTriples).unwrap();
+/// assert_eq!(
+///     String::from_utf8(nt).unwrap(),
+///     "<http://www.example.com/o> \
+///      <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> \
+///      <http://www.w3.org/2002/07/owl#Ontology> .\n"
+/// );
+/// ```
+pub fn convert<R: BufRead, W: Write>(
+    mut r: R,
+    w: &mut W,
+    from: ResourceType,
+    to: ResourceType,
+) -> Result<(), HornedError> {
+    match (&from, &to) {
+        (ResourceType::RDF, ResourceType::NTriples) => {
+            let mut on_triple = |t: rio_api::model::Triple| -> Result<(), HornedError> {
+                writeln!(w, "{} .", t)?;
+                Ok(())
+            };
+            match utf8_xml(&mut r)? {
+                Some(buf) => {
+                    rio_xml::RdfXmlParser::new(buf.as_slice(), None).parse_all(&mut on_triple)
+                }
+                None => rio_xml::RdfXmlParser::new(r, None).parse_all(&mut on_triple),
+            }
+        }
+        (ResourceType::NTriples, ResourceType::NTriples) => {
+            std::io::copy(&mut r, w)?;
+            Ok(())
+        }
+        _ => {
+            let (so, mapping): (SetOntology<RcStr>, Option<PrefixMapping>) = match from {
+                ResourceType::OWX => {
+                    let (so, mapping) = owx::reader::read(&mut r, Default::default())?;
+                    (so, Some(mapping))
+                }
+                ResourceType::RDF => (
+                    rdf::reader::read(&mut r, Default::default())?.0.into(),
+                    None,
+                ),
+                ResourceType::NTriples => {
+                    (rdf::ntriples::read_ntriples(r, &Build::new_rc())?, None)
+                }
+            };
+            let amo: AxiomMappedOntology<RcStr, RcAnnotatedAxiom> = so.into();
+            match to {
+                ResourceType::OWX => owx::writer::write(w, &amo, mapping.as_ref()),
+                ResourceType::RDF => rdf::writer::write_with_mapping(w, &amo, mapping.as_ref()),
+                ResourceType::NTriples => rdf::ntriples::write_ntriples(w, &amo),
+            }
+        }
+    }
 }
 
 pub enum ParserOutput<A: ForIRI, AA: ForIndex<A>> {
@@ -22,20 +114,131 @@ pub enum ParserOutput<A: ForIRI, AA: ForIndex<A>> {
     RDFParser(RDFOntology<A, AA>, IncompleteParse<A>),
 }
 
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct ParserConfiguration {
     // Shared Config will go here
+    pub progress: Option<ProgressConfiguration>,
+
+    /// Replace each literal with its canonical form as it is read,
+    /// so that literals with equal values are equal. See
+    /// [`Literal::canonical`](../model/enum.Literal.html#method.canonical)
+    /// for the datatypes which are canonicalized.
+    pub canonicalize_literals: bool,
+
+    /// Lowercase the language tag of each literal as it is read, so
+    /// that tags which differ only in case, such as `en-GB` and
+    /// `en-gb`, are equal. See
+    /// [`Literal::lowercase_lang`](../model/enum.Literal.html#method.lowercase_lang).
+    /// The RDF/XML parser always lowercases language tags, so this
+    /// only changes the reading of OWL/XML.
+    pub normalize_lang_tags: bool,
+
+    /// The IRI to give the ontology if the document does not declare
+    /// one, so that it always has an identifier.
+    pub default_ontology_iri: Option<String>,
     pub rdf: RDFParserConfiguration,
     pub owx: OWXParserConfiguration,
 }
 
-#[derive(Clone, Copy, Debug, Default)]
+/// Report progress during a parse.
+///
+/// `callback` is called with a running count of items parsed (for
+/// RDF, triples) every `every` items. As a closure, it can capture
+/// the state it reports to.
+#[derive(Clone)]
+pub struct ProgressConfiguration {
+    pub every: usize,
+    pub callback: Arc<dyn Fn(usize) + Send + Sync>,
+}
+
+impl std::fmt::Debug for ProgressConfiguration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProgressConfiguration")
+            .field("every", &self.every)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ProgressConfiguration {
+    /// Call the callback if `count` falls on a reporting boundary.
+    #[inline]
+    pub fn tick(&self, count: usize) {
+        if count.checked_rem(self.every) == Some(0) {
+            (self.callback)(count)
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
 pub struct RDFParserConfiguration {
-    pub lax: bool
+    pub lax: bool,
+
+    /// The IRI against which relative IRIs are resolved, if the
+    /// document does not set `xml:base`. An invalid IRI fails the
+    /// parse with `HornedError::ValidityError`.
+    pub base_iri: Option<String>,
+
+    /// What to do with triples which could not be parsed into the
+    /// ontology, normally because they use a construct that is not
+    /// supported.
+    pub on_unsupported: UnsupportedPolicy,
+
+    /// Fail the parse if an IRI which is declared as a class,
+    /// datatype or individual is used as a property. Otherwise, the
+    /// triples concerned are left unparsed.
+    pub strict_types: bool,
+
+    /// The maximum depth to which blank node class expressions and
+    /// data ranges may be nested. These are resolved recursively, so
+    /// this protects against stack overflow on hostile input; deeper
+    /// nesting fails with `HornedError::DepthExceeded`.
+    pub max_depth: usize,
+
+    /// The maximum number of members of an RDF list. Longer lists
+    /// fail with `HornedError::DepthExceeded`.
+    pub max_list_length: usize,
+}
+
+impl Default for RDFParserConfiguration {
+    fn default() -> Self {
+        RDFParserConfiguration {
+            lax: false,
+            base_iri: None,
+            on_unsupported: UnsupportedPolicy::default(),
+            strict_types: false,
+            max_depth: 128,
+            max_list_length: 1_000_000,
+        }
+    }
+}
+
+/// The handling of triples which do not form part of any supported
+/// construct.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum UnsupportedPolicy {
+    /// Leave the triples out of the ontology, reporting them in the
+    /// `IncompleteParse` as usual.
+    #[default]
+    Skip,
+    /// Fail the parse at the first such triple.
+    Error,
+    /// Move the raw triples into `IncompleteParse::unsupported`.
+    Collect,
 }
 
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug)]
 pub struct OWXParserConfiguration {
+    /// The maximum depth to which elements may be nested within an
+    /// axiom. The reader is recursive, so this protects against
+    /// stack overflow on hostile input; deeper nesting fails with
+    /// `HornedError::DepthExceeded`.
+    pub max_depth: usize,
+}
+
+impl Default for OWXParserConfiguration {
+    fn default() -> Self {
+        OWXParserConfiguration { max_depth: 128 }
+    }
 }
 
 impl<A: ForIRI, AA: ForIndex<A>> ParserOutput<A, AA> {
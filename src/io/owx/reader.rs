@@ -1,6 +1,7 @@
 use curie::PrefixMapping;
 
 use crate::error::*;
+use crate::io::encoding::utf8_xml;
 use crate::io::ParserConfiguration;
 use crate::model::*;
 use crate::vocab::Namespace::*;
@@ -26,20 +27,36 @@ where
     reader: Reader<R>,
     buf: Vec<u8>,
     ns_buf: Vec<u8>,
+    config: ParserConfiguration,
+    depth: usize,
 }
 
 pub fn read<R: BufRead>(
     bufread: &mut R,
-    _config: ParserConfiguration
+    config: ParserConfiguration
 ) -> Result<(SetOntology<RcStr>, PrefixMapping), HornedError> {
     let b = Build::new();
-    read_with_build(bufread, &b)
+    read_with_build_config(bufread, &b, config)
 }
 
 pub fn read_with_build<A: ForIRI, R: BufRead>(
     bufread: R,
     build: &Build<A>,
 ) -> Result<(SetOntology<A>, PrefixMapping), HornedError> {
+    read_with_build_config(bufread, build, Default::default())
+}
+
+/// Read an OWL/XML document, interning IRIs with `build` and
+/// following the options in `config`.
+pub fn read_with_build_config<A: ForIRI, R: BufRead>(
+    bufread: R,
+    build: &Build<A>,
+    config: ParserConfiguration,
+) -> Result<(SetOntology<A>, PrefixMapping), HornedError> {
+    let mut bufread = bufread;
+    if let Some(buf) = utf8_xml(&mut bufread)? {
+        return read_with_build_config(buf.as_slice(), build, config);
+    }
     let reader: Reader<R> = Reader::from_reader(bufread);
     let mut ont = SetOntology::new();
     let mapping = PrefixMapping::default();
@@ -50,6 +67,8 @@ pub fn read_with_build<A: ForIRI, R: BufRead>(
         mapping,
         buf: Vec::new(),
         ns_buf: Vec::new(),
+        config,
+        depth: 0,
     };
 
     loop {
@@ -59,28 +78,10 @@ pub fn read_with_build<A: ForIRI, R: BufRead>(
             {
                 match e.local_name() {
                     b"Ontology" => {
-                        let s = attrib_value(&mut r, e, b"ontologyIRI")?;
-                        if let Some(s) = s {
-                            r.mapping.set_default(&s);
-                        }
-
-                        ont.mut_id().iri = read_a_iri_attr(&mut r, e, b"ontologyIRI")?;
-                        ont.mut_id().viri = read_a_iri_attr(&mut r, e, b"versionIRI")?;
+                        *ont.mut_id() = read_ontology_id(&mut r, e)?;
                     }
                     b"Prefix" => {
-                        let iri = attrib_value(&mut r, e, b"IRI")?;
-                        let prefix = attrib_value(&mut r, e, b"name")?;
-                        match (prefix, iri) {
-                            (Some(p), Some(i)) => {
-                                r.mapping.add_prefix(&p, &i).ok();
-                            }
-                            (None, _) => {
-                                return Err(error_missing_attribute("IRI", &mut r));
-                            }
-                            (Some(_), None) => {
-                                return Err(error_missing_attribute("name", &mut r));
-                            }
-                        }
+                        read_prefix(&mut r, e)?;
                     }
                     b"Import" => {
                         ont.insert(Import(IRI::from_xml(&mut r, b"Import")?));
@@ -97,9 +98,105 @@ pub fn read_with_build<A: ForIRI, R: BufRead>(
             _ => {}
         }
     }
+
+    if ont.id().iri.is_none() {
+        ont.mut_id().iri = r
+            .config
+            .default_ontology_iri
+            .as_ref()
+            .map(|s| build.iri(s.as_str()));
+    }
     Ok((ont, r.mapping))
 }
 
+/// Read only the `OntologyID` and imports of an OWL/XML document.
+///
+/// Reading stops at the first axiom, so the rest of the document is
+/// not parsed. This is much faster than reading the whole document
+/// when, for example, building a graph of imports. Ontology
+/// annotations, which come between the imports and the axioms, are
+/// read and discarded.
+pub fn read_header<A: ForIRI, R: BufRead>(
+    bufread: R,
+    build: &Build<A>,
+) -> Result<(OntologyID<A>, Vec<IRI<A>>), HornedError> {
+    let mut bufread = bufread;
+    if let Some(buf) = utf8_xml(&mut bufread)? {
+        return read_header(buf.as_slice(), build);
+    }
+    let mut r = Read {
+        reader: Reader::from_reader(bufread),
+        build,
+        mapping: PrefixMapping::default(),
+        buf: Vec::new(),
+        ns_buf: Vec::new(),
+        config: ParserConfiguration::default(),
+        depth: 0,
+    };
+    let mut id = OntologyID::default();
+    let mut imports = vec![];
+
+    loop {
+        match read_event(&mut r)? {
+            (ref ns, Event::Start(ref e)) | (ref ns, Event::Empty(ref e))
+                if *ns == b"http://www.w3.org/2002/07/owl#" =>
+            {
+                match e.local_name() {
+                    b"Ontology" => {
+                        id = read_ontology_id(&mut r, e)?;
+                    }
+                    b"Prefix" => {
+                        read_prefix(&mut r, e)?;
+                    }
+                    b"Import" => {
+                        imports.push(IRI::from_xml(&mut r, b"Import")?);
+                    }
+                    b"Annotation" => {
+                        AnnotatedAxiom::from_start(&mut r, e)?;
+                    }
+                    _ => break,
+                }
+            }
+            (ref ns, Event::End(ref e)) if is_owl_name(ns, e, b"Ontology") => {
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    Ok((id, imports))
+}
+
+fn read_ontology_id<A: ForIRI, R: BufRead>(
+    r: &mut Read<A, R>,
+    e: &BytesStart,
+) -> Result<OntologyID<A>, HornedError> {
+    if let Some(s) = attrib_value(r, e, b"ontologyIRI")? {
+        r.mapping.set_default(&s);
+    }
+
+    Ok(OntologyID {
+        iri: read_a_iri_attr(r, e, b"ontologyIRI")?,
+        viri: read_a_iri_attr(r, e, b"versionIRI")?,
+    })
+}
+
+fn read_prefix<A: ForIRI, R: BufRead>(
+    r: &mut Read<A, R>,
+    e: &BytesStart,
+) -> Result<(), HornedError> {
+    let iri = attrib_value(r, e, b"IRI")?;
+    let prefix = attrib_value(r, e, b"name")?;
+    match (prefix, iri) {
+        (Some(p), Some(i)) => {
+            r.mapping.add_prefix(&p, &i).ok();
+            Ok(())
+        }
+        (None, _) => Err(error_missing_attribute("IRI", r)),
+        (Some(_), None) => Err(error_missing_attribute("name", r)),
+    }
+}
+
 /// Read an event from the reader, which is unowned.
 ///
 /// This method is here because it allows me to nest self called,
@@ -277,6 +374,13 @@ fn error_unexpected_end_tag<A: ForIRI, R: BufRead>(tag: &[u8], r: &mut Read<A, R
     }
 }
 
+fn error_unknown_facet<A: ForIRI, R: BufRead>(found: &[u8], r: &mut Read<A, R>) -> HornedError {
+    match decode_tag(found, r) {
+        Ok(found) => HornedError::UnknownFacet(found, r.reader.buffer_position().into()),
+        Err(e) => e,
+    }
+}
+
 fn error_unknown_entity<A: ForIRI, AA: Into<String>, R: BufRead>(
     kind: AA,
     found: &[u8],
@@ -357,7 +461,18 @@ fn from_start<A: ForIRI, R: BufRead, T: FromStart<A>>(
     r: &mut Read<A, R>,
     e: &BytesStart,
 ) -> Result<T, HornedError> {
-    T::from_start(r, e)
+    let max_depth = r.config.owx.max_depth;
+    if r.depth >= max_depth {
+        return Err(HornedError::DepthExceeded(
+            max_depth,
+            r.reader.buffer_position().into(),
+        ));
+    }
+
+    r.depth += 1;
+    let t = T::from_start(r, e);
+    r.depth -= 1;
+    t
 }
 
 from_start! {
@@ -367,23 +482,34 @@ from_start! {
         let lang = attrib_value(r, e, b"xml:lang")?;
 
         let literal = r.reader.read_text(b"Literal", &mut Vec::new())?;
-        Ok(
-            match (datatype_iri, lang, literal) {
-                (None, None, literal) =>
-                    Literal::Simple{literal},
-                (Some(ref datatype_iri), None, literal)
-                    if **datatype_iri == *"http://www.w3.org/2001/XMLSchema#string" =>
-                    Literal::Simple{literal},
-                (None, Some(lang), literal) =>
-                    Literal::Language{literal, lang},
-                (Some(ref datatype_iri), Some(ref lang), ref literal)
-                    if **datatype_iri == *"http://www.w3.org/1999/02/22-rdf-syntax-ns#PlainLiteral"
-                    => Literal::Language{literal:literal.to_string(), lang:lang.to_string()},
-                (Some(_), Some(_), _)
-                    => return Err(invalid!("Broken literal at {}", r.reader.buffer_position())),
-                (Some(datatype_iri), None, literal)
-                    => Literal::Datatype{literal, datatype_iri},
-            })
+        let literal = match (datatype_iri, lang, literal) {
+            (None, None, literal) =>
+                Literal::Simple{literal},
+            (Some(ref datatype_iri), None, literal)
+                if **datatype_iri == *"http://www.w3.org/2001/XMLSchema#string" =>
+                Literal::Simple{literal},
+            (None, Some(lang), literal) =>
+                Literal::Language{literal, lang},
+            (Some(ref datatype_iri), Some(ref lang), ref literal)
+                if **datatype_iri == *"http://www.w3.org/1999/02/22-rdf-syntax-ns#PlainLiteral"
+                => Literal::Language{literal:literal.to_string(), lang:lang.to_string()},
+            (Some(_), Some(_), _)
+                => return Err(invalid!("Broken literal at {}", r.reader.buffer_position())),
+            (Some(datatype_iri), None, literal)
+                => Literal::Datatype{literal, datatype_iri},
+        };
+
+        let literal = if r.config.canonicalize_literals {
+            literal.canonical()
+        } else {
+            literal
+        };
+
+        Ok(if r.config.normalize_lang_tags {
+            literal.lowercase_lang()
+        } else {
+            literal
+        })
     }
 }
 
@@ -978,7 +1104,7 @@ from_start! {
             FacetRestriction {
                 f: Facet::var_b(&f)
                     .ok_or_else(
-                        || error_unknown_entity("facet", &f, r))?,
+                        || error_unknown_facet(&f, r))?,
                 l: from_next(r)?
             }
         )
@@ -1000,6 +1126,11 @@ from_start! {
                         till_end(r, b"DataIntersectionOf")?
                     )
                 }
+                b"DataUnionOf" => {
+                    DataRange::DataUnionOf(
+                        till_end(r, b"DataUnionOf")?
+                    )
+                }
                 b"DataComplementOf" => {
                     DataRange::DataComplementOf(
                         Box::new(from_next(r)?)
@@ -1866,6 +1997,88 @@ pub mod test {
         assert_eq!(ont.i().sub_class_of().count(), 1);
     }
 
+    #[test]
+    fn unknown_facet() {
+        let ont_s = include_str!("../../ont/owl-xml/facet-restriction.owx")
+            .replace("XMLSchema#minExclusive", "XMLSchema#notAFacet");
+        let r = read(&mut ont_s.as_bytes(), ParserConfiguration::default());
+
+        assert!(matches!(r, Err(HornedError::UnknownFacet(f, _)) if f.ends_with("#notAFacet")));
+    }
+
+    #[test]
+    fn canonicalize_literals() {
+        let b = Build::new_rc();
+        let mut ont = AxiomMappedOntology::new_rc();
+        for l in &["1.0", "1.00"] {
+            ont.insert(DataPropertyAssertion {
+                dp: b.data_property("http://www.example.com/dp"),
+                from: b.named_individual("http://www.example.com/i").into(),
+                to: Literal::Datatype {
+                    literal: l.to_string(),
+                    datatype_iri: b.iri("http://www.w3.org/2001/XMLSchema#decimal"),
+                },
+            });
+        }
+        let mut buf = vec![];
+        crate::io::owx::writer::write(&mut buf, &ont, None).unwrap();
+
+        let (ont, _) = read(&mut buf.as_slice(), ParserConfiguration::default()).unwrap();
+        assert_eq!(ont.len(), 2);
+
+        let config = ParserConfiguration {
+            canonicalize_literals: true,
+            ..Default::default()
+        };
+        let (ont, _) = read(&mut buf.as_slice(), config).unwrap();
+        assert_eq!(ont.len(), 1);
+        assert!(matches!(
+            &ont.iter().next().unwrap().axiom,
+            Axiom::DataPropertyAssertion(DataPropertyAssertion { to, .. }) if to.literal() == "1"
+        ));
+    }
+
+    #[test]
+    fn normalize_lang_tags() {
+        let b = Build::new_rc();
+        let mut ont = AxiomMappedOntology::new_rc();
+        for lang in &["en-GB", "en-gb"] {
+            ont.insert(AnnotationAssertion {
+                subject: b.iri("http://www.example.com/a").into(),
+                ann: Annotation {
+                    ap: b.annotation_property("http://www.w3.org/2000/01/rdf-schema#label"),
+                    av: Literal::Language {
+                        literal: "colour".into(),
+                        lang: lang.to_string(),
+                    }
+                    .into(),
+                },
+            });
+        }
+        let mut buf = vec![];
+        crate::io::owx::writer::write(&mut buf, &ont, None).unwrap();
+
+        let (ont, _) = read(&mut buf.as_slice(), ParserConfiguration::default()).unwrap();
+        assert_eq!(ont.len(), 2);
+
+        let config = ParserConfiguration {
+            normalize_lang_tags: true,
+            ..Default::default()
+        };
+        let (ont, _) = read(&mut buf.as_slice(), config).unwrap();
+        assert_eq!(ont.len(), 1);
+        assert!(matches!(
+            &ont.iter().next().unwrap().axiom,
+            Axiom::AnnotationAssertion(AnnotationAssertion {
+                ann: Annotation {
+                    av: AnnotationValue::Literal(Literal::Language { lang, .. }),
+                    ..
+                },
+                ..
+            }) if lang == "en-gb"
+        ));
+    }
+
     #[test]
     fn data_only() {
         let ont_s = include_str!("../../ont/owl-xml/data-only.owx");
@@ -2057,4 +2270,120 @@ pub mod test {
         let ont_s = include_str!("../../ont/owl-xml/family.owx");
         let (_, _) = read_ok(&mut ont_s.as_bytes());
     }
+
+    #[test]
+    fn max_depth() {
+        let nested = |depth: usize| {
+            format!(
+                r#"<?xml version="1.0"?>
+<Ontology xmlns="http://www.w3.org/2002/07/owl#">
+    <SubClassOf>
+        <Class IRI="http://www.example.com/iri#B"/>
+        {}<Class IRI="http://www.example.com/iri#A"/>{}
+    </SubClassOf>
+</Ontology>"#,
+                "<ObjectComplementOf>".repeat(depth),
+                "</ObjectComplementOf>".repeat(depth)
+            )
+        };
+        let mut config = ParserConfiguration::default();
+        config.owx.max_depth = 5;
+
+        // The complements and the innermost class make five levels
+        let r = read(&mut nested(4).as_bytes(), config.clone());
+        assert!(r.is_ok(), "Expected ontology, got failure:{:?}", r.err());
+
+        let r = read(&mut nested(5).as_bytes(), config);
+        assert!(matches!(r, Err(HornedError::DepthExceeded(5, _))));
+
+        // Deep enough to overflow the stack without the default limit
+        assert!(matches!(
+            read(&mut nested(1000).as_bytes(), Default::default()),
+            Err(HornedError::DepthExceeded(..))
+        ));
+    }
+
+    #[test]
+    fn header_only() {
+        let b = Build::new_rc();
+        let (id, imports) =
+            read_header(&include_bytes!("../../ont/owl-xml/import.owx")[..], &b).unwrap();
+        assert_eq!(id.iri, Some(b.iri("http://www.example.com/iri")));
+        assert_eq!(id.viri, Some(b.iri("http://www.example.com/viri")));
+        assert_eq!(imports, vec![b.iri("http://www.example.com/other-iri")]);
+
+        // The body is never reached, so need not be complete
+        let truncated = r#"<?xml version="1.0"?>
+<Ontology xmlns="http://www.w3.org/2002/07/owl#"
+     ontologyIRI="http://www.example.com/iri">
+    <Prefix name="o" IRI="http://www.example.com/iri#"/>
+    <Import>http://www.example.com/a</Import>
+    <Import>http://www.example.com/b</Import>
+    <Annotation>
+        <AnnotationProperty abbreviatedIRI="rdfs:comment"/>
+        <Literal>Header</Literal>
+    </Annotation>
+    <Declaration>
+        <Class abbreviatedIRI="o:C"/>"#;
+        assert!(read(&mut truncated.as_bytes(), Default::default()).is_err());
+
+        let (id, imports) = read_header(truncated.as_bytes(), &b).unwrap();
+        assert_eq!(id.iri, Some(b.iri("http://www.example.com/iri")));
+        assert_eq!(id.viri, None);
+        assert_eq!(
+            imports,
+            vec![
+                b.iri("http://www.example.com/a"),
+                b.iri("http://www.example.com/b")
+            ]
+        );
+    }
+
+    #[test]
+    fn default_ontology_iri() {
+        let config = ParserConfiguration {
+            default_ontology_iri: Some("http://www.example.com/default".to_string()),
+            ..Default::default()
+        };
+        let ont_s = r#"<?xml version="1.0"?>
+<Ontology xmlns="http://www.w3.org/2002/07/owl#">
+    <Declaration>
+        <Class IRI="http://www.example.com/C"/>
+    </Declaration>
+</Ontology>"#;
+        let (ont, _) = read(&mut ont_s.as_bytes(), config.clone()).unwrap();
+        assert_eq!(
+            ont.id().iri.as_ref().map(|iri| iri.as_ref()),
+            Some("http://www.example.com/default")
+        );
+
+        let ont_s = include_str!("../../ont/owl-xml/class.owx");
+        let (ont, _) = read(&mut ont_s.as_bytes(), config).unwrap();
+        assert_eq!(
+            ont.id().iri.as_ref().map(|iri| iri.as_ref()),
+            Some("http://www.example.com/iri")
+        );
+    }
+
+    #[test]
+    fn test_bom_and_latin1() {
+        let ont_s = include_str!("../../ont/owl-xml/label.owx").replace("Some Label", "Café");
+        let (expected, _) = read_ok(&mut ont_s.as_bytes());
+
+        let mut bom = vec![0xEF, 0xBB, 0xBF];
+        bom.extend_from_slice(ont_s.as_bytes());
+        let (ont, _) = read_ok(&mut bom.as_slice());
+        assert_eq!(ont, expected);
+
+        let latin1: Vec<u8> = ont_s
+            .replace(
+                "<?xml version=\"1.0\"?>",
+                "<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?>",
+            )
+            .chars()
+            .map(|c| c as u8)
+            .collect();
+        let (ont, _) = read_ok(&mut latin1.as_slice());
+        assert_eq!(ont, expected);
+    }
 }
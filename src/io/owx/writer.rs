@@ -893,6 +893,29 @@ mod test {
         assert_eq!(ont.id().iri, ont2.id().iri);
     }
 
+    #[test]
+    fn round_inverse_object_property_assertion() {
+        let mut ont = AxiomMappedOntology::new_rc();
+        let b = Build::new_rc();
+        ont.mut_id().iri = Some(b.iri("http://www.example.com/o"));
+        let opa = ObjectPropertyAssertion {
+            ope: ObjectPropertyExpression::InverseObjectProperty(
+                b.object_property("http://www.example.com/p"),
+            ),
+            from: b.named_individual("http://www.example.com/a").into(),
+            to: b.named_individual("http://www.example.com/b").into(),
+        };
+        ont.insert(opa.clone());
+
+        let mut buf = vec![];
+        write(&mut buf, &ont, None).unwrap();
+        let (ont_round, _) = read_ok(&mut buf.as_slice());
+
+        let opas: Vec<_> = ont_round.i().object_property_assertion().collect();
+        assert_eq!(opas, vec![&opa]);
+        assert_ne!(opas[0], &opas[0].normalize());
+    }
+
     fn roundtrip_1(ont: &str) -> (RcAxiomMappedOntology, PrefixMapping, Temp) {
         let (ont_orig, prefix_orig) = read_ok(&mut ont.as_bytes());
         let temp_file = Temp::new_file().unwrap();
@@ -962,6 +985,24 @@ mod test {
         assert_eq!(ont_orig.id().iri, ont_round.id().iri);
     }
 
+    #[test]
+    fn round_version_iri() {
+        let (ont_orig, _prefix_orig, ont_round, _prefix_round) =
+            assert_round(include_str!("../../ont/owl-xml/ont.owx"));
+
+        for o in &[ont_orig, ont_round] {
+            let id = o.id();
+            assert_eq!(
+                id.iri.as_ref().unwrap().as_ref(),
+                "http://www.example.com/iri"
+            );
+            assert_eq!(
+                id.viri.as_ref().unwrap().as_ref(),
+                "http://www.example.com/viri"
+            );
+        }
+    }
+
     #[test]
     fn round_one_ont_prefix() {
         let (_ont_orig, prefix_orig, _ont_round, prefix_round) =
@@ -1423,4 +1464,20 @@ mod test {
     fn family() {
         assert_round(include_str!("../../ont/owl-xml/family.owx"));
     }
+
+    #[test]
+    fn deterministic_order() {
+        let ont_s = include_str!("../../ont/owl-xml/family.owx");
+        let first = roundtrip_to_string(ont_s);
+        assert_eq!(first, roundtrip_to_string(ont_s));
+
+        // Prefixes, then declarations, then other axioms
+        let prefix = first.find("<Prefix").unwrap();
+        let declaration = first.find("<Declaration").unwrap();
+        let subclass = first.find("<SubClassOf").unwrap();
+        assert!(prefix < declaration);
+        assert!(declaration < subclass);
+        assert!(!first[declaration..].contains("<Prefix"));
+        assert!(!first[subclass..].contains("<Declaration"));
+    }
 }
@@ -100,7 +100,7 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> ClosureOntologyParser<'a, A, AA> {
         new_doc_iri: IRI<A>,
         v: &mut Vec<IRI<A>>,
     ) -> Result<(), HornedError> {
-        let mut p = parser_with_build(&mut s.as_bytes(), self.b, self.config);
+        let mut p = parser_with_build(&mut s.as_bytes(), self.b, self.config.clone());
         let imports = p.parse_imports().unwrap();
         p.parse_declarations()?;
         let o = p.mut_ontology_ref();
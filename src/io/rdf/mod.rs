@@ -1,3 +1,4 @@
 pub mod closure_reader;
+pub mod ntriples;
 pub mod reader;
 pub mod writer;
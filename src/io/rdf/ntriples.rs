@@ -0,0 +1,541 @@
+//! N-Triples serialization of ontologies
+//!
+//! N-Triples is a line based RDF format, with one triple per line and
+//! all IRIs written in full. It is useful for comparing ontologies
+//! with line based tools and for loading into triple stores. The
+//! triples written are exactly those produced for RDF/XML by
+//! `writer::to_triples`, and triples read are turned into axioms by
+//! the RDF/XML `reader`.
+use std::fmt::{self, Display, Formatter};
+use std::io::{BufRead, Write};
+
+use rio_api::model::{BlankNode, Literal as RioLiteral, NamedNode, Subject, Term as RioTerm};
+
+use crate::error::{HornedError, Location};
+use crate::io::rdf::reader::{
+    to_term, to_term_nn, to_term_nnb, vocab_lookup, OntologyParser, PosTriple,
+};
+use crate::io::rdf::writer::{to_triples, Triple, TripleObject, TripleSubject};
+use crate::model::*;
+use crate::ontology::axiom_mapped::AxiomMappedOntology;
+use crate::ontology::indexed::ForIndex;
+use crate::ontology::set::SetOntology;
+
+/// Write `ont` as N-Triples.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// # use horned_owl::io::rdf::ntriples::write_ntriples;
+/// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+/// let b = Build::new_rc();
+/// let mut o = AxiomMappedOntology::new_rc();
+/// o.mut_id().iri = Some(b.iri("http://www.example.com/o"));
+///
+/// let mut buf = vec![];
+/// write_ntriples(&mut buf, &o).unwrap();
+/// assert_eq!(
+///     String::from_utf8(buf).unwrap(),
+///     "<http://www.example.com/o> \
+///      <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> \
+///      <http://www.w3.org/2002/07/owl#Ontology> .\n"
+/// );
+/// ```
+pub fn write_ntriples<A: ForIRI, AA: ForIndex<A>, W: Write>(
+    w: &mut W,
+    ont: &AxiomMappedOntology<A, AA>,
+) -> Result<(), HornedError> {
+    for t in to_triples(&Build::new(), ont)? {
+        writeln!(w, "{}", t)?;
+    }
+    Ok(())
+}
+
+/// Read an ontology from N-Triples.
+///
+/// Parse errors are reported with the line on which they occur.
+/// Axioms which cannot be formed from the triples are ignored, as
+/// they are by the RDF/XML reader.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// # use horned_owl::io::rdf::ntriples::read_ntriples;
+/// let b = Build::new_rc();
+/// let nt = "<http://www.example.com/o> \
+///           <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> \
+///           <http://www.w3.org/2002/07/owl#Ontology> .\n";
+///
+/// let o = read_ntriples(nt.as_bytes(), &b).unwrap();
+/// assert_eq!(o.id().iri, Some(b.iri("http://www.example.com/o")));
+/// ```
+pub fn read_ntriples<A: ForIRI, R: BufRead>(
+    r: R,
+    b: &Build<A>,
+) -> Result<SetOntology<A>, HornedError> {
+    let m = vocab_lookup();
+    let mut triples = vec![];
+    let mut pos = 0;
+
+    for (i, line) in r.lines().enumerate() {
+        let line = line?;
+        let parsed = LineParser { s: &line, pos: 0 }
+            .triple()
+            .map_err(|e| HornedError::ParserError(e.into(), Location::Line(i + 1)))?;
+
+        if let Some([s, p, o]) = parsed {
+            triples.push(PosTriple(
+                [
+                    to_term_nnb(&s.subject(), &m, b),
+                    to_term_nn(&NamedNode { iri: p.iri() }, &m, b),
+                    to_term(&o.term(), &m, b),
+                ],
+                pos,
+            ));
+        }
+        pos += line.len() + 1;
+    }
+
+    let (o, _) =
+        OntologyParser::<A, AnnotatedAxiom<A>>::new(b, triples, Default::default()).parse()?;
+    Ok(o.into())
+}
+
+/// A term of an N-Triples line.
+#[derive(Debug)]
+enum NTerm {
+    Iri(String),
+    BlankNode(String),
+    Literal {
+        value: String,
+        language: Option<String>,
+        datatype: Option<String>,
+    },
+}
+
+impl NTerm {
+    fn iri(&self) -> &str {
+        match self {
+            NTerm::Iri(iri) => iri,
+            _ => unreachable!(),
+        }
+    }
+
+    fn subject(&self) -> Subject<'_> {
+        match self {
+            NTerm::Iri(iri) => NamedNode { iri }.into(),
+            NTerm::BlankNode(id) => BlankNode { id }.into(),
+            NTerm::Literal { .. } => unreachable!(),
+        }
+    }
+
+    fn term(&self) -> RioTerm<'_> {
+        match self {
+            NTerm::Iri(iri) => NamedNode { iri }.into(),
+            NTerm::BlankNode(id) => BlankNode { id }.into(),
+            NTerm::Literal {
+                value,
+                language: Some(language),
+                ..
+            } => RioLiteral::LanguageTaggedString { value, language }.into(),
+            NTerm::Literal {
+                value,
+                datatype: Some(iri),
+                ..
+            } => RioLiteral::Typed {
+                value,
+                datatype: NamedNode { iri },
+            }
+            .into(),
+            NTerm::Literal { value, .. } => RioLiteral::Simple { value }.into(),
+        }
+    }
+}
+
+/// A parser for a single line of N-Triples.
+struct LineParser<'a> {
+    s: &'a str,
+    pos: usize,
+}
+
+impl<'a> LineParser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.s[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_ws(&mut self) {
+        while let Some(' ') | Some('\t') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), String> {
+        match self.next() {
+            Some(n) if n == c => Ok(()),
+            Some(n) => Err(format!("Expected '{}', found '{}'", c, n)),
+            None => Err(format!("Expected '{}', found end of line", c)),
+        }
+    }
+
+    /// Parse a line, returning `None` for a blank or comment line.
+    fn triple(&mut self) -> Result<Option<[NTerm; 3]>, String> {
+        self.skip_ws();
+        if let None | Some('#') = self.peek() {
+            return Ok(None);
+        }
+
+        let subject = match self.peek() {
+            Some('<') => self.iri()?,
+            Some('_') => self.blank_node()?,
+            _ => return Err("Expected an IRI or blank node as subject".to_string()),
+        };
+        self.skip_ws();
+        let predicate = self.iri()?;
+        self.skip_ws();
+        let object = match self.peek() {
+            Some('<') => self.iri()?,
+            Some('_') => self.blank_node()?,
+            Some('"') => self.literal()?,
+            _ => return Err("Expected an IRI, blank node or literal as object".to_string()),
+        };
+        self.skip_ws();
+        self.expect('.')?;
+        self.skip_ws();
+
+        match self.peek() {
+            None | Some('#') => Ok(Some([subject, predicate, object])),
+            Some(c) => Err(format!("Unexpected '{}' after end of triple", c)),
+        }
+    }
+
+    fn iri(&mut self) -> Result<NTerm, String> {
+        self.expect('<')?;
+        let mut iri = String::new();
+        loop {
+            match self.next() {
+                Some('>') => return Ok(NTerm::Iri(iri)),
+                Some('\\') => iri.push(self.unicode_escape()?),
+                Some(c) => iri.push(c),
+                None => return Err("Unterminated IRI".to_string()),
+            }
+        }
+    }
+
+    fn blank_node(&mut self) -> Result<NTerm, String> {
+        self.expect('_')?;
+        self.expect(':')?;
+        let label: String = self
+            .rest()
+            .chars()
+            .take_while(|c| !c.is_whitespace() && !"<\"".contains(*c))
+            .collect();
+        // A label may not end with '.', so this ends the triple
+        let label = label.trim_end_matches('.');
+        if label.is_empty() {
+            return Err("Empty blank node label".to_string());
+        }
+        self.pos += label.len();
+        Ok(NTerm::BlankNode(label.to_string()))
+    }
+
+    fn literal(&mut self) -> Result<NTerm, String> {
+        self.expect('"')?;
+        let mut value = String::new();
+        loop {
+            match self.next() {
+                Some('"') => break,
+                Some('\\') => match self.peek() {
+                    Some('u') | Some('U') => value.push(self.unicode_escape()?),
+                    _ => value.push(match self.next() {
+                        Some('t') => '\t',
+                        Some('b') => '\u{8}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('f') => '\u{c}',
+                        Some(c @ '"') | Some(c @ '\'') | Some(c @ '\\') => c,
+                        _ => return Err("Invalid escape in literal".to_string()),
+                    }),
+                },
+                Some(c) => value.push(c),
+                None => return Err("Unterminated literal".to_string()),
+            }
+        }
+
+        let mut language = None;
+        let mut datatype = None;
+        match self.peek() {
+            Some('@') => {
+                self.pos += 1;
+                let lang: String = self
+                    .rest()
+                    .chars()
+                    .take_while(|c| c.is_ascii_alphanumeric() || *c == '-')
+                    .collect();
+                if lang.is_empty() {
+                    return Err("Empty language tag".to_string());
+                }
+                self.pos += lang.len();
+                language = Some(lang);
+            }
+            Some('^') => {
+                self.expect('^')?;
+                self.expect('^')?;
+                datatype = Some(self.iri()?.iri().to_string());
+            }
+            _ => {}
+        }
+
+        Ok(NTerm::Literal {
+            value,
+            language,
+            datatype,
+        })
+    }
+
+    /// Parse the remainder of a `\\u` or `\\U` escape.
+    fn unicode_escape(&mut self) -> Result<char, String> {
+        let len = match self.next() {
+            Some('u') => 4,
+            Some('U') => 8,
+            _ => return Err("Invalid escape".to_string()),
+        };
+        let hex = self.rest().get(..len).ok_or("Truncated unicode escape")?;
+        self.pos += len;
+        u32::from_str_radix(hex, 16)
+            .ok()
+            .and_then(std::char::from_u32)
+            .ok_or_else(|| format!("Invalid unicode escape: {}", hex))
+    }
+}
+
+fn write_iri<A: ForIRI>(f: &mut Formatter<'_>, iri: &IRI<A>) -> fmt::Result {
+    write!(f, "<")?;
+    for c in iri.chars() {
+        match c {
+            '<' | '>' | '"' | '{' | '}' | '|' | '^' | '`' | '\\' | '\u{0}'..='\u{20}' => {
+                write!(f, "\\u{:04X}", c as u32)?
+            }
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, ">")
+}
+
+fn write_string(f: &mut Formatter<'_>, s: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+impl<A: ForIRI> Display for Triple<A> {
+    /// Format the triple as a line of N-Triples, without the newline.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.subject {
+            TripleSubject::IRI(iri) => write_iri(f, iri)?,
+            TripleSubject::BlankNode(bn) => write!(f, "_:{}", bn.borrow())?,
+        }
+        write!(f, " ")?;
+        write_iri(f, &self.predicate)?;
+        write!(f, " ")?;
+        match &self.object {
+            TripleObject::IRI(iri) => write_iri(f, iri)?,
+            TripleObject::BlankNode(bn) => write!(f, "_:{}", bn.borrow())?,
+            TripleObject::Literal(Literal::Simple { literal }) => write_string(f, literal)?,
+            TripleObject::Literal(Literal::Language { literal, lang }) => {
+                write_string(f, literal)?;
+                write!(f, "@{}", lang)?
+            }
+            TripleObject::Literal(Literal::Datatype {
+                literal,
+                datatype_iri,
+            }) => {
+                write_string(f, literal)?;
+                write!(f, "^^")?;
+                write_iri(f, datatype_iri)?
+            }
+        }
+        write!(f, " .")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::RcStr;
+
+    fn ntriples(o: &AxiomMappedOntology<RcStr, std::rc::Rc<AnnotatedAxiom<RcStr>>>) -> String {
+        let mut buf = vec![];
+        write_ntriples(&mut buf, o).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn test_escape_literal() {
+        let b = Build::new_rc();
+        let mut o = AxiomMappedOntology::new_rc();
+        o.mut_id().iri = Some(b.iri("http://www.example.com/o"));
+        o.insert(AnnotationAssertion {
+            subject: b.iri("http://www.example.com/a").into(),
+            ann: Annotation {
+                ap: b.annotation_property("http://www.w3.org/2000/01/rdf-schema#label"),
+                av: Literal::Simple {
+                    literal: "a \"quoted\"\nlabel \\".into(),
+                }
+                .into(),
+            },
+        });
+
+        let nt = ntriples(&o);
+        assert!(nt.contains(
+            "<http://www.example.com/a> <http://www.w3.org/2000/01/rdf-schema#label> \
+             \"a \\\"quoted\\\"\\nlabel \\\\\" .\n"
+        ));
+        assert_eq!(nt.lines().count(), 2);
+    }
+
+    #[test]
+    fn test_typed_and_blank() {
+        let b = Build::new_rc();
+        let mut o = AxiomMappedOntology::new_rc();
+        o.mut_id().iri = Some(b.iri("http://www.example.com/o"));
+        o.insert(SubClassOf {
+            sub: b.class("http://www.example.com/a").into(),
+            sup: ClassExpression::DataHasValue {
+                dp: b.data_property("http://www.example.com/d"),
+                l: Literal::Datatype {
+                    literal: "1".into(),
+                    datatype_iri: b.iri("http://www.w3.org/2001/XMLSchema#integer"),
+                },
+            },
+        });
+
+        let nt = ntriples(&o);
+        assert!(nt.contains(
+            "_:bn1 <http://www.w3.org/2002/07/owl#hasValue> \
+             \"1\"^^<http://www.w3.org/2001/XMLSchema#integer> .\n"
+        ));
+        assert!(nt.contains(
+            "<http://www.example.com/a> <http://www.w3.org/2000/01/rdf-schema#subClassOf> _:bn1 .\n"
+        ));
+    }
+
+    fn assert_round(ont_s: &str) {
+        let (rdf, _) =
+            crate::io::rdf::reader::read(&mut ont_s.as_bytes(), Default::default()).unwrap();
+        let amo: AxiomMappedOntology<_, _> = rdf.into();
+        let nt = ntriples(&amo);
+
+        let b = Build::new_rc();
+        let read = read_ntriples(nt.as_bytes(), &b).unwrap();
+        let original: SetOntology<_> = amo.into();
+
+        assert_eq!(read, original);
+    }
+
+    #[test]
+    fn round_class() {
+        assert_round(include_str!("../../ont/owl-rdf/class.owl"));
+    }
+
+    #[test]
+    fn round_label() {
+        assert_round(include_str!("../../ont/owl-rdf/label.owl"));
+    }
+
+    #[test]
+    fn round_literal_escaped() {
+        assert_round(include_str!("../../ont/owl-rdf/literal-escaped.owl"));
+    }
+
+    #[test]
+    fn round_complex_equivalent_classes() {
+        assert_round(include_str!(
+            "../../ont/owl-rdf/complex-equivalent-classes.owl"
+        ));
+    }
+
+    #[test]
+    fn round_facet_restriction() {
+        assert_round(include_str!("../../ont/owl-rdf/facet-restriction.owl"));
+    }
+
+    #[test]
+    fn round_annotation_on_subclass() {
+        assert_round(include_str!("../../ont/owl-rdf/annotation-on-subclass.owl"));
+    }
+
+    #[test]
+    fn read_comments_and_escapes() {
+        let b = Build::new_rc();
+        let nt = r#"# A comment
+
+<http://www.example.com/o> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/2002/07/owl#Ontology> .
+<http://www.example.com/\u0061> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/2002/07/owl#Class> . # trailing
+<http://www.example.com/a> <http://www.w3.org/2000/01/rdf-schema#label> "\"A\"\n\u00E9"@en .
+"#;
+        let o = read_ntriples(nt.as_bytes(), &b).unwrap();
+
+        assert!(o
+            .iter()
+            .any(|aa| aa.axiom == DeclareClass(b.class("http://www.example.com/a")).into()));
+        assert!(o.iter().any(|aa| match &aa.axiom {
+            Axiom::AnnotationAssertion(AnnotationAssertion {
+                ann:
+                    Annotation {
+                        av: AnnotationValue::Literal(Literal::Language { literal, lang }),
+                        ..
+                    },
+                ..
+            }) => literal == "\"A\"\n\u{e9}" && lang == "en",
+            _ => false,
+        }));
+    }
+
+    #[test]
+    fn read_error_line() {
+        let b = Build::new_rc();
+        let nt = "<http://www.example.com/o> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/2002/07/owl#Ontology> .\n\
+                  <http://www.example.com/a> <http://www.example.com/p> \"unterminated .\n";
+
+        match read_ntriples(nt.as_bytes(), &b) {
+            Err(HornedError::ParserError(_, Location::Line(2))) => {}
+            r => panic!("Expected an error on line 2, got {:?}", r),
+        }
+    }
+
+    #[test]
+    fn test_escape_iri() {
+        let b = Build::new_rc();
+        let t = Triple {
+            subject: TripleSubject::IRI(b.iri("http://www.example.com/a b")),
+            predicate: b.iri("http://www.example.com/p"),
+            object: TripleObject::Literal(Literal::Language {
+                literal: "x".into(),
+                lang: "en".into(),
+            }),
+        };
+
+        assert_eq!(
+            t.to_string(),
+            "<http://www.example.com/a\\u0020b> <http://www.example.com/p> \"x\"@en ."
+        );
+    }
+}
@@ -4,8 +4,12 @@ use rio_api::{
 };
 use Term::*;
 
-use crate::{error::HornedError, io::ParserConfiguration};
+use crate::io::encoding::utf8_xml;
 use crate::model::*;
+use crate::{
+    error::{invalid, HornedError, Location},
+    io::{ParserConfiguration, UnsupportedPolicy},
+};
 use crate::{model::Literal, ontology::axiom_mapped::AxiomMappedOntology};
 
 use crate::ontology::indexed::ForIndex;
@@ -24,13 +28,17 @@ use crate::{
     vocab::RDFS as VRDFS,
 };
 
+use curie::PrefixMapping;
 use enum_meta::Meta;
 
 use std::cmp::Ordering;
 use std::collections::BTreeSet;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::io::BufRead;
 use std::io::Cursor;
+use std::io::Read;
+use std::rc::Rc;
 
 type RioTerm<'a> = ::rio_api::model::Term<'a>;
 
@@ -193,7 +201,7 @@ impl<A: ForIRI> From<Term<A>> for OrTerm<A> {
     }
 }
 
-fn vocab_lookup<A: ForIRI>() -> HashMap<&'static str, Term<A>> {
+pub(crate) fn vocab_lookup<A: ForIRI>() -> HashMap<&'static str, Term<A>> {
     let mut m = HashMap::default();
 
     for v in VOWL::all() {
@@ -218,7 +226,7 @@ fn vocab_lookup<A: ForIRI>() -> HashMap<&'static str, Term<A>> {
     m
 }
 
-fn to_term_nn<'a, A: ForIRI>(
+pub(crate) fn to_term_nn<'a, A: ForIRI>(
     nn: &'a NamedNode,
     m: &HashMap<&str, Term<A>>,
     b: &Build<A>,
@@ -258,7 +266,7 @@ fn to_term_lt<'a, A: ForIRI>(lt: &'a rio_api::model::Literal, b: &Build<A>) -> T
     }
 }
 
-fn to_term_nnb<'a, A: ForIRI>(
+pub(crate) fn to_term_nnb<'a, A: ForIRI>(
     nnb: &'a Subject,
     m: &HashMap<&str, Term<A>>,
     b: &Build<A>,
@@ -270,7 +278,11 @@ fn to_term_nnb<'a, A: ForIRI>(
     }
 }
 
-fn to_term<'a, A: ForIRI>(t: &'a RioTerm, m: &HashMap<&str, Term<A>>, b: &Build<A>) -> Term<A> {
+pub(crate) fn to_term<'a, A: ForIRI>(
+    t: &'a RioTerm,
+    m: &HashMap<&str, Term<A>>,
+    b: &Build<A>,
+) -> Term<A> {
     match t {
         rio_api::model::Term::NamedNode(iri) => to_term_nn(iri, m, b),
         rio_api::model::Term::BlankNode(id) => to_term_bn(id),
@@ -365,11 +377,38 @@ pub struct IncompleteParse<A: ForIRI> {
     pub object_property_expression: Vec<ObjectPropertyExpression<A>>,
     pub data_range: Vec<DataRange<A>>,
     pub ann_map: HashMap<[Term<A>; 3], BTreeSet<Annotation<A>>>,
+
+    /// Triples which could not be parsed, when using
+    /// `UnsupportedPolicy::Collect`.
+    pub unsupported: Vec<PosTriple<A>>,
+
+    /// Counts of what was read.
+    pub stats: ReadStats,
+
+    /// The namespace prefixes declared on the root element of the
+    /// document, which can be passed to the writer.
+    pub mapping: PrefixMapping,
+}
+
+/// Counts of the triples and axioms read from an RDF document.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ReadStats {
+    /// The number of triples in the document.
+    pub triples: usize,
+
+    /// The number of axioms in the ontology.
+    pub axioms: usize,
+
+    /// The number of axioms which were dropped because an equal
+    /// axiom, with the same or more annotations, had already been
+    /// read.
+    pub duplicates_dropped: usize,
 }
 
 impl<A: ForIRI> IncompleteParse<A> {
     pub fn is_complete(&self) -> bool {
         self.simple.is_empty()
+            && self.unsupported.is_empty()
             && self.bnode.is_empty()
             && self.bnode_seq.is_empty()
             && self.class_expression.is_empty()
@@ -380,7 +419,7 @@ impl<A: ForIRI> IncompleteParse<A> {
 }
 
 #[derive(Clone,Debug)]
-pub struct PosTriple<A:ForIRI>([Term<A>; 3], usize);
+pub struct PosTriple<A:ForIRI>(pub(crate) [Term<A>; 3], pub(crate) usize);
 
 impl<A:ForIRI> From<[Term<A>; 3]> for PosTriple<A> {
     fn from(t: [Term<A>; 3]) -> PosTriple<A> {
@@ -433,10 +472,16 @@ pub struct OntologyParser<'a, A: ForIRI, AA: ForIndex<A>> {
     ann_map: HashMap<[Term<A>; 3], BTreeSet<Annotation<A>>>,
     state: OntologyParserState,
     error: Result<(), HornedError>,
+    stats: ReadStats,
+    mapping: PrefixMapping,
 }
 
 impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
     pub fn new(b: &'a Build<A>, triple: Vec<PosTriple<A>>, config: ParserConfiguration) -> OntologyParser<'a, A, AA> {
+        let stats = ReadStats {
+            triples: triple.len(),
+            ..Default::default()
+        };
         OntologyParser {
             o: RDFOntology(ThreeIndexedOntology::new(
                 SetIndex::new(),
@@ -457,6 +502,8 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
             ann_map: d!(),
             state: OntologyParserState::New,
             error: Ok(()),
+            stats,
+            mapping: d!(),
         }
     }
 
@@ -465,31 +512,16 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
         bufread: &'b mut R,
         config: ParserConfiguration,
     ) -> OntologyParser<'a, A, AA> {
-        let m = vocab_lookup();
-
-        let mut parser = rio_xml::RdfXmlParser::new(bufread, None);
-        let mut triples = vec![];
-        let last_pos = std::cell::Cell::new(0);
-        let mut on_triple = |rio_triple: rio_api::model::Triple| -> Result<_, HornedError> {
-            triples.push(
-                PosTriple(
-                    [
-                        to_term_nnb(&rio_triple.subject, &m, b),
-                        to_term_nn(&rio_triple.predicate, &m, b),
-                        to_term(&rio_triple.object, &m, b),
-                    ],
-                    last_pos.get()
-                )
-            );
-            Ok(())
-        };
-
-        while !parser.is_end() {
-            parser.parse_step(&mut on_triple).unwrap();
-            last_pos.set(parser.buffer_position());
+        match read_triples(b, bufread, &config) {
+            Ok((triples, mapping)) => OntologyParser {
+                mapping,
+                ..OntologyParser::new(b, triples, config)
+            },
+            Err(e) => OntologyParser {
+                error: Err(e),
+                ..OntologyParser::new(b, vec![], config)
+            },
         }
-
-        OntologyParser::new(b, triples, config)
     }
 
     pub fn from_doc_iri(b: &'a Build<A>, iri: &IRI<A>, config: ParserConfiguration) -> OntologyParser<'a, A, AA> {
@@ -526,37 +558,57 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
         }
     }
 
-    fn stitch_seqs_1(&mut self) {
-        let mut extended = false;
+    /// Extend each list in `bnode_seq` back towards its head, one
+    /// node at a time, so that each node is visited only once.
+    fn stitch_seqs_1(&mut self) -> Result<(), HornedError> {
+        let max_list_length = self.config.rdf.max_list_length;
+
+        // Map the rest of each list node to the node
+        let mut prev: HashMap<BNode<A>, BNode<A>> = HashMap::new();
+        for (k, v) in &self.bnode {
+            if let [[_, Term::RDF(VRDF::First), _],
+                    [_, Term::RDF(VRDF::Rest), Term::BNode(bnode_id)],
+                    // Some sequences have a Type List, some do not
+                    ..
+            ] = v.as_slice() {
+                prev.insert(bnode_id.clone(), k.clone());
+            }
+        }
 
-        for (k, v) in std::mem::take(&mut self.bnode) {
-            match v.as_slice() {
-                [[_, Term::RDF(VRDF::First), val],
-                 [_, Term::RDF(VRDF::Rest), Term::BNode(bnode_id)],
-                 // Some sequences have a Type List, some do not
-                 ..
-                ] => {
-                    let some_seq = self.bnode_seq.remove(bnode_id);
-                    if let Some(mut seq) = some_seq {
-                        seq.push(val.clone());
-                        self.bnode_seq.insert(k.clone(), seq);
-                        extended = true;
-                    } else {
-                        self.bnode.insert(k, v);
-                    }
-                }
-                _ => {
-                    self.bnode.insert(k, v);
+        let ends: Vec<_> = self.bnode_seq.keys().cloned().collect();
+        for end in ends {
+            let mut head = end;
+            while let Some(k) = prev.remove(&head) {
+                let v = self.bnode.remove(&k).unwrap();
+                let mut seq = self.bnode_seq.remove(&head).unwrap();
+                if seq.len() >= max_list_length {
+                    return Err(HornedError::DepthExceeded(max_list_length, v.1.into()));
                 }
-            };
+                seq.push(v[0][2].clone());
+                self.bnode_seq.insert(k.clone(), seq);
+                head = k;
+            }
         }
 
-        if extended && !self.bnode.is_empty(){
-            self.stitch_seqs_1()
+        Ok(())
+    }
+
+    /// Fail if a pass at `depth` which resolved some blank node
+    /// expression is deeper than the configured maximum.
+    ///
+    /// Each pass of `class_expressions` and `data_ranges` resolves at
+    /// least one more level of nesting, so nesting up to `max_depth`
+    /// never fails.
+    fn check_depth(&self, depth: usize) -> Result<(), HornedError> {
+        let max_depth = self.config.rdf.max_depth;
+        if depth > max_depth {
+            Err(HornedError::DepthExceeded(max_depth, Location::Unknown))
+        } else {
+            Ok(())
         }
     }
 
-    fn stitch_seqs(&mut self) {
+    fn stitch_seqs(&mut self) -> Result<(), HornedError> {
         for (k, v) in std::mem::take(&mut self.bnode) {
             match v.as_slice() {
                 [[_, Term::RDF(VRDF::First), val],
@@ -573,11 +625,80 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
             };
         }
 
-        self.stitch_seqs_1();
+        self.stitch_seqs_1()?;
 
         for (_, v) in self.bnode_seq.iter_mut() {
             v.reverse();
         }
+
+        self.check_seqs()
+    }
+
+    /// Return an error for the earliest list which could not be
+    /// stitched.
+    ///
+    /// Well-formed lists have all been stitched at this point, so any
+    /// remaining node with `rdf:first` or `rdf:rest` belongs to a
+    /// list which is missing one of these, which does not end in
+    /// `rdf:nil` or which is cyclic.
+    fn check_seqs(&self) -> Result<(), HornedError> {
+        let is_list_triple =
+            |t: &[Term<A>; 3]| matches!(t[1], Term::RDF(VRDF::First) | Term::RDF(VRDF::Rest));
+
+        let mut malformed = None;
+        for (k, v) in &self.bnode {
+            if !v.iter().any(is_list_triple) {
+                continue;
+            }
+
+            let mut visited = HashSet::new();
+            let mut node = (k, v);
+            let problem = loop {
+                if !visited.insert(node.0) {
+                    break Some("the list is cyclic");
+                }
+                let firsts = node
+                    .1
+                    .iter()
+                    .filter(|t| t[1] == Term::RDF(VRDF::First))
+                    .count();
+                let rests: Vec<_> = node
+                    .1
+                    .iter()
+                    .filter(|t| t[1] == Term::RDF(VRDF::Rest))
+                    .map(|t| &t[2])
+                    .collect();
+                match (firsts, rests.as_slice()) {
+                    (0, _) => break Some("a list node has no rdf:first"),
+                    (_, []) => break Some("a list node has no rdf:rest"),
+                    (1, [Term::RDF(VRDF::Nil)]) => break None,
+                    (1, [Term::BNode(next)]) => {
+                        if self.bnode_seq.contains_key(next) {
+                            break None;
+                        }
+                        match self.bnode.get_key_value(next) {
+                            Some(n) => node = n,
+                            None => break Some("an rdf:rest is not a list"),
+                        }
+                    }
+                    (1, [_]) => break Some("an rdf:rest is not a list"),
+                    _ => break Some("a list node has more than one rdf:first or rdf:rest"),
+                }
+            };
+
+            if let Some(problem) = problem {
+                if !matches!(&malformed, Some((pos, _)) if *pos <= v.1) {
+                    malformed = Some((v.1, problem));
+                }
+            }
+        }
+
+        match malformed {
+            Some((pos, problem)) => {
+                Err(HornedError::MalformedList(problem.to_string(), pos.into()))
+            }
+            None => Ok(()),
+        }
     }
 
     fn resolve_imports(&mut self) -> Vec<IRI<A>> {
@@ -620,7 +741,12 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
             }
         }
 
-        self.o.0.mut_id().iri = iri;
+        self.o.0.mut_id().iri = iri.or_else(|| {
+            self.config
+                .default_ontology_iri
+                .as_ref()
+                .map(|s| self.b.iri(s.as_str()))
+        });
         self.o.0.mut_id().viri = viri;
     }
 
@@ -668,6 +794,14 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
 
     fn merge<IAA: Into<AnnotatedAxiom<A>>>(&mut self, ax: IAA) {
         let ax = ax.into();
+        let lei: &LogicallyEqualIndex<_, _> = self.o.0.as_ref();
+        if lei
+            .logical_get(&ax)
+            .is_some_and(|present| ax.ann.is_subset(&present.ann))
+        {
+            self.stats.duplicates_dropped += 1;
+            return;
+        }
         update_or_insert_logically_equal_axiom(&mut self.o.0, ax);
     }
 
@@ -731,8 +865,8 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
         }
     }
 
-    fn data_ranges(&mut self) -> Result<(), HornedError>{
-        let data_range_len = self.data_range.len();
+    fn data_ranges(&mut self, depth: usize) -> Result<(), HornedError>{
+        let mut progress = false;
         let mut facet_map: HashMap<Term<A>, PosTriple<A>> = HashMap::new();
 
         for (k, v) in std::mem::take(&mut self.bnode) {
@@ -757,6 +891,15 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
                         )
                     }
                 }
+                [[_, Term::OWL(VOWL::UnionOf), Term::BNode(bnodeid)],//:
+                 [_, Term::RDF(VRDF::Type), Term::RDFS(VRDFS::Datatype)]] =>
+                {
+                    ok_some! {
+                        DataRange::DataUnionOf(
+                            self.fetch_dr_seq(bnodeid)?
+                        )
+                    }
+                }
                 [[_, Term::OWL(VOWL::DatatypeComplementOf), term],//:
                  [_, Term::RDF(VRDF::Type), Term::RDFS(VRDFS::Datatype)]] =>
                 {
@@ -809,13 +952,15 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
 
             if let Some(dr) = dr? {
                 self.data_range.insert(this_bnode, dr);
+                progress = true;
             } else {
                 self.bnode.insert(this_bnode, v);
             }
         }
 
-        if self.data_range.len() > data_range_len {
-            self.data_ranges()?;
+        if progress {
+            self.check_depth(depth)?;
+            self.data_ranges(depth + 1)?;
         }
 
         // Shove any remaining facets back onto bnode so that they get
@@ -925,6 +1070,13 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
     }
 
     fn fetch_dr_seq(&mut self, bnodeid: &BNode<A>) -> Option<Vec<DataRange<A>>> {
+        if !self.bnode_seq.get(bnodeid)?.iter().all(|t| match t {
+            Term::BNode(id) => self.data_range.contains_key(id),
+            _ => true,
+        }) {
+            return None;
+        }
+
         self
             .bnode_seq
             .remove(bnodeid)
@@ -996,6 +1148,85 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
             .flatten()
     }
 
+    /// Return every kind with which each IRI is declared, in this
+    /// ontology or its import closure.
+    ///
+    /// Unlike `find_declaration_kind`, which returns a single kind,
+    /// this includes all the kinds of a punned IRI.
+    fn find_declaration_kinds(
+        &self,
+        ic: &[&RDFOntology<A, AA>],
+    ) -> HashMap<IRI<A>, BTreeSet<NamedEntityKind>> {
+        let mut kinds: HashMap<IRI<A>, BTreeSet<NamedEntityKind>> = HashMap::new();
+        for o in [&self.o].iter().chain(ic.iter()) {
+            for ax in o.0.i() {
+                let (iri, kind) = match &ax.axiom {
+                    Axiom::DeclareClass(DeclareClass(e)) => (&e.0, NamedEntityKind::Class),
+                    Axiom::DeclareObjectProperty(DeclareObjectProperty(e)) => {
+                        (&e.0, NamedEntityKind::ObjectProperty)
+                    }
+                    Axiom::DeclareAnnotationProperty(DeclareAnnotationProperty(e)) => {
+                        (&e.0, NamedEntityKind::AnnotationProperty)
+                    }
+                    Axiom::DeclareDataProperty(DeclareDataProperty(e)) => {
+                        (&e.0, NamedEntityKind::DataProperty)
+                    }
+                    Axiom::DeclareNamedIndividual(DeclareNamedIndividual(e)) => {
+                        (&e.0, NamedEntityKind::NamedIndividual)
+                    }
+                    Axiom::DeclareDatatype(DeclareDatatype(e)) => (&e.0, NamedEntityKind::Datatype),
+                    _ => continue,
+                };
+                kinds.entry(iri.clone()).or_default().insert(kind);
+            }
+        }
+        kinds
+    }
+
+    /// Return an error for the earliest triple which uses an IRI as
+    /// a property, when it is declared only as other kinds of entity.
+    fn check_property_usage(&mut self, ic: &[&RDFOntology<A, AA>]) -> Result<(), HornedError> {
+        let mut used: Vec<(IRI<A>, usize)> = self
+            .simple
+            .iter()
+            .map(|t| (&t.0, t.1))
+            .chain(
+                self.bnode
+                    .values()
+                    .flat_map(|v| v.iter().map(move |t| (t, v.1))),
+            )
+            .filter_map(|(t, pos)| match t {
+                [_, Term::OWL(VOWL::OnProperty), Term::Iri(iri)]
+                | [_, Term::OWL(VOWL::InverseOf), Term::Iri(iri)]
+                | [_, Term::Iri(iri), _] => Some((iri.clone(), pos)),
+                _ => None,
+            })
+            .collect();
+        used.sort_by_key(|(_, pos)| *pos);
+
+        let mut declared = self.find_declaration_kinds(ic);
+        for (iri, pos) in used {
+            let kinds = declared
+                .entry(iri.clone())
+                .or_insert_with(|| crate::vocab::to_built_in_entity(&iri).into_iter().collect());
+            let is_property = kinds.iter().any(|k| {
+                matches!(
+                    k,
+                    NamedEntityKind::ObjectProperty
+                        | NamedEntityKind::DataProperty
+                        | NamedEntityKind::AnnotationProperty
+                )
+            });
+            if !is_property && !kinds.is_empty() {
+                return Err(HornedError::ValidityError(
+                    format!("{} is declared as {:?} but used as a property", iri, kinds),
+                    pos.into(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
     fn find_property_kind(
         &mut self,
         term: &Term<A>,
@@ -1023,8 +1254,10 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
         }
     }
 
-    fn class_expressions(&mut self, ic: &[&RDFOntology<A, AA>]) -> Result<(), HornedError>{
-        let class_expression_len = self.class_expression.len();
+    fn class_expressions(&mut self, ic: &[&RDFOntology<A, AA>], depth: usize) -> Result<(), HornedError>{
+        // Fetching a nested expression removes it, so the number of
+        // expressions need not grow when progress is made
+        let mut progress = false;
         for (this_bnode, v) in std::mem::take(&mut self.bnode) {
             // rustfmt breaks this (putting the triples all on one
             // line) so skip
@@ -1050,6 +1283,19 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
                         }
                     }
                 },
+                [[_, Term::OWL(VOWL::HasSelf), _],//:
+                 [_, Term::OWL(VOWL::OnProperty), pr],//:
+                 [_, Term::RDF(VRDF::Type), Term::OWL(VOWL::Restriction)]] => {
+                    match self.find_property_kind(pr, ic) {
+                        Some(PropertyExpression::ObjectPropertyExpression(ope)) => {
+                            Ok(Some(ClassExpression::ObjectHasSelf(ope)))
+                        },
+                        Some(_) => Err(HornedError::invalid(
+                            "hasSelf restriction on a property which is not an object property"
+                        )),
+                        None => Ok(None),
+                    }
+                },
                 [[_, Term::OWL(VOWL::HasValue), val],//:
                  [_, Term::OWL(VOWL::OnProperty), pr],//:
                  [_, Term::RDF(VRDF::Type), Term::OWL(VOWL::Restriction)]] => {
@@ -1258,13 +1504,15 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
 
             if let Some(ce) = ce? {
                 self.class_expression.insert(this_bnode, ce);
+                progress = true;
             } else {
                 self.bnode.insert(this_bnode, v);
             }
         }
 
-        if self.class_expression.len() > class_expression_len {
-            self.class_expressions(ic)?
+        if progress {
+            self.check_depth(depth)?;
+            self.class_expressions(ic, depth + 1)?
         }
 
         Ok(())
@@ -1314,6 +1562,15 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
                         ).into()
                     }
                 }
+                [[_, Term::OWL(VOWL::Members), Term::BNode(bnodeid)],//:
+                 [_, Term::RDF(VRDF::Type), Term::OWL(VOWL::AllDisjointClasses)]] =>
+                {
+                    ok_some! {
+                        DisjointClasses (
+                            self.fetch_ce_seq(bnodeid)?
+                        ).into()
+                    }
+                }
                 _ => Ok(None),
             };
 
@@ -1670,9 +1927,11 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
                 [Term::Iri(iri), _, _]
                     if self.o.id().iri.as_ref() == Some (iri) =>
                 {
-                    self.o.insert(
+                    if !self.o.insert(
                         OntologyAnnotation(self.annotation(&triple.0))
-                    );
+                    ) {
+                        self.stats.duplicates_dropped += 1;
+                    }
                 }
                 [Term::Iri(iri), Term::RDFS(rdfs), _] if rdfs.is_builtin() => {
                     firi(self, &triple.0, iri)
@@ -1730,7 +1989,7 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
                     vec.sort();
                 }
 
-                self.stitch_seqs();
+                self.stitch_seqs()?;
 
                 // Table 10
                 self.axiom_annotations();
@@ -1811,16 +2070,20 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
     /// ontologies do not need to be completely parsed, but will be
     /// relied on to resolve declarations.
     pub fn finish_parse(&mut self, ic: &[&RDFOntology<A, AA>]) -> Result<(), HornedError> {
+        if self.config.rdf.strict_types {
+            self.check_property_usage(ic)?;
+        }
+
         // Table 10
         self.simple_annotations(false);
 
-        self.data_ranges()?;
+        self.data_ranges(1)?;
 
         // Table 8:
         self.object_property_expressions();
 
         // Table 13: Parsing of Class Expressions
-        self.class_expressions(ic)?;
+        self.class_expressions(ic, 1)?;
 
         // Table 16: Axioms without annotations
         self.axioms(ic)?;
@@ -1884,7 +2147,35 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
 
         Self::group_triples(std::mem::take(&mut self.simple), &mut simple, &mut bnode);
 
-        let bnode: Vec<_> = bnode.into_iter().map(|kv| kv.1).collect();
+        let mut bnode: Vec<_> = bnode.into_iter().map(|kv| kv.1).collect();
+        let mut unsupported = vec![];
+
+        match self.config.rdf.on_unsupported {
+            UnsupportedPolicy::Skip => {}
+            UnsupportedPolicy::Error => {
+                let first = simple
+                    .iter()
+                    .map(|t| (t.1, format!("{:?}", t.0)))
+                    .chain(bnode.iter().map(|v| (v.1, format!("{:?}", v.0))))
+                    .min_by_key(|(pos, _)| *pos);
+
+                if let Some((pos, t)) = first {
+                    return Err(HornedError::UnsupportedConstruct(
+                        format!("Unsupported triples: {}", t),
+                        pos.into(),
+                    ));
+                }
+            }
+            UnsupportedPolicy::Collect => {
+                unsupported.append(&mut simple);
+                for v in bnode.drain(..) {
+                    let pos = v.1;
+                    unsupported.extend(v.into_iter().map(|t| PosTriple(t, pos)));
+                }
+                unsupported.sort_by_key(|t| t.1);
+            }
+        }
+
         let bnode_seq: Vec<_> = self.bnode_seq.into_iter().map(|kv| kv.1).collect();
         let class_expression: Vec<_> = self.class_expression.into_iter().map(|kv| kv.1).collect();
         let object_property_expression: Vec<_> = self
@@ -1893,6 +2184,10 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
             .map(|kv| kv.1)
             .collect();
         let data_range = self.data_range.into_iter().map(|kv| kv.1).collect();
+        let stats = ReadStats {
+            axioms: self.o.0.i().len(),
+            ..self.stats
+        };
 
         Ok((
             self.o,
@@ -1904,11 +2199,145 @@ impl<'a, A: ForIRI, AA: ForIndex<A>> OntologyParser<'a, A, AA> {
                 object_property_expression,
                 data_range,
                 ann_map: self.ann_map,
+                unsupported,
+                stats,
+                mapping: self.mapping,
             },
         ))
     }
 }
 
+/// A `BufRead` which keeps a copy of every byte consumed from it.
+struct Tee<'a, R> {
+    inner: &'a mut R,
+    read: Vec<u8>,
+}
+
+impl<R: BufRead> Read for Tee<'_, R> {
+    fn read(&mut self, out: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(out)?;
+        self.read.extend_from_slice(&out[..n]);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for Tee<'_, R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if let Ok(buf) = self.inner.fill_buf() {
+            self.read.extend_from_slice(&buf[..amt]);
+        }
+        self.inner.consume(amt)
+    }
+}
+
+/// Return the namespace prefixes declared on the root element of an
+/// RDF/XML document, with the bytes read from `bufread` to find
+/// them.
+///
+/// Reading stops at the end of the root start tag. The default
+/// namespace and the `xml` prefix are not included.
+fn read_prefixes<R: BufRead>(bufread: &mut R) -> (PrefixMapping, Vec<u8>) {
+    let mut mapping = PrefixMapping::default();
+    let mut reader = quick_xml::Reader::from_reader(Tee {
+        inner: bufread,
+        read: vec![],
+    });
+    let mut event_buf = vec![];
+
+    loop {
+        match reader.read_event(&mut event_buf) {
+            Ok(quick_xml::events::Event::Start(ref e))
+            | Ok(quick_xml::events::Event::Empty(ref e)) => {
+                for attr in e.attributes().flatten() {
+                    if let Some(prefix) = attr.key.strip_prefix(b"xmlns:") {
+                        if prefix == b"xml" {
+                            continue;
+                        }
+                        if let Ok(iri) = attr.unescaped_value() {
+                            mapping
+                                .add_prefix(
+                                    &String::from_utf8_lossy(prefix),
+                                    &String::from_utf8_lossy(&iri),
+                                )
+                                .ok();
+                        }
+                    }
+                }
+                break;
+            }
+            Ok(quick_xml::events::Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+        event_buf.clear();
+    }
+    (mapping, reader.into_underlying_reader().read)
+}
+
+fn read_triples<A: ForIRI, R: BufRead>(
+    b: &Build<A>,
+    bufread: &mut R,
+    config: &ParserConfiguration,
+) -> Result<(Vec<PosTriple<A>>, PrefixMapping), HornedError> {
+    if let Some(buf) = utf8_xml(bufread)? {
+        return read_triples(b, &mut buf.as_slice(), config);
+    }
+    let m = vocab_lookup();
+
+    // The triple parser does not report namespace declarations, so
+    // read them from the root element first, and then parse the
+    // bytes which that consumed followed by the rest of the stream
+    let (mapping, head) = read_prefixes(bufread);
+    let bufread = &mut head.as_slice().chain(bufread);
+
+    let base_iri = config
+        .rdf
+        .base_iri
+        .as_ref()
+        .map(|iri| {
+            oxiri::Iri::parse(iri.clone()).map_err(|e| invalid!("Invalid base IRI {}: {}", iri, e))
+        })
+        .transpose()?;
+    let mut parser = rio_xml::RdfXmlParser::new(bufread, base_iri);
+    let mut triples = vec![];
+    let last_pos = std::cell::Cell::new(0);
+    let mut on_triple = |rio_triple: rio_api::model::Triple| -> Result<_, HornedError> {
+        let object = match to_term(&rio_triple.object, &m, b) {
+            Term::Literal(l) if config.canonicalize_literals => Term::Literal(l.canonical()),
+            t => t,
+        };
+        triples.push(
+            PosTriple(
+                [
+                    to_term_nnb(&rio_triple.subject, &m, b),
+                    to_term_nn(&rio_triple.predicate, &m, b),
+                    object,
+                ],
+                last_pos.get()
+            )
+        );
+        if let Some(progress) = &config.progress {
+            progress.tick(triples.len());
+        }
+        Ok(())
+    };
+
+    while !parser.is_end() {
+        parser.parse_step(&mut on_triple).map_err(|e| match e {
+            HornedError::ParserError(e, Location::Unknown) => {
+                HornedError::ParserError(e, parser.buffer_position().into())
+            }
+            e => e,
+        })?;
+        last_pos.set(parser.buffer_position());
+    }
+
+    Ok((triples, mapping))
+}
+
 pub fn parser_with_build<'a, 'b, A: ForIRI, AA: ForIndex<A>, R: BufRead>(
     bufread: &'a mut R,
     build: &'b Build<A>,
@@ -1939,6 +2368,128 @@ pub fn read<R: BufRead>(
     read_with_build(bufread, &b, config)
 }
 
+/// Read only the `OntologyID` and imports of an RDF/XML document.
+///
+/// The header may occur anywhere in an RDF document, so all of its
+/// triples are read, but no axioms are parsed.
+pub fn read_header<A: ForIRI, R: BufRead>(
+    mut bufread: R,
+    build: &Build<A>,
+) -> Result<(OntologyID<A>, Vec<IRI<A>>), HornedError> {
+    let (triples, _) = read_triples(build, &mut bufread, &ParserConfiguration::default())?;
+
+    let iri = triples.iter().find_map(|t| match &t.0 {
+        [Term::Iri(s), Term::RDF(VRDF::Type), Term::OWL(VOWL::Ontology)] => Some(s.clone()),
+        _ => None,
+    });
+
+    let mut id = OntologyID::default();
+    let mut imports = vec![];
+    if let Some(iri) = iri {
+        for t in &triples {
+            match &t.0 {
+                [Term::Iri(s), Term::OWL(VOWL::VersionIRI), Term::Iri(ob)] if *s == iri => {
+                    id.viri = Some(ob.clone());
+                }
+                [Term::Iri(s), Term::OWL(VOWL::Imports), Term::Iri(ob)]
+                    if *s == iri && !imports.contains(ob) =>
+                {
+                    imports.push(ob.clone());
+                }
+                _ => {}
+            }
+        }
+        id.iri = Some(iri);
+    }
+
+    Ok((id, imports))
+}
+
+/// Read every ontology in an RDF document.
+///
+/// Most documents contain a single `owl:Ontology` node, but some
+/// contain several. Triples about each ontology IRI, such as its
+/// version IRI, imports and annotations, are returned with that
+/// ontology, as are the triples of any blank nodes they refer to,
+/// or which annotate them. All other triples cannot be tied to a
+/// single ontology, so go to the first ontology in the document. A
+/// document with no `owl:Ontology` node returns a single ontology
+/// with no IRI.
+///
+/// As with `read`, the triples of each ontology which could not be
+/// parsed are returned with it.
+#[allow(clippy::type_complexity)]
+pub fn read_all<A: ForIRI, R: BufRead>(
+    bufread: &mut R,
+    build: &Build<A>,
+    config: ParserConfiguration,
+) -> Result<Vec<(SetOntology<A>, IncompleteParse<A>)>, HornedError> {
+    let (triples, _) = read_triples(build, bufread, &config)?;
+
+    let mut ontology_iris: Vec<IRI<A>> = vec![];
+    for t in &triples {
+        if let [Term::Iri(s), Term::RDF(VRDF::Type), Term::OWL(VOWL::Ontology)] = &t.0 {
+            if !ontology_iris.contains(s) {
+                ontology_iris.push(s.clone());
+            }
+        }
+    }
+    let position = |s: &IRI<A>| ontology_iris.iter().position(|o| o == s);
+
+    let mut groups: Vec<Vec<PosTriple<A>>> =
+        (0..ontology_iris.len().max(1)).map(|_| vec![]).collect();
+    let mut bnodes: HashMap<BNode<A>, Vec<PosTriple<A>>> = HashMap::new();
+    for t in triples {
+        match &t.0[0] {
+            Term::BNode(id) => bnodes.entry(id.clone()).or_default().push(t),
+            Term::Iri(s) => groups[position(s).unwrap_or(0)].push(t),
+            _ => groups[0].push(t),
+        }
+    }
+
+    // A blank node belongs to the ontology which refers to it, or
+    // whose annotation it annotates; the rest go to the first
+    let mut annotating: Vec<Vec<BNode<A>>> = groups.iter().map(|_| vec![]).collect();
+    for (id, v) in &bnodes {
+        if let Some(i) = v.iter().find_map(|t| match &t.0 {
+            [_, Term::OWL(VOWL::AnnotatedSource), Term::Iri(s)] => position(s),
+            _ => None,
+        }) {
+            annotating[i].push(id.clone());
+        }
+    }
+    for (group, annotating) in groups.iter_mut().zip(annotating) {
+        let mut stack: Vec<BNode<A>> = group
+            .iter()
+            .filter_map(|t| match &t.0[2] {
+                Term::BNode(id) => Some(id.clone()),
+                _ => None,
+            })
+            .chain(annotating)
+            .collect();
+        while let Some(id) = stack.pop() {
+            if let Some(v) = bnodes.remove(&id) {
+                stack.extend(v.iter().filter_map(|t| match &t.0[2] {
+                    Term::BNode(id) => Some(id.clone()),
+                    _ => None,
+                }));
+                group.extend(v);
+            }
+        }
+    }
+    groups[0].extend(bnodes.into_values().flatten());
+
+    groups
+        .into_iter()
+        .map(|mut g| {
+            g.sort_by_key(|t| t.1);
+            OntologyParser::<A, Rc<AnnotatedAxiom<A>>>::new(build, g, config.clone())
+                .parse()
+                .map(|(o, incomplete)| (o.into(), incomplete))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -2119,13 +2670,202 @@ mod test {
         compare("annotation");
     }
 
+    #[test]
+    fn relative_iri_with_base() {
+        let ont_s = r##"<?xml version="1.0"?>
+<rdf:RDF xmlns:owl="http://www.w3.org/2002/07/owl#"
+         xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+    <owl:Ontology rdf:about="http://www.example.com/iri"/>
+    <owl:Class rdf:about="#Foo"/>
+</rdf:RDF>"##;
+
+        let (ont, incomplete) = read(
+            &mut ont_s.as_bytes(),
+            ParserConfiguration {
+                rdf: RDFParserConfiguration {
+                    base_iri: Some("http://www.example.com/iri".to_string()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(incomplete.is_complete());
+
+        let ont: AxiomMappedOntology<_, _> = ont.into();
+        let b = Build::new_rc();
+        assert_eq!(
+            ont.i().declare_class().next(),
+            Some(&DeclareClass(b.class("http://www.example.com/iri#Foo")))
+        );
+    }
+
+    #[test]
+    fn invalid_base_iri() {
+        let ont_s = r##"<?xml version="1.0"?>
+<rdf:RDF xmlns:owl="http://www.w3.org/2002/07/owl#"
+         xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+    <owl:Class rdf:about="#Foo"/>
+</rdf:RDF>"##;
+
+        let r = read(
+            &mut ont_s.as_bytes(),
+            ParserConfiguration {
+                rdf: RDFParserConfiguration {
+                    base_iri: Some("not an iri".to_string()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+        assert!(matches!(r, Err(HornedError::ValidityError(_, _))));
+    }
+
+    fn read_unsupported(
+        policy: UnsupportedPolicy,
+    ) -> Result<
+        (
+            AxiomMappedOntology<RcStr, RcAnnotatedAxiom>,
+            IncompleteParse<RcStr>,
+        ),
+        HornedError,
+    > {
+        // In strict mode, the annotation on an undeclared property is
+        // not supported
+        let ont_s = slurp_rdfont("manual/annotation_no_declaration");
+        read(
+            &mut ont_s.as_bytes(),
+            ParserConfiguration {
+                rdf: RDFParserConfiguration {
+                    on_unsupported: policy,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )
+        .map(|(o, i)| (o.into(), i))
+    }
+
+    #[test]
+    fn strict_property_usage() {
+        // C is declared as a class, but used as an object property
+        let ont_s = r#"<?xml version="1.0"?>
+<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+         xmlns:owl="http://www.w3.org/2002/07/owl#"
+         xmlns:rdfs="http://www.w3.org/2000/01/rdf-schema#">
+    <owl:Ontology rdf:about="http://www.example.com/iri"/>
+    <owl:Class rdf:about="http://www.example.com/C"/>
+    <owl:Class rdf:about="http://www.example.com/D">
+        <rdfs:subClassOf>
+            <owl:Restriction>
+                <owl:onProperty rdf:resource="http://www.example.com/C"/>
+                <owl:someValuesFrom rdf:resource="http://www.example.com/C"/>
+            </owl:Restriction>
+        </rdfs:subClassOf>
+    </owl:Class>
+</rdf:RDF>"#;
+        let read_strict = |strict_types| {
+            read(
+                &mut ont_s.as_bytes(),
+                ParserConfiguration {
+                    rdf: RDFParserConfiguration {
+                        strict_types,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+            )
+        };
+
+        let (_, incomp) = read_strict(false).unwrap();
+        assert!(!incomp.is_complete());
+
+        assert!(matches!(
+            read_strict(true),
+            Err(HornedError::ValidityError(_, _))
+        ));
+    }
+
+    #[test]
+    fn strict_property_usage_pun() {
+        // P is declared as both an object property and a class, so
+        // its use as an object property is legal
+        let ont_s = r#"<?xml version="1.0"?>
+<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+         xmlns:owl="http://www.w3.org/2002/07/owl#"
+         xmlns:rdfs="http://www.w3.org/2000/01/rdf-schema#">
+    <owl:Ontology rdf:about="http://www.example.com/iri"/>
+    <owl:ObjectProperty rdf:about="http://www.example.com/P"/>
+    <owl:Class rdf:about="http://www.example.com/P"/>
+    <owl:Class rdf:about="http://www.example.com/D">
+        <rdfs:subClassOf>
+            <owl:Restriction>
+                <owl:onProperty rdf:resource="http://www.example.com/P"/>
+                <owl:someValuesFrom rdf:resource="http://www.example.com/D"/>
+            </owl:Restriction>
+        </rdfs:subClassOf>
+    </owl:Class>
+</rdf:RDF>"#;
+        let read_strict = |strict_types| {
+            read(
+                &mut ont_s.as_bytes(),
+                ParserConfiguration {
+                    rdf: RDFParserConfiguration {
+                        strict_types,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+            )
+        };
+
+        let (lenient, _) = read_strict(false).unwrap();
+        let (strict, _) = read_strict(true).unwrap();
+        let lenient: SetOntology<_> = lenient.into();
+        let strict: SetOntology<_> = strict.into();
+        assert_eq!(lenient, strict);
+    }
+
+    #[test]
+    fn unsupported_skip() {
+        let (ont, incomp) = read_unsupported(UnsupportedPolicy::Skip).unwrap();
+
+        assert_eq!(ont.i().declare_class().count(), 1);
+        assert_eq!(incomp.simple.len(), 1);
+        assert!(incomp.unsupported.is_empty());
+    }
+
+    #[test]
+    fn unsupported_error() {
+        assert!(matches!(
+            read_unsupported(UnsupportedPolicy::Error),
+            Err(HornedError::UnsupportedConstruct(_, _))
+        ));
+    }
+
+    #[test]
+    fn unsupported_collect() {
+        let (ont, incomp) = read_unsupported(UnsupportedPolicy::Collect).unwrap();
+
+        assert_eq!(ont.i().declare_class().count(), 1);
+        assert!(incomp.simple.is_empty());
+        assert_eq!(incomp.unsupported.len(), 1);
+        assert!(matches!(
+            &incomp.unsupported[0].0,
+            [Term::Iri(s), Term::Iri(p), Term::Literal(_)]
+                if s.as_ref() == "http://www.example.com/iri#A"
+                && p.as_ref() == "http://www.example.com/iri#a"
+        ));
+        assert!(!incomp.is_complete());
+    }
+
     #[test]
     fn annotation_missing_declaration() {
         let ont_s = slurp_rdfont("manual/annotation_no_declaration");
 
         let ont_strict = read(&mut ont_s.as_bytes(), Default::default());
         let ont_lax = read(&mut ont_s.as_bytes(), ParserConfiguration {
-            rdf: RDFParserConfiguration{lax:true},
+            rdf: RDFParserConfiguration{lax:true, ..Default::default()},
             ..Default::default()
         });
 
@@ -2211,6 +2951,11 @@ mod test {
         compare("disjoint-class");
     }
 
+    #[test]
+    fn disjoint_with_three() {
+        compare("disjoint_with_three");
+    }
+
     #[test]
     fn disjoint_union() {
         compare("disjoint-union");
@@ -2332,6 +3077,11 @@ mod test {
         compare("object-has-value");
     }
 
+    #[test]
+    fn object_has_self() {
+        compare("object-has-self");
+    }
+
     #[test]
     fn object_one_of() {
         compare("object-one-of");
@@ -2606,6 +3356,415 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn progress_callback() {
+        let calls = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+        let c = calls.clone();
+
+        let config = ParserConfiguration {
+            progress: Some(crate::io::ProgressConfiguration {
+                every: 2,
+                callback: std::sync::Arc::new(move |n| c.lock().unwrap().push(n)),
+            }),
+            ..Default::default()
+        };
+
+        // class.owl contains three triples
+        let b = Build::new_rc();
+        let p: OntologyParser<_, Rc<AnnotatedAxiom<RcStr>>> =
+            parser_with_build(&mut slurp_rdfont("class").as_bytes(), &b, config);
+        let (_, incomplete) = p.parse().unwrap();
+        assert!(incomplete.is_complete());
+
+        assert_eq!(*calls.lock().unwrap(), vec![2]);
+    }
+
+    #[test]
+    fn default_ontology_iri() {
+        let config = ParserConfiguration {
+            default_ontology_iri: Some("http://www.example.com/default".to_string()),
+            ..Default::default()
+        };
+        let headerless = r#"<?xml version="1.0"?>
+<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+         xmlns:owl="http://www.w3.org/2002/07/owl#">
+    <owl:Class rdf:about="http://www.example.com/C"/>
+</rdf:RDF>"#;
+
+        let (ont, incomplete) = read(&mut headerless.as_bytes(), config.clone()).unwrap();
+        assert!(incomplete.is_complete());
+        let ont: SetOntology<_> = ont.into();
+        assert_eq!(
+            ont.id().iri.as_ref().map(|iri| iri.as_ref()),
+            Some("http://www.example.com/default")
+        );
+        assert_eq!(ont.len(), 1);
+
+        let (ont, _) = read(&mut slurp_rdfont("class").as_bytes(), config).unwrap();
+        let ont: SetOntology<_> = ont.into();
+        assert_eq!(
+            ont.id().iri.as_ref().map(|iri| iri.as_ref()),
+            Some("http://www.example.com/iri")
+        );
+    }
+
+    #[test]
+    fn bom_and_latin1() {
+        let ont_s = slurp_rdfont("label").replace("Some Label", "Café");
+        let expected: SetOntology<_> = read_ok(&mut ont_s.as_bytes()).into();
+
+        let mut bom = vec![0xEF, 0xBB, 0xBF];
+        bom.extend_from_slice(ont_s.as_bytes());
+        let ont: SetOntology<_> = read_ok(&mut bom.as_slice()).into();
+        assert_eq!(ont, expected);
+
+        let latin1: Vec<u8> = ont_s
+            .replace(
+                "<?xml version=\"1.0\"?>",
+                "<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?>",
+            )
+            .chars()
+            .map(|c| c as u8)
+            .collect();
+        let ont: SetOntology<_> = read_ok(&mut latin1.as_slice()).into();
+        assert_eq!(ont, expected);
+    }
+
+    #[test]
+    fn header_only() {
+        let b = Build::new_rc();
+        let (id, imports) = read_header(slurp_rdfont("import").as_bytes(), &b).unwrap();
+        assert_eq!(id.iri, Some(b.iri("http://www.example.com/iri")));
+        assert_eq!(id.viri, Some(b.iri("http://www.example.com/viri")));
+        assert_eq!(imports, vec![b.iri("http://www.example.com/other-iri")]);
+
+        let (id, imports) = read_header(slurp_rdfont("class").as_bytes(), &b).unwrap();
+        assert_eq!(id.iri, Some(b.iri("http://www.example.com/iri")));
+        assert!(imports.is_empty());
+    }
+
+    #[test]
+    fn header_truncated() {
+        let b = Build::new_rc();
+        let ont_s = slurp_rdfont("import");
+        let truncated = &ont_s.as_bytes()[..ont_s.len() / 2];
+
+        assert!(matches!(
+            read_header(truncated, &b),
+            Err(HornedError::ParserError(_, _))
+        ));
+        assert!(matches!(
+            read(&mut &truncated[..], Default::default()),
+            Err(HornedError::ParserError(_, _))
+        ));
+
+        struct Failing;
+        impl std::io::Read for Failing {
+            fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::ErrorKind::Other.into())
+            }
+        }
+        assert!(matches!(
+            read_header(std::io::BufReader::new(Failing), &b),
+            Err(HornedError::IOError(_))
+        ));
+    }
+
+    #[test]
+    fn malformed_list() {
+        let list = |rest: &str| {
+            format!(
+                r#"<?xml version="1.0"?>
+<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+         xmlns:owl="http://www.w3.org/2002/07/owl#">
+    <owl:Ontology rdf:about="http://www.example.com/iri"/>
+    <owl:Class rdf:about="http://www.example.com/C">
+        <owl:equivalentClass rdf:nodeID="i"/>
+    </owl:Class>
+    <owl:Class rdf:nodeID="i">
+        <owl:intersectionOf rdf:nodeID="l1"/>
+    </owl:Class>
+    <rdf:Description rdf:nodeID="l1">
+        <rdf:first rdf:resource="http://www.example.com/A"/>
+        <rdf:rest rdf:nodeID="l2"/>
+    </rdf:Description>
+    <rdf:Description rdf:nodeID="l2">
+        <rdf:first rdf:resource="http://www.example.com/B"/>
+        {}
+    </rdf:Description>
+</rdf:RDF>"#,
+                rest
+            )
+        };
+
+        let nil =
+            list(r#"<rdf:rest rdf:resource="http://www.w3.org/1999/02/22-rdf-syntax-ns#nil"/>"#);
+        let (_, incomplete) = read(&mut nil.as_bytes(), Default::default()).unwrap();
+        assert!(incomplete.is_complete());
+
+        let truncated = read(&mut list("").as_bytes(), Default::default());
+        match truncated {
+            Err(HornedError::MalformedList(s, _)) => assert!(s.contains("rdf:rest")),
+            _ => panic!("Expected a malformed list error"),
+        }
+
+        let cyclic = list(r#"<rdf:rest rdf:nodeID="l1"/>"#);
+        let cyclic = read(&mut cyclic.as_bytes(), Default::default());
+        match cyclic {
+            Err(HornedError::MalformedList(s, _)) => assert!(s.contains("cyclic")),
+            _ => panic!("Expected a malformed list error"),
+        }
+    }
+
+    #[test]
+    fn max_list_length() {
+        let union = |n: usize| {
+            format!(
+                r#"<?xml version="1.0"?>
+<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+         xmlns:owl="http://www.w3.org/2002/07/owl#">
+    <owl:Ontology rdf:about="http://www.example.com/iri"/>
+    <owl:Class rdf:about="http://www.example.com/C">
+        <owl:equivalentClass>
+            <owl:Class>
+                <owl:unionOf rdf:parseType="Collection">{}</owl:unionOf>
+            </owl:Class>
+        </owl:equivalentClass>
+    </owl:Class>
+</rdf:RDF>"#,
+                (0..n)
+                    .map(|i| format!(r#"<owl:Class rdf:about="http://www.example.com/A{}"/>"#, i))
+                    .collect::<String>()
+            )
+        };
+        let mut config = ParserConfiguration::default();
+        config.rdf.max_list_length = 3;
+
+        let (_, incomplete) = read(&mut union(3).as_bytes(), config.clone()).unwrap();
+        assert!(incomplete.is_complete());
+        assert!(matches!(
+            read(&mut union(4).as_bytes(), config),
+            Err(HornedError::DepthExceeded(3, _))
+        ));
+
+        // Long lists are stitched in linear time
+        let (_, incomplete) = read(&mut union(5000).as_bytes(), Default::default()).unwrap();
+        assert!(incomplete.is_complete());
+    }
+
+    #[test]
+    fn max_depth() {
+        let nested = |depth: usize| {
+            format!(
+                r#"<?xml version="1.0"?>
+<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+         xmlns:owl="http://www.w3.org/2002/07/owl#">
+    <owl:Ontology rdf:about="http://www.example.com/iri"/>
+    <owl:Class rdf:about="http://www.example.com/C">
+        <owl:equivalentClass>{}<owl:Class rdf:about="http://www.example.com/A"/>{}</owl:equivalentClass>
+    </owl:Class>
+</rdf:RDF>"#,
+                "<owl:Class><owl:complementOf>".repeat(depth),
+                "</owl:complementOf></owl:Class>".repeat(depth)
+            )
+        };
+        let mut config = ParserConfiguration::default();
+        config.rdf.max_depth = 5;
+
+        let (_, incomplete) = read(&mut nested(5).as_bytes(), config.clone()).unwrap();
+        assert!(incomplete.is_complete());
+        assert!(matches!(
+            read(&mut nested(50).as_bytes(), config),
+            Err(HornedError::DepthExceeded(5, _))
+        ));
+    }
+
+    #[test]
+    fn prefix_round_trip() {
+        let source = r#"<?xml version="1.0"?>
+<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+         xmlns:owl="http://www.w3.org/2002/07/owl#"
+         xmlns:ex="http://www.example.com/ns#">
+    <owl:Ontology rdf:about="http://www.example.com/ns"/>
+    <owl:ObjectProperty rdf:about="http://www.example.com/ns#knows"/>
+    <owl:NamedIndividual rdf:about="http://www.example.com/ns#i">
+        <ex:knows rdf:resource="http://www.example.com/ns#j"/>
+    </owl:NamedIndividual>
+    <owl:NamedIndividual rdf:about="http://www.example.com/ns#j"/>
+</rdf:RDF>"#;
+
+        let (ont, incomplete) = read(&mut source.as_bytes(), Default::default()).unwrap();
+        assert_eq!(
+            incomplete.mapping.expand_curie_string("ex:knows"),
+            Ok("http://www.example.com/ns#knows".to_string())
+        );
+
+        let amo: RcAxiomMappedOntology = ont.into();
+        let mut buf = vec![];
+        crate::io::rdf::writer::write_with_mapping(&mut buf, &amo, Some(&incomplete.mapping))
+            .unwrap();
+        let s = String::from_utf8(buf).unwrap();
+
+        assert!(s.contains(r#"xmlns:ex="http://www.example.com/ns#""#));
+        assert!(s.contains("<ex:knows"));
+    }
+
+    #[test]
+    fn read_prefixes_stops_at_root() {
+        let head = r#"<?xml version="1.0"?>
+<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+         xmlns:ex="http://www.example.com/ns#">"#;
+        let rest = r#"
+    <rdf:Description rdf:about="http://www.example.com/ns#i"/>
+</rdf:RDF>"#;
+        let source = format!("{}{}", head, rest);
+
+        let mut bufread = std::io::BufReader::with_capacity(4, source.as_bytes());
+        let (mapping, read) = read_prefixes(&mut bufread);
+        assert_eq!(
+            mapping.expand_curie_string("ex:i"),
+            Ok("http://www.example.com/ns#i".to_string())
+        );
+        assert_eq!(read, head.as_bytes());
+
+        let mut unread = String::new();
+        bufread.read_to_string(&mut unread).unwrap();
+        assert_eq!(unread, rest);
+    }
+
+    #[test]
+    fn canonicalize_literals() {
+        let b = Build::new_rc();
+        let mut ont = RcAxiomMappedOntology::new_rc();
+        ont.mut_id().iri = Some(b.iri("http://www.example.com/o"));
+        ont.declare(b.data_property("http://www.example.com/dp"));
+        ont.declare(b.named_individual("http://www.example.com/i"));
+        for l in &["1.0", "1.00"] {
+            ont.insert(DataPropertyAssertion {
+                dp: b.data_property("http://www.example.com/dp"),
+                from: b.named_individual("http://www.example.com/i").into(),
+                to: Literal::Datatype {
+                    literal: l.to_string(),
+                    datatype_iri: b.iri("http://www.w3.org/2001/XMLSchema#decimal"),
+                },
+            });
+        }
+        let mut buf = vec![];
+        crate::io::rdf::writer::write(&mut buf, &ont).unwrap();
+
+        let count = |config: ParserConfiguration| {
+            let (ont, incomplete) = read(&mut buf.as_slice(), config).unwrap();
+            assert!(incomplete.is_complete());
+            let ont: RcAxiomMappedOntology = ont.into();
+            ont.i().data_property_assertion().count()
+        };
+
+        assert_eq!(count(ParserConfiguration::default()), 2);
+        assert_eq!(
+            count(ParserConfiguration {
+                canonicalize_literals: true,
+                ..Default::default()
+            }),
+            1
+        );
+    }
+
+    #[test]
+    fn read_all_two_ontologies() {
+        let s = r#"<?xml version="1.0"?>
+<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+         xmlns:rdfs="http://www.w3.org/2000/01/rdf-schema#"
+         xmlns:owl="http://www.w3.org/2002/07/owl#">
+    <owl:Ontology rdf:about="http://www.example.com/o1">
+        <rdfs:label>First</rdfs:label>
+    </owl:Ontology>
+    <owl:Ontology rdf:about="http://www.example.com/o2">
+        <owl:versionIRI rdf:resource="http://www.example.com/o2/1.0"/>
+        <owl:imports rdf:resource="http://www.example.com/o3"/>
+        <rdfs:label>Second</rdfs:label>
+    </owl:Ontology>
+    <owl:Annotation>
+        <owl:annotatedSource rdf:resource="http://www.example.com/o2"/>
+        <owl:annotatedProperty rdf:resource="http://www.w3.org/2000/01/rdf-schema#label"/>
+        <owl:annotatedTarget>Second</owl:annotatedTarget>
+        <rdfs:comment>On the label</rdfs:comment>
+    </owl:Annotation>
+    <owl:Class rdf:about="http://www.example.com/A"/>
+    <owl:Class rdf:about="http://www.example.com/B">
+        <rdfs:subClassOf rdf:resource="http://www.example.com/A"/>
+    </owl:Class>
+</rdf:RDF>
+"#;
+        let b = Build::new_rc();
+        let onts = read_all(&mut s.as_bytes(), &b, Default::default()).unwrap();
+        assert_eq!(onts.len(), 2);
+        let (o1, i1) = &onts[0];
+        let (o2, i2) = &onts[1];
+
+        let label = |l: &str| -> AnnotatedAxiom<_> {
+            OntologyAnnotation(Annotation {
+                ap: b.annotation_property("http://www.w3.org/2000/01/rdf-schema#label"),
+                av: Literal::Simple {
+                    literal: l.to_string(),
+                }
+                .into(),
+            })
+            .into()
+        };
+
+        assert_eq!(o1.id().iri, Some(b.iri("http://www.example.com/o1")));
+        assert!(o1.iter().any(|ax| ax == &label("First")));
+        assert!(o1
+            .iter()
+            .any(|ax| ax == &DeclareClass(b.class("http://www.example.com/B")).into()));
+        assert_eq!(o1.len(), 4);
+
+        assert_eq!(o2.id().iri, Some(b.iri("http://www.example.com/o2")));
+        assert_eq!(o2.id().viri, Some(b.iri("http://www.example.com/o2/1.0")));
+        assert!(o2.iter().any(|ax| ax == &label("Second")));
+        assert!(o2
+            .iter()
+            .any(|ax| ax == &Import(b.iri("http://www.example.com/o3")).into()));
+        assert_eq!(o2.len(), 2);
+
+        // The annotation of the label of o2 is not supported, but
+        // its triples stay with o2
+        assert!(i1.is_complete());
+        assert_eq!(i2.bnode.len(), 1);
+        assert_eq!(i2.bnode[0].len(), 5);
+    }
+
+    #[test]
+    fn read_stats_duplicates() {
+        let s = r#"<?xml version="1.0"?>
+<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+         xmlns:rdfs="http://www.w3.org/2000/01/rdf-schema#"
+         xmlns:owl="http://www.w3.org/2002/07/owl#">
+    <owl:Ontology rdf:about="http://www.example.com/o"/>
+    <owl:Class rdf:about="http://www.example.com/B"/>
+    <owl:Class rdf:about="http://www.example.com/A">
+        <rdfs:subClassOf rdf:resource="http://www.example.com/B"/>
+    </owl:Class>
+    <owl:Class rdf:about="http://www.example.com/A">
+        <rdfs:subClassOf rdf:resource="http://www.example.com/B"/>
+    </owl:Class>
+</rdf:RDF>
+"#;
+        let (ont, incomplete) = read(&mut s.as_bytes(), Default::default()).unwrap();
+        assert!(incomplete.is_complete());
+
+        let ont: SetOntology<_> = ont.into();
+        assert_eq!(ont.len(), 3);
+        assert_eq!(
+            incomplete.stats,
+            ReadStats {
+                triples: 6,
+                axioms: 3,
+                duplicates_dropped: 2,
+            }
+        );
+    }
+
     #[test]
     fn annotation_with_anonymous() {
         let s = slurp_rdfont("annotation-with-anonymous");
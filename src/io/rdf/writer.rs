@@ -8,10 +8,15 @@ use crate::{
 
 use crate::ontology::indexed::ForIndex;
 
+use curie::{Curie, PrefixMapping};
 use pretty_rdf::{
     ChunkedRdfXmlFormatterConfig, PBlankNode, PLiteral, PNamedNode, PSubject, PTerm, PTriple,
     PrettyRdfXmlFormatter,
 };
+use quick_xml::{
+    events::{BytesStart, Event},
+    Reader, Writer,
+};
 use std::{
     collections::{BTreeSet, HashSet},
     fmt::Debug,
@@ -21,6 +26,55 @@ use std::{
 pub fn write<A: ForIRI, AA: ForIndex<A>, W: Write>(
     write: &mut W,
     ont: &AxiomMappedOntology<A, AA>,
+) -> Result<(), HornedError> {
+    write_with_mapping(write, ont, None)
+}
+
+/// Write `ont` as RDF/XML, declaring the namespaces in `mapping`.
+///
+/// Namespaces in `mapping` are declared on the `rdf:RDF` element and
+/// used to shorten element names. If `mapping` has a default
+/// namespace, it is written as the `xml:base`, and `rdf:about` and
+/// `rdf:resource` values are written relative to it where they
+/// resolve back to the same IRI. The namespaces declared in a
+/// document which has been read are available as
+/// `IncompleteParse::mapping`.
+pub fn write_with_mapping<A: ForIRI, AA: ForIndex<A>, W: Write>(
+    write: &mut W,
+    ont: &AxiomMappedOntology<A, AA>,
+    mapping: Option<&PrefixMapping>,
+) -> Result<(), HornedError> {
+    write_with(write, ont, mapping, NodeGenerator::default())
+}
+
+/// Write `ont` as RDF/XML, labelling blank nodes canonically.
+///
+/// `write` numbers blank nodes in the order they are generated, so
+/// adding or removing an axiom relabels the blank nodes of every
+/// axiom written after it. Here, the blank nodes of each axiom are
+/// labelled from a hash of that axiom and their position within it,
+/// so the label depends only on the axiom they belong to, and an
+/// ontology always serializes to the same bytes. This is intended
+/// for content addressed storage and for diffing serializations.
+///
+/// The hash is the 64 bit FNV-1a hash of the triples of the axiom,
+/// written as N-Triples in the order in which they are generated,
+/// with blank nodes numbered as `write` would for an ontology
+/// containing only that axiom. Labels are therefore stable across
+/// platforms and releases of Rust.
+pub fn write_canonical<A: ForIRI, AA: ForIndex<A>, W: Write>(
+    write: &mut W,
+    ont: &AxiomMappedOntology<A, AA>,
+    mapping: Option<&PrefixMapping>,
+) -> Result<(), HornedError> {
+    write_with(write, ont, mapping, NodeGenerator::canonical())
+}
+
+fn write_with<A: ForIRI, AA: ForIndex<A>, W: Write>(
+    write: &mut W,
+    ont: &AxiomMappedOntology<A, AA>,
+    mapping: Option<&PrefixMapping>,
+    mut bng: NodeGenerator<A>,
 ) -> Result<(), HornedError> {
     // Entirely unsatisfying to set this randomly here, but we can't
     // access ns our parser yet
@@ -33,23 +87,192 @@ pub fn write<A: ForIRI, AA: ForIndex<A>, W: Write>(
         "http://www.w3.org/XML/1998/namespace".to_string(),
         "xml".to_string(),
     );
+    if let Some(mapping) = mapping {
+        for (prefix, iri) in mapping.mappings() {
+            p.insert(iri.clone(), prefix.clone());
+        }
+    }
+    let config = ChunkedRdfXmlFormatterConfig::all().prefix(p);
+
+    // pretty_rdf knows nothing of xml:base, so with a base we format
+    // into a buffer and relativize the output.
+    match mapping.and_then(|m| m.expand_curie(&Curie::new(None, "")).ok()) {
+        Some(base) => {
+            let mut buf = vec![];
+            let mut f: PrettyRdfXmlFormatter<_, _> = PrettyRdfXmlFormatter::new(&mut buf, config)?;
+            ont.render(&mut f, &mut bng)?;
+            f.finish()?;
+            write_relative(&buf, write, &base)
+        }
+        None => {
+            let mut f: PrettyRdfXmlFormatter<_, _> = PrettyRdfXmlFormatter::new(write, config)?;
+            ont.render(&mut f, &mut bng)?;
+
+            // for i in f.triples() {
+            //     println!("{}", i.printable());
+            // }
+
+            f.finish()?;
+            Ok(())
+        }
+    }
+}
+
+/// Copy the RDF/XML document `xml` to `write`, adding an `xml:base`
+/// of `base` to the `rdf:RDF` element and writing `rdf:about` and
+/// `rdf:resource` values relative to it where possible.
+fn write_relative<W: Write>(xml: &[u8], write: &mut W, base: &str) -> Result<(), HornedError> {
+    let base = base.split('#').next().unwrap_or_default();
+    let mut reader = Reader::from_reader(xml);
+    let mut writer = Writer::new(write);
+    let mut buf = vec![];
+
+    loop {
+        match reader.read_event(&mut buf)? {
+            Event::Eof => break,
+            Event::Start(e) => writer.write_event(Event::Start(relativize(&e, base)?))?,
+            Event::Empty(e) => writer.write_event(Event::Empty(relativize(&e, base)?))?,
+            e => writer.write_event(e)?,
+        }
+        buf.clear();
+    }
+    Ok(())
+}
+
+fn relativize(e: &BytesStart, base: &str) -> Result<BytesStart<'static>, HornedError> {
+    let mut new = BytesStart::owned_name(e.name());
+    if e.name() == b"rdf:RDF" {
+        new.push_attribute(("xml:base", base));
+    }
+    for attr in e.attributes() {
+        let attr = attr?;
+        if attr.key == b"rdf:about" || attr.key == b"rdf:resource" {
+            let value = attr.unescaped_value()?;
+            let iri = std::str::from_utf8(&value).map_err(|e| invalid!("{}", e))?;
+            if let Some(rel) = relative_iri(base, iri) {
+                new.push_attribute((std::str::from_utf8(attr.key).unwrap_or_default(), rel));
+                continue;
+            }
+        }
+        new.push_attribute(attr);
+    }
+    Ok(new)
+}
+
+/// Return `iri` as a reference relative to `base`, which must not
+/// have a fragment, if there is one which resolves back to `iri`.
+///
+/// Only the simple cases are handled: a fragment of `base` itself,
+/// or a path below the directory of `base`.
+fn relative_iri<'a>(base: &str, iri: &'a str) -> Option<&'a str> {
+    if let Some(rest) = iri.strip_prefix(base) {
+        if rest.starts_with('#') {
+            return Some(rest);
+        }
+    }
+
+    let path = base.split('?').next()?;
+    let authority = path.find("://")? + 3;
+    let dir = &path[..=path[authority..].rfind('/')? + authority];
+    let rest = iri.strip_prefix(dir)?;
+    let first = rest.split(&['/', '?', '#'][..]).next()?;
+    let dot_segment = rest
+        .split(&['?', '#'][..])
+        .next()?
+        .split('/')
+        .any(|seg| seg == "." || seg == "..");
+
+    if first.is_empty() || first.contains(':') || dot_segment {
+        None
+    } else {
+        Some(rest)
+    }
+}
+
+/// The subject of a `Triple`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum TripleSubject<A> {
+    IRI(IRI<A>),
+    BlankNode(A),
+}
+
+/// The object of a `Triple`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum TripleObject<A> {
+    IRI(IRI<A>),
+    BlankNode(A),
+    Literal(Literal<A>),
+}
+
+/// An RDF triple.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Triple<A> {
+    pub subject: TripleSubject<A>,
+    pub predicate: IRI<A>,
+    pub object: TripleObject<A>,
+}
+
+impl<A: ForIRI> Triple<A> {
+    fn from_ptriple(b: &Build<A>, t: PTriple<A>) -> Triple<A> {
+        let subject = match t.subject {
+            PSubject::NamedNode(nn) => TripleSubject::IRI(b.iri(nn.iri.as_ref())),
+            PSubject::BlankNode(bn) => TripleSubject::BlankNode(bn.id),
+        };
+
+        let object = match t.object {
+            PTerm::NamedNode(nn) => TripleObject::IRI(b.iri(nn.iri.as_ref())),
+            PTerm::BlankNode(bn) => TripleObject::BlankNode(bn.id),
+            PTerm::Literal(PLiteral::Simple { value }) => TripleObject::Literal(Literal::Simple {
+                literal: value.as_ref().to_string(),
+            }),
+            PTerm::Literal(PLiteral::LanguageTaggedString { value, language }) => {
+                TripleObject::Literal(Literal::Language {
+                    literal: value.as_ref().to_string(),
+                    lang: language.as_ref().to_string(),
+                })
+            }
+            PTerm::Literal(PLiteral::Typed { value, datatype }) => {
+                TripleObject::Literal(Literal::Datatype {
+                    literal: value.as_ref().to_string(),
+                    datatype_iri: b.iri(datatype.iri.as_ref()),
+                })
+            }
+        };
+
+        Triple {
+            subject,
+            predicate: b.iri(t.predicate.iri.as_ref()),
+            object,
+        }
+    }
+}
+
+/// Return the triples that `write` would serialize for `ont`.
+///
+/// Blank nodes are generated exactly as they are for `write`; IRIs
+/// are interned with `b`.
+pub fn to_triples<A: ForIRI, AA: ForIndex<A>>(
+    b: &Build<A>,
+    ont: &AxiomMappedOntology<A, AA>,
+) -> Result<Vec<Triple<A>>, HornedError> {
     let mut f: PrettyRdfXmlFormatter<_, _> =
-        PrettyRdfXmlFormatter::new(write, ChunkedRdfXmlFormatterConfig::all().prefix(p))?;
+        PrettyRdfXmlFormatter::new(std::io::sink(), ChunkedRdfXmlFormatterConfig::none())?;
     let mut bng = NodeGenerator::default();
     ont.render(&mut f, &mut bng)?;
 
-    // for i in f.triples() {
-    //     println!("{}", i.printable());
-    // }
-
-    f.finish()?;
-    Ok(())
+    Ok(f.triples()
+        .into_iter()
+        .map(|t| Triple::from_ptriple(b, t))
+        .collect())
 }
 
 struct NodeGenerator<A: ForIRI> {
     i: u64,
     b: HashSet<A>,
     this_bn: Option<PSubject<A>>,
+    // The FNV-1a hash of the axiom being rendered, if blank nodes
+    // are labelled canonically
+    axiom_hash: Option<u64>,
 }
 
 impl<A: ForIRI> Default for NodeGenerator<A> {
@@ -58,11 +281,41 @@ impl<A: ForIRI> Default for NodeGenerator<A> {
             i: 0,
             b: HashSet::new(),
             this_bn: None,
+            axiom_hash: None,
         }
     }
 }
 
 impl<A: ForIRI> NodeGenerator<A> {
+    fn canonical() -> Self {
+        NodeGenerator {
+            axiom_hash: Some(0),
+            ..Default::default()
+        }
+    }
+
+    /// Start the blank nodes for `ax`, if labelling canonically.
+    fn start_axiom(&mut self, ax: &AnnotatedAxiom<A>) -> Result<(), HornedError> {
+        if self.axiom_hash.is_some() {
+            let mut f: PrettyRdfXmlFormatter<_, _> =
+                PrettyRdfXmlFormatter::new(std::io::sink(), ChunkedRdfXmlFormatterConfig::none())?;
+            ax.render(&mut f, &mut NodeGenerator::default())?;
+
+            let b = Build::new();
+            let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+            for t in f.triples() {
+                let line = format!("{}\n", Triple::from_ptriple(&b, t));
+                for byte in line.bytes() {
+                    hash ^= u64::from(byte);
+                    hash = hash.wrapping_mul(0x0100_0000_01b3);
+                }
+            }
+            self.axiom_hash = Some(hash);
+            self.i = 0;
+        }
+        Ok(())
+    }
+
     /// Generate a NamedNode from a given Vocab element.
     pub fn nn<V: Into<Vocab>>(&mut self, v: V) -> PNamedNode<A> {
         PNamedNode::new(self.cache_rc(v))
@@ -82,9 +335,11 @@ impl<A: ForIRI> NodeGenerator<A> {
 
     pub fn bn(&mut self) -> PSubject<A> {
         self.i += 1;
-        PSubject::BlankNode(PBlankNode {
-            id: format! {"bn{}", self.i}.into(),
-        })
+        let id = match self.axiom_hash {
+            Some(hash) => format!("bn{:016x}_{}", hash, self.i),
+            None => format!("bn{}", self.i),
+        };
+        PSubject::BlankNode(PBlankNode { id: id.into() })
     }
 
     pub fn keep_this_bn(&mut self, bn: PSubject<A>) {
@@ -417,6 +672,7 @@ impl<A: ForIRI, AA: ForIndex<A>> Render<A, ()> for &AxiomMappedOntology<A, AA> {
             }
 
             for ax in self.i().iter() {
+                ng.start_axiom(ax)?;
                 ax.render(f, ng)?;
             }
         }
@@ -459,9 +715,26 @@ impl<A: ForIRI> Render<A, ()> for AnnotatedAxiom<A> {
     }
 }
 
+/// Return true if `c` may occur in an XML 1.0 document.
+///
+/// Other characters, such as most ASCII control characters, cannot
+/// be written even as a character reference.
+fn is_xml_char(c: char) -> bool {
+    matches!(c, '\t' | '\n' | '\r' | '\u{20}'..='\u{D7FF}' | '\u{E000}'..='\u{FFFD}')
+        || c >= '\u{10000}'
+}
+
 render! {
     Literal, self, _f, _ng, PTerm,
     {
+        if let Some(c) = self.literal().chars().find(|c| !is_xml_char(*c)) {
+            return Err(invalid!(
+                "Literal {:?} contains {:?}, which cannot be written in XML",
+                self.literal(),
+                c
+            ));
+        }
+
         Ok(
             match self {
                 Literal::Simple{literal} =>
@@ -1239,7 +1512,25 @@ render_to_node! {
                         bn, ng.nn(OWL::HasValue), ind
                     )
                 }
-                Self::ObjectHasSelf(_ope) =>todo!(),
+                Self::ObjectHasSelf(ope) => {
+                    //_:x rdf:type owl:Restriction .
+                    //_:x owl:onProperty T(OPE) .
+                    //_:x owl:hasSelf "true"^^xsd:boolean .
+                    let bn = ng.bn();
+
+                    let node_ope:PTerm<_> = ope.render(f, ng)?.into();
+                    let node_true = PTerm::Literal(PLiteral::Typed {
+                        value: "true".to_string().into(),
+                        datatype: ng.nn(XSD::Boolean),
+                    });
+
+                    triples_to_node!(
+                        f,
+                        bn.clone(), ng.nn(RDF::Type), ng.nn(OWL::Restriction),
+                        bn.clone(), ng.nn(OWL::OnProperty), node_ope,
+                        bn, ng.nn(OWL::HasSelf), node_true
+                    )
+                }
                 Self::ObjectMinCardinality{n, ope, bce} => {
                     obj_cardinality(n, ope, bce,
                                     ng.nn(OWL::MinCardinality),
@@ -1364,8 +1655,11 @@ render_to_node! {
 render_to_vec! {
     DisjointClasses, self, f, ng,
     {
-        let pred = ng.nn(OWL::DisjointWith);
-        nary(f, ng, &self.0, pred)
+        members(f, ng,
+                OWL::DisjointWith,
+                OWL::AllDisjointClasses,
+                &self.0
+        )
     }
 }
 
@@ -1569,6 +1863,317 @@ mod test {
         assert_eq!(ont.id().iri, ont2.id().iri);
     }
 
+    #[test]
+    fn test_to_triples_subclass() {
+        let mut ont = AxiomMappedOntology::new_rc();
+        let b = Build::new_rc();
+        ont.mut_id().iri = Some(b.iri("http://www.example.com/o"));
+
+        ont.insert(SubClassOf {
+            sub: b.class("http://www.example.com/a").into(),
+            sup: b.class("http://www.example.com/b").into(),
+        });
+
+        let triples = to_triples(&b, &ont).unwrap();
+        assert_eq!(
+            triples,
+            vec![
+                Triple {
+                    subject: TripleSubject::IRI(b.iri("http://www.example.com/o")),
+                    predicate: b.iri("http://www.w3.org/1999/02/22-rdf-syntax-ns#type"),
+                    object: TripleObject::IRI(b.iri("http://www.w3.org/2002/07/owl#Ontology")),
+                },
+                Triple {
+                    subject: TripleSubject::IRI(b.iri("http://www.example.com/a")),
+                    predicate: b.iri("http://www.w3.org/2000/01/rdf-schema#subClassOf"),
+                    object: TripleObject::IRI(b.iri("http://www.example.com/b")),
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_write_prefix() {
+        let mut ont = AxiomMappedOntology::new_rc();
+        let b = Build::new_rc();
+        ont.mut_id().iri = Some(b.iri("http://www.example.com/ns"));
+        ont.declare(b.object_property("http://www.example.com/ns#knows"));
+        ont.declare(b.named_individual("http://www.example.com/ns#i"));
+        ont.declare(b.named_individual("http://www.example.com/ns#j"));
+        ont.insert(ObjectPropertyAssertion {
+            ope: b.object_property("http://www.example.com/ns#knows").into(),
+            from: b.named_individual("http://www.example.com/ns#i").into(),
+            to: b.named_individual("http://www.example.com/ns#j").into(),
+        });
+
+        let mut mapping = PrefixMapping::default();
+        mapping
+            .add_prefix("ex", "http://www.example.com/ns#")
+            .unwrap();
+
+        let mut buf = vec![];
+        write_with_mapping(&mut buf, &ont, Some(&mapping)).unwrap();
+        let s = String::from_utf8(buf).unwrap();
+
+        assert!(s.contains(r#"xmlns:ex="http://www.example.com/ns#""#));
+        assert!(s.contains("<ex:knows"));
+
+        let ont_round = read_ok(&mut s.as_bytes());
+        let ont: SetOntology<_> = ont.into();
+        assert_eq!(ont, ont_round);
+    }
+
+    #[test]
+    fn test_write_base() {
+        let mut ont = AxiomMappedOntology::new_rc();
+        let b = Build::new_rc();
+        ont.mut_id().iri = Some(b.iri("http://www.example.com/ns"));
+        ont.declare(b.class("http://www.example.com/ns#A"));
+        ont.insert(SubClassOf {
+            sub: b.class("http://www.example.com/ns#A").into(),
+            sup: b.class("http://www.example.org/other#B").into(),
+        });
+
+        let mut mapping = PrefixMapping::default();
+        mapping.set_default("http://www.example.com/ns#");
+
+        let mut buf = vec![];
+        write_with_mapping(&mut buf, &ont, Some(&mapping)).unwrap();
+        let s = String::from_utf8(buf).unwrap();
+
+        assert!(s.contains(r#"xml:base="http://www.example.com/ns""#));
+        assert!(s.contains(r##"rdf:about="#A""##));
+        assert!(s.contains(r#"rdf:resource="http://www.example.org/other#B""#));
+
+        let ont_round = read_ok(&mut s.as_bytes());
+        let ont: SetOntology<_> = ont.into();
+        assert_eq!(ont, ont_round);
+    }
+
+    #[test]
+    fn test_relative_iri() {
+        let base = "http://www.example.com/ns/onto.owl";
+        assert_eq!(
+            relative_iri(base, "http://www.example.com/ns/onto.owl#A"),
+            Some("#A")
+        );
+        assert_eq!(relative_iri(base, "http://www.example.com/ns/A"), Some("A"));
+        assert_eq!(
+            relative_iri(base, "http://www.example.com/ns/a/b?q"),
+            Some("a/b?q")
+        );
+        assert_eq!(relative_iri(base, "http://www.example.com/ns/"), None);
+        assert_eq!(relative_iri(base, "http://www.example.com/ns/x:y"), None);
+        assert_eq!(relative_iri(base, "http://www.example.com/ns/a/../b"), None);
+        assert_eq!(relative_iri(base, "http://www.example.com/other"), None);
+        assert_eq!(
+            relative_iri("http://www.example.com", "http://www.example.com/A"),
+            None
+        );
+    }
+
+    #[test]
+    fn round_annotated_declarations() {
+        let mut ont = AxiomMappedOntology::new_rc();
+        let b = Build::new_rc();
+        ont.mut_id().iri = Some(b.iri("http://www.example.com/o"));
+        let comment = |s: &str| -> BTreeSet<Annotation<_>> {
+            vec![Annotation {
+                ap: b.annotation_property("http://www.w3.org/2000/01/rdf-schema#comment"),
+                av: Literal::Simple {
+                    literal: s.to_string(),
+                }
+                .into(),
+            }]
+            .into_iter()
+            .collect()
+        };
+        let decls: Vec<AnnotatedAxiom<_>> = vec![
+            AnnotatedAxiom::new(
+                DeclareClass(b.class("http://www.example.com/c")),
+                comment("c"),
+            ),
+            AnnotatedAxiom::new(
+                DeclareObjectProperty(b.object_property("http://www.example.com/op")),
+                comment("op"),
+            ),
+            AnnotatedAxiom::new(
+                DeclareDataProperty(b.data_property("http://www.example.com/dp")),
+                comment("dp"),
+            ),
+            AnnotatedAxiom::new(
+                DeclareAnnotationProperty(b.annotation_property("http://www.example.com/ap")),
+                comment("ap"),
+            ),
+            AnnotatedAxiom::new(
+                DeclareNamedIndividual(b.named_individual("http://www.example.com/i")),
+                comment("i"),
+            ),
+            AnnotatedAxiom::new(
+                DeclareDatatype(b.datatype("http://www.example.com/d")),
+                comment("d"),
+            ),
+        ];
+        for d in &decls {
+            ont.insert(d.clone());
+        }
+
+        let mut buf = vec![];
+        write(&mut buf, &ont).unwrap();
+        let ont_round = read_ok(&mut buf.as_slice());
+
+        for d in decls {
+            assert!(
+                ont_round.iter().any(|aa| aa == &d),
+                "Lost annotation on {:?}",
+                d
+            );
+            let bare: AnnotatedAxiom<_> = d.axiom.clone().into();
+            assert_eq!(d.strip_annotations(), bare);
+        }
+        let ont: SetOntology<_> = ont.into();
+        assert_eq!(ont, ont_round);
+    }
+
+    #[test]
+    fn round_inverse_object_property_assertion() {
+        let mut ont = AxiomMappedOntology::new_rc();
+        let b = Build::new_rc();
+        ont.mut_id().iri = Some(b.iri("http://www.example.com/o"));
+        ont.declare(b.object_property("http://www.example.com/p"));
+        ont.declare(b.named_individual("http://www.example.com/a"));
+        ont.declare(b.named_individual("http://www.example.com/b"));
+        let opa = ObjectPropertyAssertion {
+            ope: ObjectPropertyExpression::InverseObjectProperty(
+                b.object_property("http://www.example.com/p"),
+            ),
+            from: b.named_individual("http://www.example.com/a").into(),
+            to: b.named_individual("http://www.example.com/b").into(),
+        };
+        ont.insert(opa.clone());
+
+        let mut buf = vec![];
+        write(&mut buf, &ont).unwrap();
+        let ont_round = read_ok(&mut buf.as_slice());
+
+        // RDF has no syntax for the inverse, so it is read back as
+        // the equivalent assertion of p
+        assert!(ont_round
+            .iter()
+            .any(|aa| aa.axiom == opa.normalize().into()));
+        assert!(!ont_round.iter().any(|aa| aa.axiom == opa.clone().into()));
+    }
+
+    #[test]
+    fn round_complex_object_property_range() {
+        let mut ont = AxiomMappedOntology::new_rc();
+        let b = Build::new_rc();
+        ont.mut_id().iri = Some(b.iri("http://www.example.com/o"));
+        ont.declare(b.object_property("http://www.example.com/p"));
+        ont.declare(b.object_property("http://www.example.com/q"));
+        ont.declare(b.class("http://www.example.com/D"));
+        let range = b.object_property_range(
+            b.object_property("http://www.example.com/p"),
+            ClassExpression::ObjectSomeValuesFrom {
+                ope: b.object_property("http://www.example.com/q").into(),
+                bce: Box::new(b.class("http://www.example.com/D").into()),
+            },
+        );
+        ont.insert(range.clone());
+
+        let mut buf = vec![];
+        write(&mut buf, &ont).unwrap();
+        let ont_round = read_ok(&mut buf.as_slice());
+
+        assert!(ont_round.iter().any(|aa| aa.axiom == range.clone().into()));
+        assert_eq!(ont_round.len(), 4);
+    }
+
+    #[test]
+    fn write_canonical_stable() {
+        let node_ids = |o: &SetOntology<RcStr>| {
+            let amo: AxiomMappedOntology<RcStr, Rc<AnnotatedAxiom<RcStr>>> = o.clone().into();
+            let mut v = vec![];
+            write_canonical(&mut v, &amo, None).unwrap();
+            let s = String::from_utf8(v).unwrap();
+            let ids: BTreeSet<String> = s
+                .split("rdf:nodeID=\"")
+                .skip(1)
+                .map(|rest| rest.split('"').next().unwrap().to_string())
+                .collect();
+            (s, ids)
+        };
+
+        let b = Build::new_rc();
+        let annotated_some = |sub: &str, sup: &str| {
+            AnnotatedAxiom::new(
+                SubClassOf {
+                    sub: b.class(sub).into(),
+                    sup: ClassExpression::ObjectSomeValuesFrom {
+                        ope: b.object_property("http://www.example.com/iri#r").into(),
+                        bce: Box::new(b.class(sup).into()),
+                    },
+                },
+                vec![Annotation {
+                    ap: b.annotation_property("http://www.w3.org/2000/01/rdf-schema#comment"),
+                    av: "Annotation on subclass axiom".into(),
+                }]
+                .into_iter()
+                .collect(),
+            )
+        };
+
+        let ont = |axioms: Vec<AnnotatedAxiom<RcStr>>| {
+            let mut o = SetOntology::new_rc();
+            o.mut_id().iri = Some(b.iri("http://www.example.com/iri"));
+            for ax in axioms {
+                o.insert(ax);
+            }
+            o
+        };
+        let ab = annotated_some(
+            "http://www.example.com/iri#A",
+            "http://www.example.com/iri#B",
+        );
+        let ba = annotated_some(
+            "http://www.example.com/iri#B",
+            "http://www.example.com/iri#A",
+        );
+
+        let (first, ab_ids) = node_ids(&ont(vec![ab.clone()]));
+        let (second, _) = node_ids(&ont(vec![ab.clone()]));
+        assert_eq!(first, second);
+        assert!(!ab_ids.is_empty());
+
+        // The blank nodes of one axiom are not relabelled by another
+        let (_, ba_ids) = node_ids(&ont(vec![ba.clone()]));
+        let (_, both_ids) = node_ids(&ont(vec![ab, ba]));
+        assert_eq!(both_ids, &ab_ids | &ba_ids);
+    }
+
+    #[test]
+    fn write_canonical_golden() {
+        let b = Build::new_rc();
+        let mut ont = AxiomMappedOntology::new_rc();
+        ont.mut_id().iri = Some(b.iri("http://www.example.com/o"));
+        ont.insert(SubClassOf {
+            sub: b.class("http://www.example.com/o#A").into(),
+            sup: ClassExpression::ObjectSomeValuesFrom {
+                ope: b.object_property("http://www.example.com/o#r").into(),
+                bce: Box::new(b.class("http://www.example.com/o#B").into()),
+            },
+        });
+
+        let mut buf = vec![];
+        write_canonical(&mut buf, &ont, None).unwrap();
+        let s = String::from_utf8(buf).unwrap();
+
+        // The label is fixed by the axiom alone, so this must never
+        // change between platforms or releases
+        assert!(s.contains(r#"rdf:nodeID="bnc2a3e82fd837a722_1""#));
+        assert_eq!(s.matches("rdf:nodeID=").count(), 1);
+    }
+
     fn roundtrip(ont: &str) -> (SetOntology<RcStr>, SetOntology<RcStr>) {
         let ont_orig = read_ok(&mut ont.as_bytes());
         let temp_file = Temp::new_file().unwrap();
@@ -1603,6 +2208,23 @@ mod test {
         assert_round(include_str!("../../ont/owl-rdf/ont.owl"));
     }
 
+    #[test]
+    fn round_version_iri() {
+        let (ont_orig, ont_round) = assert_round(include_str!("../../ont/owl-rdf/ont.owl"));
+
+        for o in &[ont_orig, ont_round] {
+            let id = o.id();
+            assert_eq!(
+                id.iri.as_ref().unwrap().as_ref(),
+                "http://www.example.com/iri"
+            );
+            assert_eq!(
+                id.viri.as_ref().unwrap().as_ref(),
+                "http://www.example.com/viri"
+            );
+        }
+    }
+
     #[test]
     fn round_class() {
         assert_round(include_str!("../../ont/owl-rdf/class.owl"));
@@ -1687,6 +2309,150 @@ mod test {
         ));
     }
 
+    fn simple(literal: &str) -> Literal<RcStr> {
+        Literal::Simple {
+            literal: literal.to_string(),
+        }
+    }
+
+    fn round_literals(literals: Vec<Literal<RcStr>>) {
+        let mut ont = AxiomMappedOntology::new_rc();
+        let build = Build::new_rc();
+        ont.mut_id().iri = Some(build.iri("http://www.example.com/o"));
+        for (i, l) in literals.iter().enumerate() {
+            ont.insert(AnnotationAssertion {
+                subject: build.iri(format!("http://www.example.com/a{}", i)).into(),
+                ann: Annotation {
+                    ap: build.annotation_property("http://www.w3.org/2000/01/rdf-schema#label"),
+                    av: l.clone().into(),
+                },
+            });
+        }
+
+        let mut buf = vec![];
+        write(&mut buf, &ont).unwrap();
+        let s = String::from_utf8(buf).unwrap();
+        let ont2 = read_ok(&mut s.as_bytes());
+
+        let mut round: Vec<_> = ont2
+            .iter()
+            .filter_map(|aa| match &aa.axiom {
+                Axiom::AnnotationAssertion(AnnotationAssertion {
+                    subject: AnnotationSubject::IRI(iri),
+                    ann:
+                        Annotation {
+                            av: AnnotationValue::Literal(l),
+                            ..
+                        },
+                }) => Some((iri.to_string(), l.clone())),
+                _ => None,
+            })
+            .collect();
+        round.sort();
+        let round: Vec<_> = round.into_iter().map(|(_, l)| l).collect();
+        assert_eq!(round, literals, "{}", s);
+    }
+
+    #[test]
+    fn round_literal_xml_escaped() {
+        round_literals(vec![
+            simple("A & B < C"),
+            simple("C > D"),
+            simple("\"double\" and 'single' quotes"),
+            simple("&amp; is already an entity"),
+            simple("<b>not markup</b>]]>"),
+            simple("tab\tand\nnewline"),
+            simple("carriage\r\nreturn"),
+            Literal::Language {
+                literal: "A & B".to_string(),
+                lang: "en".to_string(),
+            },
+        ]);
+    }
+
+    #[test]
+    fn round_literal_non_ascii() {
+        round_literals(vec![
+            simple("Smile 😀"),
+            simple("Größe"),
+            simple("日本語"),
+            Literal::Datatype {
+                literal: "🎉 & <".to_string(),
+                datatype_iri: Build::new_rc().iri("http://www.example.com/dt"),
+            },
+        ]);
+    }
+
+    #[test]
+    fn literal_not_xml_char() {
+        let mut ont = AxiomMappedOntology::new_rc();
+        let build = Build::new_rc();
+        ont.mut_id().iri = Some(build.iri("http://www.example.com/o"));
+        ont.insert(build.annotation_assertion(
+            "http://www.example.com/a",
+            "http://www.w3.org/2000/01/rdf-schema#label",
+            "bell\u{7}",
+        ));
+
+        let mut buf = vec![];
+        assert!(matches!(
+            write(&mut buf, &ont),
+            Err(HornedError::ValidityError(_, _))
+        ));
+    }
+
+    #[test]
+    fn round_iri_xml_escaped() {
+        let mut ont = AxiomMappedOntology::new_rc();
+        let build = Build::new_rc();
+        ont.mut_id().iri = Some(build.iri("http://www.example.com/o"));
+        let sub = build.class("http://www.example.com/a?x=1&y='2'");
+        let sup = build.class("http://www.example.com/caf\u{e9}");
+        ont.insert(SubClassOf {
+            sub: sub.clone().into(),
+            sup: sup.clone().into(),
+        });
+
+        let mut buf = vec![];
+        write(&mut buf, &ont).unwrap();
+        let ont2 = read_ok(&mut buf.as_slice());
+        assert!(ont2.iter().any(|aa| aa.axiom
+            == SubClassOf {
+                sub: sub.clone().into(),
+                sup: sup.clone().into(),
+            }
+            .into()));
+    }
+
+    #[test]
+    fn round_ontology_label() {
+        let mut ont = AxiomMappedOntology::new_rc();
+        let build = Build::new_rc();
+        ont.mut_id().iri = Some(build.iri("http://www.example.com/o"));
+        let label = Annotation {
+            ap: build.annotation_property("http://www.w3.org/2000/01/rdf-schema#label"),
+            av: "An ontology".into(),
+        };
+        ont.insert(OntologyAnnotation(label.clone()));
+
+        let mut buf = vec![];
+        write(&mut buf, &ont).unwrap();
+
+        // The label is in the header
+        let s = String::from_utf8(buf).unwrap();
+        let header = &s[s.find("<owl:Ontology").unwrap()..s.find("</owl:Ontology>").unwrap()];
+        assert!(header.contains("An ontology"));
+
+        let ont2 = read_ok(&mut s.as_bytes());
+        assert_eq!(
+            ont2.ontology_annotations().collect::<Vec<_>>(),
+            vec![&label]
+        );
+        assert!(!ont2
+            .iter()
+            .any(|aa| aa.kind() == AxiomKind::AnnotationAssertion));
+    }
+
     #[test]
     fn round_equivalent_class() {
         assert_round(include_str!("../../ont/owl-rdf/equivalent-class.owl"));
@@ -1697,6 +2463,11 @@ mod test {
         assert_round(include_str!("../../ont/owl-rdf/disjoint-class.owl"));
     }
 
+    #[test]
+    fn round_disjoint_with_three() {
+        assert_round(include_str!("../../ont/owl-rdf/disjoint_with_three.owl"));
+    }
+
     #[test]
     fn round_disjoint_union() {
         assert_round(include_str!("../../ont/owl-rdf/disjoint-union.owl"));
@@ -1778,6 +2549,11 @@ mod test {
         assert_round(include_str!("../../ont/owl-rdf/object-has-value.owl"));
     }
 
+    #[test]
+    fn object_has_self() {
+        assert_round(include_str!("../../ont/owl-rdf/object-has-self.owl"));
+    }
+
     #[test]
     fn object_one_of() {
         assert_round(include_str!("../../ont/owl-rdf/object-one-of.owl"));
@@ -1827,6 +2603,43 @@ mod test {
         assert_round(include_str!("../../ont/owl-rdf/datatype-union.owl"));
     }
 
+    #[test]
+    fn round_datatype_intersection_of_restrictions() {
+        let b = Build::new_rc();
+        let mut ont = AxiomMappedOntology::new_rc();
+        ont.mut_id().iri = Some(b.iri("http://www.example.com/o"));
+        let d = b.datatype("http://www.example.com/D");
+        ont.declare(d.clone());
+
+        let integer = "http://www.w3.org/2001/XMLSchema#integer";
+        let restriction = |f, n: &str| {
+            DataRange::DatatypeRestriction(
+                b.datatype(integer),
+                vec![FacetRestriction {
+                    f,
+                    l: Literal::Datatype {
+                        literal: n.into(),
+                        datatype_iri: b.iri(integer),
+                    },
+                }],
+            )
+        };
+        ont.insert(DatatypeDefinition {
+            kind: d,
+            range: b.data_intersection_of(vec![
+                restriction(Facet::MinInclusive, "0"),
+                restriction(Facet::MaxExclusive, "10"),
+            ]),
+        });
+
+        let mut buf = vec![];
+        write(&mut buf, &ont).unwrap();
+        let ont2 = read_ok(&mut buf.as_slice());
+
+        let ont: SetOntology<_> = ont.into();
+        assert_eq!(ont, ont2);
+    }
+
     #[test]
     fn datatype_complement() {
         assert_round(include_str!("../../ont/owl-rdf/datatype-complement.owl"));
@@ -94,6 +94,7 @@ use std::borrow::Borrow;
 use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::collections::BTreeSet;
+use std::convert::TryFrom;
 use std::fmt::Debug;
 use std::fmt::Display;
 use std::fmt::Formatter;
@@ -103,6 +104,19 @@ use std::ops::Deref;
 use std::rc::Rc;
 use std::sync::Arc;
 
+use crate::error::HornedError;
+use crate::visitor::{Visit, Walk};
+use crate::vocab::{WithIRI, OWL};
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+
+mod signature;
+mod summary;
+
+pub use self::signature::axioms_signature;
+pub(crate) use self::signature::{signature_of, NamedEntityExtract};
+
 /// An
 /// [IRI](https://en.wikipedia.org/wiki/Internationalized_Resource_Identifier)
 /// is an internationalized version of an URI/URL.
@@ -111,8 +125,19 @@ use std::sync::Arc;
 /// created through `Build`; this caches the underlying String meaning
 /// that IRIs are light-weight to `clone`.
 #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "persist", derive(serde::Serialize, serde::Deserialize))]
 pub struct IRI<A>(A);
 
+/// The backing store of the strings underlying an `IRI`.
+///
+/// Every type in the model is generic over its backing store, which
+/// is fixed by the `Build` that creates its IRIs: `Build::new_rc`
+/// gives `RcStr` and `Build::new_arc` gives `ArcStr`. Values with
+/// different backing stores are different types and cannot be mixed,
+/// so an axiom built with `Build::new_rc` cannot be inserted into an
+/// `ArcStr` ontology; the compiler reports this as a mismatch between
+/// `Rc<str>` and `Arc<str>`. Use `Build::new_arc` throughout where
+/// ontologies are shared between threads.
 pub trait ForIRI:
     AsRef<str> + Borrow<str> + Clone + Debug + Eq + From<String> + Hash + PartialEq + Ord + PartialOrd
 {
@@ -132,9 +157,31 @@ impl<T: ?Sized> ForIRI for T where
 {
 }
 
+/// A single threaded backing store, created by `Build::new_rc`.
 pub type RcStr = Rc<str>;
+/// A thread safe backing store, created by `Build::new_arc`.
 pub type ArcStr = Arc<str>;
 
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for super::RcStr {}
+    impl Sealed for super::ArcStr {}
+    impl Sealed for String {}
+}
+
+/// A backing store provided by Horned-OWL.
+///
+/// This trait is sealed. Every backing store is serialized as a
+/// plain string, so data written with one can be read with another.
+pub trait BackingStore: ForIRI + sealed::Sealed {}
+
+impl BackingStore for RcStr {}
+
+impl BackingStore for ArcStr {}
+
+impl BackingStore for String {}
+
 impl<A: ForIRI> IRI<A> {
     pub fn underlying(&self) -> A {
         self.0.clone()
@@ -191,6 +238,76 @@ impl<A: ForIRI> Display for IRI<A> {
     }
 }
 
+/// Normalize the string form of an IRI.
+///
+/// Different IRIs may identify the same resource, for instance when
+/// they differ only in the case of their scheme. A `Build` given an
+/// `IriNormalizer` applies it to every IRI it creates, so that such
+/// IRIs are equal.
+pub trait IriNormalizer: Debug + Send + Sync {
+    fn normalize(&self, iri: &str) -> String;
+}
+
+/// An `IriNormalizer` which leaves every IRI unchanged.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoopNormalizer;
+
+impl IriNormalizer for NoopNormalizer {
+    fn normalize(&self, iri: &str) -> String {
+        iri.to_string()
+    }
+}
+
+/// An `IriNormalizer` which lowercases the scheme and host of an
+/// IRI, both of which are case-insensitive.
+///
+/// # Examples
+///
+/// ```
+/// # use horned_owl::model::*;
+/// assert_eq!(
+///     StandardNormalizer.normalize("HTTP://User@WWW.Example.COM:80/Path#Frag"),
+///     "http://User@www.example.com:80/Path#Frag"
+/// );
+/// assert_eq!(StandardNormalizer.normalize("URN:ISBN:X"), "urn:ISBN:X");
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StandardNormalizer;
+
+impl IriNormalizer for StandardNormalizer {
+    fn normalize(&self, iri: &str) -> String {
+        let scheme_end = match iri.find(':') {
+            Some(i)
+                if i > 0
+                    && iri[..i]
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c)) =>
+            {
+                i
+            }
+            _ => return iri.to_string(),
+        };
+        let mut normal = iri[..scheme_end].to_ascii_lowercase();
+        let rest = &iri[scheme_end..];
+        if let Some(after) = rest.strip_prefix("://") {
+            let authority_end = after.find(|c| "/?#".contains(c)).unwrap_or(after.len());
+            let authority = &after[..authority_end];
+            let host_start = authority.rfind('@').map_or(0, |i| i + 1);
+            let host_end = match authority.rfind(':') {
+                Some(i) if i > host_start && !authority[i..].contains(']') => i,
+                _ => authority.len(),
+            };
+            normal.push_str("://");
+            normal.push_str(&authority[..host_start]);
+            normal.push_str(&authority[host_start..host_end].to_lowercase());
+            normal.push_str(&after[host_end..]);
+        } else {
+            normal.push_str(rest);
+        }
+        normal
+    }
+}
+
 /// `Build` creates new `IRI` and `NamedEntity` instances.
 ///
 /// There is caching for performance. An `IRI` or `NamedEntity` with a
@@ -208,11 +325,40 @@ impl<A: ForIRI> Display for IRI<A> {
 pub struct Build<A: ForIRI>(
     RefCell<BTreeSet<IRI<A>>>,
     RefCell<BTreeSet<AnonymousIndividual<A>>>,
+    Option<Box<dyn IriNormalizer>>,
 );
 
 impl<A: ForIRI> Build<A> {
     pub fn new() -> Build<A> {
-        Build(RefCell::new(BTreeSet::new()), RefCell::new(BTreeSet::new()))
+        Build(
+            RefCell::new(BTreeSet::new()),
+            RefCell::new(BTreeSet::new()),
+            None,
+        )
+    }
+
+    /// Apply `normalizer` to every IRI constructed by this `Build`,
+    /// so that IRIs which normalize to the same string are equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc().with_normalizer(StandardNormalizer);
+    /// assert_eq!(
+    ///     b.iri("HTTP://www.example.com/iri"),
+    ///     b.iri("http://www.example.com/iri")
+    /// );
+    ///
+    /// // A thread safe `Build` can still be sent to another thread
+    /// let b = Build::new_arc().with_normalizer(StandardNormalizer);
+    /// std::thread::spawn(move || b.iri("HTTP://www.example.com/iri"))
+    ///     .join()
+    ///     .unwrap();
+    /// ```
+    pub fn with_normalizer<N: IriNormalizer + 'static>(mut self, normalizer: N) -> Build<A> {
+        self.2 = Some(Box::new(normalizer));
+        self
     }
 
     /// Constructs a new `AnonymousIndividual`
@@ -247,11 +393,18 @@ impl<A: ForIRI> Build<A> {
     /// assert_eq!("http://www.example.com", String::from(iri));
     /// ```
     pub fn iri<S: Borrow<str>>(&self, s: S) -> IRI<A> {
+        match &self.2 {
+            Some(normalizer) => self.cached_iri(&normalizer.normalize(s.borrow())),
+            None => self.cached_iri(s.borrow()),
+        }
+    }
+
+    fn cached_iri(&self, s: &str) -> IRI<A> {
         let mut cache = self.0.borrow_mut();
-        if let Some(iri) = cache.get(s.borrow()) {
+        if let Some(iri) = cache.get(s) {
             iri.clone()
         } else {
-            let iri = IRI(s.borrow().to_string().into());
+            let iri = IRI(s.to_string().into());
             cache.insert(iri.clone());
             iri
         }
@@ -297,6 +450,28 @@ impl<A: ForIRI> Build<A> {
         ObjectProperty(self.iri(s))
     }
 
+    /// Constructs a new `ObjectHasSelf` restriction on the
+    /// `ObjectProperty` with the IRI `s`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc();
+    /// let ce = b.has_self("http://www.example.com/knows");
+    ///
+    /// assert_eq!(
+    ///     ce,
+    ///     ClassExpression::ObjectHasSelf(b.object_property("http://www.example.com/knows").into())
+    /// );
+    /// ```
+    pub fn has_self<S>(&self, s: S) -> ClassExpression<A>
+    where
+        S: Borrow<str>,
+    {
+        ClassExpression::ObjectHasSelf(self.object_property(s).into())
+    }
+
     /// Constructs a new `AnnotationProperty`.
     ///
     /// # Examples
@@ -316,6 +491,51 @@ impl<A: ForIRI> Build<A> {
         AnnotationProperty(self.iri(s))
     }
 
+    /// Constructs a new `AnnotationAssertion` axiom.
+    ///
+    /// The `value` can be anything that converts to an
+    /// `AnnotationValue`, including a `&str` or `String` for a simple
+    /// literal, and an `IRI`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc();
+    /// let ax = b.annotation_assertion(
+    ///     "http://www.example.com/a",
+    ///     "http://www.w3.org/2000/01/rdf-schema#label",
+    ///     "A",
+    /// );
+    ///
+    /// assert_eq!(
+    ///     ax,
+    ///     AnnotationAssertion {
+    ///         subject: b.iri("http://www.example.com/a").into(),
+    ///         ann: Annotation {
+    ///             ap: b.annotation_property("http://www.w3.org/2000/01/rdf-schema#label"),
+    ///             av: Literal::Simple { literal: "A".into() }.into(),
+    ///         },
+    ///     }
+    ///     .into()
+    /// );
+    /// ```
+    pub fn annotation_assertion<S, T, V>(&self, subject: S, ap: T, value: V) -> AnnotatedAxiom<A>
+    where
+        S: Borrow<str>,
+        T: Borrow<str>,
+        V: Into<AnnotationValue<A>>,
+    {
+        AnnotationAssertion {
+            subject: self.iri(subject).into(),
+            ann: Annotation {
+                ap: self.annotation_property(ap),
+                av: value.into(),
+            },
+        }
+        .into()
+    }
+
     /// Constructs a new `DataProperty`.
     ///
     /// # Examples
@@ -372,15 +592,381 @@ impl<A: ForIRI> Build<A> {
     {
         Datatype(self.iri(s))
     }
+
+    /// Constructs a new `SameIndividual` axiom.
+    ///
+    /// The individuals are kept in the order given, with any
+    /// repeats removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc();
+    /// let ax = b.same_individual(vec![
+    ///     b.named_individual("http://www.example.com/a"),
+    ///     b.named_individual("http://www.example.com/b"),
+    ///     b.named_individual("http://www.example.com/a"),
+    /// ]);
+    ///
+    /// assert_eq!(
+    ///     ax,
+    ///     SameIndividual(vec![
+    ///         b.named_individual("http://www.example.com/a").into(),
+    ///         b.named_individual("http://www.example.com/b").into(),
+    ///     ])
+    /// );
+    /// ```
+    pub fn same_individual<I, T>(&self, it: I) -> SameIndividual<A>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Individual<A>>,
+    {
+        SameIndividual(unique_individuals(it))
+    }
+
+    /// Constructs a new `DifferentIndividuals` axiom.
+    ///
+    /// The individuals are kept in the order given, with any
+    /// repeats removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc();
+    /// let ax = b.different_individuals(vec![
+    ///     b.named_individual("http://www.example.com/a"),
+    ///     b.named_individual("http://www.example.com/b"),
+    ///     b.named_individual("http://www.example.com/c"),
+    /// ]);
+    ///
+    /// assert_eq!(ax.0.len(), 3);
+    /// assert_eq!(ax.0[2], b.named_individual("http://www.example.com/c").into());
+    /// ```
+    pub fn different_individuals<I, T>(&self, it: I) -> DifferentIndividuals<A>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Individual<A>>,
+    {
+        DifferentIndividuals(unique_individuals(it))
+    }
+
+    /// Constructs the intersection of the given class expressions.
+    ///
+    /// Operands which are themselves intersections are flattened one
+    /// level, and repeats are removed, keeping the first occurrence.
+    /// A single operand is returned as it is, and the intersection
+    /// of no operands is `owl:Thing`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc();
+    /// let a: ClassExpression<_> = b.class("http://www.example.com/a").into();
+    /// let c: ClassExpression<_> = b.class("http://www.example.com/c").into();
+    ///
+    /// assert_eq!(
+    ///     b.intersection_of(vec![
+    ///         a.clone(),
+    ///         ClassExpression::ObjectIntersectionOf(vec![a.clone(), c.clone()])
+    ///     ]),
+    ///     ClassExpression::ObjectIntersectionOf(vec![a.clone(), c])
+    /// );
+    /// assert_eq!(b.intersection_of(vec![a.clone()]), a);
+    /// ```
+    pub fn intersection_of<I>(&self, it: I) -> ClassExpression<A>
+    where
+        I: IntoIterator<Item = ClassExpression<A>>,
+    {
+        let mut v = flatten_operands(it, |ce| match ce {
+            ClassExpression::ObjectIntersectionOf(v) => v,
+            ce => vec![ce],
+        });
+        match v.len() {
+            0 => self.class(OWL::Thing.iri_str()).into(),
+            1 => v.remove(0),
+            _ => ClassExpression::ObjectIntersectionOf(v),
+        }
+    }
+
+    /// Constructs the union of the given class expressions.
+    ///
+    /// Operands which are themselves unions are flattened one level,
+    /// and repeats are removed, keeping the first occurrence. A
+    /// single operand is returned as it is, and the union of no
+    /// operands is `owl:Nothing`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc();
+    /// let a: ClassExpression<_> = b.class("http://www.example.com/a").into();
+    /// let c: ClassExpression<_> = b.class("http://www.example.com/c").into();
+    ///
+    /// assert_eq!(
+    ///     b.union_of(vec![a.clone(), c.clone(), a]),
+    ///     ClassExpression::ObjectUnionOf(vec![
+    ///         b.class("http://www.example.com/a").into(),
+    ///         c
+    ///     ])
+    /// );
+    /// assert_eq!(
+    ///     b.union_of(vec![]),
+    ///     b.class("http://www.w3.org/2002/07/owl#Nothing").into()
+    /// );
+    /// ```
+    pub fn union_of<I>(&self, it: I) -> ClassExpression<A>
+    where
+        I: IntoIterator<Item = ClassExpression<A>>,
+    {
+        let mut v = flatten_operands(it, |ce| match ce {
+            ClassExpression::ObjectUnionOf(v) => v,
+            ce => vec![ce],
+        });
+        match v.len() {
+            0 => self.class(OWL::Nothing.iri_str()).into(),
+            1 => v.remove(0),
+            _ => ClassExpression::ObjectUnionOf(v),
+        }
+    }
+
+    /// Constructs a new `ObjectPropertyRange` axiom.
+    ///
+    /// The range may be any class expression, not just a named
+    /// class.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc();
+    /// let ce = ClassExpression::ObjectSomeValuesFrom {
+    ///     ope: b.object_property("http://www.example.com/q").into(),
+    ///     bce: Box::new(b.class("http://www.example.com/D").into()),
+    /// };
+    /// let ax = b.object_property_range(b.object_property("http://www.example.com/p"), ce.clone());
+    ///
+    /// assert_eq!(ax.ce, ce);
+    /// ```
+    pub fn object_property_range<O, C>(&self, ope: O, ce: C) -> ObjectPropertyRange<A>
+    where
+        O: Into<ObjectPropertyExpression<A>>,
+        C: Into<ClassExpression<A>>,
+    {
+        ObjectPropertyRange {
+            ope: ope.into(),
+            ce: ce.into(),
+        }
+    }
+
+    /// Constructs a `DataHasValue` class expression: the class of
+    /// individuals which have `l` as a value of `dp`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc();
+    /// let l = Literal::Simple { literal: "blue".to_string() };
+    /// let ce = b.data_has_value(b.data_property("http://www.example.com/colour"), l.clone());
+    ///
+    /// assert_eq!(
+    ///     ce,
+    ///     ClassExpression::DataHasValue {
+    ///         dp: b.data_property("http://www.example.com/colour"),
+    ///         l
+    ///     }
+    /// );
+    /// ```
+    pub fn data_has_value(&self, dp: DataProperty<A>, l: Literal<A>) -> ClassExpression<A> {
+        ClassExpression::DataHasValue { dp, l }
+    }
+
+    /// Constructs a `DataSomeValuesFrom` class expression: the class
+    /// of individuals which have some value of `dp` in `dr`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc();
+    /// let integer = "http://www.w3.org/2001/XMLSchema#integer";
+    /// let non_negative = DataRange::DatatypeRestriction(
+    ///     b.datatype(integer),
+    ///     vec![FacetRestriction {
+    ///         f: Facet::MinInclusive,
+    ///         l: Literal::Datatype {
+    ///             literal: "0".to_string(),
+    ///             datatype_iri: b.iri(integer),
+    ///         },
+    ///     }],
+    /// );
+    /// let ce = b.data_some_values_from(b.data_property("http://www.example.com/age"), non_negative);
+    ///
+    /// assert!(matches!(
+    ///     ce,
+    ///     ClassExpression::DataSomeValuesFrom { dr: DataRange::DatatypeRestriction(..), .. }
+    /// ));
+    /// ```
+    pub fn data_some_values_from<D>(&self, dp: DataProperty<A>, dr: D) -> ClassExpression<A>
+    where
+        D: Into<DataRange<A>>,
+    {
+        ClassExpression::DataSomeValuesFrom { dp, dr: dr.into() }
+    }
+
+    /// Constructs a `DataIntersectionOf` data range: the values in
+    /// all of the given data ranges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc();
+    /// let dr = b.data_intersection_of(vec![
+    ///     b.datatype("http://www.w3.org/2002/07/owl#rational"),
+    ///     b.datatype("http://www.w3.org/2002/07/owl#real"),
+    /// ]);
+    ///
+    /// assert!(matches!(dr, DataRange::DataIntersectionOf(v) if v.len() == 2));
+    /// ```
+    pub fn data_intersection_of<I, D>(&self, it: I) -> DataRange<A>
+    where
+        I: IntoIterator<Item = D>,
+        D: Into<DataRange<A>>,
+    {
+        DataRange::DataIntersectionOf(it.into_iter().map(Into::into).collect())
+    }
+
+    /// Constructs a `DataUnionOf` data range: the values in any of
+    /// the given data ranges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc();
+    /// let dr = b.data_union_of(vec![
+    ///     b.datatype("http://www.w3.org/2001/XMLSchema#integer"),
+    ///     b.datatype("http://www.w3.org/2001/XMLSchema#string"),
+    /// ]);
+    ///
+    /// assert!(matches!(dr, DataRange::DataUnionOf(v) if v.len() == 2));
+    /// ```
+    pub fn data_union_of<I, D>(&self, it: I) -> DataRange<A>
+    where
+        I: IntoIterator<Item = D>,
+        D: Into<DataRange<A>>,
+    {
+        DataRange::DataUnionOf(it.into_iter().map(Into::into).collect())
+    }
+
+    /// Constructs a `DataComplementOf` data range: the values which
+    /// are not in `dr`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc();
+    /// let integer = b.datatype("http://www.w3.org/2001/XMLSchema#integer");
+    ///
+    /// assert_eq!(
+    ///     b.data_complement_of(integer.clone()),
+    ///     DataRange::DataComplementOf(Box::new(integer.into()))
+    /// );
+    /// ```
+    pub fn data_complement_of<D>(&self, dr: D) -> DataRange<A>
+    where
+        D: Into<DataRange<A>>,
+    {
+        DataRange::DataComplementOf(Box::new(dr.into()))
+    }
+}
+
+/// Flatten `it`, replacing each class expression with the operands
+/// returned by `operands`, and removing repeats.
+fn flatten_operands<A: ForIRI, I>(
+    it: I,
+    operands: fn(ClassExpression<A>) -> Vec<ClassExpression<A>>,
+) -> Vec<ClassExpression<A>>
+where
+    I: IntoIterator<Item = ClassExpression<A>>,
+{
+    let mut v: Vec<ClassExpression<A>> = vec![];
+    for ce in it {
+        for ce in operands(ce) {
+            if !v.contains(&ce) {
+                v.push(ce);
+            }
+        }
+    }
+    v
+}
+
+fn unique_individuals<A: ForIRI, I, T>(it: I) -> Vec<Individual<A>>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<Individual<A>>,
+{
+    let mut v: Vec<Individual<A>> = vec![];
+    for i in it {
+        let i = i.into();
+        if !v.contains(&i) {
+            v.push(i);
+        }
+    }
+    v
 }
 
 impl Build<RcStr> {
+    /// Create a `Build` whose IRIs are backed by `Rc<str>`.
+    ///
+    /// Entities from this builder belong in `RcStr` ontologies, such
+    /// as `SetOntology::new_rc`; see `Build::new_arc` for what
+    /// happens when the two are mixed.
     pub fn new_rc() -> Build<RcStr> {
         Build::new()
     }
 }
 
 impl Build<ArcStr> {
+    /// Create a `Build` whose IRIs are backed by `Arc<str>`, for
+    /// ontologies which are shared between threads.
+    ///
+    /// The backing store is part of every type, so an axiom built
+    /// with `Build::new_rc` cannot be inserted into an `ArcStr`
+    /// ontology. The compiler reports that the trait
+    /// `From<DeclareClass<Rc<str>>>` is not implemented for
+    /// `AnnotatedAxiom<Arc<str>>`:
+    ///
+    /// ```compile_fail,E0277
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_arc();
+    /// o.insert(DeclareClass(b.class("http://www.example.com/a")));
+    /// ```
+    ///
+    /// Build the axiom with a builder of the same store, or use
+    /// `Rebuild` to convert an axiom which already exists:
+    ///
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let rc = Build::new_rc();
+    /// let arc = Build::new_arc();
+    /// let mut o = SetOntology::new_arc();
+    ///
+    /// o.insert(DeclareClass(arc.class("http://www.example.com/a")));
+    ///
+    /// let dc = DeclareClass(rc.class("http://www.example.com/b"));
+    /// o.insert(dc.rebuild(&arc));
+    /// assert_eq!(o.len(), 2);
+    /// ```
     pub fn new_arc() -> Build<ArcStr> {
         Build::new()
     }
@@ -392,6 +978,92 @@ impl Build<String> {
     }
 }
 
+/// Rebuild an entity with a different string type.
+///
+/// Every `IRI` and `AnonymousIndividual` in the entity is recreated
+/// through the given `Build`, so the result shares its strings
+/// exactly as if it had been constructed with that `Build` in the
+/// first place. This can be used, for example, to move an entity
+/// built with `RcStr` to another thread as `ArcStr`.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// let rc = Build::new_rc();
+/// let arc = Build::new_arc();
+///
+/// let sc: SubClassOf<RcStr> = SubClassOf {
+///     sup: rc.class("http://www.example.com/a").into(),
+///     sub: rc.class("http://www.example.com/b").into(),
+/// };
+/// let sc: SubClassOf<ArcStr> = sc.rebuild(&arc);
+/// assert_eq!(sc.sup, arc.class("http://www.example.com/a").into());
+/// ```
+pub trait Rebuild<B: ForIRI> {
+    type Output;
+
+    fn rebuild(&self, b: &Build<B>) -> Self::Output;
+}
+
+impl<B: ForIRI, T: Rebuild<B>> Rebuild<B> for Vec<T> {
+    type Output = Vec<T::Output>;
+
+    fn rebuild(&self, b: &Build<B>) -> Vec<T::Output> {
+        self.iter().map(|t| t.rebuild(b)).collect()
+    }
+}
+
+impl<B: ForIRI, T: Rebuild<B>> Rebuild<B> for Box<T> {
+    type Output = Box<T::Output>;
+
+    fn rebuild(&self, b: &Build<B>) -> Box<T::Output> {
+        Box::new((**self).rebuild(b))
+    }
+}
+
+impl<B: ForIRI, T: Rebuild<B>> Rebuild<B> for Option<T> {
+    type Output = Option<T::Output>;
+
+    fn rebuild(&self, b: &Build<B>) -> Option<T::Output> {
+        self.as_ref().map(|t| t.rebuild(b))
+    }
+}
+
+impl<B: ForIRI, T: Rebuild<B>> Rebuild<B> for BTreeSet<T>
+where
+    T::Output: Ord,
+{
+    type Output = BTreeSet<T::Output>;
+
+    fn rebuild(&self, b: &Build<B>) -> BTreeSet<T::Output> {
+        self.iter().map(|t| t.rebuild(b)).collect()
+    }
+}
+
+impl<B: ForIRI> Rebuild<B> for u32 {
+    type Output = u32;
+
+    fn rebuild(&self, _b: &Build<B>) -> u32 {
+        *self
+    }
+}
+
+impl<B: ForIRI> Rebuild<B> for String {
+    type Output = String;
+
+    fn rebuild(&self, _b: &Build<B>) -> String {
+        self.clone()
+    }
+}
+
+impl<A: ForIRI, B: ForIRI> Rebuild<B> for IRI<A> {
+    type Output = IRI<B>;
+
+    fn rebuild(&self, b: &Build<B>) -> IRI<B> {
+        b.iri(self.0.as_ref())
+    }
+}
+
 macro_rules! named {
     ($($(#[$attr:meta])* $name:ident),*)  => {
 
@@ -406,13 +1078,25 @@ macro_rules! named {
         /// equivalent form. The individual structs for each variant
         /// provide us types for use elsewhere in the library.
         #[derive(Clone, Debug, Eq, PartialEq, Hash)]
+        #[cfg_attr(feature = "persist", derive(serde::Serialize, serde::Deserialize))]
         pub enum NamedEntity<A>{
             $($name($name<A>)),*
         }
 
+        impl<A: ForIRI, B: ForIRI> Rebuild<B> for NamedEntity<A> {
+            type Output = NamedEntity<B>;
+
+            fn rebuild(&self, b: &Build<B>) -> NamedEntity<B> {
+                match self {
+                    $(NamedEntity::$name(n) => NamedEntity::$name(n.rebuild(b))),*
+                }
+            }
+        }
+
         $(
             $(#[$attr]) *
             #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+            #[cfg_attr(feature = "persist", derive(serde::Serialize, serde::Deserialize))]
             pub struct $name<A>(pub IRI<A>);
 
             impl<A: ForIRI> From<IRI<A>> for $name<A> {
@@ -463,7 +1147,15 @@ macro_rules! named {
                 }
             }
 
-            impl<A:ForIRI> $name<A> {
+            impl<A: ForIRI, B: ForIRI> Rebuild<B> for $name<A> {
+                type Output = $name<B>;
+
+                fn rebuild(&self, b: &Build<B>) -> $name<B> {
+                    $name(self.0.rebuild(b))
+                }
+            }
+
+            impl<A:ForIRI> $name<A> {
                 pub fn is<I>(&self, iri: I) -> bool
                     where I:Into<IRI<A>>
                 {
@@ -528,6 +1220,7 @@ named! {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "persist", derive(serde::Serialize, serde::Deserialize))]
 pub struct AnonymousIndividual<A>(pub A);
 
 impl<A: ForIRI> Deref for AnonymousIndividual<A> {
@@ -563,6 +1256,7 @@ impl<A: ForIRI> From<AnonymousIndividual<A>> for String {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "persist", derive(serde::Serialize, serde::Deserialize))]
 pub enum Individual<A> {
     Anonymous(AnonymousIndividual<A>),
     Named(NamedIndividual<A>),
@@ -616,6 +1310,7 @@ impl<A: ForIRI> From<IRI<A>> for Individual<A> {
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+#[cfg_attr(feature = "persist", derive(serde::Serialize, serde::Deserialize))]
 pub enum AnnotationSubject<A> {
     IRI(IRI<A>),
     AnonymousIndividual(AnonymousIndividual<A>),
@@ -682,6 +1377,175 @@ impl<A: ForIRI> From<NamedEntity<A>> for AnnotatedAxiom<A> {
     }
 }
 
+impl<A: ForIRI> Axiom<A> {
+    /// Return the entity declared by this axiom, or `None` if it is
+    /// not a declaration.
+    ///
+    /// This is the inverse of `From<NamedEntity<A>> for Axiom<A>`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc();
+    /// let ne: NamedEntity<_> = b.class("http://www.example.com/a").into();
+    /// let ax: Axiom<_> = ne.clone().into();
+    ///
+    /// assert_eq!(ax.as_entity(), Some(ne));
+    /// ```
+    pub fn as_entity(&self) -> Option<NamedEntity<A>> {
+        match self {
+            Axiom::DeclareClass(DeclareClass(e)) => Some(e.clone().into()),
+            Axiom::DeclareObjectProperty(DeclareObjectProperty(e)) => Some(e.clone().into()),
+            Axiom::DeclareAnnotationProperty(DeclareAnnotationProperty(e)) => {
+                Some(e.clone().into())
+            }
+            Axiom::DeclareDataProperty(DeclareDataProperty(e)) => Some(e.clone().into()),
+            Axiom::DeclareNamedIndividual(DeclareNamedIndividual(e)) => Some(e.clone().into()),
+            Axiom::DeclareDatatype(DeclareDatatype(e)) => Some(e.clone().into()),
+            _ => None,
+        }
+    }
+
+    /// Return the IRI of the conventional subject of this axiom.
+    ///
+    /// The subject is:
+    ///
+    /// - the entity of a declaration;
+    /// - the subclass of `SubClassOf`, the only named class of
+    ///   `EquivalentClasses` and the class of `DisjointUnion`;
+    /// - the sub property of `SubObjectPropertyOf`,
+    ///   `SubDataPropertyOf` and `SubAnnotationPropertyOf`;
+    /// - the property of a domain, range or characteristic axiom;
+    /// - the datatype of `DatatypeDefinition` and the class of
+    ///   `HasKey`;
+    /// - the individual of `ClassAssertion` and the source
+    ///   individual of a property assertion;
+    /// - the subject of `AnnotationAssertion`.
+    ///
+    /// `None` is returned when the subject is anonymous, such as
+    /// for a general class inclusion axiom, and for axioms with no
+    /// single subject, such as `DisjointClasses`, `SameIndividual`,
+    /// `InverseObjectProperties`, `Import` and `OntologyAnnotation`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc();
+    /// let a = b.class("http://www.example.com/a");
+    /// let c = b.class("http://www.example.com/c");
+    ///
+    /// let ax: Axiom<_> = SubClassOf { sub: a.clone().into(), sup: c.clone().into() }.into();
+    /// assert_eq!(ax.subject_iri(), Some(&a.0));
+    ///
+    /// let ax: Axiom<_> = ClassAssertion {
+    ///     ce: a.clone().into(),
+    ///     i: b.named_individual("http://www.example.com/i").into(),
+    /// }
+    /// .into();
+    /// assert_eq!(ax.subject_iri(), Some(&b.iri("http://www.example.com/i")));
+    ///
+    /// let ax: Axiom<_> = DeclareClass(c.clone()).into();
+    /// assert_eq!(ax.subject_iri(), Some(&c.0));
+    ///
+    /// let ax: Axiom<_> = AnnotationAssertion {
+    ///     subject: c.0.clone().into(),
+    ///     ann: Annotation {
+    ///         ap: b.annotation_property("http://www.w3.org/2000/01/rdf-schema#label"),
+    ///         av: "c".into(),
+    ///     },
+    /// }
+    /// .into();
+    /// assert_eq!(ax.subject_iri(), Some(&c.0));
+    ///
+    /// let gci: Axiom<_> = SubClassOf {
+    ///     sub: ClassExpression::ObjectComplementOf(Box::new(a.clone().into())),
+    ///     sup: c.clone().into(),
+    /// }
+    /// .into();
+    /// assert_eq!(gci.subject_iri(), None);
+    ///
+    /// let ax: Axiom<_> = DisjointClasses(vec![a.into(), c.into()]).into();
+    /// assert_eq!(ax.subject_iri(), None);
+    /// ```
+    pub fn subject_iri(&self) -> Option<&IRI<A>> {
+        fn ce<A>(ce: &ClassExpression<A>) -> Option<&IRI<A>> {
+            match ce {
+                ClassExpression::Class(c) => Some(&c.0),
+                _ => None,
+            }
+        }
+
+        fn ope<A>(ope: &ObjectPropertyExpression<A>) -> Option<&IRI<A>> {
+            match ope {
+                ObjectPropertyExpression::ObjectProperty(op) => Some(&op.0),
+                _ => None,
+            }
+        }
+
+        fn ind<A>(i: &Individual<A>) -> Option<&IRI<A>> {
+            match i {
+                Individual::Named(ni) => Some(&ni.0),
+                _ => None,
+            }
+        }
+
+        match self {
+            Axiom::DeclareClass(DeclareClass(e)) => Some(&e.0),
+            Axiom::DeclareObjectProperty(DeclareObjectProperty(e)) => Some(&e.0),
+            Axiom::DeclareAnnotationProperty(DeclareAnnotationProperty(e)) => Some(&e.0),
+            Axiom::DeclareDataProperty(DeclareDataProperty(e)) => Some(&e.0),
+            Axiom::DeclareNamedIndividual(DeclareNamedIndividual(e)) => Some(&e.0),
+            Axiom::DeclareDatatype(DeclareDatatype(e)) => Some(&e.0),
+            Axiom::SubClassOf(SubClassOf { sub, .. }) => ce(sub),
+            Axiom::EquivalentClasses(EquivalentClasses(v)) => {
+                let mut named = v.iter().filter_map(ce);
+                match (named.next(), named.next()) {
+                    (Some(iri), None) => Some(iri),
+                    _ => None,
+                }
+            }
+            Axiom::DisjointUnion(DisjointUnion(c, _)) => Some(&c.0),
+            Axiom::SubObjectPropertyOf(SubObjectPropertyOf {
+                sub: SubObjectPropertyExpression::ObjectPropertyExpression(sub),
+                ..
+            }) => ope(sub),
+            Axiom::ObjectPropertyDomain(ObjectPropertyDomain { ope: e, .. })
+            | Axiom::ObjectPropertyRange(ObjectPropertyRange { ope: e, .. })
+            | Axiom::FunctionalObjectProperty(FunctionalObjectProperty(e))
+            | Axiom::InverseFunctionalObjectProperty(InverseFunctionalObjectProperty(e))
+            | Axiom::ReflexiveObjectProperty(ReflexiveObjectProperty(e))
+            | Axiom::IrreflexiveObjectProperty(IrreflexiveObjectProperty(e))
+            | Axiom::SymmetricObjectProperty(SymmetricObjectProperty(e))
+            | Axiom::AsymmetricObjectProperty(AsymmetricObjectProperty(e))
+            | Axiom::TransitiveObjectProperty(TransitiveObjectProperty(e)) => ope(e),
+            Axiom::SubDataPropertyOf(SubDataPropertyOf { sub, .. }) => Some(&sub.0),
+            Axiom::DataPropertyDomain(DataPropertyDomain { dp, .. })
+            | Axiom::DataPropertyRange(DataPropertyRange { dp, .. })
+            | Axiom::FunctionalDataProperty(FunctionalDataProperty(dp)) => Some(&dp.0),
+            Axiom::DatatypeDefinition(DatatypeDefinition { kind, .. }) => Some(&kind.0),
+            Axiom::HasKey(HasKey { ce: e, .. }) => ce(e),
+            Axiom::ClassAssertion(ClassAssertion { i, .. }) => ind(i),
+            Axiom::ObjectPropertyAssertion(ObjectPropertyAssertion { from, .. })
+            | Axiom::NegativeObjectPropertyAssertion(NegativeObjectPropertyAssertion {
+                from,
+                ..
+            })
+            | Axiom::DataPropertyAssertion(DataPropertyAssertion { from, .. })
+            | Axiom::NegativeDataPropertyAssertion(NegativeDataPropertyAssertion {
+                from, ..
+            }) => ind(from),
+            Axiom::AnnotationAssertion(AnnotationAssertion {
+                subject: AnnotationSubject::IRI(iri),
+                ..
+            }) => Some(iri),
+            Axiom::SubAnnotationPropertyOf(SubAnnotationPropertyOf { sub, .. }) => Some(&sub.0),
+            Axiom::AnnotationPropertyDomain(AnnotationPropertyDomain { ap, .. })
+            | Axiom::AnnotationPropertyRange(AnnotationPropertyRange { ap, .. }) => Some(&ap.0),
+            _ => None,
+        }
+    }
+}
+
 /// An interface providing access to any `Annotation` attached to an
 /// entity.
 trait Annotated<A> {
@@ -691,6 +1555,20 @@ trait Annotated<A> {
     fn annotation(&self) -> &BTreeSet<Annotation<A>>;
 }
 
+/// A characteristic of an object property, each of which is
+/// asserted with its own kind of axiom, such as
+/// `FunctionalObjectProperty` for `Functional`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum PropertyCharacteristic {
+    Functional,
+    InverseFunctional,
+    Transitive,
+    Symmetric,
+    Asymmetric,
+    Reflexive,
+    Irreflexive,
+}
+
 /// An interface providing access to the `AxiomKind`
 ///
 /// An OWL ontology consists of a set of axioms of one of many
@@ -703,6 +1581,11 @@ pub trait Kinded {
 
 /// An `AnnotatedAxiom` is an `Axiom` with one orpmore `Annotation`.
 #[derive(Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+#[cfg_attr(feature = "persist", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "persist",
+    serde(bound(deserialize = "A: Ord + serde::Deserialize<'de>"))
+)]
 pub struct AnnotatedAxiom<A> {
     pub axiom: Axiom<A>,
     pub ann: BTreeSet<Annotation<A>>,
@@ -738,6 +1621,31 @@ impl<A: ForIRI> AnnotatedAxiom<A> {
     pub fn logical_hash<H: Hasher>(&self, state: &mut H) {
         self.axiom.hash(state)
     }
+
+    /// Return this axiom without its annotations.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc();
+    /// let decl = AnnotatedAxiom::new(
+    ///     DeclareClass(b.class("http://www.example.com/a")),
+    ///     vec![Annotation {
+    ///         ap: b.annotation_property("http://www.w3.org/2000/01/rdf-schema#comment"),
+    ///         av: b.iri("http://www.example.com/c").into(),
+    ///     }]
+    ///     .into_iter()
+    ///     .collect(),
+    /// );
+    ///
+    /// assert_eq!(
+    ///     decl.strip_annotations(),
+    ///     DeclareClass(b.class("http://www.example.com/a")).into()
+    /// );
+    /// ```
+    pub fn strip_annotations(self) -> AnnotatedAxiom<A> {
+        self.axiom.into()
+    }
 }
 
 impl<A: ForIRI> From<Axiom<A>> for AnnotatedAxiom<A> {
@@ -778,6 +1686,28 @@ macro_rules! axiomimpl {
     };
 }
 
+/// Generate `Rebuild` for a tuple-like axiom with one or two fields
+macro_rules! rebuild_tuple {
+    ($A:ident, $name:ident, $t0:ty) => {
+        impl<$A: ForIRI, B: ForIRI> Rebuild<B> for $name<$A> {
+            type Output = $name<B>;
+
+            fn rebuild(&self, b: &Build<B>) -> $name<B> {
+                $name(self.0.rebuild(b))
+            }
+        }
+    };
+    ($A:ident, $name:ident, $t0:ty, $t1:ty) => {
+        impl<$A: ForIRI, B: ForIRI> Rebuild<B> for $name<$A> {
+            type Output = $name<B>;
+
+            fn rebuild(&self, b: &Build<B>) -> $name<B> {
+                $name(self.0.rebuild(b), self.1.rebuild(b))
+            }
+        }
+    };
+}
+
 /// Define a new axiom
 ///
 /// Axioms can be either a tuple-like or normal struct. Documentation
@@ -792,8 +1722,10 @@ macro_rules! axiom {
     {
         $(#[$attr]) *
         #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        #[cfg_attr(feature = "persist", derive(serde::Serialize, serde::Deserialize))]
         pub struct $name<$A>($(pub $tt),*);
         axiomimpl!($A, $name);
+        rebuild_tuple!($A, $name, $($tt),*);
     };
     ($A:ident $name:ident {
         $($field_name:ident: $field_type:ty),*
@@ -802,6 +1734,7 @@ macro_rules! axiom {
     ) => {
         $(#[$attr]) *
         #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        #[cfg_attr(feature = "persist", derive(serde::Serialize, serde::Deserialize))]
         pub struct $name<$A>
         {
             $(pub $field_name: $field_type),*,
@@ -818,6 +1751,16 @@ macro_rules! axiom {
 
         }
         axiomimpl!($A, $name);
+
+        impl<$A: ForIRI, B: ForIRI> Rebuild<B> for $name<$A> {
+            type Output = $name<B>;
+
+            fn rebuild(&self, b: &Build<B>) -> $name<B> {
+                $name {
+                    $($field_name: self.$field_name.rebuild(b)),*
+                }
+            }
+        }
     }
 }
 
@@ -850,6 +1793,19 @@ macro_rules! axioms {
             pub fn all_kinds() -> Vec<AxiomKind> {
                 vec![$(AxiomKind::$name),*]
             }
+
+            /// Return the name of this kind of axiom.
+            ///
+            /// # Examples
+            /// ```
+            /// # use horned_owl::model::*;
+            /// assert_eq!(AxiomKind::SubClassOf.name(), "SubClassOf");
+            /// ```
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(AxiomKind::$name => stringify!($name)),*
+                }
+            }
         }
 
         impl std::fmt::Debug for AxiomKind {
@@ -880,6 +1836,7 @@ macro_rules! axioms {
         /// type for all structs. The struct and enum variants all
         /// share identical names.
         #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        #[cfg_attr(feature = "persist", derive(serde::Serialize, serde::Deserialize))]
         pub enum Axiom<$A>{
             $($name($name<$A>)),*
         }
@@ -903,6 +1860,16 @@ macro_rules! axioms {
         //     }
         // }
 
+        impl<$A: ForIRI, B: ForIRI> Rebuild<B> for Axiom<$A> {
+            type Output = Axiom<B>;
+
+            fn rebuild(&self, b: &Build<B>) -> Axiom<B> {
+                match self {
+                    $(Axiom::$name(ax) => Axiom::$name(ax.rebuild(b))),*
+                }
+            }
+        }
+
         impl<$A:ForIRI> Kinded for Axiom<$A>
         {
             fn kind(&self) -> AxiomKind
@@ -1284,6 +2251,7 @@ axioms! {
 
 // Non-axiom data structures associated with OWL
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "persist", derive(serde::Serialize, serde::Deserialize))]
 pub enum Literal<A> {
     // Simple Literals are syntactic sugar for a Datatype with type:
     // http://www.w3.org/2001/XMLSchema#string
@@ -1303,6 +2271,32 @@ pub enum Literal<A> {
     },
 }
 
+/// The datatype of a simple literal.
+const XSD_STRING: &str = "http://www.w3.org/2001/XMLSchema#string";
+
+/// The datatype of a language-tagged literal.
+const RDF_LANG_STRING: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#langString";
+
+/// The local names of `xsd:integer` and its derived datatypes.
+const XSD_INTEGER_TYPES: &[&str] = &[
+    "integer",
+    "long",
+    "int",
+    "short",
+    "byte",
+    "nonNegativeInteger",
+    "nonPositiveInteger",
+    "positiveInteger",
+    "negativeInteger",
+    "unsignedLong",
+    "unsignedInt",
+    "unsignedShort",
+    "unsignedByte",
+];
+
+/// The local names of the other numeric XSD datatypes.
+const XSD_FLOAT_TYPES: &[&str] = &["double", "float", "decimal"];
+
 impl<A: ForIRI> Literal<A> {
     pub fn literal(&self) -> &String {
         match self {
@@ -1311,6 +2305,336 @@ impl<A: ForIRI> Literal<A> {
             Literal::Datatype { literal, .. } => literal,
         }
     }
+
+    /// Return the effective datatype of this literal.
+    ///
+    /// A simple literal has the datatype `xsd:string` and a
+    /// language-tagged literal has `rdf:langString`, so that a plain
+    /// literal and one with an explicit `xsd:string` datatype can be
+    /// compared on the same datatype.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc();
+    /// let simple: Literal<RcStr> = Literal::Simple { literal: "a".into() };
+    /// let typed = Literal::Datatype {
+    ///     literal: "a".into(),
+    ///     datatype_iri: b.iri("http://www.w3.org/2001/XMLSchema#string"),
+    /// };
+    /// assert_eq!(simple.canonical_datatype(), typed.canonical_datatype());
+    ///
+    /// let tagged: Literal<RcStr> = Literal::Language {
+    ///     literal: "a".into(),
+    ///     lang: "en".into(),
+    /// };
+    /// assert_eq!(
+    ///     tagged.canonical_datatype(),
+    ///     b.iri("http://www.w3.org/1999/02/22-rdf-syntax-ns#langString")
+    /// );
+    /// ```
+    pub fn canonical_datatype(&self) -> IRI<A> {
+        match self {
+            Literal::Simple { .. } => IRI(XSD_STRING.to_string().into()),
+            Literal::Language { .. } => IRI(RDF_LANG_STRING.to_string().into()),
+            Literal::Datatype { datatype_iri, .. } => datatype_iri.clone(),
+        }
+    }
+
+    /// Return the local name of the datatype of this literal if it
+    /// is an XSD datatype.
+    fn xsd_datatype(&self) -> Option<&str> {
+        match self {
+            Literal::Datatype { datatype_iri, .. } => {
+                datatype_iri.strip_prefix("http://www.w3.org/2001/XMLSchema#")
+            }
+            _ => None,
+        }
+    }
+
+    /// Return the value of this literal as an `i64`.
+    ///
+    /// Returns `None` unless the datatype is `xsd:integer` or one of
+    /// its derived types and the lexical form is a valid integer
+    /// which fits into an `i64`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc();
+    /// let l: Literal<_> = Literal::Datatype {
+    ///     literal: "42".into(),
+    ///     datatype_iri: b.iri("http://www.w3.org/2001/XMLSchema#integer"),
+    /// };
+    /// assert_eq!(l.as_i64(), Some(42));
+    ///
+    /// let l: Literal<_> = Literal::Datatype {
+    ///     literal: "4.2".into(),
+    ///     datatype_iri: b.iri("http://www.w3.org/2001/XMLSchema#integer"),
+    /// };
+    /// assert_eq!(l.as_i64(), None);
+    ///
+    /// let l: Literal<RcStr> = Literal::Simple { literal: "42".into() };
+    /// assert_eq!(l.as_i64(), None);
+    /// ```
+    pub fn as_i64(&self) -> Option<i64> {
+        self.as_i128().and_then(|i| i64::try_from(i).ok())
+    }
+
+    /// Return the value of this literal as an `i128`, if it has an
+    /// integer XSD datatype and fits.
+    fn as_i128(&self) -> Option<i128> {
+        match self.xsd_datatype()? {
+            dt if XSD_INTEGER_TYPES.contains(&dt) => {
+                let lit = self.literal().trim();
+                lit.strip_prefix('+').unwrap_or(lit).parse().ok()
+            }
+            _ => None,
+        }
+    }
+
+    /// Return the value of this literal as an `f64`.
+    ///
+    /// Returns `None` unless the datatype is `xsd:double`,
+    /// `xsd:float` or `xsd:decimal` and the lexical form is valid.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc();
+    /// let l: Literal<_> = Literal::Datatype {
+    ///     literal: "-INF".into(),
+    ///     datatype_iri: b.iri("http://www.w3.org/2001/XMLSchema#double"),
+    /// };
+    /// assert_eq!(l.as_f64(), Some(f64::NEG_INFINITY));
+    /// ```
+    pub fn as_f64(&self) -> Option<f64> {
+        match self.xsd_datatype()? {
+            dt if XSD_FLOAT_TYPES.contains(&dt) => match self.literal().trim() {
+                "INF" | "+INF" => Some(f64::INFINITY),
+                "-INF" => Some(f64::NEG_INFINITY),
+                "NaN" => Some(f64::NAN),
+                lit if lit.chars().all(|c| "0123456789+-.eE".contains(c)) => lit.parse().ok(),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Return the value of this literal as an `f64`, if it has any
+    /// numeric XSD datatype.
+    fn numeric_value(&self) -> Option<f64> {
+        self.as_i128().map(|i| i as f64).or_else(|| self.as_f64())
+    }
+
+    /// Compare two literals by value.
+    ///
+    /// Literals with numeric XSD datatypes are compared by their
+    /// value, even when their datatypes differ, and are incomparable
+    /// if either is not a valid number. Other literals are compared
+    /// lexically, if they have the same datatype or language tag.
+    /// All other pairs are incomparable, and `None` is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use std::cmp::Ordering;
+    /// let b = Build::new_rc();
+    /// let int = |s: &str| -> Literal<_> {
+    ///     Literal::Datatype {
+    ///         literal: s.into(),
+    ///         datatype_iri: b.iri("http://www.w3.org/2001/XMLSchema#integer"),
+    ///     }
+    /// };
+    ///
+    /// // The derived ordering is lexical
+    /// assert!(int("10") < int("9"));
+    /// assert_eq!(int("10").value_cmp(&int("9")), Some(Ordering::Greater));
+    ///
+    /// let mut v = vec![int("10"), int("9"), int("-1")];
+    /// v.sort_by(|a, b| a.value_cmp(b).unwrap());
+    /// assert_eq!(v, vec![int("-1"), int("9"), int("10")]);
+    /// assert_eq!(int("10").value_cmp(&Literal::Simple { literal: "9".into() }), None);
+    /// ```
+    pub fn value_cmp(&self, other: &Literal<A>) -> Option<Ordering> {
+        let is_numeric = |l: &Literal<A>| {
+            l.xsd_datatype()
+                .is_some_and(|dt| XSD_INTEGER_TYPES.contains(&dt) || XSD_FLOAT_TYPES.contains(&dt))
+        };
+
+        match (self, other) {
+            _ if is_numeric(self) && is_numeric(other) => {
+                if let (Some(a), Some(b)) = (self.as_i128(), other.as_i128()) {
+                    Some(a.cmp(&b))
+                } else {
+                    self.numeric_value()?.partial_cmp(&other.numeric_value()?)
+                }
+            }
+            _ if is_numeric(self) || is_numeric(other) => None,
+            (Literal::Simple { literal: a }, Literal::Simple { literal: b }) => Some(a.cmp(b)),
+            (
+                Literal::Language {
+                    literal: a,
+                    lang: la,
+                },
+                Literal::Language {
+                    literal: b,
+                    lang: lb,
+                },
+            ) if la == lb => Some(a.cmp(b)),
+            (
+                Literal::Datatype {
+                    literal: a,
+                    datatype_iri: da,
+                },
+                Literal::Datatype {
+                    literal: b,
+                    datatype_iri: db,
+                },
+            ) if da == db => Some(a.cmp(b)),
+            _ => None,
+        }
+    }
+
+    /// Return the value of this literal as a `bool`.
+    ///
+    /// Returns `None` unless the datatype is `xsd:boolean` and the
+    /// lexical form is one of `true`, `false`, `1` or `0`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc();
+    /// let l: Literal<_> = Literal::Datatype {
+    ///     literal: "1".into(),
+    ///     datatype_iri: b.iri("http://www.w3.org/2001/XMLSchema#boolean"),
+    /// };
+    /// assert_eq!(l.as_bool(), Some(true));
+    /// assert_eq!(l.as_i64(), None);
+    /// ```
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.xsd_datatype()? {
+            "boolean" => match self.literal().trim() {
+                "true" | "1" => Some(true),
+                "false" | "0" => Some(false),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Return this literal with its lexical form in canonical form.
+    ///
+    /// Equal values with different lexical forms, such as
+    /// `"1.0"^^xsd:decimal` and `"1.00"^^xsd:decimal`, have the same
+    /// canonical form. The datatypes which are canonicalized are:
+    ///
+    ///  - `xsd:boolean`, where `1` and `0` become `true` and `false`.
+    ///  - `xsd:integer` and its derived types, where a leading `+`
+    ///    and leading zeros are removed. Values which do not fit into
+    ///    an `i64` are unchanged.
+    ///  - `xsd:decimal`, where a leading `+`, leading zeros and
+    ///    trailing zeros after the decimal point are removed, as is
+    ///    the decimal point if nothing follows it, as for XSD 1.1.
+    ///
+    /// All other literals, including those of `xsd:double` and
+    /// `xsd:float` and those with an invalid lexical form, are
+    /// returned unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc();
+    /// let l: Literal<_> = Literal::Datatype {
+    ///     literal: "+01.50".into(),
+    ///     datatype_iri: b.iri("http://www.w3.org/2001/XMLSchema#decimal"),
+    /// };
+    /// assert_eq!(l.canonical().literal(), "1.5");
+    /// ```
+    pub fn canonical(&self) -> Literal<A> {
+        let canonical = match (self, self.xsd_datatype()) {
+            (Literal::Datatype { datatype_iri, .. }, Some(dt)) => {
+                let literal = if dt == "boolean" {
+                    self.as_bool().map(|b| b.to_string())
+                } else if dt == "decimal" {
+                    canonical_decimal(self.literal().trim())
+                } else {
+                    self.as_i64().map(|i| i.to_string())
+                };
+
+                literal.map(|literal| Literal::Datatype {
+                    literal,
+                    datatype_iri: datatype_iri.clone(),
+                })
+            }
+            _ => None,
+        };
+
+        canonical.unwrap_or_else(|| self.clone())
+    }
+
+    /// Return this literal with its language tag, if it has one,
+    /// lowercased.
+    ///
+    /// Language tags are case insensitive, so this lowercases every
+    /// subtag, and `en-GB` and `en-gb` both become `en-gb`. This is
+    /// safe for matching, but not the conventional case of BCP 47,
+    /// which uppercases region subtags.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let l: Literal<RcStr> = Literal::Language {
+    ///     literal: "colour".into(),
+    ///     lang: "en-GB".into(),
+    /// };
+    /// assert_eq!(
+    ///     l.lowercase_lang(),
+    ///     Literal::Language {
+    ///         literal: "colour".into(),
+    ///         lang: "en-gb".into(),
+    ///     }
+    /// );
+    /// ```
+    pub fn lowercase_lang(&self) -> Literal<A> {
+        match self {
+            Literal::Language { literal, lang } => Literal::Language {
+                literal: literal.clone(),
+                lang: lang.to_lowercase(),
+            },
+            _ => self.clone(),
+        }
+    }
+}
+
+/// Return the canonical form of the decimal `s`, or `None` if it is
+/// not a valid decimal.
+fn canonical_decimal(s: &str) -> Option<String> {
+    let (negative, unsigned) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    let (int, frac) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+    if int.is_empty() && frac.is_empty()
+        || !int.chars().chain(frac.chars()).all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let int = int.trim_start_matches('0');
+    let frac = frac.trim_end_matches('0');
+    let int = if int.is_empty() { "0" } else { int };
+
+    let mut canonical = String::new();
+    if negative && (int != "0" || !frac.is_empty()) {
+        canonical.push('-');
+    }
+    canonical.push_str(int);
+    if !frac.is_empty() {
+        canonical.push('.');
+        canonical.push_str(frac);
+    }
+    Some(canonical)
 }
 
 // #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
@@ -1325,6 +2649,7 @@ impl<A: ForIRI> Literal<A> {
 /// Annotations are associated an IRI and describe that IRI in a
 /// particular way, defined by the property.
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "persist", derive(serde::Serialize, serde::Deserialize))]
 pub struct Annotation<A> {
     pub ap: AnnotationProperty<A>,
     pub av: AnnotationValue<A>,
@@ -1334,6 +2659,7 @@ pub struct Annotation<A> {
 ///
 /// This Enum is currently not complete.
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "persist", derive(serde::Serialize, serde::Deserialize))]
 pub enum AnnotationValue<A> {
     Literal(Literal<A>),
     IRI(IRI<A>),
@@ -1351,8 +2677,23 @@ impl<A: ForIRI> From<IRI<A>> for AnnotationValue<A> {
     }
 }
 
+impl<A: ForIRI> From<&str> for AnnotationValue<A> {
+    fn from(literal: &str) -> AnnotationValue<A> {
+        AnnotationValue::Literal(Literal::Simple {
+            literal: literal.to_string(),
+        })
+    }
+}
+
+impl<A: ForIRI> From<String> for AnnotationValue<A> {
+    fn from(literal: String) -> AnnotationValue<A> {
+        AnnotationValue::Literal(Literal::Simple { literal })
+    }
+}
+
 /// A object property expression
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "persist", derive(serde::Serialize, serde::Deserialize))]
 pub enum ObjectPropertyExpression<A> {
     ObjectProperty(ObjectProperty<A>),
     InverseObjectProperty(ObjectProperty<A>),
@@ -1384,10 +2725,86 @@ impl<A: ForIRI> ObjectPropertyExpression<A> {
             ObjectPropertyExpression::InverseObjectProperty(_) => None,
         }
     }
+
+    /// Returns the inverse of this expression.
+    ///
+    /// The inverse of an inverse is the property itself; as
+    /// `InverseObjectProperty` wraps an `ObjectProperty` rather than
+    /// an expression, double inverses cannot occur and every
+    /// `ObjectPropertyExpression` is already in normal form.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc();
+    /// let p: ObjectPropertyExpression<_> = b.object_property("http://www.example.com/p").into();
+    ///
+    /// assert_eq!(p.inverse().inverse(), p);
+    /// assert_eq!(
+    ///     p.inverse(),
+    ///     ObjectPropertyExpression::InverseObjectProperty(
+    ///         b.object_property("http://www.example.com/p")
+    ///     )
+    /// );
+    /// ```
+    pub fn inverse(&self) -> ObjectPropertyExpression<A> {
+        match self {
+            ObjectPropertyExpression::ObjectProperty(op) => {
+                ObjectPropertyExpression::InverseObjectProperty(op.clone())
+            }
+            ObjectPropertyExpression::InverseObjectProperty(op) => {
+                ObjectPropertyExpression::ObjectProperty(op.clone())
+            }
+        }
+    }
+}
+
+impl<A: ForIRI> ObjectPropertyAssertion<A> {
+    /// Return an equivalent assertion using a named property.
+    ///
+    /// An assertion of `inverse(p)` from `a` to `b` is the same as
+    /// an assertion of `p` from `b` to `a`. The RDF mapping of OWL
+    /// cannot distinguish the two, so an assertion read from RDF is
+    /// always in this form.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc();
+    /// let p: ObjectPropertyExpression<_> = b.object_property("http://www.example.com/p").into();
+    /// let i = |s| -> Individual<_> { b.named_individual(s).into() };
+    ///
+    /// let inverse = ObjectPropertyAssertion {
+    ///     ope: p.inverse(),
+    ///     from: i("http://www.example.com/a"),
+    ///     to: i("http://www.example.com/b"),
+    /// };
+    /// assert_eq!(
+    ///     inverse.normalize(),
+    ///     ObjectPropertyAssertion {
+    ///         ope: p,
+    ///         from: i("http://www.example.com/b"),
+    ///         to: i("http://www.example.com/a"),
+    ///     }
+    /// );
+    /// ```
+    pub fn normalize(&self) -> ObjectPropertyAssertion<A> {
+        match &self.ope {
+            ObjectPropertyExpression::ObjectProperty(_) => self.clone(),
+            ObjectPropertyExpression::InverseObjectProperty(op) => ObjectPropertyAssertion {
+                ope: op.clone().into(),
+                from: self.to.clone(),
+                to: self.from.clone(),
+            },
+        }
+    }
 }
 
 /// A sub-object property expression
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "persist", derive(serde::Serialize, serde::Deserialize))]
 pub enum SubObjectPropertyExpression<A> {
     // We use Vec here rather than BTreeSet because, perhaps
     // surprisingly, BTreeSet is not itself hashable.
@@ -1403,6 +2820,7 @@ impl<A: ForIRI> From<ObjectPropertyExpression<A>> for SubObjectPropertyExpressio
 
 /// A property expression
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "persist", derive(serde::Serialize, serde::Deserialize))]
 pub enum PropertyExpression<A> {
     ObjectPropertyExpression(ObjectPropertyExpression<A>),
     DataProperty(DataProperty<A>),
@@ -1422,12 +2840,14 @@ impl<A: ForIRI> From<DataProperty<A>> for PropertyExpression<A> {
 
 // Data!!!
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "persist", derive(serde::Serialize, serde::Deserialize))]
 pub struct FacetRestriction<A> {
     pub f: Facet,
     pub l: Literal<A>,
 }
 
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "persist", derive(serde::Serialize, serde::Deserialize))]
 pub enum Facet {
     Length,
     MinLength,
@@ -1443,6 +2863,7 @@ pub enum Facet {
 }
 
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "persist", derive(serde::Serialize, serde::Deserialize))]
 pub enum DataRange<A> {
     Datatype(Datatype<A>),
     DataIntersectionOf(Vec<DataRange<A>>),
@@ -1458,11 +2879,221 @@ impl<A: ForIRI> From<Datatype<A>> for DataRange<A> {
     }
 }
 
+impl<A: ForIRI> DataRange<A> {
+    /// Return whether `lit` is in this data range, where this can
+    /// be determined without a reasoner.
+    ///
+    /// Currently, a `DataOneOf` is supported, as is a
+    /// `DatatypeRestriction` of a numeric XSD datatype with any of
+    /// the `minInclusive`, `minExclusive`, `maxInclusive` and
+    /// `maxExclusive` facets. Literals in a `DataOneOf` are compared
+    /// structurally, so `"01"^^xsd:integer` is not considered equal
+    /// to `"1"^^xsd:integer`. Values in a `DatatypeRestriction` are
+    /// compared as `f64`. `None` is returned for other data ranges
+    /// or facets, or if `lit` or a facet value is not numeric.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc();
+    /// let int = |s: &str| Literal::Datatype {
+    ///     literal: s.to_string(),
+    ///     datatype_iri: b.iri("http://www.w3.org/2001/XMLSchema#integer"),
+    /// };
+    /// let dr = DataRange::DatatypeRestriction(
+    ///     b.datatype("http://www.w3.org/2001/XMLSchema#integer"),
+    ///     vec![
+    ///         FacetRestriction { f: Facet::MinInclusive, l: int("0") },
+    ///         FacetRestriction { f: Facet::MaxExclusive, l: int("10") },
+    ///     ],
+    /// );
+    ///
+    /// assert_eq!(dr.contains_literal(&int("0")), Some(true));
+    /// assert_eq!(dr.contains_literal(&int("10")), Some(false));
+    /// assert_eq!(dr.contains_literal(&Literal::Simple { literal: "5".into() }), None);
+    ///
+    /// let dr = DataRange::DataOneOf(vec![int("1"), int("2"), int("3")]);
+    /// assert_eq!(dr.contains_literal(&int("2")), Some(true));
+    /// assert_eq!(dr.contains_literal(&int("4")), Some(false));
+    /// ```
+    pub fn contains_literal(&self, lit: &Literal<A>) -> Option<bool> {
+        match self {
+            DataRange::DataOneOf(ls) => Some(ls.contains(lit)),
+            DataRange::DatatypeRestriction(dt, facets) => {
+                let local = dt.0.strip_prefix("http://www.w3.org/2001/XMLSchema#")?;
+                if !XSD_INTEGER_TYPES.contains(&local) && !XSD_FLOAT_TYPES.contains(&local) {
+                    return None;
+                }
+
+                let value = lit.numeric_value()?;
+                let mut contained = true;
+                for fr in facets {
+                    let bound = fr.l.numeric_value()?;
+                    contained &= match fr.f {
+                        Facet::MinInclusive => value >= bound,
+                        Facet::MinExclusive => value > bound,
+                        Facet::MaxInclusive => value <= bound,
+                        Facet::MaxExclusive => value < bound,
+                        _ => return None,
+                    };
+                }
+                Some(contained)
+            }
+            _ => None,
+        }
+    }
+
+    /// Check that the facets of a `DatatypeRestriction` are
+    /// consistent.
+    ///
+    /// An error is returned if the numeric bounds given by
+    /// `minInclusive`, `minExclusive`, `maxInclusive` and
+    /// `maxExclusive` admit no values, if `minLength` is greater than
+    /// `maxLength`, or if `length` is combined with `minLength` or
+    /// `maxLength`. Facets with a non-numeric value are ignored, as
+    /// are all other data ranges.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc();
+    /// let int = |s: &str| Literal::Datatype {
+    ///     literal: s.to_string(),
+    ///     datatype_iri: b.iri("http://www.w3.org/2001/XMLSchema#integer"),
+    /// };
+    /// let restriction = |facets| {
+    ///     DataRange::DatatypeRestriction(b.datatype("http://www.w3.org/2001/XMLSchema#integer"), facets)
+    /// };
+    ///
+    /// let dr = restriction(vec![
+    ///     FacetRestriction { f: Facet::MinInclusive, l: int("3") },
+    ///     FacetRestriction { f: Facet::MaxInclusive, l: int("5") },
+    /// ]);
+    /// assert!(dr.facet_consistency().is_ok());
+    ///
+    /// let dr = restriction(vec![
+    ///     FacetRestriction { f: Facet::MinInclusive, l: int("5") },
+    ///     FacetRestriction { f: Facet::MaxInclusive, l: int("3") },
+    /// ]);
+    /// assert!(dr.facet_consistency().is_err());
+    ///
+    /// let dr = restriction(vec![
+    ///     FacetRestriction { f: Facet::MinInclusive, l: int("5") },
+    ///     FacetRestriction { f: Facet::MaxExclusive, l: int("5") },
+    /// ]);
+    /// assert!(dr.facet_consistency().is_err());
+    ///
+    /// // Integers are compared exactly, even beyond the precision
+    /// // of an f64
+    /// let dr = restriction(vec![
+    ///     FacetRestriction { f: Facet::MinInclusive, l: int("9007199254740993") },
+    ///     FacetRestriction { f: Facet::MaxInclusive, l: int("9007199254740992") },
+    /// ]);
+    /// assert!(dr.facet_consistency().is_err());
+    ///
+    /// let dr = restriction(vec![
+    ///     FacetRestriction { f: Facet::Length, l: int("4") },
+    ///     FacetRestriction { f: Facet::MinLength, l: int("2") },
+    /// ]);
+    /// assert!(dr.facet_consistency().is_err());
+    /// ```
+    pub fn facet_consistency(&self) -> Result<(), HornedError> {
+        let facets = match self {
+            DataRange::DatatypeRestriction(_, facets) => facets,
+            _ => return Ok(()),
+        };
+
+        // The tightest bounds, and whether each is exclusive. Bounds
+        // are compared with `value_cmp`, so integers are compared
+        // exactly rather than as `f64`
+        let mut lower: Option<(&Literal<A>, bool)> = None;
+        let mut upper: Option<(&Literal<A>, bool)> = None;
+        let mut length = false;
+        let mut min_length: Option<f64> = None;
+        let mut max_length: Option<f64> = None;
+
+        for fr in facets {
+            let v = match fr.l.numeric_value() {
+                Some(v) => v,
+                None => continue,
+            };
+
+            match fr.f {
+                Facet::MinInclusive | Facet::MinExclusive => {
+                    let exclusive = fr.f == Facet::MinExclusive;
+                    let tighter = match lower {
+                        None => true,
+                        Some((l, _)) => match fr.l.value_cmp(l) {
+                            Some(Ordering::Greater) => true,
+                            Some(Ordering::Equal) => exclusive,
+                            _ => false,
+                        },
+                    };
+                    if tighter {
+                        lower = Some((&fr.l, exclusive));
+                    }
+                }
+                Facet::MaxInclusive | Facet::MaxExclusive => {
+                    let exclusive = fr.f == Facet::MaxExclusive;
+                    let tighter = match upper {
+                        None => true,
+                        Some((u, _)) => match fr.l.value_cmp(u) {
+                            Some(Ordering::Less) => true,
+                            Some(Ordering::Equal) => exclusive,
+                            _ => false,
+                        },
+                    };
+                    if tighter {
+                        upper = Some((&fr.l, exclusive));
+                    }
+                }
+                Facet::Length => length = true,
+                Facet::MinLength => min_length = Some(min_length.map_or(v, |m| m.max(v))),
+                Facet::MaxLength => max_length = Some(max_length.map_or(v, |m| m.min(v))),
+                _ => {}
+            }
+        }
+
+        if let (Some((l, l_excl)), Some((u, u_excl))) = (lower, upper) {
+            let empty = match l.value_cmp(u) {
+                Some(Ordering::Greater) => true,
+                Some(Ordering::Equal) => l_excl || u_excl,
+                _ => false,
+            };
+            if empty {
+                return Err(HornedError::InconsistentFacets(format!(
+                    "no value lies between {} and {}",
+                    l.literal(),
+                    u.literal()
+                )));
+            }
+        }
+
+        if length && (min_length.is_some() || max_length.is_some()) {
+            return Err(HornedError::InconsistentFacets(
+                "length is combined with minLength or maxLength".to_string(),
+            ));
+        }
+
+        if let (Some(min), Some(max)) = (min_length, max_length) {
+            if min > max {
+                return Err(HornedError::InconsistentFacets(format!(
+                    "minLength {} is greater than maxLength {}",
+                    min, max
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
 /// A class expression
 ///
 /// As well as a named class, it is possible to define classes of
 /// individuals based on these class constructors.
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "persist", derive(serde::Serialize, serde::Deserialize))]
 pub enum ClassExpression<A> {
     /// A named class
     Class(Class<A>),
@@ -1648,12 +3279,105 @@ impl<A: ForIRI> From<Class<A>> for Box<ClassExpression<A>> {
     }
 }
 
+impl<A: ForIRI> ClassExpression<A> {
+    /// Return true if this is an `ObjectOneOf` which enumerates `ind`.
+    ///
+    /// For any other class expression, false is returned, as
+    /// membership cannot be determined without a reasoner.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc();
+    /// let i = |s: &str| -> Individual<_> { b.named_individual(s).into() };
+    /// let ce = ClassExpression::ObjectOneOf(vec![
+    ///     i("http://www.example.com/a"),
+    ///     i("http://www.example.com/b"),
+    ///     i("http://www.example.com/c"),
+    /// ]);
+    ///
+    /// assert!(ce.contains(&i("http://www.example.com/b")));
+    /// assert!(!ce.contains(&i("http://www.example.com/d")));
+    /// ```
+    pub fn contains(&self, ind: &Individual<A>) -> bool {
+        match self {
+            ClassExpression::ObjectOneOf(v) => v.contains(ind),
+            _ => false,
+        }
+    }
+
+    /// Return the number of class expressions in this class
+    /// expression, including itself.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc();
+    /// let ce = ClassExpression::ObjectIntersectionOf(vec![
+    ///     b.class("http://www.example.com/a").into(),
+    ///     ClassExpression::ObjectSomeValuesFrom {
+    ///         ope: b.object_property("http://www.example.com/p").into(),
+    ///         bce: Box::new(b.class("http://www.example.com/b").into()),
+    ///     },
+    /// ]);
+    /// assert_eq!(ce.size(), 4);
+    /// ```
+    pub fn size(&self) -> usize {
+        let mut walk = Walk::new(ClassExpressionCount(0));
+        walk.class_expression(self);
+        walk.into_visit().0
+    }
+
+    /// Return the nesting depth of this class expression.
+    ///
+    /// A named class, or any expression which contains no other class
+    /// expression, has a depth of 1.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// let b = Build::new_rc();
+    /// let ce = ClassExpression::ObjectIntersectionOf(vec![
+    ///     b.class("http://www.example.com/a").into(),
+    ///     ClassExpression::ObjectSomeValuesFrom {
+    ///         ope: b.object_property("http://www.example.com/p").into(),
+    ///         bce: Box::new(b.class("http://www.example.com/b").into()),
+    ///     },
+    /// ]);
+    /// assert_eq!(ce.depth(), 3);
+    /// ```
+    pub fn depth(&self) -> usize {
+        use ClassExpression::*;
+        1 + match self {
+            ObjectIntersectionOf(v) | ObjectUnionOf(v) => {
+                v.iter().map(|ce| ce.depth()).max().unwrap_or(0)
+            }
+            ObjectComplementOf(bce)
+            | ObjectSomeValuesFrom { bce, .. }
+            | ObjectAllValuesFrom { bce, .. }
+            | ObjectMinCardinality { bce, .. }
+            | ObjectMaxCardinality { bce, .. }
+            | ObjectExactCardinality { bce, .. } => bce.depth(),
+            _ => 0,
+        }
+    }
+}
+
+struct ClassExpressionCount(usize);
+
+impl<A: ForIRI> Visit<A> for ClassExpressionCount {
+    fn visit_class_expression(&mut self, _: &ClassExpression<A>) {
+        self.0 += 1;
+    }
+}
+
 /// An ontology identifier
 ///
 /// An ontology is identified by an IRI which is expected to remain
 /// stable over the lifetime of the ontology, and a version IRI which
 /// is expected to change between versions.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "persist", derive(serde::Serialize, serde::Deserialize))]
 pub struct OntologyID<A> {
     pub iri: Option<IRI<A>>,
     pub viri: Option<IRI<A>>,
@@ -1668,12 +3392,355 @@ impl<A> Default for OntologyID<A> {
     }
 }
 
+impl<A: ForIRI, B: ForIRI> Rebuild<B> for OntologyID<A> {
+    type Output = OntologyID<B>;
+
+    fn rebuild(&self, b: &Build<B>) -> OntologyID<B> {
+        OntologyID {
+            iri: self.iri.rebuild(b),
+            viri: self.viri.rebuild(b),
+        }
+    }
+}
+
+impl<A: ForIRI, B: ForIRI> Rebuild<B> for AnonymousIndividual<A> {
+    type Output = AnonymousIndividual<B>;
+
+    fn rebuild(&self, b: &Build<B>) -> AnonymousIndividual<B> {
+        b.anon(self.0.as_ref())
+    }
+}
+
+impl<A: ForIRI, B: ForIRI> Rebuild<B> for Individual<A> {
+    type Output = Individual<B>;
+
+    fn rebuild(&self, b: &Build<B>) -> Individual<B> {
+        match self {
+            Individual::Anonymous(ai) => Individual::Anonymous(ai.rebuild(b)),
+            Individual::Named(ni) => Individual::Named(ni.rebuild(b)),
+        }
+    }
+}
+
+impl<A: ForIRI, B: ForIRI> Rebuild<B> for AnnotationSubject<A> {
+    type Output = AnnotationSubject<B>;
+
+    fn rebuild(&self, b: &Build<B>) -> AnnotationSubject<B> {
+        match self {
+            AnnotationSubject::IRI(iri) => AnnotationSubject::IRI(iri.rebuild(b)),
+            AnnotationSubject::AnonymousIndividual(ai) => {
+                AnnotationSubject::AnonymousIndividual(ai.rebuild(b))
+            }
+        }
+    }
+}
+
+impl<A: ForIRI, B: ForIRI> Rebuild<B> for AnnotatedAxiom<A> {
+    type Output = AnnotatedAxiom<B>;
+
+    fn rebuild(&self, b: &Build<B>) -> AnnotatedAxiom<B> {
+        AnnotatedAxiom {
+            axiom: self.axiom.rebuild(b),
+            ann: self.ann.rebuild(b),
+        }
+    }
+}
+
+impl<A: ForIRI, B: ForIRI> Rebuild<B> for Literal<A> {
+    type Output = Literal<B>;
+
+    fn rebuild(&self, b: &Build<B>) -> Literal<B> {
+        match self {
+            Literal::Simple { literal } => Literal::Simple {
+                literal: literal.clone(),
+            },
+            Literal::Language { literal, lang } => Literal::Language {
+                literal: literal.clone(),
+                lang: lang.clone(),
+            },
+            Literal::Datatype {
+                literal,
+                datatype_iri,
+            } => Literal::Datatype {
+                literal: literal.clone(),
+                datatype_iri: datatype_iri.rebuild(b),
+            },
+        }
+    }
+}
+
+impl<A: ForIRI, B: ForIRI> Rebuild<B> for Annotation<A> {
+    type Output = Annotation<B>;
+
+    fn rebuild(&self, b: &Build<B>) -> Annotation<B> {
+        Annotation {
+            ap: self.ap.rebuild(b),
+            av: self.av.rebuild(b),
+        }
+    }
+}
+
+impl<A: ForIRI, B: ForIRI> Rebuild<B> for AnnotationValue<A> {
+    type Output = AnnotationValue<B>;
+
+    fn rebuild(&self, b: &Build<B>) -> AnnotationValue<B> {
+        match self {
+            AnnotationValue::Literal(l) => AnnotationValue::Literal(l.rebuild(b)),
+            AnnotationValue::IRI(iri) => AnnotationValue::IRI(iri.rebuild(b)),
+        }
+    }
+}
+
+impl<A: ForIRI, B: ForIRI> Rebuild<B> for ObjectPropertyExpression<A> {
+    type Output = ObjectPropertyExpression<B>;
+
+    fn rebuild(&self, b: &Build<B>) -> ObjectPropertyExpression<B> {
+        match self {
+            ObjectPropertyExpression::ObjectProperty(op) => {
+                ObjectPropertyExpression::ObjectProperty(op.rebuild(b))
+            }
+            ObjectPropertyExpression::InverseObjectProperty(op) => {
+                ObjectPropertyExpression::InverseObjectProperty(op.rebuild(b))
+            }
+        }
+    }
+}
+
+impl<A: ForIRI, B: ForIRI> Rebuild<B> for SubObjectPropertyExpression<A> {
+    type Output = SubObjectPropertyExpression<B>;
+
+    fn rebuild(&self, b: &Build<B>) -> SubObjectPropertyExpression<B> {
+        match self {
+            SubObjectPropertyExpression::ObjectPropertyChain(v) => {
+                SubObjectPropertyExpression::ObjectPropertyChain(v.rebuild(b))
+            }
+            SubObjectPropertyExpression::ObjectPropertyExpression(ope) => {
+                SubObjectPropertyExpression::ObjectPropertyExpression(ope.rebuild(b))
+            }
+        }
+    }
+}
+
+impl<A: ForIRI, B: ForIRI> Rebuild<B> for PropertyExpression<A> {
+    type Output = PropertyExpression<B>;
+
+    fn rebuild(&self, b: &Build<B>) -> PropertyExpression<B> {
+        match self {
+            PropertyExpression::ObjectPropertyExpression(ope) => {
+                PropertyExpression::ObjectPropertyExpression(ope.rebuild(b))
+            }
+            PropertyExpression::DataProperty(dp) => PropertyExpression::DataProperty(dp.rebuild(b)),
+            PropertyExpression::AnnotationProperty(ap) => {
+                PropertyExpression::AnnotationProperty(ap.rebuild(b))
+            }
+        }
+    }
+}
+
+impl<A: ForIRI, B: ForIRI> Rebuild<B> for FacetRestriction<A> {
+    type Output = FacetRestriction<B>;
+
+    fn rebuild(&self, b: &Build<B>) -> FacetRestriction<B> {
+        FacetRestriction {
+            f: self.f.clone(),
+            l: self.l.rebuild(b),
+        }
+    }
+}
+
+impl<A: ForIRI, B: ForIRI> Rebuild<B> for DataRange<A> {
+    type Output = DataRange<B>;
+
+    fn rebuild(&self, b: &Build<B>) -> DataRange<B> {
+        match self {
+            DataRange::Datatype(dt) => DataRange::Datatype(dt.rebuild(b)),
+            DataRange::DataIntersectionOf(v) => DataRange::DataIntersectionOf(v.rebuild(b)),
+            DataRange::DataUnionOf(v) => DataRange::DataUnionOf(v.rebuild(b)),
+            DataRange::DataComplementOf(dr) => DataRange::DataComplementOf(dr.rebuild(b)),
+            DataRange::DataOneOf(v) => DataRange::DataOneOf(v.rebuild(b)),
+            DataRange::DatatypeRestriction(dt, v) => {
+                DataRange::DatatypeRestriction(dt.rebuild(b), v.rebuild(b))
+            }
+        }
+    }
+}
+
+impl<A: ForIRI, B: ForIRI> Rebuild<B> for ClassExpression<A> {
+    type Output = ClassExpression<B>;
+
+    fn rebuild(&self, b: &Build<B>) -> ClassExpression<B> {
+        use ClassExpression::*;
+        match self {
+            Class(c) => Class(c.rebuild(b)),
+            ObjectIntersectionOf(v) => ObjectIntersectionOf(v.rebuild(b)),
+            ObjectUnionOf(v) => ObjectUnionOf(v.rebuild(b)),
+            ObjectComplementOf(bce) => ObjectComplementOf(bce.rebuild(b)),
+            ObjectOneOf(v) => ObjectOneOf(v.rebuild(b)),
+            ObjectSomeValuesFrom { ope, bce } => ObjectSomeValuesFrom {
+                ope: ope.rebuild(b),
+                bce: bce.rebuild(b),
+            },
+            ObjectAllValuesFrom { ope, bce } => ObjectAllValuesFrom {
+                ope: ope.rebuild(b),
+                bce: bce.rebuild(b),
+            },
+            ObjectHasValue { ope, i } => ObjectHasValue {
+                ope: ope.rebuild(b),
+                i: i.rebuild(b),
+            },
+            ObjectHasSelf(ope) => ObjectHasSelf(ope.rebuild(b)),
+            ObjectMinCardinality { n, ope, bce } => ObjectMinCardinality {
+                n: *n,
+                ope: ope.rebuild(b),
+                bce: bce.rebuild(b),
+            },
+            ObjectMaxCardinality { n, ope, bce } => ObjectMaxCardinality {
+                n: *n,
+                ope: ope.rebuild(b),
+                bce: bce.rebuild(b),
+            },
+            ObjectExactCardinality { n, ope, bce } => ObjectExactCardinality {
+                n: *n,
+                ope: ope.rebuild(b),
+                bce: bce.rebuild(b),
+            },
+            DataSomeValuesFrom { dp, dr } => DataSomeValuesFrom {
+                dp: dp.rebuild(b),
+                dr: dr.rebuild(b),
+            },
+            DataAllValuesFrom { dp, dr } => DataAllValuesFrom {
+                dp: dp.rebuild(b),
+                dr: dr.rebuild(b),
+            },
+            DataHasValue { dp, l } => DataHasValue {
+                dp: dp.rebuild(b),
+                l: l.rebuild(b),
+            },
+            DataMinCardinality { n, dp, dr } => DataMinCardinality {
+                n: *n,
+                dp: dp.rebuild(b),
+                dr: dr.rebuild(b),
+            },
+            DataMaxCardinality { n, dp, dr } => DataMaxCardinality {
+                n: *n,
+                dp: dp.rebuild(b),
+                dr: dr.rebuild(b),
+            },
+            DataExactCardinality { n, dp, dr } => DataExactCardinality {
+                n: *n,
+                dp: dp.rebuild(b),
+                dr: dr.rebuild(b),
+            },
+        }
+    }
+}
+
 /// Access or change the `OntologyID` of an `Ontology`
 pub trait Ontology<A> {
     fn id(&self) -> &OntologyID<A>;
     fn mut_id(&mut self) -> &mut OntologyID<A>;
     fn doc_iri(&self) -> &Option<IRI<A>>;
     fn mut_doc_iri(&mut self) -> &mut Option<IRI<A>>;
+
+    /// Return the document IRI, the location from which the ontology
+    /// was read, as a string.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// assert_eq!(o.doc_iri_str(), None);
+    ///
+    /// o.set_doc_iri_str(&b, "file:///tmp/o.owl");
+    /// assert_eq!(o.doc_iri_str(), Some("file:///tmp/o.owl"));
+    /// assert_eq!(o.id().iri, None);
+    /// ```
+    fn doc_iri_str<'a>(&'a self) -> Option<&'a str>
+    where
+        A: ForIRI + 'a,
+    {
+        self.doc_iri().as_ref().map(|iri| iri.as_ref())
+    }
+
+    /// Set the document IRI from a string.
+    ///
+    /// This does not change the IRI of the ontology; see `mut_id`.
+    fn set_doc_iri_str(&mut self, b: &Build<A>, s: &str)
+    where
+        A: ForIRI,
+    {
+        *self.mut_doc_iri() = Some(b.iri(s));
+    }
+
+    /// Fold `f` over every axiom in the ontology, starting from
+    /// `init`.
+    ///
+    /// The order in which axioms are visited is that of the
+    /// ontology's own iteration, which implementations may override
+    /// with a more efficient traversal.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.declare(b.class("http://www.example.com/a"));
+    /// o.declare(b.class("http://www.example.com/b"));
+    ///
+    /// let declarations = o.fold_axioms(0, |n, aa| {
+    ///     n + (aa.kind() == AxiomKind::DeclareClass) as usize
+    /// });
+    /// assert_eq!(declarations, 2);
+    /// ```
+    fn fold_axioms<B, F>(&self, init: B, f: F) -> B
+    where
+        Self: Sized,
+        for<'a> &'a Self: IntoIterator<Item = &'a AnnotatedAxiom<A>>,
+        F: FnMut(B, &AnnotatedAxiom<A>) -> B,
+    {
+        self.into_iter().fold(init, f)
+    }
+
+    /// Returns the number of axioms in the ontology.
+    ///
+    /// By default this counts the axioms by iteration; ontologies
+    /// which know their size override it.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// fn size<O: Ontology<RcStr>>(o: &O) -> usize
+    /// where
+    ///     for<'a> &'a O: IntoIterator<Item = &'a AnnotatedAxiom<RcStr>>,
+    /// {
+    ///     Ontology::len(o)
+    /// }
+    ///
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.declare(b.class("http://www.example.com/a"));
+    /// assert_eq!(size(&o), 1);
+    /// ```
+    fn len(&self) -> usize
+    where
+        Self: Sized,
+        for<'a> &'a Self: IntoIterator<Item = &'a AnnotatedAxiom<A>>,
+    {
+        self.into_iter().count()
+    }
+
+    /// Returns true if the ontology contains no axioms.
+    fn is_empty(&self) -> bool
+    where
+        Self: Sized,
+        for<'a> &'a Self: IntoIterator<Item = &'a AnnotatedAxiom<A>>,
+    {
+        self.into_iter().next().is_none()
+    }
 }
 
 /// Add or remove axioms to an `MutableOntology`
@@ -1701,6 +3768,32 @@ pub trait MutableOntology<A> {
 
     fn take(&mut self, ax: &AnnotatedAxiom<A>) -> Option<AnnotatedAxiom<A>>;
 
+    /// Remove several axioms from the ontology.
+    ///
+    /// Returns the axioms which were present and have been removed,
+    /// in the order that they were requested.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let mut o = SetOntology::new_rc();
+    /// let b = Build::new();
+    /// let a: AnnotatedAxiom<_> = DeclareClass(b.class("http://www.example.com/a")).into();
+    /// let c: AnnotatedAxiom<_> = DeclareClass(b.class("http://www.example.com/c")).into();
+    /// o.insert(a.clone());
+    ///
+    /// assert_eq!(o.take_all(vec![&c, &a]), vec![a]);
+    /// assert!(o.is_empty());
+    /// ```
+    fn take_all<'a, I>(&mut self, it: I) -> Vec<AnnotatedAxiom<A>>
+    where
+        A: 'a,
+        I: IntoIterator<Item = &'a AnnotatedAxiom<A>>,
+    {
+        it.into_iter().filter_map(|ax| self.take(ax)).collect()
+    }
+
     /// Declare an NamedEntity for the ontology.
     ///
     /// # Examples
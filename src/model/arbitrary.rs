@@ -0,0 +1,239 @@
+//! `Arbitrary` implementations for fuzzing and property testing
+
+//! # Overview
+//!
+//! Values generated here are always valid OWL; in particular, IRIs
+//! are absolute, n-ary constructs have at least two operands and
+//! literals contain only characters which can be serialized in any
+//! syntax. This means that they should survive a round trip through
+//! any of the writers and parsers.
+//!
+//! Only a subset of the axiom and class expression kinds are
+//! generated.
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+use super::*;
+
+/// The maximum nesting depth of generated class expressions.
+const MAX_DEPTH: u32 = 3;
+
+const NAME_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+
+const LITERAL_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789 ";
+
+const LANGUAGES: &[&str] = &["en", "de", "fr-CA"];
+
+const DATATYPES: &[&str] = &[
+    "http://www.w3.org/2001/XMLSchema#string",
+    "http://www.w3.org/2001/XMLSchema#integer",
+    "http://www.w3.org/2001/XMLSchema#decimal",
+];
+
+fn string_of(u: &mut Unstructured, chars: &[u8], min: usize, max: usize) -> Result<String> {
+    let len = u.int_in_range(min..=max)?;
+    (0..len)
+        .map(|_| u.choose(chars).map(|c| *c as char))
+        .collect()
+}
+
+fn two_or_more<'a, T, F>(u: &mut Unstructured<'a>, mut f: F) -> Result<Vec<T>>
+where
+    F: FnMut(&mut Unstructured<'a>) -> Result<T>,
+{
+    let len = u.int_in_range(2..=3)?;
+    (0..len).map(|_| f(u)).collect()
+}
+
+impl<'a, A: ForIRI> Arbitrary<'a> for IRI<A> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        // A short name, so that the same IRI is reasonably likely to
+        // be generated more than once.
+        let name = string_of(u, NAME_CHARS, 1, 2)?;
+        Ok(IRI(format!("http://www.example.com/{}", name).into()))
+    }
+}
+
+impl<'a, A: ForIRI> Arbitrary<'a> for Literal<A> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let literal = string_of(u, LITERAL_CHARS, 0, 8)?;
+        Ok(match u.int_in_range(0..=2)? {
+            0 => Literal::Simple { literal },
+            1 => Literal::Language {
+                literal,
+                lang: u.choose(LANGUAGES)?.to_string(),
+            },
+            // A literal of type xsd:string is read as a simple literal
+            _ => Literal::Datatype {
+                literal,
+                datatype_iri: IRI(u.choose(&DATATYPES[1..])?.to_string().into()),
+            },
+        })
+    }
+}
+
+impl<'a, A: ForIRI> Arbitrary<'a> for ClassExpression<A> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        class_expression(u, MAX_DEPTH)
+    }
+}
+
+fn class_expression<A: ForIRI>(u: &mut Unstructured, depth: u32) -> Result<ClassExpression<A>> {
+    if depth == 0 {
+        return Ok(ClassExpression::Class(Class(u.arbitrary()?)));
+    }
+
+    let bce = |u: &mut Unstructured| class_expression(u, depth - 1).map(Box::new);
+
+    Ok(match u.int_in_range(0..=10)? {
+        0 => ClassExpression::ObjectIntersectionOf(two_or_more(u, |u| {
+            class_expression(u, depth - 1)
+        })?),
+        1 => ClassExpression::ObjectUnionOf(two_or_more(u, |u| class_expression(u, depth - 1))?),
+        2 => ClassExpression::ObjectComplementOf(bce(u)?),
+        3 => ClassExpression::ObjectOneOf(two_or_more(u, individual)?),
+        4 => ClassExpression::ObjectSomeValuesFrom {
+            ope: object_property_expression(u)?,
+            bce: bce(u)?,
+        },
+        5 => ClassExpression::ObjectAllValuesFrom {
+            ope: object_property_expression(u)?,
+            bce: bce(u)?,
+        },
+        6 => ClassExpression::ObjectHasValue {
+            ope: object_property_expression(u)?,
+            i: individual(u)?,
+        },
+        7 => ClassExpression::ObjectMinCardinality {
+            n: u.int_in_range(0..=5)?,
+            ope: object_property_expression(u)?,
+            bce: bce(u)?,
+        },
+        8 => ClassExpression::DataSomeValuesFrom {
+            dp: DataProperty(u.arbitrary()?),
+            dr: DataRange::Datatype(Datatype(IRI(u.choose(DATATYPES)?.to_string().into()))),
+        },
+        9 => ClassExpression::DataHasValue {
+            dp: DataProperty(u.arbitrary()?),
+            l: u.arbitrary()?,
+        },
+        _ => ClassExpression::Class(Class(u.arbitrary()?)),
+    })
+}
+
+fn object_property_expression<A: ForIRI>(
+    u: &mut Unstructured,
+) -> Result<ObjectPropertyExpression<A>> {
+    let op = ObjectProperty(u.arbitrary()?);
+    Ok(if u.arbitrary()? {
+        ObjectPropertyExpression::InverseObjectProperty(op)
+    } else {
+        ObjectPropertyExpression::ObjectProperty(op)
+    })
+}
+
+fn individual<A: ForIRI>(u: &mut Unstructured) -> Result<Individual<A>> {
+    Ok(Individual::Named(NamedIndividual(u.arbitrary()?)))
+}
+
+impl<'a, A: ForIRI> Arbitrary<'a> for Axiom<A> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=11)? {
+            0 => DeclareClass(Class(u.arbitrary()?)).into(),
+            1 => DeclareObjectProperty(ObjectProperty(u.arbitrary()?)).into(),
+            2 => DeclareDataProperty(DataProperty(u.arbitrary()?)).into(),
+            3 => DeclareNamedIndividual(NamedIndividual(u.arbitrary()?)).into(),
+            4 => SubClassOf {
+                sub: u.arbitrary()?,
+                sup: u.arbitrary()?,
+            }
+            .into(),
+            5 => EquivalentClasses(two_or_more(u, |u| u.arbitrary())?).into(),
+            6 => DisjointClasses(two_or_more(u, |u| u.arbitrary())?).into(),
+            7 => SubObjectPropertyOf {
+                sub: ObjectPropertyExpression::ObjectProperty(ObjectProperty(u.arbitrary()?))
+                    .into(),
+                sup: ObjectProperty(u.arbitrary()?).into(),
+            }
+            .into(),
+            8 => ClassAssertion {
+                ce: u.arbitrary()?,
+                i: individual(u)?,
+            }
+            .into(),
+            9 => ObjectPropertyAssertion {
+                ope: ObjectProperty(u.arbitrary()?).into(),
+                from: individual(u)?,
+                to: individual(u)?,
+            }
+            .into(),
+            10 => DataPropertyAssertion {
+                dp: DataProperty(u.arbitrary()?),
+                from: individual(u)?,
+                to: u.arbitrary()?,
+            }
+            .into(),
+            _ => AnnotationAssertion {
+                subject: AnnotationSubject::IRI(u.arbitrary()?),
+                ann: Annotation {
+                    ap: AnnotationProperty(u.arbitrary()?),
+                    av: AnnotationValue::Literal(u.arbitrary()?),
+                },
+            }
+            .into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::io::owx::{reader::read, writer::write};
+    use crate::ontology::{axiom_mapped::AxiomMappedOntology, set::SetOntology};
+
+    /// A deterministic stream of bytes, standing in for the input of
+    /// a fuzzer.
+    fn bytes(seed: u64, len: usize) -> Vec<u8> {
+        let mut x = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+        (0..len)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 7;
+                x ^= x << 17;
+                x as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_iri_absolute() {
+        for seed in 0..100 {
+            let data = bytes(seed, 16);
+            let iri: IRI<RcStr> = Unstructured::new(&data).arbitrary().unwrap();
+            assert!(oxiri::Iri::parse(&*iri).is_ok(), "{:?}", iri);
+        }
+    }
+
+    #[test]
+    fn test_owx_round_trip() {
+        // For any generated ontology, reading back what was written
+        // gives the same ontology.
+        let mut total = 0;
+        for seed in 0..100 {
+            let data = bytes(seed, 1024);
+            let mut u = Unstructured::new(&data);
+            let so: SetOntology<_> = (0..20)
+                .map(|_| u.arbitrary::<Axiom<RcStr>>().unwrap().into())
+                .collect();
+            let amo: AxiomMappedOntology<_, RcAnnotatedAxiom> = so.clone().into();
+
+            let mut buf = vec![];
+            write(&mut buf, &amo, None).unwrap();
+            let (round, _) = read(&mut buf.as_slice(), Default::default()).unwrap();
+
+            assert!(so.logical_eq(&round), "seed {}", seed);
+            assert_eq!(so.len(), round.len(), "seed {}", seed);
+            total += so.len();
+        }
+        assert!(total > 100);
+    }
+}
@@ -0,0 +1,102 @@
+//! The signature of a set of axioms
+
+//! # Overview
+//!
+//! The signature is the set of named entities which are used in the
+//! axioms, including in their annotations. It is computed here
+//! directly from the axioms, so that it can be found for axioms which
+//! are not yet in an ontology; the `signature` methods of the
+//! ontology implementations are built on the same functions.
+use std::collections::HashSet;
+
+use super::*;
+
+/// Return the signature of `axioms`.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// let b = Build::new_rc();
+/// let axioms: Vec<AnnotatedAxiom<_>> = vec![
+///     DeclareClass(b.class("http://www.example.com/a")).into(),
+///     SubClassOf {
+///         sub: b.class("http://www.example.com/a").into(),
+///         sup: b.class("http://www.example.com/b").into(),
+///     }
+///     .into(),
+/// ];
+///
+/// assert_eq!(axioms_signature(&axioms).len(), 2);
+/// ```
+pub fn axioms_signature<A: ForIRI>(axioms: &[AnnotatedAxiom<A>]) -> HashSet<NamedEntity<A>> {
+    signature_of(axioms)
+}
+
+pub(crate) struct NamedEntityExtract<A>(pub(crate) HashSet<NamedEntity<A>>);
+
+impl<A: ForIRI> Visit<A> for NamedEntityExtract<A> {
+    fn visit_class(&mut self, e: &Class<A>) {
+        self.0.insert(e.clone().into());
+    }
+
+    fn visit_datatype(&mut self, e: &Datatype<A>) {
+        self.0.insert(e.clone().into());
+    }
+
+    fn visit_object_property(&mut self, e: &ObjectProperty<A>) {
+        self.0.insert(e.clone().into());
+    }
+
+    fn visit_data_property(&mut self, e: &DataProperty<A>) {
+        self.0.insert(e.clone().into());
+    }
+
+    fn visit_annotation_property(&mut self, e: &AnnotationProperty<A>) {
+        self.0.insert(e.clone().into());
+    }
+
+    fn visit_named_individual(&mut self, e: &NamedIndividual<A>) {
+        self.0.insert(e.clone().into());
+    }
+}
+
+pub(crate) fn signature_of<'a, A: ForIRI + 'a, I>(axioms: I) -> HashSet<NamedEntity<A>>
+where
+    I: IntoIterator<Item = &'a AnnotatedAxiom<A>>,
+{
+    let mut walk = Walk::new(NamedEntityExtract(HashSet::new()));
+    for aa in axioms {
+        walk.annotated_axiom(aa);
+    }
+    walk.into_visit().0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_axioms_signature() {
+        let b = Build::new_rc();
+        let a = b.class("http://www.example.com/a");
+        let c = b.class("http://www.example.com/c");
+        let r = b.object_property("http://www.example.com/r");
+        let axioms: Vec<AnnotatedAxiom<_>> = vec![
+            DeclareClass(a.clone()).into(),
+            SubClassOf {
+                sub: a.clone().into(),
+                sup: ClassExpression::ObjectSomeValuesFrom {
+                    ope: r.clone().into(),
+                    bce: Box::new(c.clone().into()),
+                },
+            }
+            .into(),
+            DisjointClasses(vec![a.clone().into(), c.clone().into()]).into(),
+        ];
+
+        let expected: HashSet<NamedEntity<_>> =
+            vec![a.into(), c.into(), r.into()].into_iter().collect();
+        assert_eq!(axioms_signature(&axioms), expected);
+        assert!(axioms_signature::<RcStr>(&[]).is_empty());
+    }
+}
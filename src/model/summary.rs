@@ -0,0 +1,278 @@
+//! One line summaries of axioms
+
+//! # Overview
+//!
+//! A summary shows the kind of an axiom and its main entities, with
+//! IRIs abbreviated through a `PrefixMapping` where possible. Any
+//! component which is not named, such as a complex class expression,
+//! is shown as `(...)`. Summaries are intended for logging and
+//! listing axioms; they are not a complete serialization.
+use curie::PrefixMapping;
+
+use super::*;
+
+impl<A: ForIRI> AnnotatedAxiom<A> {
+    /// Return a one line summary of this axiom.
+    ///
+    /// Annotations on the axiom are not shown.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use curie::PrefixMapping;
+    /// let b = Build::new_rc();
+    /// let mut pm = PrefixMapping::default();
+    /// pm.add_prefix("ex", "http://www.example.com/").unwrap();
+    ///
+    /// let aa: AnnotatedAxiom<_> = SubClassOf {
+    ///     sub: b.class("http://www.example.com/Dog").into(),
+    ///     sup: b.class("http://www.example.com/Animal").into(),
+    /// }
+    /// .into();
+    /// assert_eq!(aa.summary(&pm), "SubClassOf(ex:Dog ex:Animal)");
+    ///
+    /// let aa: AnnotatedAxiom<_> = ClassAssertion {
+    ///     ce: ClassExpression::ObjectComplementOf(Box::new(
+    ///         b.class("http://www.example.com/Cat").into(),
+    ///     )),
+    ///     i: b.named_individual("http://www.example.org/rex").into(),
+    /// }
+    /// .into();
+    /// assert_eq!(
+    ///     aa.summary(&pm),
+    ///     "ClassAssertion((...) <http://www.example.org/rex>)"
+    /// );
+    /// ```
+    pub fn summary(&self, pm: &PrefixMapping) -> String {
+        format!(
+            "{}({})",
+            self.axiom.kind().name(),
+            parts(&self.axiom, pm).join(" ")
+        )
+    }
+}
+
+/// Return the summaries of the components of `ax`, in the order of
+/// the OWL functional syntax.
+fn parts<A: ForIRI>(ax: &Axiom<A>, pm: &PrefixMapping) -> Vec<String> {
+    match ax {
+        Axiom::OntologyAnnotation(OntologyAnnotation(ann)) => ann.parts(pm),
+        Axiom::Import(Import(iri)) => vec![iri.part(pm)],
+        Axiom::DeclareClass(DeclareClass(e)) => vec![e.part(pm)],
+        Axiom::DeclareObjectProperty(DeclareObjectProperty(e)) => vec![e.part(pm)],
+        Axiom::DeclareAnnotationProperty(DeclareAnnotationProperty(e)) => vec![e.part(pm)],
+        Axiom::DeclareDataProperty(DeclareDataProperty(e)) => vec![e.part(pm)],
+        Axiom::DeclareNamedIndividual(DeclareNamedIndividual(e)) => vec![e.part(pm)],
+        Axiom::DeclareDatatype(DeclareDatatype(e)) => vec![e.part(pm)],
+        Axiom::SubClassOf(SubClassOf { sub, sup }) => vec![sub.part(pm), sup.part(pm)],
+        Axiom::EquivalentClasses(EquivalentClasses(v)) => vec![v.part(pm)],
+        Axiom::DisjointClasses(DisjointClasses(v)) => vec![v.part(pm)],
+        Axiom::DisjointUnion(DisjointUnion(c, v)) => vec![c.part(pm), v.part(pm)],
+        Axiom::SubObjectPropertyOf(SubObjectPropertyOf { sub, sup }) => {
+            vec![sub.part(pm), sup.part(pm)]
+        }
+        Axiom::EquivalentObjectProperties(EquivalentObjectProperties(v)) => vec![v.part(pm)],
+        Axiom::DisjointObjectProperties(DisjointObjectProperties(v)) => vec![v.part(pm)],
+        Axiom::InverseObjectProperties(InverseObjectProperties(a, b)) => {
+            vec![a.part(pm), b.part(pm)]
+        }
+        Axiom::ObjectPropertyDomain(ObjectPropertyDomain { ope, ce }) => {
+            vec![ope.part(pm), ce.part(pm)]
+        }
+        Axiom::ObjectPropertyRange(ObjectPropertyRange { ope, ce }) => {
+            vec![ope.part(pm), ce.part(pm)]
+        }
+        Axiom::FunctionalObjectProperty(FunctionalObjectProperty(ope)) => vec![ope.part(pm)],
+        Axiom::InverseFunctionalObjectProperty(InverseFunctionalObjectProperty(ope)) => {
+            vec![ope.part(pm)]
+        }
+        Axiom::ReflexiveObjectProperty(ReflexiveObjectProperty(ope)) => vec![ope.part(pm)],
+        Axiom::IrreflexiveObjectProperty(IrreflexiveObjectProperty(ope)) => vec![ope.part(pm)],
+        Axiom::SymmetricObjectProperty(SymmetricObjectProperty(ope)) => vec![ope.part(pm)],
+        Axiom::AsymmetricObjectProperty(AsymmetricObjectProperty(ope)) => vec![ope.part(pm)],
+        Axiom::TransitiveObjectProperty(TransitiveObjectProperty(ope)) => vec![ope.part(pm)],
+        Axiom::SubDataPropertyOf(SubDataPropertyOf { sub, sup }) => {
+            vec![sub.part(pm), sup.part(pm)]
+        }
+        Axiom::EquivalentDataProperties(EquivalentDataProperties(v)) => vec![v.part(pm)],
+        Axiom::DisjointDataProperties(DisjointDataProperties(v)) => vec![v.part(pm)],
+        Axiom::DataPropertyDomain(DataPropertyDomain { dp, ce }) => vec![dp.part(pm), ce.part(pm)],
+        Axiom::DataPropertyRange(DataPropertyRange { dp, dr }) => vec![dp.part(pm), dr.part(pm)],
+        Axiom::FunctionalDataProperty(FunctionalDataProperty(dp)) => vec![dp.part(pm)],
+        Axiom::DatatypeDefinition(DatatypeDefinition { kind, range }) => {
+            vec![kind.part(pm), range.part(pm)]
+        }
+        Axiom::HasKey(HasKey { ce, vpe }) => vec![ce.part(pm), vpe.part(pm)],
+        Axiom::SameIndividual(SameIndividual(v)) => vec![v.part(pm)],
+        Axiom::DifferentIndividuals(DifferentIndividuals(v)) => vec![v.part(pm)],
+        Axiom::ClassAssertion(ClassAssertion { ce, i }) => vec![ce.part(pm), i.part(pm)],
+        Axiom::ObjectPropertyAssertion(ObjectPropertyAssertion { ope, from, to }) => {
+            vec![ope.part(pm), from.part(pm), to.part(pm)]
+        }
+        Axiom::NegativeObjectPropertyAssertion(NegativeObjectPropertyAssertion {
+            ope,
+            from,
+            to,
+        }) => vec![ope.part(pm), from.part(pm), to.part(pm)],
+        Axiom::DataPropertyAssertion(DataPropertyAssertion { dp, from, to }) => {
+            vec![dp.part(pm), from.part(pm), to.part(pm)]
+        }
+        Axiom::NegativeDataPropertyAssertion(NegativeDataPropertyAssertion { dp, from, to }) => {
+            vec![dp.part(pm), from.part(pm), to.part(pm)]
+        }
+        Axiom::AnnotationAssertion(AnnotationAssertion { subject, ann }) => {
+            let mut v = ann.parts(pm);
+            v.insert(1, subject.part(pm));
+            v
+        }
+        Axiom::SubAnnotationPropertyOf(SubAnnotationPropertyOf { sub, sup }) => {
+            vec![sub.part(pm), sup.part(pm)]
+        }
+        Axiom::AnnotationPropertyDomain(AnnotationPropertyDomain { ap, iri }) => {
+            vec![ap.part(pm), iri.part(pm)]
+        }
+        Axiom::AnnotationPropertyRange(AnnotationPropertyRange { ap, iri }) => {
+            vec![ap.part(pm), iri.part(pm)]
+        }
+    }
+}
+
+/// A component of an axiom which can be summarized.
+trait Part {
+    fn part(&self, pm: &PrefixMapping) -> String;
+}
+
+/// The summary of a component which is not named.
+const ELIDED: &str = "(...)";
+
+impl<T: Part> Part for Vec<T> {
+    fn part(&self, pm: &PrefixMapping) -> String {
+        self.iter()
+            .map(|t| t.part(pm))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl<A: ForIRI> Part for IRI<A> {
+    fn part(&self, pm: &PrefixMapping) -> String {
+        match pm.shrink_iri(self) {
+            Ok(curie) => format!("{}", curie),
+            Err(_) => format!("<{}>", self),
+        }
+    }
+}
+
+macro_rules! named_part {
+    ($($name:ident),*) => {
+        $(
+            impl<A: ForIRI> Part for $name<A> {
+                fn part(&self, pm: &PrefixMapping) -> String {
+                    self.0.part(pm)
+                }
+            }
+        )*
+    };
+}
+
+named_part!(
+    Class,
+    Datatype,
+    ObjectProperty,
+    DataProperty,
+    AnnotationProperty,
+    NamedIndividual
+);
+
+impl<A: ForIRI> Part for AnonymousIndividual<A> {
+    fn part(&self, _pm: &PrefixMapping) -> String {
+        self.to_string()
+    }
+}
+
+impl<A: ForIRI> Part for Individual<A> {
+    fn part(&self, pm: &PrefixMapping) -> String {
+        match self {
+            Individual::Anonymous(ai) => ai.part(pm),
+            Individual::Named(ni) => ni.part(pm),
+        }
+    }
+}
+
+impl<A: ForIRI> Part for AnnotationSubject<A> {
+    fn part(&self, pm: &PrefixMapping) -> String {
+        match self {
+            AnnotationSubject::IRI(iri) => iri.part(pm),
+            AnnotationSubject::AnonymousIndividual(ai) => ai.part(pm),
+        }
+    }
+}
+
+impl<A: ForIRI> Part for Literal<A> {
+    fn part(&self, pm: &PrefixMapping) -> String {
+        match self {
+            Literal::Simple { literal } => format!("{:?}", literal),
+            Literal::Language { literal, lang } => format!("{:?}@{}", literal, lang),
+            Literal::Datatype {
+                literal,
+                datatype_iri,
+            } => format!("{:?}^^{}", literal, datatype_iri.part(pm)),
+        }
+    }
+}
+
+impl<A: ForIRI> Annotation<A> {
+    fn parts(&self, pm: &PrefixMapping) -> Vec<String> {
+        let av = match &self.av {
+            AnnotationValue::Literal(l) => l.part(pm),
+            AnnotationValue::IRI(iri) => iri.part(pm),
+        };
+        vec![self.ap.part(pm), av]
+    }
+}
+
+impl<A: ForIRI> Part for ClassExpression<A> {
+    fn part(&self, pm: &PrefixMapping) -> String {
+        match self {
+            ClassExpression::Class(c) => c.part(pm),
+            _ => ELIDED.to_string(),
+        }
+    }
+}
+
+impl<A: ForIRI> Part for ObjectPropertyExpression<A> {
+    fn part(&self, pm: &PrefixMapping) -> String {
+        match self {
+            ObjectPropertyExpression::ObjectProperty(op) => op.part(pm),
+            ObjectPropertyExpression::InverseObjectProperty(_) => ELIDED.to_string(),
+        }
+    }
+}
+
+impl<A: ForIRI> Part for SubObjectPropertyExpression<A> {
+    fn part(&self, pm: &PrefixMapping) -> String {
+        match self {
+            SubObjectPropertyExpression::ObjectPropertyExpression(ope) => ope.part(pm),
+            SubObjectPropertyExpression::ObjectPropertyChain(_) => ELIDED.to_string(),
+        }
+    }
+}
+
+impl<A: ForIRI> Part for PropertyExpression<A> {
+    fn part(&self, pm: &PrefixMapping) -> String {
+        match self {
+            PropertyExpression::ObjectPropertyExpression(ope) => ope.part(pm),
+            PropertyExpression::DataProperty(dp) => dp.part(pm),
+            PropertyExpression::AnnotationProperty(ap) => ap.part(pm),
+        }
+    }
+}
+
+impl<A: ForIRI> Part for DataRange<A> {
+    fn part(&self, pm: &PrefixMapping) -> String {
+        match self {
+            DataRange::Datatype(dt) => dt.part(pm),
+            _ => ELIDED.to_string(),
+        }
+    }
+}
@@ -15,14 +15,21 @@ use super::set::SetOntology;
 use crate::model::*;
 use std::{
     cell::RefCell,
-    collections::{BTreeMap, BTreeSet, VecDeque},
+    collections::{BTreeMap, BTreeSet, HashSet, VecDeque},
     rc::Rc,
     sync::Arc,
 };
 
 use std::marker::PhantomData;
 
-use super::indexed::{OneIndexedOntology, OntologyIndex};
+use super::indexed::{OneIndexedOntology, OntologyIndex, ReindexInto};
+
+#[cfg(feature = "persist")]
+use super::set::Reintern;
+#[cfg(feature = "persist")]
+use crate::error::HornedError;
+#[cfg(feature = "persist")]
+use crate::visitor::WalkMut;
 
 /// Return all axioms of a specific `AxiomKind`
 #[allow(unused_macros)]
@@ -54,12 +61,18 @@ macro_rules! onimpl {
     };
 }
 
-#[derive(Debug, Default, Eq, PartialEq)]
+#[derive(Debug, Eq, PartialEq)]
 pub struct AxiomMappedIndex<A, AA> {
     axiom: RefCell<BTreeMap<AxiomKind, BTreeSet<AA>>>,
     pd: PhantomData<A>,
 }
 
+impl<A: ForIRI, AA: ForIndex<A>> Default for AxiomMappedIndex<A, AA> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<A: ForIRI, AA: ForIndex<A>> AxiomMappedIndex<A, AA> {
     /// Create a new ontology.
     ///
@@ -103,6 +116,16 @@ impl<A: ForIRI, AA: ForIndex<A>> AxiomMappedIndex<A, AA> {
         unsafe { (*self.axioms_as_ptr(axk)).get_mut(&axk).unwrap() }
     }
 
+    /// Returns the number of axioms in the index.
+    pub fn len(&self) -> usize {
+        self.axiom.borrow().values().map(|s| s.len()).sum()
+    }
+
+    /// Returns true if the index contains no axioms.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     /// Gets an iterator that visits the annotated axioms of the ontology.
     pub fn iter(&self) -> AxiomMappedIter<A, AA> {
         // TODO -- what can't this just use flat_map?
@@ -156,6 +179,48 @@ impl<A: ForIRI, AA: ForIndex<A>> AxiomMappedIndex<A, AA> {
     pub fn axiom(&self, axk: AxiomKind) -> impl Iterator<Item = &Axiom<A>> {
         self.axiom_for_kind(axk).map(|ann| &ann.axiom)
     }
+
+    /// Returns the number of axioms of the given kind, without
+    /// iterating over them.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+    /// let mut o = AxiomMappedOntology::new_rc();
+    /// let b = Build::new_rc();
+    /// o.declare(b.class("http://www.example.com/a"));
+    /// o.declare(b.class("http://www.example.com/b"));
+    ///
+    /// assert_eq!(o.i().count_kind(AxiomKind::DeclareClass), 2);
+    /// assert_eq!(o.i().count_kind(AxiomKind::SubClassOf), 0);
+    /// ```
+    pub fn count_kind(&self, axk: AxiomKind) -> usize {
+        self.set_for_kind(axk).map_or(0, |s| s.len())
+    }
+
+    /// Returns the number of axioms of each kind present in the
+    /// index, in the order of `AxiomKind`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+    /// let mut o = AxiomMappedOntology::new_rc();
+    /// let b = Build::new_rc();
+    /// o.declare(b.class("http://www.example.com/a"));
+    /// o.declare(b.class("http://www.example.com/b"));
+    ///
+    /// assert_eq!(
+    ///     o.i().kind_counts().collect::<Vec<_>>(),
+    ///     vec![(AxiomKind::DeclareClass, 2)]
+    /// );
+    /// ```
+    pub fn kind_counts(&self) -> impl Iterator<Item = (AxiomKind, usize)> + '_ {
+        unsafe { (*self.axiom.as_ptr()).iter() }
+            .filter(|(_, s)| !s.is_empty())
+            .map(|(k, s)| (*k, s.len()))
+    }
 }
 // In the ideal world, we would have generated these onimpl! calls as
 // part of the axiom macro. This should be possible, as their is a
@@ -209,6 +274,279 @@ onimpl! {SubAnnotationPropertyOf, sub_annotation_property_of}
 onimpl! {AnnotationPropertyDomain, annotation_property_domain}
 onimpl! {AnnotationPropertyRange, annotation_property_range}
 
+impl<A: ForIRI, AA: ForIndex<A>> AxiomMappedIndex<A, AA> {
+    /// Return all general class axioms in the ontology.
+    ///
+    /// These are the `SubClassOf` axioms whose subclass is not a
+    /// named class, and which therefore do not describe any one
+    /// class.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+    /// let mut o = AxiomMappedOntology::new_rc();
+    /// let b = Build::new_rc();
+    /// o.insert(SubClassOf {
+    ///     sub: b.class("http://www.example.com/a").into(),
+    ///     sup: b.class("http://www.example.com/b").into(),
+    /// });
+    /// o.insert(SubClassOf {
+    ///     sub: ClassExpression::ObjectSomeValuesFrom {
+    ///         ope: b.object_property("http://www.example.com/r").into(),
+    ///         bce: Box::new(b.class("http://www.example.com/a").into()),
+    ///     },
+    ///     sup: b.class("http://www.example.com/b").into(),
+    /// });
+    ///
+    /// assert_eq!(o.i().general_class_axioms().count(), 1);
+    /// ```
+    pub fn general_class_axioms(&self) -> impl Iterator<Item = &SubClassOf<A>> {
+        self.sub_class_of()
+            .filter(|sc| !matches!(sc.sub, ClassExpression::Class(_)))
+    }
+
+    /// Return every pair of class expressions which are asserted to
+    /// be disjoint.
+    ///
+    /// Each `DisjointClasses` axiom is expanded into all of the pairs
+    /// of its class expressions, in the order in which they occur in
+    /// the axiom.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+    /// let mut o = AxiomMappedOntology::new_rc();
+    /// let b = Build::new_rc();
+    /// o.insert(DisjointClasses(vec![
+    ///     b.class("http://www.example.com/a").into(),
+    ///     b.class("http://www.example.com/b").into(),
+    ///     b.class("http://www.example.com/c").into(),
+    /// ]));
+    ///
+    /// assert_eq!(o.i().disjoint_pairs().count(), 3);
+    /// ```
+    pub fn disjoint_pairs(
+        &self,
+    ) -> impl Iterator<Item = (&ClassExpression<A>, &ClassExpression<A>)> {
+        self.disjoint_class().flat_map(|DisjointClasses(ces)| {
+            ces.iter()
+                .enumerate()
+                .flat_map(move |(i, a)| ces[i + 1..].iter().map(move |b| (a, b)))
+        })
+    }
+
+    /// Return the domains of a data property.
+    ///
+    /// These are taken from the `DataPropertyDomain` axioms of the
+    /// ontology, and so follow its insertions and removals.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+    /// let mut o = AxiomMappedOntology::new_rc();
+    /// let b = Build::new_rc();
+    /// let dp = b.data_property("http://www.example.com/age");
+    /// o.insert(DataPropertyDomain {
+    ///     dp: dp.clone(),
+    ///     ce: b.class("http://www.example.com/Person").into(),
+    /// });
+    ///
+    /// assert_eq!(
+    ///     o.i().data_domains_of(&dp).collect::<Vec<_>>(),
+    ///     vec![&b.class("http://www.example.com/Person").into()]
+    /// );
+    /// ```
+    pub fn data_domains_of<'a>(
+        &'a self,
+        dp: &'a DataProperty<A>,
+    ) -> impl Iterator<Item = &'a ClassExpression<A>> {
+        self.data_property_domain()
+            .filter(move |d| &d.dp == dp)
+            .map(|d| &d.ce)
+    }
+
+    /// Return the ranges of a data property.
+    ///
+    /// These are taken from the `DataPropertyRange` axioms of the
+    /// ontology, and so follow its insertions and removals.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+    /// let mut o = AxiomMappedOntology::new_rc();
+    /// let b = Build::new_rc();
+    /// let dp = b.data_property("http://www.example.com/age");
+    /// o.insert(DataPropertyRange {
+    ///     dp: dp.clone(),
+    ///     dr: b.datatype("http://www.w3.org/2001/XMLSchema#integer").into(),
+    /// });
+    ///
+    /// assert_eq!(
+    ///     o.i().data_ranges_of(&dp).collect::<Vec<_>>(),
+    ///     vec![&b.datatype("http://www.w3.org/2001/XMLSchema#integer").into()]
+    /// );
+    /// ```
+    pub fn data_ranges_of<'a>(
+        &'a self,
+        dp: &'a DataProperty<A>,
+    ) -> impl Iterator<Item = &'a DataRange<A>> {
+        self.data_property_range()
+            .filter(move |r| &r.dp == dp)
+            .map(|r| &r.dr)
+    }
+
+    /// Return the domains of an annotation property.
+    ///
+    /// These are taken from the `AnnotationPropertyDomain` axioms of
+    /// the ontology. Unlike other domains, they have no logical
+    /// meaning.
+    pub fn annotation_domains_of<'a>(
+        &'a self,
+        ap: &'a AnnotationProperty<A>,
+    ) -> impl Iterator<Item = &'a IRI<A>> {
+        self.annotation_property_domain()
+            .filter(move |d| &d.ap == ap)
+            .map(|d| &d.iri)
+    }
+
+    /// Return the ranges of an annotation property.
+    ///
+    /// These are taken from the `AnnotationPropertyRange` axioms of
+    /// the ontology, and have no logical meaning.
+    pub fn annotation_ranges_of<'a>(
+        &'a self,
+        ap: &'a AnnotationProperty<A>,
+    ) -> impl Iterator<Item = &'a IRI<A>> {
+        self.annotation_property_range()
+            .filter(move |r| &r.ap == ap)
+            .map(|r| &r.iri)
+    }
+
+    /// Return the characteristics of an object property.
+    ///
+    /// These are taken from the `FunctionalObjectProperty`,
+    /// `TransitiveObjectProperty` and other characteristic axioms
+    /// which are asserted of the property itself, rather than of its
+    /// inverse.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+    /// let mut o = AxiomMappedOntology::new_rc();
+    /// let b = Build::new_rc();
+    /// let op = b.object_property("http://www.example.com/r");
+    /// o.insert(FunctionalObjectProperty(op.clone().into()));
+    /// o.insert(TransitiveObjectProperty(op.clone().into()));
+    ///
+    /// let c = o.i().characteristics_of(&op);
+    /// assert_eq!(c.len(), 2);
+    /// assert!(c.contains(&PropertyCharacteristic::Functional));
+    /// assert!(c.contains(&PropertyCharacteristic::Transitive));
+    /// ```
+    pub fn characteristics_of(&self, p: &ObjectProperty<A>) -> HashSet<PropertyCharacteristic> {
+        let ope: ObjectPropertyExpression<A> = p.clone().into();
+        let mut c = HashSet::new();
+        if self.functional_object_property().any(|ax| ax.0 == ope) {
+            c.insert(PropertyCharacteristic::Functional);
+        }
+        if self
+            .inverse_functional_object_property()
+            .any(|ax| ax.0 == ope)
+        {
+            c.insert(PropertyCharacteristic::InverseFunctional);
+        }
+        if self.transitive_object_property().any(|ax| ax.0 == ope) {
+            c.insert(PropertyCharacteristic::Transitive);
+        }
+        if self.symmetric_object_property().any(|ax| ax.0 == ope) {
+            c.insert(PropertyCharacteristic::Symmetric);
+        }
+        if self.assymmetric_object_property().any(|ax| ax.0 == ope) {
+            c.insert(PropertyCharacteristic::Asymmetric);
+        }
+        if self.reflexive_object_property().any(|ax| ax.0 == ope) {
+            c.insert(PropertyCharacteristic::Reflexive);
+        }
+        if self.irreflexive_object_property().any(|ax| ax.0 == ope) {
+            c.insert(PropertyCharacteristic::Irreflexive);
+        }
+        c
+    }
+
+    /// Return every object property which has two contradictory
+    /// characteristics.
+    ///
+    /// A property cannot be both `Symmetric` and `Asymmetric`, nor
+    /// both `Reflexive` and `Irreflexive`, without becoming empty
+    /// (and, if reflexive, the ontology inconsistent). Each such pair
+    /// is reported once, in property order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+    /// let mut o = AxiomMappedOntology::new_rc();
+    /// let b = Build::new_rc();
+    /// let op = b.object_property("http://www.example.com/r");
+    /// o.insert(SymmetricObjectProperty(op.clone().into()));
+    /// o.insert(AsymmetricObjectProperty(op.clone().into()));
+    ///
+    /// assert_eq!(
+    ///     o.i().conflicting_property_characteristics(),
+    ///     vec![(
+    ///         op,
+    ///         PropertyCharacteristic::Symmetric,
+    ///         PropertyCharacteristic::Asymmetric
+    ///     )]
+    /// );
+    /// ```
+    pub fn conflicting_property_characteristics(
+        &self,
+    ) -> Vec<(
+        ObjectProperty<A>,
+        PropertyCharacteristic,
+        PropertyCharacteristic,
+    )> {
+        let named = |ope: &ObjectPropertyExpression<A>| match ope {
+            ObjectPropertyExpression::ObjectProperty(op) => Some(op.clone()),
+            _ => None,
+        };
+        let props: BTreeSet<ObjectProperty<A>> = self
+            .symmetric_object_property()
+            .filter_map(|ax| named(&ax.0))
+            .chain(
+                self.reflexive_object_property()
+                    .filter_map(|ax| named(&ax.0)),
+            )
+            .collect();
+
+        let mut conflicts = vec![];
+        for op in props {
+            let c = self.characteristics_of(&op);
+            for (a, b) in &[
+                (
+                    PropertyCharacteristic::Symmetric,
+                    PropertyCharacteristic::Asymmetric,
+                ),
+                (
+                    PropertyCharacteristic::Reflexive,
+                    PropertyCharacteristic::Irreflexive,
+                ),
+            ] {
+                if c.contains(a) && c.contains(b) {
+                    conflicts.push((op.clone(), *a, *b));
+                }
+            }
+        }
+        conflicts
+    }
+}
+
 /// An owning iterator over the annotated axioms of an `Ontology`.
 impl<A: ForIRI, AA: ForIndex<A>> IntoIterator for AxiomMappedIndex<A, AA> {
     type Item = AnnotatedAxiom<A>;
@@ -278,9 +616,31 @@ impl<A: ForIRI, AA: ForIndex<A>> OntologyIndex<A, AA> for AxiomMappedIndex<A, AA
     }
 }
 
-#[derive(Default, Debug, Eq, PartialEq)]
+impl<A: ForIRI, AA: ForIndex<A>, B: ForIRI, BB: ForIndex<B>> ReindexInto<B, BB>
+    for AxiomMappedIndex<A, AA>
+{
+    type Output = AxiomMappedIndex<B, BB>;
+
+    fn empty_index(&self) -> AxiomMappedIndex<B, BB> {
+        AxiomMappedIndex::new()
+    }
+}
+
+/// An ontology indexed by the kind of each axiom.
+///
+/// The backing store is fixed by the type, as for `SetOntology`:
+/// `RcAxiomMappedOntology` accepts axioms built with
+/// `Build::new_rc`, and `ArcAxiomMappedOntology` those built with
+/// `Build::new_arc`.
+#[derive(Debug, Eq, PartialEq)]
 pub struct AxiomMappedOntology<A, AA>(OneIndexedOntology<A, AA, AxiomMappedIndex<A, AA>>);
 
+impl<A: ForIRI, AA: ForIndex<A>> Default for AxiomMappedOntology<A, AA> {
+    fn default() -> Self {
+        AxiomMappedOntology(OneIndexedOntology::new(AxiomMappedIndex::new()))
+    }
+}
+
 pub type RcAxiomMappedOntology = AxiomMappedOntology<RcStr, Rc<AnnotatedAxiom<RcStr>>>;
 pub type ArcAxiomMappedOntology = AxiomMappedOntology<ArcStr, Arc<AnnotatedAxiom<ArcStr>>>;
 
@@ -300,6 +660,22 @@ impl<A: ForIRI, AA: ForIndex<A>> Ontology<A> for AxiomMappedOntology<A, AA> {
     fn mut_doc_iri(&mut self) -> &mut Option<IRI<A>> {
         self.0.mut_doc_iri()
     }
+
+    fn len(&self) -> usize {
+        AxiomMappedOntology::len(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        AxiomMappedOntology::is_empty(self)
+    }
+}
+
+impl<'a, A: ForIRI, AA: ForIndex<A>> IntoIterator for &'a AxiomMappedOntology<A, AA> {
+    type Item = &'a AnnotatedAxiom<A>;
+    type IntoIter = AxiomMappedIter<'a, A, AA>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.i().into_iter()
+    }
 }
 
 impl<A: ForIRI, AA: ForIndex<A>> MutableOntology<A> for AxiomMappedOntology<A, AA> {
@@ -323,6 +699,26 @@ impl<A: ForIRI, AA: ForIndex<A>> AxiomMappedOntology<A, AA> {
     pub fn i(&self) -> &AxiomMappedIndex<A, AA> {
         self.0.i()
     }
+
+    /// Returns the number of axioms in the ontology.
+    pub fn len(&self) -> usize {
+        self.i().len()
+    }
+
+    /// Returns true if the ontology contains no axioms.
+    ///
+    /// The `OntologyID` is not an axiom, so an ontology with only
+    /// an IRI is empty.
+    pub fn is_empty(&self) -> bool {
+        self.i().is_empty()
+    }
+
+    /// Return the number of successful changes made to this ontology.
+    ///
+    /// See `OneIndexedOntology::revision`.
+    pub fn revision(&self) -> u64 {
+        self.0.revision()
+    }
 }
 
 impl<A: ForIRI, AA: ForIndex<A>> AxiomMappedOntology<A, AA> {
@@ -331,6 +727,50 @@ impl<A: ForIRI, AA: ForIndex<A>> AxiomMappedOntology<A, AA> {
     }
 }
 
+#[cfg(feature = "persist")]
+impl<A: ForIRI, AA: ForIndex<A>> AxiomMappedOntology<A, AA> {
+    /// Write this ontology to `w` in a binary format which can be
+    /// read by `load_index`.
+    pub fn save_index<W: std::io::Write>(&self, w: W) -> Result<(), HornedError>
+    where
+        A: BackingStore + serde::Serialize,
+    {
+        let axioms: Vec<&AnnotatedAxiom<A>> = self.i().iter().collect();
+        Ok(bincode::serialize_into(
+            w,
+            &(self.id(), self.doc_iri(), &axioms),
+        )?)
+    }
+
+    /// Read an ontology written by `save_index` from `r`, interning
+    /// its IRIs with `b`.
+    ///
+    /// The axiom index is rebuilt as the axioms are read. The
+    /// ontology may have been saved with any backing store.
+    pub fn load_index<R: std::io::Read>(r: R, b: &Build<A>) -> Result<Self, HornedError>
+    where
+        A: BackingStore + serde::de::DeserializeOwned,
+    {
+        let (mut id, mut doc_iri, axioms): (OntologyID<A>, Option<IRI<A>>, Vec<AnnotatedAxiom<A>>) =
+            bincode::deserialize_from(r)?;
+
+        let mut walk = WalkMut::new(Reintern(b));
+        walk.ontology_id(&mut id);
+        walk.option_iri(&mut doc_iri);
+
+        let mut ami = AxiomMappedIndex::new();
+        for mut ax in axioms {
+            walk.annotated_axiom(&mut ax);
+            ami.index_insert(AA::from(ax));
+        }
+
+        let mut o = AxiomMappedOntology(OneIndexedOntology::new(ami));
+        *o.mut_id() = id;
+        *o.mut_doc_iri() = doc_iri;
+        Ok(o)
+    }
+}
+
 impl RcAxiomMappedOntology {
     pub fn new_rc() -> Self {
         AxiomMappedOntology::new()
@@ -388,6 +828,21 @@ mod test {
         assert!(true);
     }
 
+    #[test]
+    fn test_ontology_len() {
+        let mut o = AxiomMappedOntology::new_rc();
+        let b = Build::new_rc();
+        o.mut_id().iri = Some(b.iri("http://www.example.com/iri"));
+        assert_eq!(o.len(), 0);
+        assert!(o.is_empty());
+
+        o.declare(b.class("http://www.example.com/a"));
+        o.declare(b.object_property("http://www.example.com/r"));
+        o.declare(b.object_property("http://www.example.com/r"));
+        assert_eq!(o.len(), 2);
+        assert!(!o.is_empty());
+    }
+
     #[test]
     fn test_ontology_iter_empty() {
         // Empty ontologies should stop iteration right away
@@ -519,4 +974,227 @@ mod test {
         assert_eq!(it.next(), None);
         assert_eq!(it.next(), None);
     }
+
+    #[test]
+    fn test_fold_axioms() {
+        let build = Build::new_rc();
+        let mut o = AxiomMappedOntology::new_rc();
+        o.insert(DeclareClass(build.class("http://www.example.com#a")));
+        o.insert(DisjointClasses(vec![
+            build.class("http://www.example.com#a").into(),
+            build.class("http://www.example.com#b").into(),
+            build.class("http://www.example.com#c").into(),
+        ]));
+        o.insert(SubClassOf {
+            sub: build.class("http://www.example.com#a").into(),
+            sup: build.class("http://www.example.com#b").into(),
+        });
+
+        // The number of class expressions in each axiom
+        let size = |aa: &AnnotatedAxiom<_>| match &aa.axiom {
+            Axiom::DisjointClasses(DisjointClasses(v)) => v.len(),
+            Axiom::SubClassOf(_) => 2,
+            _ => 0,
+        };
+        assert_eq!(o.fold_axioms(0, |n, aa| n + size(aa)), 5);
+
+        let so: SetOntology<_> = o.into();
+        assert_eq!(so.fold_axioms(0, |n, aa| n + size(aa)), 5);
+    }
+
+    #[test]
+    fn test_general_class_axioms() {
+        let build = Build::new_rc();
+        let mut o = AxiomMappedOntology::new_rc();
+        let named = SubClassOf {
+            sub: build.class("http://www.example.com#a").into(),
+            sup: build.class("http://www.example.com#b").into(),
+        };
+        let gci = SubClassOf {
+            sub: ClassExpression::ObjectIntersectionOf(vec![
+                build.class("http://www.example.com#a").into(),
+                build.class("http://www.example.com#c").into(),
+            ]),
+            sup: build.class("http://www.example.com#b").into(),
+        };
+        o.insert(named);
+        o.insert(gci.clone());
+
+        assert_eq!(o.i().general_class_axioms().collect::<Vec<_>>(), vec![&gci]);
+    }
+
+    #[test]
+    fn test_data_ranges_of() {
+        let build = Build::new_rc();
+        let mut o = AxiomMappedOntology::new_rc();
+        let age = build.data_property("http://www.example.com#age");
+        let name = build.data_property("http://www.example.com#name");
+        o.declare(age.clone());
+        o.insert(DataPropertyRange {
+            dp: age.clone(),
+            dr: build
+                .datatype("http://www.w3.org/2001/XMLSchema#integer")
+                .into(),
+        });
+        o.insert(DataPropertyRange {
+            dp: name.clone(),
+            dr: build
+                .datatype("http://www.w3.org/2001/XMLSchema#string")
+                .into(),
+        });
+        o.insert(DataPropertyDomain {
+            dp: age.clone(),
+            ce: build.class("http://www.example.com#Person").into(),
+        });
+
+        assert_eq!(
+            o.i().data_ranges_of(&age).collect::<Vec<_>>(),
+            vec![&build
+                .datatype("http://www.w3.org/2001/XMLSchema#integer")
+                .into()]
+        );
+        assert_eq!(o.i().data_domains_of(&age).count(), 1);
+        assert_eq!(o.i().data_domains_of(&name).count(), 0);
+
+        o.remove(
+            &DataPropertyRange {
+                dp: age.clone(),
+                dr: build
+                    .datatype("http://www.w3.org/2001/XMLSchema#integer")
+                    .into(),
+            }
+            .into(),
+        );
+        assert_eq!(o.i().data_ranges_of(&age).count(), 0);
+        assert_eq!(o.i().data_ranges_of(&name).count(), 1);
+    }
+
+    #[test]
+    fn test_disjoint_pairs() {
+        let build = Build::new_rc();
+        let mut o = AxiomMappedOntology::new_rc();
+        let c = |s: &str| -> ClassExpression<_> {
+            build.class(format!("http://www.example.com#{}", s)).into()
+        };
+        o.insert(DisjointClasses(vec![c("a"), c("b"), c("c")]));
+
+        assert_eq!(
+            o.i().disjoint_pairs().collect::<Vec<_>>(),
+            vec![(&c("a"), &c("b")), (&c("a"), &c("c")), (&c("b"), &c("c"))]
+        );
+    }
+
+    #[test]
+    fn test_count_kind() {
+        let build = Build::new_rc();
+        let mut o = AxiomMappedOntology::new_rc();
+        let a = build.class("http://www.example.com#a");
+        o.declare(a.clone());
+        o.declare(build.class("http://www.example.com#b"));
+        o.declare(build.object_property("http://www.example.com#r"));
+
+        for kind in [AxiomKind::DeclareClass, AxiomKind::DeclareObjectProperty] {
+            assert_eq!(o.i().count_kind(kind), o.i().axiom_for_kind(kind).count());
+        }
+        assert_eq!(o.i().count_kind(AxiomKind::DeclareClass), 2);
+
+        o.remove(&DeclareClass(a).into());
+        assert_eq!(o.i().count_kind(AxiomKind::DeclareClass), 1);
+        assert_eq!(o.i().count_kind(AxiomKind::SubClassOf), 0);
+    }
+
+    #[test]
+    fn test_kind_counts() {
+        let build = Build::new_rc();
+        let mut o = AxiomMappedOntology::new_rc();
+        let a = build.class("http://www.example.com#a");
+        o.declare(a.clone());
+        o.declare(build.object_property("http://www.example.com#r"));
+        o.insert(SubClassOf {
+            sub: a.clone().into(),
+            sup: build.class("http://www.example.com#b").into(),
+        });
+
+        assert_eq!(
+            o.i().kind_counts().map(|(_, n)| n).sum::<usize>(),
+            o.i().len()
+        );
+        assert_eq!(o.i().kind_counts().count(), 3);
+
+        // Emptied kinds are not returned
+        o.remove(&DeclareClass(a).into());
+        assert!(o
+            .i()
+            .kind_counts()
+            .all(|(k, _)| k != AxiomKind::DeclareClass));
+        assert_eq!(o.i().kind_counts().map(|(_, n)| n).sum::<usize>(), 2);
+    }
+
+    #[test]
+    fn test_annotation_domains_of() {
+        let build = Build::new_rc();
+        let mut o = AxiomMappedOntology::new_rc();
+        let ap = build.annotation_property("http://www.example.com#ap");
+        let person = build.iri("http://www.example.com#Person");
+        let string = build.iri("http://www.w3.org/2001/XMLSchema#string");
+        o.mut_id().iri = Some(build.iri("http://www.example.com"));
+        o.declare(ap.clone());
+        o.insert(AnnotationPropertyDomain {
+            ap: ap.clone(),
+            iri: person.clone(),
+        });
+        o.insert(AnnotationPropertyRange {
+            ap: ap.clone(),
+            iri: string.clone(),
+        });
+
+        let mut buf = vec![];
+        crate::io::rdf::writer::write(&mut buf, &o).unwrap();
+        let (read, _) =
+            crate::io::rdf::reader::read(&mut buf.as_slice(), Default::default()).unwrap();
+        let read: RcAxiomMappedOntology = read.into();
+
+        assert_eq!(
+            read.i().annotation_domains_of(&ap).collect::<Vec<_>>(),
+            vec![&person]
+        );
+        assert_eq!(
+            read.i().annotation_ranges_of(&ap).collect::<Vec<_>>(),
+            vec![&string]
+        );
+        let so: SetOntology<_> = read.into();
+        assert!(so.signature().contains(&ap.into()));
+    }
+
+    #[cfg(feature = "persist")]
+    #[test]
+    fn test_save_load_index() {
+        let build = Build::new_rc();
+        let mut o = AxiomMappedOntology::new_rc();
+        o.mut_id().iri = Some(build.iri("http://www.example.com/o"));
+        let a = build.class("http://www.example.com#a");
+        let b = build.class("http://www.example.com#b");
+        o.declare(a.clone());
+        o.declare(b.clone());
+        o.insert(SubClassOf {
+            sub: a.into(),
+            sup: b.into(),
+        });
+
+        let mut buf = vec![];
+        o.save_index(&mut buf).unwrap();
+        let loaded = RcAxiomMappedOntology::load_index(buf.as_slice(), &build).unwrap();
+
+        assert_eq!(loaded, o);
+        assert_eq!(
+            loaded.i().axiom_for_kind(AxiomKind::DeclareClass).count(),
+            2
+        );
+        assert!(std::ptr::eq(
+            loaded.id().iri.as_ref().unwrap().as_ref(),
+            build.iri("http://www.example.com/o").as_ref()
+        ));
+
+        assert!(RcAxiomMappedOntology::load_index(&buf[..10], &build).is_err());
+    }
 }
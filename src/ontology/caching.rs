@@ -0,0 +1,151 @@
+//! An index that caches a value derived from another index
+
+//! # Overview
+//!
+//! A `CachingIndex` wraps any `OntologyIndex`, together with a query
+//! which derives a value from it, such as the signature of the
+//! ontology. The value is computed the first time that it is asked
+//! for and kept until the index next changes, so that an expensive
+//! query is not repeated while the ontology is unchanged.
+use crate::model::*;
+
+use super::indexed::ForIndex;
+use super::indexed::OntologyIndex;
+use super::indexed::ReindexInto;
+
+use std::cell::OnceCell;
+use std::fmt::{self, Debug, Formatter};
+use std::marker::PhantomData;
+
+pub struct CachingIndex<A, AA, I, Q> {
+    index: I,
+    query: Box<dyn Fn(&I) -> Q>,
+    cache: OnceCell<Q>,
+    p: PhantomData<(A, AA)>,
+}
+
+impl<A: ForIRI, AA: ForIndex<A>, I: OntologyIndex<A, AA>, Q> CachingIndex<A, AA, I, Q> {
+    /// Wrap `index`, caching the result of `query` over it.
+    pub fn new<F: Fn(&I) -> Q + 'static>(index: I, query: F) -> CachingIndex<A, AA, I, Q> {
+        CachingIndex {
+            index,
+            query: Box::new(query),
+            cache: OnceCell::new(),
+            p: PhantomData,
+        }
+    }
+
+    /// Return the result of the query, computing it if the index has
+    /// changed since it was last computed.
+    pub fn cached(&self) -> &Q {
+        self.cache.get_or_init(|| (self.query)(&self.index))
+    }
+
+    /// Returns true if the result of the query is currently cached.
+    pub fn is_cached(&self) -> bool {
+        self.cache.get().is_some()
+    }
+
+    /// Return the wrapped index.
+    pub fn index(&self) -> &I {
+        &self.index
+    }
+
+    pub fn into_index(self) -> I {
+        self.index
+    }
+}
+
+impl<A, AA, I: Debug, Q: Debug> Debug for CachingIndex<A, AA, I, Q> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CachingIndex")
+            .field("index", &self.index)
+            .field("cache", &self.cache)
+            .finish()
+    }
+}
+
+impl<A: ForIRI, AA: ForIndex<A>, I: OntologyIndex<A, AA>, Q> OntologyIndex<A, AA>
+    for CachingIndex<A, AA, I, Q>
+{
+    fn index_insert(&mut self, ax: AA) -> bool {
+        let rtn = self.index.index_insert(ax);
+        if rtn {
+            self.cache.take();
+        }
+        rtn
+    }
+
+    fn index_remove(&mut self, ax: &AnnotatedAxiom<A>) -> bool {
+        let rtn = self.index.index_remove(ax);
+        if rtn {
+            self.cache.take();
+        }
+        rtn
+    }
+}
+
+/// The query is specific to the type of the wrapped index, so it
+/// cannot be carried over; a reindexed `CachingIndex` is just the
+/// reindexed wrapped index.
+impl<A: ForIRI, AA: ForIndex<A>, B: ForIRI, BB: ForIndex<B>, I, Q> ReindexInto<B, BB>
+    for CachingIndex<A, AA, I, Q>
+where
+    I: OntologyIndex<A, AA> + ReindexInto<B, BB>,
+{
+    type Output = I::Output;
+
+    fn empty_index(&self) -> I::Output {
+        self.index.empty_index()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CachingIndex;
+    use crate::model::*;
+    use crate::ontology::{
+        axiom_mapped::AxiomMappedIndex, indexed::TwoIndexedOntology, set::SetIndex,
+    };
+
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_recompute_after_change() {
+        let b = Build::new_rc();
+        let computed = Rc::new(Cell::new(0));
+        let counter = computed.clone();
+        let mut o = TwoIndexedOntology::new(
+            SetIndex::new_rc(),
+            CachingIndex::new(
+                AxiomMappedIndex::new(),
+                move |i: &AxiomMappedIndex<RcStr, RcAnnotatedAxiom>| {
+                    counter.set(counter.get() + 1);
+                    i.declare_class().count()
+                },
+            ),
+            Default::default(),
+        );
+
+        assert!(!o.j().is_cached());
+        assert_eq!(*o.j().cached(), 0);
+        assert_eq!(*o.j().cached(), 0);
+        assert_eq!(computed.get(), 1);
+
+        o.declare(b.class("http://www.example.com/a"));
+        assert!(!o.j().is_cached());
+        assert_eq!(*o.j().cached(), 1);
+        assert_eq!(*o.j().cached(), 1);
+        assert_eq!(computed.get(), 2);
+
+        // Inserting an axiom which is already present changes nothing
+        o.declare(b.class("http://www.example.com/a"));
+        assert!(o.j().is_cached());
+        assert_eq!(computed.get(), 2);
+
+        o.remove(&DeclareClass(b.class("http://www.example.com/a")).into());
+        assert_eq!(*o.j().cached(), 0);
+        assert_eq!(computed.get(), 3);
+    }
+}
@@ -0,0 +1,196 @@
+//! An index that provides rapid look up of the asserted individuals
+//! of a class
+
+//! # Overview
+//!
+//! The `ClassAssertionMappedIndex` indexes `ClassAssertion` axioms
+//! by their class, so that the individuals asserted to be members of
+//! a class can be found without a scan. Only asserted membership is
+//! indexed; nothing is inferred. `ClassAssertion` axioms with a
+//! complex class expression are skipped, as they have no class to be
+//! indexed under.
+use crate::model::*;
+
+use super::indexed::ForIndex;
+use super::indexed::OntologyIndex;
+use super::indexed::ReindexInto;
+
+use std::collections::{BTreeSet, HashMap};
+
+#[derive(Debug, Default)]
+pub struct ClassAssertionMappedIndex<A, AA>(HashMap<Class<A>, BTreeSet<AA>>);
+
+impl<A: ForIRI, AA: ForIndex<A>> ClassAssertionMappedIndex<A, AA> {
+    pub fn new() -> ClassAssertionMappedIndex<A, AA> {
+        ClassAssertionMappedIndex(HashMap::new())
+    }
+
+    /// Return the individuals asserted to be members of `c`, in
+    /// sorted order.
+    ///
+    /// Each individual is returned once, even if its membership is
+    /// asserted by several axioms with different annotations.
+    pub fn individuals_of(&self, c: &Class<A>) -> impl Iterator<Item = &Individual<A>> {
+        self.0
+            .get(c)
+            .into_iter()
+            .flat_map(|axioms| axioms.iter())
+            .filter_map(|aa| Self::aa_to_membership(aa.borrow()))
+            .map(|(_, i)| i)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+    }
+
+    fn aa_to_membership(ax: &AnnotatedAxiom<A>) -> Option<(&Class<A>, &Individual<A>)> {
+        match &ax.axiom {
+            Axiom::ClassAssertion(ClassAssertion {
+                ce: ClassExpression::Class(c),
+                i,
+            }) => Some((c, i)),
+            _ => None,
+        }
+    }
+}
+
+impl<A: ForIRI, AA: ForIndex<A>> OntologyIndex<A, AA> for ClassAssertionMappedIndex<A, AA> {
+    fn index_insert(&mut self, ax: AA) -> bool {
+        if let Some((c, _)) = Self::aa_to_membership(ax.borrow()) {
+            let c = c.clone();
+            self.0.entry(c).or_default().insert(ax)
+        } else {
+            false
+        }
+    }
+
+    fn index_remove(&mut self, ax: &AnnotatedAxiom<A>) -> bool {
+        let c = match Self::aa_to_membership(ax) {
+            Some((c, _)) => c,
+            None => return false,
+        };
+
+        let axioms = match self.0.get_mut(c) {
+            Some(axioms) => axioms,
+            None => return false,
+        };
+
+        let rtn = axioms.remove(ax);
+        if axioms.is_empty() {
+            self.0.remove(c);
+        }
+        rtn
+    }
+}
+
+impl<A: ForIRI, AA: ForIndex<A>, B: ForIRI, BB: ForIndex<B>> ReindexInto<B, BB>
+    for ClassAssertionMappedIndex<A, AA>
+{
+    type Output = ClassAssertionMappedIndex<B, BB>;
+
+    fn empty_index(&self) -> ClassAssertionMappedIndex<B, BB> {
+        ClassAssertionMappedIndex::new()
+    }
+}
+
+impl ClassAssertionMappedIndex<RcStr, RcAnnotatedAxiom> {
+    pub fn new_rc() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ClassAssertionMappedIndex;
+    use crate::model::*;
+    use crate::ontology::{indexed::TwoIndexedOntology, set::SetIndex};
+
+    #[test]
+    fn test_individuals_of() {
+        let b = Build::new_rc();
+        let mut o = TwoIndexedOntology::new(
+            SetIndex::new_rc(),
+            ClassAssertionMappedIndex::new_rc(),
+            Default::default(),
+        );
+        let c = b.class("http://www.example.com/c");
+        let i1: Individual<_> = b.named_individual("http://www.example.com/i1").into();
+        let i2: Individual<_> = b.named_individual("http://www.example.com/i2").into();
+
+        let ca1 = ClassAssertion {
+            ce: c.clone().into(),
+            i: i1.clone(),
+        };
+        o.insert(ca1.clone());
+        o.insert(ClassAssertion {
+            ce: c.clone().into(),
+            i: i2.clone(),
+        });
+        // Complex class expressions are not indexed
+        o.insert(ClassAssertion {
+            ce: ClassExpression::ObjectComplementOf(Box::new(c.clone().into())),
+            i: b.named_individual("http://www.example.com/i3").into(),
+        });
+
+        // The same membership with an annotation
+        let annotated = AnnotatedAxiom::new(
+            ca1.clone(),
+            vec![Annotation {
+                ap: b.annotation_property("http://www.example.com/ap"),
+                av: "note".into(),
+            }]
+            .into_iter()
+            .collect(),
+        );
+        o.insert(annotated.clone());
+
+        assert_eq!(o.j().individuals_of(&c).collect::<Vec<_>>(), vec![&i1, &i2]);
+
+        o.remove(&ca1.clone().into());
+        assert_eq!(o.j().individuals_of(&c).collect::<Vec<_>>(), vec![&i1, &i2]);
+
+        o.remove(&annotated);
+        assert_eq!(o.j().individuals_of(&c).collect::<Vec<_>>(), vec![&i2]);
+
+        assert_eq!(
+            o.j()
+                .individuals_of(&b.class("http://www.example.com/d"))
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_duplicate_insert() {
+        let b = Build::new_rc();
+        let mut o = TwoIndexedOntology::new(
+            SetIndex::new_rc(),
+            ClassAssertionMappedIndex::new_rc(),
+            Default::default(),
+        );
+        let c = b.class("http://www.example.com/c");
+        let ca: AnnotatedAxiom<_> = ClassAssertion {
+            ce: c.clone().into(),
+            i: b.named_individual("http://www.example.com/i").into(),
+        }
+        .into();
+
+        assert!(o.insert(ca.clone()));
+        assert!(!o.insert(ca.clone()));
+        assert!(o.remove(&ca));
+        assert_eq!(o.i().len(), 0);
+        assert_eq!(o.j().individuals_of(&c).count(), 0);
+
+        // An absent assertion with different annotations
+        o.insert(ca.clone());
+        let annotated = AnnotatedAxiom::new(
+            ca.axiom.clone(),
+            vec![Annotation {
+                ap: b.annotation_property("http://www.example.com/ap"),
+                av: "note".into(),
+            }]
+            .into_iter()
+            .collect(),
+        );
+        assert!(!o.remove(&annotated));
+        assert_eq!(o.j().individuals_of(&c).count(), 1);
+    }
+}
@@ -4,6 +4,7 @@ use crate::model::{AnnotatedAxiom, Axiom, AxiomKind, ForIRI, Kinded, NamedEntity
 
 use super::indexed::ForIndex;
 use super::indexed::OntologyIndex;
+use super::indexed::ReindexInto;
 
 use std::collections::HashSet;
 use std::marker::PhantomData;
@@ -124,6 +125,16 @@ impl<A: ForIRI, AA: ForIndex<A>> OntologyIndex<A, AA> for DeclarationMappedIndex
     }
 }
 
+impl<A: ForIRI, AA: ForIndex<A>, B: ForIRI, BB: ForIndex<B>> ReindexInto<B, BB>
+    for DeclarationMappedIndex<A, AA>
+{
+    type Output = DeclarationMappedIndex<B, BB>;
+
+    fn empty_index(&self) -> DeclarationMappedIndex<B, BB> {
+        DeclarationMappedIndex::new()
+    }
+}
+
 impl DeclarationMappedIndex<RcStr, RcAnnotatedAxiom> {
     pub fn new_rc() -> Self {
         Self::new()
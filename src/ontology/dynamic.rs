@@ -0,0 +1,105 @@
+//! An object safe interface to read any ontology
+
+//! # Overview
+//!
+//! The [`Ontology`](../../model/trait.Ontology.html) and
+//! [`MutableOntology`](../../model/trait.MutableOntology.html) traits
+//! have generic methods, and iteration over an ontology is provided
+//! through `IntoIterator`, so different implementations cannot be
+//! used through a single trait object. `DynOntology` provides
+//! read-only access without generics, and is implemented for every
+//! ontology which can be iterated by reference, so that, for
+//! example, a `SetOntology` and an `AxiomMappedOntology` can be held
+//! in the same `Vec<Box<dyn DynOntology<A>>>`.
+//!
+//! The methods of `DynOntology` share their names with those of
+//! other traits and inherent methods, so this trait is best used
+//! through a trait object, rather than imported alongside
+//! `horned_owl::model::*`.
+use crate::model::*;
+
+/// Read-only access to an ontology through a trait object.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// # use horned_owl::ontology::axiom_mapped::AxiomMappedOntology;
+/// # use horned_owl::ontology::dynamic::DynOntology;
+/// # use horned_owl::ontology::set::SetOntology;
+/// let b = Build::new_rc();
+/// let mut so = SetOntology::new_rc();
+/// so.declare(b.class("http://www.example.com/a"));
+/// let mut amo = AxiomMappedOntology::new_rc();
+/// amo.declare(b.class("http://www.example.com/b"));
+///
+/// let onts: Vec<Box<dyn DynOntology<_>>> = vec![Box::new(so), Box::new(amo)];
+/// assert_eq!(onts.iter().map(|o| o.iter().count()).sum::<usize>(), 2);
+/// ```
+pub trait DynOntology<A> {
+    /// Return the `OntologyID` of the ontology.
+    fn id(&self) -> &OntologyID<A>;
+
+    /// Return an iterator over the axioms of the ontology.
+    fn iter(&self) -> Box<dyn Iterator<Item = &AnnotatedAxiom<A>> + '_>;
+
+    /// Return true if the ontology contains `ax`.
+    fn contains(&self, ax: &AnnotatedAxiom<A>) -> bool;
+}
+
+impl<A: ForIRI, O> DynOntology<A> for O
+where
+    O: Ontology<A>,
+    for<'a> &'a O: IntoIterator<Item = &'a AnnotatedAxiom<A>>,
+{
+    fn id(&self) -> &OntologyID<A> {
+        Ontology::id(self)
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &AnnotatedAxiom<A>> + '_> {
+        Box::new(self.into_iter())
+    }
+
+    fn contains(&self, ax: &AnnotatedAxiom<A>) -> bool {
+        // Not all ontologies can test membership directly, so search
+        self.into_iter().any(|a| a == ax)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DynOntology;
+    use crate::model::{Build, DeclareClass, MutableOntology, Ontology};
+    use crate::ontology::axiom_mapped::AxiomMappedOntology;
+    use crate::ontology::set::SetOntology;
+
+    #[test]
+    fn test_heterogeneous() {
+        let b = Build::new_rc();
+        let mut so = SetOntology::new_rc();
+        so.mut_id().iri = Some(b.iri("http://www.example.com/so"));
+        so.declare(b.class("http://www.example.com/a"));
+
+        let mut amo = AxiomMappedOntology::new_rc();
+        amo.mut_id().iri = Some(b.iri("http://www.example.com/amo"));
+        amo.declare(b.class("http://www.example.com/b"));
+        amo.declare(b.class("http://www.example.com/c"));
+
+        let onts: Vec<Box<dyn DynOntology<_>>> = vec![Box::new(so), Box::new(amo)];
+
+        assert_eq!(
+            onts.iter().map(|o| o.id().iri.clone()).collect::<Vec<_>>(),
+            vec![
+                Some(b.iri("http://www.example.com/so")),
+                Some(b.iri("http://www.example.com/amo"))
+            ]
+        );
+        assert_eq!(
+            onts.iter().map(|o| o.iter().count()).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+
+        let b_decl = DeclareClass(b.class("http://www.example.com/b")).into();
+        assert!(!onts[0].contains(&b_decl));
+        assert!(onts[1].contains(&b_decl));
+    }
+}
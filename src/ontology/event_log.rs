@@ -0,0 +1,138 @@
+//! A record of the changes made to a `MutableOntology`
+
+//! # Overview
+//!
+//! An `EventLoggingOntology` owns any
+//! [`MutableOntology`](../../model/trait.MutableOntology.html), and
+//! appends each insertion or removal made through it to a log, in
+//! order. The log can be read with `changes`, for instance to send
+//! the changes to another copy of the ontology, and emptied with
+//! `clear_log`.
+//!
+//! As with a [`Transaction`](../transaction/struct.Transaction.html),
+//! only changes which actually alter the ontology are recorded.
+use std::ops::Deref;
+
+use crate::model::*;
+
+/// A single change to an ontology.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Change<A> {
+    Insert(AnnotatedAxiom<A>),
+    Remove(AnnotatedAxiom<A>),
+}
+
+/// An ontology which logs the changes made to it.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// # use horned_owl::ontology::set::SetOntology;
+/// # use horned_owl::ontology::event_log::{Change, EventLoggingOntology};
+/// let b = Build::new_rc();
+/// let mut o = EventLoggingOntology::new(SetOntology::new_rc());
+/// let decl: AnnotatedAxiom<_> = DeclareClass(b.class("http://www.example.com/a")).into();
+/// o.insert(decl.clone());
+///
+/// assert_eq!(o.changes(), &[Change::Insert(decl)]);
+/// assert_eq!(o.len(), 1);
+/// ```
+#[derive(Debug, Default)]
+pub struct EventLoggingOntology<A, O> {
+    ontology: O,
+    changes: Vec<Change<A>>,
+}
+
+impl<A: ForIRI, O: MutableOntology<A>> EventLoggingOntology<A, O> {
+    pub fn new(ontology: O) -> EventLoggingOntology<A, O> {
+        EventLoggingOntology {
+            ontology,
+            changes: vec![],
+        }
+    }
+
+    /// Return the changes made since this ontology was created or
+    /// the log last cleared, oldest first.
+    pub fn changes(&self) -> &[Change<A>] {
+        &self.changes
+    }
+
+    /// Empty the log, leaving the ontology as it is.
+    pub fn clear_log(&mut self) {
+        self.changes.clear();
+    }
+
+    /// Return the wrapped ontology, discarding the log.
+    pub fn into_inner(self) -> O {
+        self.ontology
+    }
+}
+
+impl<A, O> Deref for EventLoggingOntology<A, O> {
+    type Target = O;
+
+    fn deref(&self) -> &O {
+        &self.ontology
+    }
+}
+
+impl<A: ForIRI, O: MutableOntology<A>> MutableOntology<A> for EventLoggingOntology<A, O> {
+    fn insert<AA>(&mut self, ax: AA) -> bool
+    where
+        AA: Into<AnnotatedAxiom<A>>,
+    {
+        let ax = ax.into();
+        let inserted = self.ontology.insert(ax.clone());
+        if inserted {
+            self.changes.push(Change::Insert(ax));
+        }
+        inserted
+    }
+
+    fn take(&mut self, ax: &AnnotatedAxiom<A>) -> Option<AnnotatedAxiom<A>> {
+        let taken = self.ontology.take(ax);
+        if let Some(ax) = &taken {
+            self.changes.push(Change::Remove(ax.clone()));
+        }
+        taken
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ontology::set::SetOntology;
+
+    #[test]
+    fn test_changes() {
+        let b = Build::new_rc();
+        let decl = |s: &str| -> AnnotatedAxiom<_> {
+            DeclareClass(b.class(format!("http://www.example.com/{}", s))).into()
+        };
+
+        let mut o = EventLoggingOntology::new(SetOntology::new_rc());
+        o.insert(decl("a"));
+        o.insert(decl("b"));
+        // Already present, so not recorded
+        o.insert(decl("a"));
+        o.remove(&decl("a"));
+        // Absent, so not recorded
+        o.remove(&decl("x"));
+        o.insert(decl("c"));
+
+        assert_eq!(
+            o.changes(),
+            &[
+                Change::Insert(decl("a")),
+                Change::Insert(decl("b")),
+                Change::Remove(decl("a")),
+                Change::Insert(decl("c")),
+            ]
+        );
+        assert_eq!(o.len(), 2);
+
+        o.clear_log();
+        assert!(o.changes().is_empty());
+        assert_eq!(o.into_inner().len(), 2);
+    }
+}
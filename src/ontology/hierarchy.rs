@@ -0,0 +1,125 @@
+//! Asserted hierarchies between named entities
+
+//! # Overview
+//!
+//! A `HierarchyView` is a snapshot of the direct sub/super
+//! relationships asserted between named entities, such as that
+//! returned by
+//! [`SetOntology::sub_property_hierarchy`](../set/struct.SetOntology.html#method.sub_property_hierarchy).
+//! It supports both direct queries and their transitive closures.
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::model::*;
+
+/// The direct sub and super relationships between named entities.
+#[derive(Clone, Debug)]
+pub struct HierarchyView<A> {
+    sub: HashMap<NamedEntity<A>, HashSet<NamedEntity<A>>>,
+    sup: HashMap<NamedEntity<A>, HashSet<NamedEntity<A>>>,
+}
+
+impl<A: ForIRI> Default for HierarchyView<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: ForIRI> HierarchyView<A> {
+    pub fn new() -> HierarchyView<A> {
+        HierarchyView {
+            sub: HashMap::new(),
+            sup: HashMap::new(),
+        }
+    }
+
+    /// Add an edge stating that `sub` is directly below `sup`.
+    ///
+    /// Returns false if the edge was already present.
+    pub fn insert(&mut self, sub: NamedEntity<A>, sup: NamedEntity<A>) -> bool {
+        self.sub.entry(sup.clone()).or_default().insert(sub.clone());
+        self.sup.entry(sub).or_default().insert(sup)
+    }
+
+    /// Return the number of edges in the hierarchy.
+    pub fn len(&self) -> usize {
+        self.sup.values().map(HashSet::len).sum()
+    }
+
+    /// Return true if the hierarchy has no edges.
+    pub fn is_empty(&self) -> bool {
+        self.sup.is_empty()
+    }
+
+    /// Return the entities directly below `e`.
+    pub fn direct_sub_properties(&self, e: &NamedEntity<A>) -> HashSet<NamedEntity<A>> {
+        self.sub.get(e).cloned().unwrap_or_default()
+    }
+
+    /// Return the entities directly above `e`.
+    pub fn direct_super_properties(&self, e: &NamedEntity<A>) -> HashSet<NamedEntity<A>> {
+        self.sup.get(e).cloned().unwrap_or_default()
+    }
+
+    /// Return all of the entities below `e`.
+    ///
+    /// `e` itself is only included if it is part of a cycle.
+    pub fn sub_properties(&self, e: &NamedEntity<A>) -> HashSet<NamedEntity<A>> {
+        closure(&self.sub, e)
+    }
+
+    /// Return all of the entities above `e`.
+    ///
+    /// `e` itself is only included if it is part of a cycle.
+    pub fn super_properties(&self, e: &NamedEntity<A>) -> HashSet<NamedEntity<A>> {
+        closure(&self.sup, e)
+    }
+}
+
+fn closure<A: ForIRI>(
+    edges: &HashMap<NamedEntity<A>, HashSet<NamedEntity<A>>>,
+    e: &NamedEntity<A>,
+) -> HashSet<NamedEntity<A>> {
+    let mut seen = HashSet::new();
+    let mut queue: VecDeque<&NamedEntity<A>> = VecDeque::new();
+    queue.push_back(e);
+
+    while let Some(next) = queue.pop_front() {
+        for n in edges.get(next).into_iter().flatten() {
+            if seen.insert(n.clone()) {
+                queue.push_back(n);
+            }
+        }
+    }
+
+    seen
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_closure_with_cycle() {
+        let b = Build::new_rc();
+        let p: NamedEntity<_> = b.object_property("http://www.example.com/p").into();
+        let q: NamedEntity<_> = b.object_property("http://www.example.com/q").into();
+        let r: NamedEntity<_> = b.object_property("http://www.example.com/r").into();
+
+        let mut h = HierarchyView::new();
+        assert!(h.is_empty());
+        assert!(h.insert(p.clone(), q.clone()));
+        assert!(!h.insert(p.clone(), q.clone()));
+        h.insert(q.clone(), r.clone());
+        assert_eq!(h.len(), 2);
+
+        assert_eq!(
+            h.super_properties(&p),
+            vec![q.clone(), r.clone()].into_iter().collect()
+        );
+        assert!(!h.super_properties(&p).contains(&p));
+
+        h.insert(r.clone(), p.clone());
+        assert!(h.super_properties(&p).contains(&p));
+        assert_eq!(h.sub_properties(&q).len(), 3);
+    }
+}
@@ -18,7 +18,10 @@
 //! and `ThreeIndexedOntology`, each of which operate something like a
 //! named tuple, allowing differently typed `OntologyIndex` objects to
 //! be added.
-use crate::model::{AnnotatedAxiom, ArcStr, ForIRI, MutableOntology, Ontology, OntologyID, IRI, RcStr};
+use crate::model::{
+    AnnotatedAxiom, ArcAnnotatedAxiom, ArcStr, Build, ForIRI, MutableOntology, Ontology,
+    OntologyID, RcAnnotatedAxiom, RcStr, Rebuild, IRI,
+};
 use std::borrow::Borrow;
 use std::fmt::Debug;
 use std::hash::Hash;
@@ -26,6 +29,37 @@ use std::marker::PhantomData;
 use std::rc::Rc;
 use std::sync::Arc;
 
+/// The reference counting behaviour of a `ForIndex` type.
+///
+/// This is implemented for `Rc` and `Arc`, which report their strong
+/// count, and for `AnnotatedAxiom` which is never shared. It is
+/// separate from `ForIndex`, so that other `ForIndex` types need
+/// not implement it.
+pub trait RefCounted {
+    /// Return the number of strong references to this axiom.
+    ///
+    /// For an axiom shared between the indexes of an ontology, this
+    /// is the number of indexes holding it, plus any other
+    /// references held elsewhere.
+    fn strong_count(&self) -> usize {
+        1
+    }
+}
+
+impl<A: ForIRI> RefCounted for AnnotatedAxiom<A> {}
+
+impl<T: ?Sized> RefCounted for Rc<T> {
+    fn strong_count(&self) -> usize {
+        Rc::strong_count(self)
+    }
+}
+
+impl<T: ?Sized> RefCounted for Arc<T> {
+    fn strong_count(&self) -> usize {
+        Arc::strong_count(self)
+    }
+}
+
 pub trait ForIndex<A: ForIRI>:
     Borrow<AnnotatedAxiom<A>>
     + Clone
@@ -108,10 +142,121 @@ impl<A: ForIRI, AA: ForIndex<A>> OntologyIndex<A, AA> for NullIndex {
     }
 }
 
+impl<B: ForIRI, BB: ForIndex<B>> ReindexInto<B, BB> for NullIndex {
+    type Output = NullIndex;
+
+    fn empty_index(&self) -> NullIndex {
+        NullIndex()
+    }
+}
+
+/// An `OntologyIndex` which can be recreated for a different IRI
+/// and axiom type.
+///
+/// This is used to rebuild an indexed ontology with a different
+/// backing store, for example with `OneIndexedOntology::into_arc`.
+pub trait ReindexInto<B: ForIRI, BB: ForIndex<B>> {
+    /// The same kind of index for `B` and `BB`.
+    type Output: OntologyIndex<B, BB>;
+
+    /// Return a new, empty index of the same kind.
+    fn empty_index(&self) -> Self::Output;
+}
+
+/// The operation reported by an `ObservingIndex`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IndexOperation {
+    Insert,
+    Remove,
+}
+
+/// An `OntologyIndex` which forwards to another `OntologyIndex`,
+/// calling a function after each insertion or removal.
+///
+/// The function is passed the operation, the axiom and the return
+/// value of the inner index.
+pub struct ObservingIndex<A, AA, I, F>(I, F, PhantomData<A>, PhantomData<AA>);
+
+impl<A: ForIRI, AA: ForIndex<A>, I: OntologyIndex<A, AA>, F> ObservingIndex<A, AA, I, F>
+where
+    F: FnMut(IndexOperation, &AnnotatedAxiom<A>, bool),
+{
+    pub fn new(i: I, f: F) -> Self {
+        ObservingIndex(i, f, Default::default(), Default::default())
+    }
+
+    pub fn i(&self) -> &I {
+        &self.0
+    }
+
+    pub fn index(self) -> I {
+        self.0
+    }
+}
+
+impl<A: ForIRI, AA: ForIndex<A>, I: OntologyIndex<A, AA>, F> OntologyIndex<A, AA>
+    for ObservingIndex<A, AA, I, F>
+where
+    F: FnMut(IndexOperation, &AnnotatedAxiom<A>, bool),
+{
+    fn index_insert(&mut self, ax: AA) -> bool {
+        let rtn = self.0.index_insert(ax.clone());
+        (self.1)(IndexOperation::Insert, ax.borrow(), rtn);
+        rtn
+    }
+
+    fn index_remove(&mut self, ax: &AnnotatedAxiom<A>) -> bool {
+        let rtn = self.0.index_remove(ax);
+        (self.1)(IndexOperation::Remove, ax, rtn);
+        rtn
+    }
+
+    fn index_take(&mut self, ax: &AnnotatedAxiom<A>) -> Option<AnnotatedAxiom<A>> {
+        let rtn = self.0.index_take(ax);
+        (self.1)(IndexOperation::Remove, ax, rtn.is_some());
+        rtn
+    }
+}
+
+/// A counter of the changes made to an ontology.
+///
+/// This is not part of the content of an ontology, so all revisions
+/// compare as equal.
+#[derive(Clone, Copy, Debug, Default)]
+struct Revision(u64);
+
+impl Revision {
+    fn bump(&mut self, changed: bool) {
+        if changed {
+            self.0 += 1;
+        }
+    }
+}
+
+impl PartialEq for Revision {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for Revision {}
+
 /// A `OneIndexedOntology` operates as a simple adaptor betweeen any
 /// `OntologyIndex` and an `Ontology`.
 #[derive(Default, Debug, Eq, PartialEq)]
-pub struct OneIndexedOntology<A, AA, I>(I, OntologyID<A>, Option<IRI<A>>, PhantomData<AA>);
+pub struct OneIndexedOntology<A, AA, I>(
+    I,
+    OntologyID<A>,
+    Option<IRI<A>>,
+    PhantomData<AA>,
+    Revision,
+);
+
+/// A `OneIndexedOntology` sharing axioms with `Rc`.
+pub type RcOneIndexedOntology<I> = OneIndexedOntology<RcStr, RcAnnotatedAxiom, I>;
+
+/// A `OneIndexedOntology` sharing axioms with `Arc`.
+pub type ArcOneIndexedOntology<I> = OneIndexedOntology<ArcStr, ArcAnnotatedAxiom, I>;
 
 impl<A: ForIRI, AA: ForIndex<A>, I: Clone> Clone for OneIndexedOntology<A, AA, I> {
     fn clone(&self) -> Self {
@@ -120,6 +265,7 @@ impl<A: ForIRI, AA: ForIndex<A>, I: Clone> Clone for OneIndexedOntology<A, AA, I
             self.1.clone(),
             self.2.clone(),
             Default::default(),
+            self.4,
         )
     }
 }
@@ -131,6 +277,7 @@ impl<A: ForIRI, AA: ForIndex<A>, I: OntologyIndex<A, AA>> OneIndexedOntology<A,
             Default::default(),
             Default::default(),
             Default::default(),
+            Default::default(),
         )
     }
 
@@ -141,6 +288,112 @@ impl<A: ForIRI, AA: ForIndex<A>, I: OntologyIndex<A, AA>> OneIndexedOntology<A,
     pub fn index(self) -> I {
         self.0
     }
+
+    /// Return the number of successful changes made to this ontology
+    /// through `MutableOntology`.
+    ///
+    /// This increases every time an axiom is inserted or removed;
+    /// duplicate inserts and removals of absent axioms do not change
+    /// it. It can be used to check whether data derived from the
+    /// ontology needs to be recalculated.
+    pub fn revision(&self) -> u64 {
+        (self.4).0
+    }
+
+    /// Move all axioms from this ontology into a new index.
+    ///
+    /// The current index is drained and every `AnnotatedAxiom` is
+    /// inserted into a freshly created `J`. The `OntologyID` and
+    /// document IRI are carried across. This allows a cheap index,
+    /// such as `SetIndex`, to be used while reading, with more
+    /// specialised indexes being created afterwards.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::axiom_mapped::AxiomMappedIndex;
+    /// # use horned_owl::ontology::indexed::OneIndexedOntology;
+    /// # use horned_owl::ontology::set::SetIndex;
+    /// let b = Build::new_rc();
+    /// let mut o = OneIndexedOntology::new_rc(SetIndex::new());
+    /// o.declare(b.class("http://www.example.com/a"));
+    ///
+    /// let o: OneIndexedOntology<_, _, AxiomMappedIndex<_, _>> = o.reindex();
+    /// assert_eq!(o.i().declare_class().count(), 1);
+    /// ```
+    pub fn reindex<J>(self) -> OneIndexedOntology<A, AA, J>
+    where
+        I: IntoIterator<Item = AnnotatedAxiom<A>>,
+        J: OntologyIndex<A, AA> + Default,
+    {
+        let OneIndexedOntology(i, id, doc_iri, _, revision) = self;
+        let mut j = J::default();
+        for ax in i {
+            j.index_insert(ax.into());
+        }
+
+        OneIndexedOntology(j, id, doc_iri, Default::default(), revision)
+    }
+
+    /// Rebuild this ontology with a different IRI and axiom type.
+    ///
+    /// Each axiom is rebuilt once with `b`, and the result is
+    /// inserted into every index of the new ontology, so that it is
+    /// shared between them just as it is here. The axioms are taken
+    /// from the first index, which must contain all of them. The
+    /// `OntologyID` and document IRI are carried across.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::indexed::OneIndexedOntology;
+    /// # use horned_owl::ontology::set::SetIndex;
+    /// let b = Build::new_rc();
+    /// let mut o = OneIndexedOntology::new_rc(SetIndex::new());
+    /// o.declare(b.class("http://www.example.com/a"));
+    ///
+    /// let o = o.into_arc();
+    /// let arc = Build::new_arc();
+    /// assert!(o.i().contains(&DeclareClass(arc.class("http://www.example.com/a")).into()));
+    /// ```
+    pub fn rebuild<B: ForIRI, BB: ForIndex<B>>(
+        &self,
+        b: &Build<B>,
+    ) -> OneIndexedOntology<B, BB, I::Output>
+    where
+        I: ReindexInto<B, BB>,
+        for<'a> &'a I: IntoIterator<Item = &'a AnnotatedAxiom<A>>,
+    {
+        let mut o = OneIndexedOntology::new(self.i().empty_index());
+        *o.mut_id() = self.id().rebuild(b);
+        *o.mut_doc_iri() = self.doc_iri().rebuild(b);
+        for ax in self.i() {
+            o.insert(ax.rebuild(b));
+        }
+        o
+    }
+
+    /// Rebuild this ontology using `ArcStr` and `ArcAnnotatedAxiom`.
+    ///
+    /// See `rebuild`.
+    pub fn into_arc(self) -> OneIndexedOntology<ArcStr, ArcAnnotatedAxiom, I::Output>
+    where
+        I: ReindexInto<ArcStr, ArcAnnotatedAxiom>,
+        for<'a> &'a I: IntoIterator<Item = &'a AnnotatedAxiom<A>>,
+    {
+        self.rebuild(&Build::new_arc())
+    }
+
+    /// Rebuild this ontology using `RcStr` and `RcAnnotatedAxiom`.
+    ///
+    /// See `rebuild`.
+    pub fn into_rc(self) -> OneIndexedOntology<RcStr, RcAnnotatedAxiom, I::Output>
+    where
+        I: ReindexInto<RcStr, RcAnnotatedAxiom>,
+        for<'a> &'a I: IntoIterator<Item = &'a AnnotatedAxiom<A>>,
+    {
+        self.rebuild(&Build::new_rc())
+    }
 }
 
 impl<I> OneIndexedOntology<RcStr, Rc<AnnotatedAxiom<RcStr>>, I>
@@ -186,11 +439,15 @@ impl<A: ForIRI, AA: ForIndex<A>, I: OntologyIndex<A, AA>> MutableOntology<A>
 {
     fn insert<IAA: Into<AnnotatedAxiom<A>>>(&mut self, ax: IAA) -> bool {
         let ax = ax.into();
-        self.0.index_insert(ax.into())
+        let rtn = self.0.index_insert(ax.into());
+        self.4.bump(rtn);
+        rtn
     }
 
     fn take(&mut self, ax: &AnnotatedAxiom<A>) -> Option<AnnotatedAxiom<A>> {
-        self.0.index_take(ax)
+        let rtn = self.0.index_take(ax);
+        self.4.bump(rtn.is_some());
+        rtn
     }
 }
 
@@ -203,13 +460,33 @@ pub struct TwoIndexedOntology<
     AA: ForIndex<A>,
     I: OntologyIndex<A, AA>,
     J: OntologyIndex<A, AA>,
->(I, J, OntologyID<A>, Option<IRI<A>>, PhantomData<AA>);
+>(
+    I,
+    J,
+    OntologyID<A>,
+    Option<IRI<A>>,
+    PhantomData<AA>,
+    Revision,
+);
+
+/// A `TwoIndexedOntology` sharing axioms with `Rc`.
+pub type RcTwoIndexedOntology<I, J> = TwoIndexedOntology<RcStr, RcAnnotatedAxiom, I, J>;
+
+/// A `TwoIndexedOntology` sharing axioms with `Arc`.
+pub type ArcTwoIndexedOntology<I, J> = TwoIndexedOntology<ArcStr, ArcAnnotatedAxiom, I, J>;
 
 impl<A: ForIRI, AA: ForIndex<A>, I: OntologyIndex<A, AA>, J: OntologyIndex<A, AA>>
     TwoIndexedOntology<A, AA, I, J>
 {
     pub fn new(i: I, j: J, id: OntologyID<A>) -> Self {
-        TwoIndexedOntology(i, j, id, Default::default(), Default::default())
+        TwoIndexedOntology(
+            i,
+            j,
+            id,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+        )
     }
 
     pub fn i(&self) -> &I {
@@ -223,6 +500,57 @@ impl<A: ForIRI, AA: ForIndex<A>, I: OntologyIndex<A, AA>, J: OntologyIndex<A, AA
     pub fn index(self) -> (I, J) {
         (self.0, self.1)
     }
+
+    /// Return the number of successful changes made to this ontology
+    /// through `MutableOntology`.
+    ///
+    /// See `OneIndexedOntology::revision`.
+    pub fn revision(&self) -> u64 {
+        (self.5).0
+    }
+
+    /// Rebuild every index of this ontology, as `OneIndexedOntology::rebuild`.
+    pub fn rebuild<B: ForIRI, BB: ForIndex<B>>(
+        &self,
+        b: &Build<B>,
+    ) -> TwoIndexedOntology<B, BB, I::Output, J::Output>
+    where
+        I: ReindexInto<B, BB>,
+        J: ReindexInto<B, BB>,
+        for<'a> &'a I: IntoIterator<Item = &'a AnnotatedAxiom<A>>,
+    {
+        let mut o = TwoIndexedOntology::new(
+            self.i().empty_index(),
+            self.j().empty_index(),
+            Default::default(),
+        );
+        *o.mut_id() = self.id().rebuild(b);
+        *o.mut_doc_iri() = self.doc_iri().rebuild(b);
+        for ax in self.i() {
+            o.insert(ax.rebuild(b));
+        }
+        o
+    }
+
+    /// As `OneIndexedOntology::into_arc`.
+    pub fn into_arc(self) -> TwoIndexedOntology<ArcStr, ArcAnnotatedAxiom, I::Output, J::Output>
+    where
+        I: ReindexInto<ArcStr, ArcAnnotatedAxiom>,
+        J: ReindexInto<ArcStr, ArcAnnotatedAxiom>,
+        for<'a> &'a I: IntoIterator<Item = &'a AnnotatedAxiom<A>>,
+    {
+        self.rebuild(&Build::new_arc())
+    }
+
+    /// As `OneIndexedOntology::into_rc`.
+    pub fn into_rc(self) -> TwoIndexedOntology<RcStr, RcAnnotatedAxiom, I::Output, J::Output>
+    where
+        I: ReindexInto<RcStr, RcAnnotatedAxiom>,
+        J: ReindexInto<RcStr, RcAnnotatedAxiom>,
+        for<'a> &'a I: IntoIterator<Item = &'a AnnotatedAxiom<A>>,
+    {
+        self.rebuild(&Build::new_rc())
+    }
 }
 
 impl<A: ForIRI, AA: ForIndex<A>, I: OntologyIndex<A, AA>, J: OntologyIndex<A, AA>> Ontology<A>
@@ -250,11 +578,15 @@ impl<A: ForIRI, AA: ForIndex<A>, I: OntologyIndex<A, AA>, J: OntologyIndex<A, AA
 {
     fn insert<IAA: Into<AnnotatedAxiom<A>>>(&mut self, ax: IAA) -> bool {
         let ax = ax.into();
-        self.index_insert(ax.into())
+        let rtn = self.index_insert(ax.into());
+        self.5.bump(rtn);
+        rtn
     }
 
     fn take(&mut self, ax: &AnnotatedAxiom<A>) -> Option<AnnotatedAxiom<A>> {
-        self.index_take(ax)
+        let rtn = self.index_take(ax);
+        self.5.bump(rtn.is_some());
+        rtn
     }
 }
 
@@ -284,6 +616,13 @@ pub struct ThreeIndexedOntology<
     K: OntologyIndex<A, AA>,
 >(TwoIndexedOntology<A, AA, I, TwoIndexedOntology<A, AA, J, K>>);
 
+/// A `ThreeIndexedOntology` sharing axioms with `Rc`.
+pub type RcThreeIndexedOntology<I, J, K> = ThreeIndexedOntology<RcStr, RcAnnotatedAxiom, I, J, K>;
+
+/// A `ThreeIndexedOntology` sharing axioms with `Arc`.
+pub type ArcThreeIndexedOntology<I, J, K> =
+    ThreeIndexedOntology<ArcStr, ArcAnnotatedAxiom, I, J, K>;
+
 impl<
         A: ForIRI,
         AA: ForIndex<A>,
@@ -293,18 +632,10 @@ impl<
     > ThreeIndexedOntology<A, AA, I, J, K>
 {
     pub fn new(i: I, j: J, k: K, id: OntologyID<A>) -> Self {
-        ThreeIndexedOntology(TwoIndexedOntology(
+        ThreeIndexedOntology(TwoIndexedOntology::new(
             i,
-            TwoIndexedOntology(
-                j,
-                k,
-                Default::default(),
-                Default::default(),
-                Default::default(),
-            ),
+            TwoIndexedOntology::new(j, k, Default::default()),
             id,
-            Default::default(),
-            Default::default(),
         ))
     }
 
@@ -324,6 +655,65 @@ impl<
         let index = (self.0).1.index();
         ((self.0).0, index.0, index.1)
     }
+
+    /// Return the number of successful changes made to this ontology
+    /// through `MutableOntology`.
+    ///
+    /// See `OneIndexedOntology::revision`.
+    pub fn revision(&self) -> u64 {
+        self.0.revision()
+    }
+
+    /// Rebuild every index of this ontology, as `OneIndexedOntology::rebuild`.
+    pub fn rebuild<B: ForIRI, BB: ForIndex<B>>(
+        &self,
+        b: &Build<B>,
+    ) -> ThreeIndexedOntology<B, BB, I::Output, J::Output, K::Output>
+    where
+        I: ReindexInto<B, BB>,
+        J: ReindexInto<B, BB>,
+        K: ReindexInto<B, BB>,
+        for<'a> &'a I: IntoIterator<Item = &'a AnnotatedAxiom<A>>,
+    {
+        let mut o = ThreeIndexedOntology::new(
+            self.i().empty_index(),
+            self.j().empty_index(),
+            self.k().empty_index(),
+            Default::default(),
+        );
+        *o.mut_id() = self.id().rebuild(b);
+        *o.mut_doc_iri() = self.doc_iri().rebuild(b);
+        for ax in self.i() {
+            o.insert(ax.rebuild(b));
+        }
+        o
+    }
+
+    /// As `OneIndexedOntology::into_arc`.
+    pub fn into_arc(
+        self,
+    ) -> ThreeIndexedOntology<ArcStr, ArcAnnotatedAxiom, I::Output, J::Output, K::Output>
+    where
+        I: ReindexInto<ArcStr, ArcAnnotatedAxiom>,
+        J: ReindexInto<ArcStr, ArcAnnotatedAxiom>,
+        K: ReindexInto<ArcStr, ArcAnnotatedAxiom>,
+        for<'a> &'a I: IntoIterator<Item = &'a AnnotatedAxiom<A>>,
+    {
+        self.rebuild(&Build::new_arc())
+    }
+
+    /// As `OneIndexedOntology::into_rc`.
+    pub fn into_rc(
+        self,
+    ) -> ThreeIndexedOntology<RcStr, RcAnnotatedAxiom, I::Output, J::Output, K::Output>
+    where
+        I: ReindexInto<RcStr, RcAnnotatedAxiom>,
+        J: ReindexInto<RcStr, RcAnnotatedAxiom>,
+        K: ReindexInto<RcStr, RcAnnotatedAxiom>,
+        for<'a> &'a I: IntoIterator<Item = &'a AnnotatedAxiom<A>>,
+    {
+        self.rebuild(&Build::new_rc())
+    }
 }
 
 impl<
@@ -400,6 +790,14 @@ pub struct FourIndexedOntology<
     L: OntologyIndex<A, AA>,
 >(TwoIndexedOntology<A, AA, I, ThreeIndexedOntology<A, AA, J, K, L>>);
 
+/// A `FourIndexedOntology` sharing axioms with `Rc`.
+pub type RcFourIndexedOntology<I, J, K, L> =
+    FourIndexedOntology<RcStr, RcAnnotatedAxiom, I, J, K, L>;
+
+/// A `FourIndexedOntology` sharing axioms with `Arc`.
+pub type ArcFourIndexedOntology<I, J, K, L> =
+    FourIndexedOntology<ArcStr, ArcAnnotatedAxiom, I, J, K, L>;
+
 impl<
         A: ForIRI,
         AA: ForIndex<A>,
@@ -410,12 +808,10 @@ impl<
     > FourIndexedOntology<A, AA, I, J, K, L>
 {
     pub fn new(i: I, j: J, k: K, l: L, id: OntologyID<A>) -> Self {
-        FourIndexedOntology(TwoIndexedOntology(
+        FourIndexedOntology(TwoIndexedOntology::new(
             i,
             ThreeIndexedOntology::new(j, k, l, Default::default()),
             id,
-            Default::default(),
-            Default::default(),
         ))
     }
 
@@ -439,6 +835,69 @@ impl<
         let index = (self.0).1.index();
         ((self.0).0, index.0, index.1, index.2)
     }
+
+    /// Return the number of successful changes made to this ontology
+    /// through `MutableOntology`.
+    ///
+    /// See `OneIndexedOntology::revision`.
+    pub fn revision(&self) -> u64 {
+        self.0.revision()
+    }
+
+    /// Rebuild every index of this ontology, as `OneIndexedOntology::rebuild`.
+    pub fn rebuild<B: ForIRI, BB: ForIndex<B>>(
+        &self,
+        b: &Build<B>,
+    ) -> FourIndexedOntology<B, BB, I::Output, J::Output, K::Output, L::Output>
+    where
+        I: ReindexInto<B, BB>,
+        J: ReindexInto<B, BB>,
+        K: ReindexInto<B, BB>,
+        L: ReindexInto<B, BB>,
+        for<'a> &'a I: IntoIterator<Item = &'a AnnotatedAxiom<A>>,
+    {
+        let mut o = FourIndexedOntology::new(
+            self.i().empty_index(),
+            self.j().empty_index(),
+            self.k().empty_index(),
+            self.l().empty_index(),
+            Default::default(),
+        );
+        *o.mut_id() = self.id().rebuild(b);
+        *o.mut_doc_iri() = self.doc_iri().rebuild(b);
+        for ax in self.i() {
+            o.insert(ax.rebuild(b));
+        }
+        o
+    }
+
+    /// As `OneIndexedOntology::into_arc`.
+    pub fn into_arc(
+        self,
+    ) -> FourIndexedOntology<ArcStr, ArcAnnotatedAxiom, I::Output, J::Output, K::Output, L::Output>
+    where
+        I: ReindexInto<ArcStr, ArcAnnotatedAxiom>,
+        J: ReindexInto<ArcStr, ArcAnnotatedAxiom>,
+        K: ReindexInto<ArcStr, ArcAnnotatedAxiom>,
+        L: ReindexInto<ArcStr, ArcAnnotatedAxiom>,
+        for<'a> &'a I: IntoIterator<Item = &'a AnnotatedAxiom<A>>,
+    {
+        self.rebuild(&Build::new_arc())
+    }
+
+    /// As `OneIndexedOntology::into_rc`.
+    pub fn into_rc(
+        self,
+    ) -> FourIndexedOntology<RcStr, RcAnnotatedAxiom, I::Output, J::Output, K::Output, L::Output>
+    where
+        I: ReindexInto<RcStr, RcAnnotatedAxiom>,
+        J: ReindexInto<RcStr, RcAnnotatedAxiom>,
+        K: ReindexInto<RcStr, RcAnnotatedAxiom>,
+        L: ReindexInto<RcStr, RcAnnotatedAxiom>,
+        for<'a> &'a I: IntoIterator<Item = &'a AnnotatedAxiom<A>>,
+    {
+        self.rebuild(&Build::new_rc())
+    }
 }
 
 impl<
@@ -489,13 +948,18 @@ impl<
 mod test {
 
     use super::{
-        FourIndexedOntology, NullIndex, OneIndexedOntology, ThreeIndexedOntology,
-        TwoIndexedOntology,
+        FourIndexedOntology, IndexOperation, NullIndex, ObservingIndex, OneIndexedOntology,
+        OntologyIndex, RefCounted, ThreeIndexedOntology, TwoIndexedOntology,
     };
     use crate::{
-        model::{AnnotatedAxiom, Build, MutableOntology, NamedEntity, RcStr},
-        ontology::set::SetIndex,
+        model::{
+            AnnotatedAxiom, ArcAnnotatedAxiom, ArcStr, Build, DeclareClass, MutableOntology,
+            NamedEntity, Ontology, OntologyID, RcStr,
+        },
+        ontology::{axiom_mapped::AxiomMappedIndex, set::SetIndex},
     };
+    use std::collections::HashSet;
+    use std::rc::Rc;
 
     fn stuff() -> (
         AnnotatedAxiom<RcStr>,
@@ -546,6 +1010,53 @@ mod test {
         assert!(!o.remove(&e.2));
     }
 
+    #[test]
+    fn one_reindex() {
+        let mut o = OneIndexedOntology::new_rc(SetIndex::new());
+        let e = stuff();
+        o.insert(e.0.clone());
+        o.insert(e.1.clone());
+        o.insert(e.2.clone());
+        o.mut_id().iri = Some(Build::new().iri("http://www.example.com/o"));
+
+        let mut before: Vec<_> = o.i().into_iter().cloned().collect();
+        before.sort();
+
+        let o: OneIndexedOntology<_, _, AxiomMappedIndex<_, _>> = o.reindex();
+        let mut after: Vec<_> = o.i().iter().cloned().collect();
+        after.sort();
+
+        assert_eq!(before, after);
+        assert_eq!(o.i().declare_class().count(), 1);
+        assert_eq!(
+            o.id().iri,
+            Some(Build::new().iri("http://www.example.com/o"))
+        );
+    }
+
+    #[test]
+    fn one_revision() {
+        let mut o = OneIndexedOntology::new_rc(SetIndex::new());
+        let e = stuff();
+        assert_eq!(o.revision(), 0);
+
+        o.insert(e.0.clone());
+        o.insert(e.1.clone());
+        assert_eq!(o.revision(), 2);
+
+        o.insert(e.0.clone());
+        assert_eq!(o.revision(), 2);
+
+        o.remove(&e.0);
+        o.remove(&e.0);
+        o.remove(&e.2);
+        assert_eq!(o.revision(), 3);
+
+        let mut p = OneIndexedOntology::new_rc(SetIndex::new());
+        p.insert(e.1);
+        assert_eq!(o, p);
+    }
+
     #[test]
     fn two_cons() {
         let _o = TwoIndexedOntology::new(SetIndex::new_rc(), SetIndex::new(), Default::default());
@@ -556,6 +1067,69 @@ mod test {
         assert!(true);
     }
 
+    #[derive(Default)]
+    struct VecIndex(Vec<Rc<AnnotatedAxiom<RcStr>>>);
+
+    impl OntologyIndex<RcStr, Rc<AnnotatedAxiom<RcStr>>> for VecIndex {
+        fn index_insert(&mut self, ax: Rc<AnnotatedAxiom<RcStr>>) -> bool {
+            self.0.push(ax);
+            true
+        }
+
+        fn index_remove(&mut self, ax: &AnnotatedAxiom<RcStr>) -> bool {
+            let len = self.0.len();
+            self.0.retain(|a| &**a != ax);
+            len != self.0.len()
+        }
+    }
+
+    #[test]
+    fn two_strong_count() {
+        let mut o =
+            TwoIndexedOntology::new(SetIndex::new_rc(), VecIndex::default(), Default::default());
+        let e = stuff();
+        o.insert(e.0.clone());
+
+        assert_eq!(o.j().0[0].strong_count(), 2);
+        assert_eq!(e.0.strong_count(), 1);
+
+        o.remove(&e.0);
+        o.insert(e.1);
+        let (_, j) = o.index();
+        assert_eq!(j.0[0].strong_count(), 1);
+    }
+
+    #[test]
+    fn two_observing() {
+        let ops = std::cell::RefCell::new(vec![]);
+        let mut o = TwoIndexedOntology::new(
+            SetIndex::new_rc(),
+            ObservingIndex::new(SetIndex::new(), |op, _ax: &_, rtn| {
+                ops.borrow_mut().push((op, rtn))
+            }),
+            Default::default(),
+        );
+
+        let e = stuff();
+        o.insert(e.0.clone());
+        o.insert(e.0.clone());
+        o.insert(e.1);
+        o.remove(&e.0);
+        o.remove(&e.0);
+        drop(o);
+
+        assert_eq!(
+            ops.into_inner(),
+            vec![
+                (IndexOperation::Insert, true),
+                (IndexOperation::Insert, false),
+                (IndexOperation::Insert, true),
+                (IndexOperation::Remove, true),
+                (IndexOperation::Remove, false),
+            ]
+        );
+    }
+
     #[test]
     fn two_insert() {
         let mut o =
@@ -592,6 +1166,49 @@ mod test {
         assert_eq!(o.i(), o.j());
     }
 
+    #[test]
+    fn two_revision() {
+        let mut o =
+            TwoIndexedOntology::new(SetIndex::new_rc(), SetIndex::new(), Default::default());
+        let e = stuff();
+        o.insert(e.0.clone());
+        o.insert(e.0.clone());
+        assert_eq!(o.revision(), 1);
+
+        o.take(&e.0);
+        assert_eq!(o.revision(), 2);
+    }
+
+    #[test]
+    fn two_into_arc() {
+        let b = Build::new_rc();
+        let mut o = TwoIndexedOntology::new(
+            SetIndex::new_rc(),
+            AxiomMappedIndex::new(),
+            OntologyID {
+                iri: Some(b.iri("http://www.example.com/o")),
+                viri: None,
+            },
+        );
+        let e = stuff();
+        o.insert(e.0);
+        o.insert(e.1);
+        o.insert(e.2);
+
+        let o: TwoIndexedOntology<ArcStr, ArcAnnotatedAxiom, _, _> = o.into_arc();
+        let arc = Build::new_arc();
+        assert_eq!(o.id().iri, Some(arc.iri("http://www.example.com/o")));
+        assert_eq!(o.i().into_iter().count(), 3);
+        assert_eq!(
+            o.i().into_iter().collect::<HashSet<_>>(),
+            o.j().into_iter().collect::<HashSet<_>>()
+        );
+
+        let c: AnnotatedAxiom<ArcStr> = DeclareClass(arc.class("http://www.example.com/c")).into();
+        assert_eq!(o.i().get(&c).unwrap().strong_count(), 2);
+        assert_eq!(o.j().declare_class().count(), 1);
+    }
+
     #[test]
     fn three_remove() {
         let mut o = ThreeIndexedOntology::new(
@@ -649,4 +1266,22 @@ mod test {
         assert_eq!(o.i(), o.k());
         assert_eq!(o.i(), o.l());
     }
+
+    #[test]
+    fn aliases() {
+        use super::{ArcOneIndexedOntology, RcOneIndexedOntology, RcTwoIndexedOntology};
+
+        let e = stuff();
+        let mut o: RcOneIndexedOntology<SetIndex<_, _>> =
+            OneIndexedOntology::new_rc(SetIndex::new());
+        o.insert(e.0.clone());
+
+        let a: ArcOneIndexedOntology<SetIndex<_, _>> = OneIndexedOntology::new_arc(SetIndex::new());
+        assert!(a.i().is_empty());
+
+        let mut t: RcTwoIndexedOntology<SetIndex<_, _>, AxiomMappedIndex<_, _>> =
+            TwoIndexedOntology::new(SetIndex::new(), AxiomMappedIndex::new(), Default::default());
+        t.insert(e.0);
+        assert_eq!(o.i(), t.i());
+    }
 }
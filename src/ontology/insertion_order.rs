@@ -0,0 +1,165 @@
+//! An `OntologyIndex` which preserves the order of insertion
+
+//! # Overview
+//!
+//! The `InsertionOrderIndex` returns axioms in the order in which
+//! they were first inserted; when used with a parser, this is the
+//! order of the source document. This is useful for serialization
+//! which should be faithful to the original. Insertion is nearly as
+//! fast as `SetIndex`, but removal is linear in the size of the
+//! index.
+use std::{collections::HashSet, marker::PhantomData, rc::Rc};
+
+use super::indexed::{ForIndex, OntologyIndex, ReindexInto};
+use crate::model::*;
+
+/// An `OntologyIndex` which iterates over axioms in the order in
+/// which they were first inserted.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct InsertionOrderIndex<A: ForIRI, AA: ForIndex<A>>(Vec<AA>, HashSet<AA>, PhantomData<A>);
+
+impl<A: ForIRI, AA: ForIndex<A>> OntologyIndex<A, AA> for InsertionOrderIndex<A, AA> {
+    fn index_insert(&mut self, ax: AA) -> bool {
+        if self.1.insert(ax.clone()) {
+            self.0.push(ax);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn index_remove(&mut self, ax: &AnnotatedAxiom<A>) -> bool {
+        if self.1.remove(ax) {
+            if let Some(pos) = self.0.iter().position(|aa| aa.borrow() == ax) {
+                self.0.remove(pos);
+            }
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl<A: ForIRI, AA: ForIndex<A>, B: ForIRI, BB: ForIndex<B>> ReindexInto<B, BB>
+    for InsertionOrderIndex<A, AA>
+{
+    type Output = InsertionOrderIndex<B, BB>;
+
+    fn empty_index(&self) -> InsertionOrderIndex<B, BB> {
+        InsertionOrderIndex::new()
+    }
+}
+
+impl<A: ForIRI, AA: ForIndex<A>> InsertionOrderIndex<A, AA> {
+    pub fn new() -> InsertionOrderIndex<A, AA> {
+        InsertionOrderIndex(Default::default(), Default::default(), Default::default())
+    }
+
+    pub fn contains(&self, ax: &AnnotatedAxiom<A>) -> bool {
+        self.1.contains(ax)
+    }
+
+    /// Gets an iterator that visits the annotated axioms of the index
+    /// in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = &AnnotatedAxiom<A>> {
+        self.0.iter().map(|aa| aa.borrow())
+    }
+
+    /// Returns the number of axioms in the index.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if the index contains no axioms.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl InsertionOrderIndex<RcStr, Rc<AnnotatedAxiom<RcStr>>> {
+    pub fn new_rc() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: ForIRI, AA: ForIndex<A>> IntoIterator for InsertionOrderIndex<A, AA> {
+    type Item = AnnotatedAxiom<A>;
+    type IntoIter = std::vec::IntoIter<AnnotatedAxiom<A>>;
+    fn into_iter(self) -> Self::IntoIter {
+        #[allow(clippy::needless_collect)]
+        let v: Vec<AnnotatedAxiom<_>> = self.0.into_iter().map(|fi| fi.unwrap()).collect();
+        v.into_iter()
+    }
+}
+
+impl<'a, A: ForIRI, AA: ForIndex<A>> IntoIterator for &'a InsertionOrderIndex<A, AA> {
+    type Item = &'a AnnotatedAxiom<A>;
+    type IntoIter = std::vec::IntoIter<&'a AnnotatedAxiom<A>>;
+    fn into_iter(self) -> Self::IntoIter {
+        #[allow(clippy::needless_collect)]
+        let v: Vec<&'a AnnotatedAxiom<A>> = self.iter().collect();
+        v.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::InsertionOrderIndex;
+    use crate::{
+        model::*,
+        ontology::{indexed::TwoIndexedOntology, set::SetIndex},
+    };
+
+    fn classes(n: usize) -> Vec<AnnotatedAxiom<RcStr>> {
+        let b = Build::new_rc();
+        (0..n)
+            .map(|i| {
+                let ne: NamedEntity<_> = b.class(format!("http://www.example.com/c{}", i)).into();
+                ne.into()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_insertion_order() {
+        let mut o = TwoIndexedOntology::new(
+            SetIndex::new_rc(),
+            InsertionOrderIndex::new(),
+            Default::default(),
+        );
+
+        let mut c = classes(5);
+        c.reverse();
+        for ax in &c {
+            o.insert(ax.clone());
+        }
+        assert!(!o.insert(c[1].clone()));
+        assert_eq!(o.j().len(), 5);
+
+        assert!(o.remove(&c[2]));
+        assert!(!o.remove(&c[2]));
+        c.remove(2);
+
+        let order: Vec<_> = o.j().iter().cloned().collect();
+        assert_eq!(order, c);
+    }
+
+    #[test]
+    fn test_reinsert_after_remove() {
+        let mut o = TwoIndexedOntology::new(
+            SetIndex::new_rc(),
+            InsertionOrderIndex::new(),
+            Default::default(),
+        );
+
+        let c = classes(3);
+        for ax in &c {
+            o.insert(ax.clone());
+        }
+        o.remove(&c[0]);
+        o.insert(c[0].clone());
+
+        let order: Vec<_> = o.j().into_iter().cloned().collect();
+        assert_eq!(order, vec![c[1].clone(), c[2].clone(), c[0].clone()]);
+    }
+}
@@ -20,11 +20,18 @@ use std::{
 
 use super::axiom_mapped::AxiomMappedIndex;
 use super::declaration_mapped::DeclarationMappedIndex;
-use super::indexed::{FourIndexedOntology, OntologyIndex};
+use super::indexed::{FourIndexedOntology, OntologyIndex, ReindexInto};
 use super::set::SetIndex;
 
 use std::collections::HashSet;
 
+#[cfg(feature = "persist")]
+use super::set::Reintern;
+#[cfg(feature = "persist")]
+use crate::error::{invalid, HornedError};
+#[cfg(feature = "persist")]
+use crate::visitor::WalkMut;
+
 #[derive(Debug, Default, Eq, PartialEq)]
 pub struct IRIMappedIndex<A, AA> {
     irindex: RefCell<BTreeMap<IRI<A>, BTreeSet<AA>>>,
@@ -216,6 +223,22 @@ impl<A: ForIRI, AA: ForIndex<A>> OntologyIndex<A, AA> for IRIMappedIndex<A, AA>
     }
 }
 
+impl<A: ForIRI, AA: ForIndex<A>, B: ForIRI, BB: ForIndex<B>> ReindexInto<B, BB>
+    for IRIMappedIndex<A, AA>
+{
+    type Output = IRIMappedIndex<B, BB>;
+
+    fn empty_index(&self) -> IRIMappedIndex<B, BB> {
+        IRIMappedIndex::new()
+    }
+}
+
+/// An ontology indexed by the IRIs which its axioms mention.
+///
+/// `RcIRIMappedOntology` takes entities from `Build::new_rc`, and
+/// `ArcIRIMappedOntology` from `Build::new_arc`; an axiom from the
+/// other builder will not compile, and must first be converted with
+/// `Rebuild`.
 #[allow(clippy::type_complexity)]
 pub struct IRIMappedOntology<A: ForIRI, AA: ForIndex<A>>(
     FourIndexedOntology<
@@ -247,6 +270,22 @@ impl<A: ForIRI, AA: ForIndex<A>> Ontology<A> for IRIMappedOntology<A, AA> {
     fn mut_doc_iri(&mut self) -> &mut Option<IRI<A>> {
         self.0.mut_doc_iri()
     }
+
+    fn len(&self) -> usize {
+        IRIMappedOntology::len(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        IRIMappedOntology::is_empty(self)
+    }
+}
+
+impl<'a, A: ForIRI, AA: ForIndex<A>> IntoIterator for &'a IRIMappedOntology<A, AA> {
+    type Item = &'a AnnotatedAxiom<A>;
+    type IntoIter = std::vec::IntoIter<&'a AnnotatedAxiom<A>>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
 }
 
 impl<A: ForIRI, AA: ForIndex<A>> MutableOntology<A> for IRIMappedOntology<A, AA> {
@@ -262,6 +301,41 @@ impl<A: ForIRI, AA: ForIndex<A>> MutableOntology<A> for IRIMappedOntology<A, AA>
     }
 }
 
+/// All of the axioms about a single entity, as displayed by a
+/// frame-based editor.
+///
+/// Only axioms with the entity as their subject are included; for
+/// example, `super_classes` comes from `SubClassOf` axioms with the
+/// entity as the sub class, but not those where it is the super
+/// class. All fields are in sorted order.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Frame<A> {
+    pub entity: NamedEntity<A>,
+    /// True if the entity has a declaration.
+    pub declared: bool,
+    /// Annotations from `AnnotationAssertion` axioms.
+    pub annotations: Vec<Annotation<A>>,
+    pub super_classes: Vec<ClassExpression<A>>,
+    /// The other classes from any `EquivalentClasses` axioms.
+    pub equivalent_classes: Vec<ClassExpression<A>>,
+    /// The other classes from any `DisjointClasses` axioms.
+    pub disjoint_classes: Vec<ClassExpression<A>>,
+    /// The kinds of any property characteristic axioms, such as
+    /// `AxiomKind::TransitiveObjectProperty`.
+    pub characteristics: Vec<AxiomKind>,
+}
+
+fn entity_iri<A: ForIRI>(entity: &NamedEntity<A>) -> &IRI<A> {
+    match entity {
+        NamedEntity::Class(e) => &e.0,
+        NamedEntity::ObjectProperty(e) => &e.0,
+        NamedEntity::DataProperty(e) => &e.0,
+        NamedEntity::AnnotationProperty(e) => &e.0,
+        NamedEntity::NamedIndividual(e) => &e.0,
+        NamedEntity::Datatype(e) => &e.0,
+    }
+}
+
 impl<A: ForIRI, AA: ForIndex<A>> IRIMappedOntology<A, AA> {
     pub fn default() -> IRIMappedOntology<A, AA> {
         IRIMappedOntology(FourIndexedOntology::new(
@@ -283,6 +357,143 @@ impl<A: ForIRI, AA: ForIndex<A>> IRIMappedOntology<A, AA> {
         self.0.k().axiom_for_kind(axkind)
     }
 
+    /// Return the `Frame` for `entity`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::iri_mapped::IRIMappedOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = IRIMappedOntology::new_rc();
+    /// o.insert(SubClassOf {
+    ///     sub: b.class("http://www.example.com/a").into(),
+    ///     sup: b.class("http://www.example.com/b").into(),
+    /// });
+    ///
+    /// let frame = o.frame(&b.class("http://www.example.com/a").into());
+    /// assert_eq!(frame.super_classes, vec![b.class("http://www.example.com/b").into()]);
+    /// assert!(!frame.declared);
+    /// ```
+    pub fn frame(&self, entity: &NamedEntity<A>) -> Frame<A> {
+        let iri = entity_iri(entity);
+        let class = match entity {
+            NamedEntity::Class(c) => Some(ClassExpression::Class(c.clone())),
+            _ => None,
+        };
+        let ope = match entity {
+            NamedEntity::ObjectProperty(op) => {
+                Some(ObjectPropertyExpression::ObjectProperty(op.clone()))
+            }
+            _ => None,
+        };
+
+        let mut frame = Frame {
+            entity: entity.clone(),
+            declared: false,
+            annotations: vec![],
+            super_classes: vec![],
+            equivalent_classes: vec![],
+            disjoint_classes: vec![],
+            characteristics: vec![],
+        };
+
+        let others = |v: &Vec<ClassExpression<A>>| -> Vec<ClassExpression<A>> {
+            if class.as_ref().is_some_and(|c| v.contains(c)) {
+                v.iter()
+                    .filter(|ce| Some(*ce) != class.as_ref())
+                    .cloned()
+                    .collect()
+            } else {
+                vec![]
+            }
+        };
+
+        for ax in self.0.j().axiom(iri) {
+            match ax {
+                _ if ax.as_entity().as_ref() == Some(entity) => frame.declared = true,
+                Axiom::AnnotationAssertion(AnnotationAssertion {
+                    subject: AnnotationSubject::IRI(s),
+                    ann,
+                }) if s == iri => frame.annotations.push(ann.clone()),
+                Axiom::SubClassOf(SubClassOf { sub, sup }) if Some(sub) == class.as_ref() => {
+                    frame.super_classes.push(sup.clone())
+                }
+                Axiom::EquivalentClasses(EquivalentClasses(v)) => {
+                    frame.equivalent_classes.extend(others(v))
+                }
+                Axiom::DisjointClasses(DisjointClasses(v)) => {
+                    frame.disjoint_classes.extend(others(v))
+                }
+                Axiom::FunctionalObjectProperty(FunctionalObjectProperty(p))
+                | Axiom::InverseFunctionalObjectProperty(InverseFunctionalObjectProperty(p))
+                | Axiom::ReflexiveObjectProperty(ReflexiveObjectProperty(p))
+                | Axiom::IrreflexiveObjectProperty(IrreflexiveObjectProperty(p))
+                | Axiom::SymmetricObjectProperty(SymmetricObjectProperty(p))
+                | Axiom::AsymmetricObjectProperty(AsymmetricObjectProperty(p))
+                | Axiom::TransitiveObjectProperty(TransitiveObjectProperty(p))
+                    if Some(p) == ope.as_ref() =>
+                {
+                    frame.characteristics.push(ax.kind())
+                }
+                Axiom::FunctionalDataProperty(FunctionalDataProperty(dp))
+                    if &NamedEntity::DataProperty(dp.clone()) == entity =>
+                {
+                    frame.characteristics.push(ax.kind())
+                }
+                _ => {}
+            }
+        }
+
+        frame.annotations.sort();
+        frame.annotations.dedup();
+        frame.super_classes.sort();
+        frame.super_classes.dedup();
+        frame.equivalent_classes.sort();
+        frame.equivalent_classes.dedup();
+        frame.disjoint_classes.sort();
+        frame.disjoint_classes.dedup();
+        frame.characteristics.sort();
+        frame.characteristics.dedup();
+        frame
+    }
+
+    /// Return the axioms which would be affected by removing
+    /// `entity` from the ontology.
+    ///
+    /// These are all of the axioms which refer to the IRI of
+    /// `entity`, including its declaration and any
+    /// `AnnotationAssertion` axioms of which it is the subject. If
+    /// the IRI is punned, axioms which use it as another kind of
+    /// entity are also returned.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::iri_mapped::IRIMappedOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = IRIMappedOntology::new_rc();
+    /// o.declare(b.class("http://www.example.com/a"));
+    /// o.declare(b.class("http://www.example.com/b"));
+    ///
+    /// let affected = o.axioms_affected_by_removal(&b.class("http://www.example.com/a").into());
+    /// assert_eq!(affected.len(), 1);
+    /// ```
+    pub fn axioms_affected_by_removal(&self, entity: &NamedEntity<A>) -> Vec<&AnnotatedAxiom<A>> {
+        self.0.j().axiom_for_iri(entity_iri(entity)).collect()
+    }
+
+    /// Return true if any axiom other than a declaration refers to
+    /// the IRI of `entity`.
+    ///
+    /// When this is true, removing the declaration of `entity` would
+    /// leave it used but undeclared.
+    pub fn declaration_still_used(&self, entity: &NamedEntity<A>) -> bool {
+        self.0
+            .j()
+            .axiom_for_iri(entity_iri(entity))
+            .any(|ax| ax.axiom.as_entity().is_none())
+    }
+
     //Utility method updates an axiom in the index
     pub fn update_axiom(&mut self, ax: &AnnotatedAxiom<A>, new_ax: AnnotatedAxiom<A>) -> bool {
         self.take(ax);
@@ -292,7 +503,113 @@ impl<A: ForIRI, AA: ForIndex<A>> IRIMappedOntology<A, AA> {
     pub fn iter(&self) -> std::vec::IntoIter<&AnnotatedAxiom<A>> {
         self.0.i().into_iter()
     }
+
+    /// Returns the number of axioms in the ontology.
+    pub fn len(&self) -> usize {
+        self.0.i().len()
+    }
+
+    /// Returns true if the ontology contains no axioms.
+    pub fn is_empty(&self) -> bool {
+        self.0.i().is_empty()
+    }
+
+    /// Return the number of successful changes made to this ontology.
+    ///
+    /// See `OneIndexedOntology::revision`.
+    pub fn revision(&self) -> u64 {
+        self.0.revision()
+    }
+}
+#[cfg(feature = "persist")]
+impl<A: ForIRI, AA: ForIndex<A>> IRIMappedOntology<A, AA> {
+    /// Write this ontology, including its IRI index, to `w` in a
+    /// binary format which can be read by `load_index`.
+    ///
+    /// Each axiom is written once; the IRI index is written as
+    /// positions in the list of axioms.
+    pub fn save_index<W: std::io::Write>(&self, w: W) -> Result<(), HornedError>
+    where
+        A: BackingStore + serde::Serialize,
+    {
+        let axioms: Vec<&AnnotatedAxiom<A>> = self.iter().collect();
+        let position: std::collections::HashMap<_, _> =
+            axioms.iter().enumerate().map(|(i, ax)| (*ax, i)).collect();
+
+        let irindex = self.0.j().irindex.borrow();
+        let iris: Vec<(&IRI<A>, Vec<usize>)> = irindex
+            .iter()
+            .filter(|(_, axs)| !axs.is_empty())
+            .map(|(iri, axs)| (iri, axs.iter().map(|ax| position[ax.borrow()]).collect()))
+            .collect();
+
+        Ok(bincode::serialize_into(
+            w,
+            &(self.id(), self.doc_iri(), &axioms, &iris),
+        )?)
+    }
+
+    /// Read an ontology written by `save_index` from `r`, interning
+    /// its IRIs with `b`.
+    ///
+    /// The IRI index is restored without walking the axioms, while
+    /// the other indexes are rebuilt from them. The ontology may have
+    /// been saved with any backing store.
+    pub fn load_index<R: std::io::Read>(r: R, b: &Build<A>) -> Result<Self, HornedError>
+    where
+        A: BackingStore + serde::de::DeserializeOwned,
+    {
+        #[allow(clippy::type_complexity)]
+        let (mut id, mut doc_iri, axioms, iris): (
+            OntologyID<A>,
+            Option<IRI<A>>,
+            Vec<AnnotatedAxiom<A>>,
+            Vec<(IRI<A>, Vec<usize>)>,
+        ) = bincode::deserialize_from(r)?;
+
+        let mut walk = WalkMut::new(Reintern(b));
+        walk.ontology_id(&mut id);
+        walk.option_iri(&mut doc_iri);
+        let axioms: Vec<AA> = axioms
+            .into_iter()
+            .map(|mut ax| {
+                walk.annotated_axiom(&mut ax);
+                AA::from(ax)
+            })
+            .collect();
+        let mut si = SetIndex::new();
+        let mut ami = AxiomMappedIndex::new();
+        let mut dmi = DeclarationMappedIndex::new();
+        for ax in &axioms {
+            si.index_insert(ax.clone());
+            ami.index_insert(ax.clone());
+            dmi.index_insert(ax.clone());
+        }
+
+        let mut irindex = BTreeMap::new();
+        for (mut iri, positions) in iris {
+            walk.iri(&mut iri);
+            let axs = positions
+                .into_iter()
+                .map(|i| {
+                    axioms
+                        .get(i)
+                        .cloned()
+                        .ok_or_else(|| invalid!("Index position {} out of range", i))
+                })
+                .collect::<Result<BTreeSet<_>, _>>()?;
+            irindex.insert(iri, axs);
+        }
+        let iri = IRIMappedIndex {
+            irindex: RefCell::new(irindex),
+        };
+
+        let mut o = IRIMappedOntology(FourIndexedOntology::new(si, iri, ami, dmi, id));
+        *o.mut_doc_iri() = doc_iri;
+        Ok(o)
+    }
 }
+
 impl RcIRIMappedOntology {
     pub fn new_rc() -> Self {
         IRIMappedOntology::default()
@@ -434,4 +751,188 @@ mod test {
             ]
         );
     }
+
+    #[cfg(feature = "persist")]
+    #[test]
+    fn test_save_load_index() {
+        use super::RcIRIMappedOntology;
+
+        let build = Build::new_rc();
+        let mut o = IRIMappedOntology::new_rc();
+        o.mut_id().iri = Some(build.iri("http://www.example.com/o"));
+        let a = build.class("http://www.example.com#a");
+        let b = build.class("http://www.example.com#b");
+        o.declare(a.clone());
+        o.insert(SubClassOf {
+            sub: a.clone().into(),
+            sup: b.clone().into(),
+        });
+        o.insert(AnnotatedAxiom::new(
+            DeclareClass(b.clone()),
+            vec![Annotation {
+                ap: build.annotation_property("http://www.example.com#ap"),
+                av: "b".into(),
+            }]
+            .into_iter()
+            .collect(),
+        ));
+
+        let mut buf = vec![];
+        o.save_index(&mut buf).unwrap();
+        let mut loaded = RcIRIMappedOntology::load_index(buf.as_slice(), &build).unwrap();
+
+        assert_eq!(loaded.id(), o.id());
+        // IRIs are interned by the `Build`, rather than one copy per use
+        assert!(std::ptr::eq(
+            loaded.id().iri.as_ref().unwrap().as_ref(),
+            build.iri("http://www.example.com/o").as_ref()
+        ));
+        let sorted = |o: &RcIRIMappedOntology| {
+            let mut v: Vec<_> = o.iter().cloned().collect();
+            v.sort();
+            v
+        };
+        assert_eq!(sorted(&loaded), sorted(&o));
+        for iri in &[
+            build.iri("http://www.example.com#a"),
+            build.iri("http://www.example.com#b"),
+            build.iri("http://www.example.com#ap"),
+            build.iri("http://www.example.com#c"),
+        ] {
+            let mut expected: Vec<_> = o.axiom_for_iri(iri).cloned().collect();
+            let mut actual: Vec<_> = loaded.axiom_for_iri(iri).cloned().collect();
+            expected.sort();
+            actual.sort();
+            assert_eq!(expected, actual);
+        }
+        assert_eq!(loaded.axiom_for_kind(AxiomKind::DeclareClass).count(), 2);
+
+        assert!(RcIRIMappedOntology::load_index(&buf[..10], &build).is_err());
+    }
+
+    #[cfg(feature = "persist")]
+    #[test]
+    fn test_load_index_other_store() {
+        use super::ArcIRIMappedOntology;
+
+        let build = Build::new_rc();
+        let mut o = IRIMappedOntology::new_rc();
+        o.declare(build.class("http://www.example.com#a"));
+
+        let mut buf = vec![];
+        o.save_index(&mut buf).unwrap();
+        let arc_build = Build::new_arc();
+        let loaded = ArcIRIMappedOntology::load_index(buf.as_slice(), &arc_build).unwrap();
+
+        assert_eq!(
+            loaded.iter().collect::<Vec<_>>(),
+            vec![&DeclareClass(arc_build.class("http://www.example.com#a")).into()]
+        );
+    }
+
+    #[test]
+    fn test_axioms_affected_by_removal() {
+        let build = Build::new_rc();
+        let mut o = IRIMappedOntology::new_rc();
+        let a = build.class("http://www.example.com#a");
+        let b = build.class("http://www.example.com#b");
+        let c = build.class("http://www.example.com#c");
+
+        let sub: AnnotatedAxiom<_> = SubClassOf {
+            sub: a.clone().into(),
+            sup: b.clone().into(),
+        }
+        .into();
+        let disjoint: AnnotatedAxiom<_> =
+            DisjointClasses(vec![a.clone().into(), c.clone().into()]).into();
+        o.insert(sub.clone());
+        o.insert(disjoint.clone());
+        o.insert(SubClassOf {
+            sub: c.into(),
+            sup: b.into(),
+        });
+
+        let mut affected = o.axioms_affected_by_removal(&a.into());
+        affected.sort();
+        let mut expected = vec![&sub, &disjoint];
+        expected.sort();
+        assert_eq!(affected, expected);
+
+        // Nothing is removed
+        assert_eq!(o.len(), 3);
+        assert!(o
+            .axioms_affected_by_removal(&build.class("http://www.example.com#d").into())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_declaration_still_used() {
+        let build = Build::new_rc();
+        let mut o = IRIMappedOntology::new_rc();
+        let a = build.class("http://www.example.com#a");
+        let b = build.class("http://www.example.com#b");
+        let c = build.class("http://www.example.com#c");
+        o.declare(a.clone());
+        o.declare(b.clone());
+        o.declare(c.clone());
+        o.insert(SubClassOf {
+            sub: a.clone().into(),
+            sup: b.clone().into(),
+        });
+
+        assert!(o.declaration_still_used(&a.into()));
+        assert!(o.declaration_still_used(&b.into()));
+        assert!(!o.declaration_still_used(&c.into()));
+    }
+
+    #[test]
+    fn test_frame() {
+        let build = Build::new_rc();
+        let mut o = IRIMappedOntology::new_rc();
+        let a = build.class("http://www.example.com#a");
+        let b = build.class("http://www.example.com#b");
+        let c = build.class("http://www.example.com#c");
+        let label = "http://www.w3.org/2000/01/rdf-schema#label";
+
+        o.declare(a.clone());
+        o.insert(build.annotation_assertion("http://www.example.com#a", label, "A"));
+        o.insert(build.annotation_assertion("http://www.example.com#b", label, "B"));
+        o.insert(SubClassOf {
+            sub: a.clone().into(),
+            sup: b.clone().into(),
+        });
+        o.insert(SubClassOf {
+            sub: c.clone().into(),
+            sup: a.clone().into(),
+        });
+        o.insert(DisjointClasses(vec![a.clone().into(), c.clone().into()]));
+
+        let frame = o.frame(&a.clone().into());
+        assert!(frame.declared);
+        assert_eq!(
+            frame.annotations,
+            vec![Annotation {
+                ap: build.annotation_property(label),
+                av: "A".into(),
+            }]
+        );
+        assert_eq!(frame.super_classes, vec![b.clone().into()]);
+        assert_eq!(frame.disjoint_classes, vec![c.into()]);
+        assert!(frame.equivalent_classes.is_empty());
+
+        let op = build.object_property("http://www.example.com#op");
+        o.insert(TransitiveObjectProperty(op.clone().into()));
+        o.insert(ObjectPropertyDomain {
+            ope: op.clone().into(),
+            ce: a.into(),
+        });
+
+        let frame = o.frame(&op.into());
+        assert!(!frame.declared);
+        assert_eq!(
+            frame.characteristics,
+            vec![AxiomKind::TransitiveObjectProperty]
+        );
+        assert!(frame.super_classes.is_empty());
+    }
 }
@@ -0,0 +1,152 @@
+//! An `Ontology` which is parsed from a file when first used
+
+//! # Overview
+//!
+//! A `LazyOntology` holds the path to an ontology file, and parses it
+//! into a `SetOntology` the first time that it is queried; the result
+//! is cached thereafter. This is useful for tools which may open many
+//! ontologies but only inspect a few of them.
+use std::cell::OnceCell;
+use std::path::{Path, PathBuf};
+
+use crate::command::parse_path;
+use crate::error::HornedError;
+use crate::io::ParserConfiguration;
+use crate::model::{Ontology, OntologyID, RcStr, IRI};
+use crate::ontology::set::SetOntology;
+
+/// An `Ontology` which is parsed from a file on first access.
+///
+/// The format of the file is determined by its extension, as for
+/// `command::parse_path`. Parse errors are returned by `try_load`;
+/// the `Ontology` methods do not panic, but return an empty
+/// `OntologyID` if the file cannot be parsed, so `try_load` should
+/// normally be called first.
+#[derive(Debug)]
+pub struct LazyOntology {
+    path: PathBuf,
+    config: ParserConfiguration,
+    ontology: OnceCell<SetOntology<RcStr>>,
+    empty_id: OntologyID<RcStr>,
+    empty_doc_iri: Option<IRI<RcStr>>,
+}
+
+impl LazyOntology {
+    pub fn new<P: AsRef<Path>>(path: P) -> LazyOntology {
+        Self::with_config(path, Default::default())
+    }
+
+    pub fn with_config<P: AsRef<Path>>(path: P, config: ParserConfiguration) -> LazyOntology {
+        LazyOntology {
+            path: path.as_ref().to_path_buf(),
+            config,
+            ontology: OnceCell::new(),
+            empty_id: Default::default(),
+            empty_doc_iri: None,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns true if the ontology has already been parsed.
+    pub fn is_loaded(&self) -> bool {
+        self.ontology.get().is_some()
+    }
+
+    /// Return the ontology, parsing the file if it has not already
+    /// been parsed.
+    ///
+    /// If parsing fails, the error is returned and nothing is
+    /// cached, so a later call will try again.
+    pub fn try_load(&self) -> Result<&SetOntology<RcStr>, HornedError> {
+        if let Some(o) = self.ontology.get() {
+            return Ok(o);
+        }
+
+        let o = parse_path(&self.path, self.config.clone())?.into();
+        Ok(self.ontology.get_or_init(|| o))
+    }
+
+    /// Return the ontology, consuming `self` and parsing the file if
+    /// it has not already been parsed.
+    pub fn into_ontology(self) -> Result<SetOntology<RcStr>, HornedError> {
+        self.try_load()?;
+        Ok(self.ontology.into_inner().unwrap())
+    }
+}
+
+impl Ontology<RcStr> for LazyOntology {
+    fn id(&self) -> &OntologyID<RcStr> {
+        match self.try_load() {
+            Ok(o) => o.id(),
+            Err(_) => &self.empty_id,
+        }
+    }
+
+    fn mut_id(&mut self) -> &mut OntologyID<RcStr> {
+        if self.try_load().is_ok() {
+            if let Some(o) = self.ontology.get_mut() {
+                return o.mut_id();
+            }
+        }
+        &mut self.empty_id
+    }
+
+    fn doc_iri(&self) -> &Option<IRI<RcStr>> {
+        match self.try_load() {
+            Ok(o) => o.doc_iri(),
+            Err(_) => &self.empty_doc_iri,
+        }
+    }
+
+    fn mut_doc_iri(&mut self) -> &mut Option<IRI<RcStr>> {
+        if self.try_load().is_ok() {
+            if let Some(o) = self.ontology.get_mut() {
+                return o.mut_doc_iri();
+            }
+        }
+        &mut self.empty_doc_iri
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate mktemp;
+
+    use self::mktemp::Temp;
+    use super::*;
+    use crate::model::Build;
+
+    #[test]
+    fn test_parsed_once() {
+        let dir = Temp::new_dir().unwrap();
+        let path = dir.as_ref().join("class.owx");
+        std::fs::write(&path, include_str!("../ont/owl-xml/class.owx")).unwrap();
+
+        let lazy = LazyOntology::new(&path);
+        assert!(!lazy.is_loaded());
+
+        let b = Build::new_rc();
+        assert_eq!(lazy.id().iri, Some(b.iri("http://www.example.com/iri")));
+        assert!(lazy.is_loaded());
+
+        // Any further parse would now fail
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(lazy.id().iri, Some(b.iri("http://www.example.com/iri")));
+        assert_eq!(lazy.try_load().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_load_error() {
+        let dir = Temp::new_dir().unwrap();
+        let mut lazy = LazyOntology::new(dir.as_ref().join("missing.owx"));
+
+        assert!(lazy.try_load().is_err());
+        assert_eq!(lazy.id(), &OntologyID::default());
+        assert!(lazy.mut_doc_iri().is_none());
+        assert!(!lazy.is_loaded());
+        assert!(lazy.into_ontology().is_err());
+    }
+}
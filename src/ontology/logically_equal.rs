@@ -1,7 +1,7 @@
 use crate::model::{AnnotatedAxiom, Axiom, ForIRI, MutableOntology, RcStr};
 use crate::ontology::indexed::ForIndex;
 
-use super::indexed::{OntologyIndex, ThreeIndexedOntology, TwoIndexedOntology};
+use super::indexed::{OntologyIndex, ReindexInto, ThreeIndexedOntology, TwoIndexedOntology};
 use std::collections::HashMap;
 use std::convert::AsRef;
 use std::rc::Rc;
@@ -31,6 +31,16 @@ impl<A: ForIRI, AA: ForIndex<A>> OntologyIndex<A, AA> for LogicallyEqualIndex<A,
     }
 }
 
+impl<A: ForIRI, AA: ForIndex<A>, B: ForIRI, BB: ForIndex<B>> ReindexInto<B, BB>
+    for LogicallyEqualIndex<A, AA>
+{
+    type Output = LogicallyEqualIndex<B, BB>;
+
+    fn empty_index(&self) -> LogicallyEqualIndex<B, BB> {
+        LogicallyEqualIndex::new()
+    }
+}
+
 impl<A: ForIRI, AA: ForIndex<A>> LogicallyEqualIndex<A, AA> {
     pub fn logical_contains(&self, ax: &AnnotatedAxiom<A>) -> bool {
         self.0.contains_key(&ax.axiom)
@@ -0,0 +1,345 @@
+//! Lightweight materialization of simple entailments
+
+//! # Overview
+//!
+//! This module adds methods to
+//! [`SetOntology`](../set/struct.SetOntology.html) which compute a
+//! few cheap entailments and add them to the ontology as axioms. This
+//! is not a reasoner; it covers only a small subset of RDFS/OWL
+//! entailments, but one that is often sufficient.
+//!
+//! Every axiom added carries an annotation with the property
+//! [`INFERRED`](constant.INFERRED.html), so that it can be
+//! distinguished from those that were asserted.
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::set::SetOntology;
+use crate::model::*;
+use crate::vocab::{WithIRI, XSD};
+
+/// The annotation property used to mark axioms added by
+/// materialization.
+pub const INFERRED: &str = "https://github.com/phillord/horned-owl/vocab#inferred";
+
+fn inferred<A: ForIRI, AX: Into<Axiom<A>>>(b: &Build<A>, ax: AX) -> AnnotatedAxiom<A> {
+    let ann = Annotation {
+        ap: b.annotation_property(INFERRED),
+        av: Literal::Datatype {
+            literal: "true".to_string(),
+            datatype_iri: b.iri(XSD::Boolean.iri_str()),
+        }
+        .into(),
+    };
+    AnnotatedAxiom::new(ax, vec![ann].into_iter().collect())
+}
+
+impl<A: ForIRI> SetOntology<A> {
+    /// Add the transitive closure of the `SubClassOf` axioms between
+    /// named classes, returning the number of axioms added.
+    ///
+    /// Neither reflexive axioms, nor those for which an equivalent
+    /// `SubClassOf` axiom already exists, are added. The annotations
+    /// marking the new axioms are built with `b`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.insert(SubClassOf {
+    ///     sub: b.class("http://www.example.com/a").into(),
+    ///     sup: b.class("http://www.example.com/b").into(),
+    /// });
+    /// o.insert(SubClassOf {
+    ///     sub: b.class("http://www.example.com/b").into(),
+    ///     sup: b.class("http://www.example.com/c").into(),
+    /// });
+    ///
+    /// assert_eq!(o.materialize_subclass_closure(&b), 1);
+    /// assert_eq!(o.materialize_subclass_closure(&b), 0);
+    /// ```
+    pub fn materialize_subclass_closure(&mut self, b: &Build<A>) -> usize {
+        let edges = self.named_subclass_edges();
+
+        let mut sups: BTreeMap<&Class<A>, Vec<&Class<A>>> = BTreeMap::new();
+        for (sub, sup) in &edges {
+            sups.entry(sub).or_default().push(sup);
+        }
+
+        let mut closure = BTreeSet::new();
+        for &sub in sups.keys() {
+            let mut seen = BTreeSet::new();
+            let mut stack = sups[sub].clone();
+            while let Some(sup) = stack.pop() {
+                if seen.insert(sup) {
+                    stack.extend(sups.get(sup).into_iter().flatten());
+                }
+            }
+            closure.extend(seen.into_iter().map(|sup| (sub, sup)));
+        }
+
+        let new: Vec<_> = closure
+            .into_iter()
+            .filter(|(sub, sup)| sub != sup && !edges.contains(&((*sub).clone(), (*sup).clone())))
+            .map(|(sub, sup)| {
+                inferred(
+                    b,
+                    SubClassOf {
+                        sub: sub.clone().into(),
+                        sup: sup.clone().into(),
+                    },
+                )
+            })
+            .collect();
+
+        new.into_iter().filter(|aa| self.insert(aa.clone())).count()
+    }
+
+    /// Add the `ObjectPropertyAssertion` axioms entailed by
+    /// `SymmetricObjectProperty` and `InverseObjectProperties`
+    /// axioms, returning the number of axioms added.
+    ///
+    /// Assertions are added until no more are entailed, so a
+    /// symmetric property with an inverse is handled. Only named
+    /// object properties are considered, and no assertion is added
+    /// if an equivalent one already exists. The annotations marking
+    /// the new axioms are built with `b`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.insert(SymmetricObjectProperty(b.object_property("http://www.example.com/p").into()));
+    /// o.insert(ObjectPropertyAssertion {
+    ///     ope: b.object_property("http://www.example.com/p").into(),
+    ///     from: b.named_individual("http://www.example.com/a").into(),
+    ///     to: b.named_individual("http://www.example.com/b").into(),
+    /// });
+    ///
+    /// assert_eq!(o.materialize_property_assertions(&b), 1);
+    /// ```
+    pub fn materialize_property_assertions(&mut self, b: &Build<A>) -> usize {
+        let mut symmetric = BTreeSet::new();
+        let mut inverse: BTreeMap<ObjectProperty<A>, BTreeSet<ObjectProperty<A>>> = BTreeMap::new();
+        let mut facts = BTreeSet::new();
+
+        for aa in self.iter() {
+            match &aa.axiom {
+                Axiom::SymmetricObjectProperty(SymmetricObjectProperty(
+                    ObjectPropertyExpression::ObjectProperty(p),
+                )) => {
+                    symmetric.insert(p.clone());
+                }
+                Axiom::InverseObjectProperties(InverseObjectProperties(p, q)) => {
+                    inverse.entry(p.clone()).or_default().insert(q.clone());
+                    inverse.entry(q.clone()).or_default().insert(p.clone());
+                }
+                Axiom::ObjectPropertyAssertion(ObjectPropertyAssertion {
+                    ope: ObjectPropertyExpression::ObjectProperty(p),
+                    from,
+                    to,
+                }) => {
+                    facts.insert((p.clone(), from.clone(), to.clone()));
+                }
+                _ => {}
+            }
+        }
+
+        let mut new = vec![];
+        let mut todo: Vec<_> = facts.iter().cloned().collect();
+        while let Some((p, from, to)) = todo.pop() {
+            let entailed = symmetric
+                .get(&p)
+                .into_iter()
+                .chain(inverse.get(&p).into_iter().flatten());
+
+            for q in entailed {
+                let fact = (q.clone(), to.clone(), from.clone());
+                if facts.insert(fact.clone()) {
+                    new.push(fact.clone());
+                    todo.push(fact);
+                }
+            }
+        }
+
+        new.into_iter()
+            .filter(|(p, from, to)| {
+                self.insert(inferred(
+                    b,
+                    ObjectPropertyAssertion {
+                        ope: p.clone().into(),
+                        from: from.clone(),
+                        to: to.clone(),
+                    },
+                ))
+            })
+            .count()
+    }
+
+    /// Remove every axiom marked as inferred, returning the number of
+    /// axioms removed.
+    ///
+    /// This reverses the effect of the `materialize` methods, so that
+    /// they can be run again from the asserted axioms alone.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.insert(SymmetricObjectProperty(b.object_property("http://www.example.com/p").into()));
+    /// o.insert(ObjectPropertyAssertion {
+    ///     ope: b.object_property("http://www.example.com/p").into(),
+    ///     from: b.named_individual("http://www.example.com/a").into(),
+    ///     to: b.named_individual("http://www.example.com/b").into(),
+    /// });
+    ///
+    /// o.materialize_property_assertions(&b);
+    /// assert_eq!(o.remove_inferred(), 1);
+    /// assert_eq!(o.len(), 2);
+    /// ```
+    pub fn remove_inferred(&mut self) -> usize {
+        let inferred: Vec<_> = self
+            .iter()
+            .filter(|aa| aa.ann.iter().any(|ann| ann.ap.0.as_ref() == INFERRED))
+            .cloned()
+            .collect();
+
+        inferred.iter().filter(|aa| self.remove(aa)).count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_subclass_closure() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        let c = |s: &str| -> ClassExpression<_> {
+            b.class(format!("http://www.example.com/{}", s)).into()
+        };
+        let sc = |sub, sup| SubClassOf {
+            sub: c(sub),
+            sup: c(sup),
+        };
+
+        // a < b < c < d, with a < c already asserted, and a cycle
+        // between d and e
+        o.insert(sc("a", "b"));
+        o.insert(sc("b", "c"));
+        o.insert(sc("c", "d"));
+        o.insert(sc("a", "c"));
+        o.insert(sc("d", "e"));
+        o.insert(sc("e", "d"));
+
+        // a<d, a<e, b<d, b<e, c<e
+        assert_eq!(o.materialize_subclass_closure(&b), 5);
+        assert_eq!(o.len(), 11);
+
+        let added: AnnotatedAxiom<_> = inferred(&b, sc("a", "d"));
+        assert!(o.iter().any(|aa| aa == &added));
+        assert!(!o.iter().any(|aa| aa.axiom == sc("d", "d").into()));
+        assert!(o.iter().any(|aa| aa == &sc("a", "c").into()));
+
+        assert_eq!(o.materialize_subclass_closure(&b), 0);
+    }
+
+    fn opa(b: &Build<RcStr>, p: &str, from: &str, to: &str) -> ObjectPropertyAssertion<RcStr> {
+        ObjectPropertyAssertion {
+            ope: b
+                .object_property(format!("http://www.example.com/{}", p))
+                .into(),
+            from: b
+                .named_individual(format!("http://www.example.com/{}", from))
+                .into(),
+            to: b
+                .named_individual(format!("http://www.example.com/{}", to))
+                .into(),
+        }
+    }
+
+    #[test]
+    fn test_symmetric_assertions() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        o.insert(SymmetricObjectProperty(
+            b.object_property("http://www.example.com/p").into(),
+        ));
+        o.insert(opa(&b, "p", "a", "b"));
+        o.insert(opa(&b, "p", "c", "d"));
+        o.insert(opa(&b, "p", "d", "c"));
+        o.insert(opa(&b, "q", "a", "b"));
+
+        assert_eq!(o.materialize_property_assertions(&b), 1);
+        assert!(o
+            .iter()
+            .any(|aa| aa == &inferred(&b, opa(&b, "p", "b", "a"))));
+        assert!(!o.iter().any(|aa| aa.axiom == opa(&b, "q", "b", "a").into()));
+
+        assert_eq!(o.materialize_property_assertions(&b), 0);
+    }
+
+    #[test]
+    fn test_inverse_assertions() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        o.insert(InverseObjectProperties(
+            b.object_property("http://www.example.com/p"),
+            b.object_property("http://www.example.com/q"),
+        ));
+        o.insert(opa(&b, "p", "a", "b"));
+        o.insert(opa(&b, "q", "c", "d"));
+
+        assert_eq!(o.materialize_property_assertions(&b), 2);
+        assert!(o
+            .iter()
+            .any(|aa| aa == &inferred(&b, opa(&b, "q", "b", "a"))));
+        assert!(o
+            .iter()
+            .any(|aa| aa == &inferred(&b, opa(&b, "p", "d", "c"))));
+
+        // With p symmetric, both directions hold for q too
+        o.insert(SymmetricObjectProperty(
+            b.object_property("http://www.example.com/p").into(),
+        ));
+        assert_eq!(o.materialize_property_assertions(&b), 4);
+        assert!(o.iter().any(|aa| aa.axiom == opa(&b, "q", "a", "b").into()));
+        assert_eq!(o.materialize_property_assertions(&b), 0);
+    }
+
+    #[test]
+    fn test_remove_inferred() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        let c = |s: &str| -> ClassExpression<_> {
+            b.class(format!("http://www.example.com/{}", s)).into()
+        };
+        o.insert(SubClassOf {
+            sub: c("a"),
+            sup: c("b"),
+        });
+        o.insert(SubClassOf {
+            sub: c("b"),
+            sup: c("c"),
+        });
+        o.insert(SymmetricObjectProperty(
+            b.object_property("http://www.example.com/p").into(),
+        ));
+        o.insert(opa(&b, "p", "a", "b"));
+        let original = o.clone();
+
+        assert_eq!(o.materialize_subclass_closure(&b), 1);
+        assert_eq!(o.materialize_property_assertions(&b), 1);
+        assert_ne!(o, original);
+
+        assert_eq!(o.remove_inferred(), 2);
+        assert_eq!(o, original);
+        assert_eq!(o.remove_inferred(), 0);
+    }
+}
@@ -31,14 +31,74 @@
 //! [`declaration_mapped`](declaration_mapped.html) indexes only
 //! declaration axioms, allowing rapid look up of the declarated type
 //! of an IRI. As it ignores most axioms passed to it, it does not
-//! provide iteration.
+//! provide iteration. Likewise,
+//! [`class_assertion_mapped`](class_assertion_mapped.html) indexes
+//! only class assertions, allowing rapid look up of the asserted
+//! individuals of a class, and
+//! [`property_assertion_mapped`](property_assertion_mapped.html)
+//! indexes only object property assertions, allowing navigation from
+//! an individual to its asserted neighbours.
+
+//! The [`subject_mapped`](subject_mapped.html) index groups axioms by
+//! their subject, providing the axioms about an entity for a
+//! frame-based view.
+
+//! The [`insertion_order`](insertion_order.html) index iterates over
+//! axioms in the order in which they were first inserted, which can
+//! be used to preserve the order of a parsed document.
+
+//! A [`CachingIndex`](caching/struct.CachingIndex.html) wraps
+//! another index, caching a value derived from it until the next
+//! change.
+
+//! The [`hierarchy`](hierarchy.html) module provides a view over the
+//! asserted hierarchy between named entities.
+
+//! The [`materialize`](materialize.html) module adds a few simple
+//! entailments to a `SetOntology` as axioms, and a
+//! [`Pipeline`](pipeline/struct.Pipeline.html) applies a sequence of
+//! transformations to one.
+
+//! [`TimestampedAxiom`](timestamped/struct.TimestampedAxiom.html) is
+//! an example of a custom `ForIndex` type, which records when each
+//! axiom was inserted.
+
+//! A [`Transaction`](transaction/struct.Transaction.html) records
+//! the changes made to any `MutableOntology`, and reverses them
+//! unless they are committed.
+
+//! An [`EventLoggingOntology`](event_log/struct.EventLoggingOntology.html)
+//! keeps a log of the changes made to the ontology it wraps, and a
+//! [`Patch`](patch/struct.Patch.html) replays such a log onto
+//! another ontology.
+
+//! [`DynOntology`](dynamic/trait.DynOntology.html) provides
+//! read-only access to any of these through a trait object.
+
+//! Finally, [`LazyOntology`](lazy/struct.LazyOntology.html) wraps a
+//! path to an ontology file, which is only parsed the first time that
+//! the ontology is queried.
 
 pub mod axiom_mapped;
+pub mod caching;
+pub mod class_assertion_mapped;
 pub mod declaration_mapped;
+pub mod dynamic;
+pub mod event_log;
+pub mod hierarchy;
 pub mod indexed;
+pub mod insertion_order;
 pub mod iri_mapped;
+pub mod lazy;
 pub mod logically_equal;
+pub mod materialize;
+pub mod patch;
+pub mod pipeline;
+pub mod property_assertion_mapped;
 pub mod set;
+pub mod subject_mapped;
+pub mod timestamped;
+pub mod transaction;
 
 // There isn't a very formal interface here, but a set of traits that
 // can be implemented.
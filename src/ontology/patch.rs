@@ -0,0 +1,152 @@
+//! Replayable and reversible lists of changes
+
+//! # Overview
+//!
+//! A `Patch` is a list of [`Change`](../event_log/enum.Change.html)
+//! instances, such as those logged by an
+//! [`EventLoggingOntology`](../event_log/struct.EventLoggingOntology.html),
+//! which can be applied to any
+//! [`MutableOntology`](../../model/trait.MutableOntology.html). The
+//! inverse of a patch undoes it.
+//!
+//! Patches are applied strictly: inserting an axiom which is
+//! already present, or removing one which is not, is an error. A
+//! tolerant patch would apply, but its inverse would then remove or
+//! restore axioms that the patch never touched, so undo would no
+//! longer restore the original ontology.
+use super::event_log::Change;
+use super::transaction::Transaction;
+use crate::error::HornedError;
+use crate::model::*;
+
+/// A list of changes to an ontology.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// # use horned_owl::ontology::set::SetOntology;
+/// # use horned_owl::ontology::event_log::Change;
+/// # use horned_owl::ontology::patch::Patch;
+/// let b = Build::new_rc();
+/// let mut o = SetOntology::new_rc();
+/// let decl: AnnotatedAxiom<_> = DeclareClass(b.class("http://www.example.com/a")).into();
+/// let patch = Patch(vec![Change::Insert(decl.clone())]);
+///
+/// patch.apply(&mut o).unwrap();
+/// assert_eq!(o.iter().collect::<Vec<_>>(), vec![&decl]);
+///
+/// patch.invert().apply(&mut o).unwrap();
+/// assert!(o.is_empty());
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Patch<A>(pub Vec<Change<A>>);
+
+impl<A: ForIRI> Patch<A> {
+    /// Apply each change in this patch to `ont`, in order.
+    ///
+    /// If any change cannot be applied, a `HornedError::PatchError`
+    /// is returned and the changes already made are reversed, so
+    /// that `ont` is left as it was.
+    pub fn apply<O: MutableOntology<A>>(&self, ont: &mut O) -> Result<(), HornedError> {
+        let mut t = Transaction::new(ont);
+        for change in &self.0 {
+            match change {
+                Change::Insert(ax) => {
+                    if !t.insert(ax.clone()) {
+                        return Err(HornedError::PatchError(format!(
+                            "Cannot insert axiom which is already present: {:?}",
+                            ax
+                        )));
+                    }
+                }
+                Change::Remove(ax) => {
+                    if !t.remove(ax) {
+                        return Err(HornedError::PatchError(format!(
+                            "Cannot remove axiom which is not present: {:?}",
+                            ax
+                        )));
+                    }
+                }
+            }
+        }
+        t.commit();
+        Ok(())
+    }
+
+    /// Return the patch which reverses this one.
+    pub fn invert(&self) -> Patch<A> {
+        Patch(
+            self.0
+                .iter()
+                .rev()
+                .map(|change| match change {
+                    Change::Insert(ax) => Change::Remove(ax.clone()),
+                    Change::Remove(ax) => Change::Insert(ax.clone()),
+                })
+                .collect(),
+        )
+    }
+}
+
+impl<A> From<Vec<Change<A>>> for Patch<A> {
+    fn from(changes: Vec<Change<A>>) -> Patch<A> {
+        Patch(changes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ontology::event_log::EventLoggingOntology;
+    use crate::ontology::set::SetOntology;
+
+    fn decl(b: &Build<RcStr>, s: &str) -> AnnotatedAxiom<RcStr> {
+        DeclareClass(b.class(format!("http://www.example.com/{}", s))).into()
+    }
+
+    #[test]
+    fn test_apply_invert() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        o.insert(decl(&b, "a"));
+        o.insert(decl(&b, "b"));
+        let original = o.clone();
+
+        let mut logged = EventLoggingOntology::new(o.clone());
+        logged.insert(decl(&b, "c"));
+        logged.remove(&decl(&b, "a"));
+        logged.insert(decl(&b, "a"));
+        logged.remove(&decl(&b, "b"));
+        let patch: Patch<_> = logged.changes().to_vec().into();
+
+        patch.apply(&mut o).unwrap();
+        assert_eq!(&o, &*logged);
+
+        patch.invert().apply(&mut o).unwrap();
+        assert_eq!(o, original);
+    }
+
+    #[test]
+    fn test_apply_conflict() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        o.insert(decl(&b, "a"));
+        let original = o.clone();
+
+        let patch = Patch(vec![
+            Change::Insert(decl(&b, "b")),
+            Change::Remove(decl(&b, "x")),
+        ]);
+        assert!(matches!(
+            patch.apply(&mut o),
+            Err(HornedError::PatchError(_))
+        ));
+        assert_eq!(o, original);
+
+        let patch = Patch(vec![Change::Insert(decl(&b, "a"))]);
+        assert!(matches!(
+            patch.apply(&mut o),
+            Err(HornedError::PatchError(_))
+        ));
+    }
+}
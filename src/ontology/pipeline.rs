@@ -0,0 +1,109 @@
+//! Sequences of transformations of a `SetOntology`
+
+//! # Overview
+//!
+//! A `Pipeline` collects a number of stages, each of which changes a
+//! [`SetOntology`](../set/struct.SetOntology.html) and returns the
+//! number of changes it made, and runs them in order. Many of the
+//! methods of `SetOntology`, such as
+//! `remove_reflexive_subclass_axioms`, already have this form.
+use super::set::SetOntology;
+use crate::model::*;
+
+type Stage<A> = Box<dyn Fn(&mut SetOntology<A>) -> usize>;
+
+/// An ordered list of transformations of a `SetOntology`.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// # use horned_owl::ontology::pipeline::Pipeline;
+/// # use horned_owl::ontology::set::SetOntology;
+/// let b = Build::new_rc();
+/// let mut o = SetOntology::new_rc();
+/// o.insert(SubClassOf {
+///     sub: b.class("http://www.example.com/a").into(),
+///     sup: b.class("http://www.example.com/a").into(),
+/// });
+///
+/// let p = Pipeline::new()
+///     .stage(|o: &mut SetOntology<_>| o.remove_reflexive_subclass_axioms())
+///     .stage(|o: &mut SetOntology<_>| o.dedup_annotations());
+/// assert_eq!(p.run(&mut o), vec![1, 0]);
+/// ```
+pub struct Pipeline<A: ForIRI> {
+    stages: Vec<Stage<A>>,
+}
+
+impl<A: ForIRI> Default for Pipeline<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: ForIRI> Pipeline<A> {
+    pub fn new() -> Pipeline<A> {
+        Pipeline { stages: vec![] }
+    }
+
+    /// Add a stage to the end of the pipeline.
+    ///
+    /// `f` should return the number of changes it made.
+    pub fn stage<F>(mut self, f: F) -> Pipeline<A>
+    where
+        F: Fn(&mut SetOntology<A>) -> usize + 'static,
+    {
+        self.stages.push(Box::new(f));
+        self
+    }
+
+    /// Return the number of stages in the pipeline.
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// Return true if the pipeline has no stages.
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Apply each stage to `o` in order, returning the number of
+    /// changes made by each.
+    pub fn run(&self, o: &mut SetOntology<A>) -> Vec<usize> {
+        self.stages.iter().map(|f| f(o)).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Pipeline;
+    use crate::model::*;
+    use crate::ontology::set::SetOntology;
+
+    #[test]
+    fn test_two_stages() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        let sub = |sub: &str, sup: &str| SubClassOf {
+            sub: b.class(sub).into(),
+            sup: b.class(sup).into(),
+        };
+        o.insert(sub("http://www.example.com/a", "http://www.example.com/a"));
+        o.insert(sub("http://www.example.com/a", "http://www.example.com/b"));
+        o.insert(sub("http://www.example.com/b", "http://www.example.com/c"));
+        o.insert(sub("http://www.example.com/c", "http://www.example.com/d"));
+
+        let mb = Build::new_rc();
+        let p = Pipeline::new()
+            .stage(|o: &mut SetOntology<_>| o.remove_reflexive_subclass_axioms())
+            .stage(move |o: &mut SetOntology<_>| o.materialize_subclass_closure(&mb));
+        assert_eq!(p.len(), 2);
+
+        assert_eq!(p.run(&mut o), vec![1, 3]);
+        assert_eq!(o.len(), 6);
+
+        // Running again changes nothing
+        assert_eq!(p.run(&mut o), vec![0, 0]);
+        assert!(Pipeline::new().run(&mut o).is_empty());
+    }
+}
@@ -0,0 +1,213 @@
+//! An index that provides rapid look up of the object property
+//! assertions about an individual
+
+//! # Overview
+//!
+//! The `PropertyAssertionMappedIndex` indexes
+//! `ObjectPropertyAssertion` axioms by the individual they are
+//! from, so that the neighbours of an individual can be found
+//! without a scan. An assertion of an inverse property is indexed in
+//! its [normalized](../../model/struct.ObjectPropertyAssertion.html#method.normalize)
+//! form, so an assertion of `inverse(p)` from `a` to `b` is found
+//! from `b`, with property `p`. Only asserted relationships are
+//! indexed; nothing is inferred.
+use crate::model::*;
+
+use super::indexed::ForIndex;
+use super::indexed::OntologyIndex;
+use super::indexed::ReindexInto;
+
+use std::collections::{BTreeSet, HashMap};
+
+#[derive(Debug, Default)]
+pub struct PropertyAssertionMappedIndex<A, AA>(HashMap<Individual<A>, BTreeSet<AA>>);
+
+impl<A: ForIRI, AA: ForIndex<A>> PropertyAssertionMappedIndex<A, AA> {
+    pub fn new() -> PropertyAssertionMappedIndex<A, AA> {
+        PropertyAssertionMappedIndex(HashMap::new())
+    }
+
+    /// Return the object properties asserted from `subject`, with
+    /// the individual each relates it to, sorted so that the
+    /// assertions of each property are together.
+    ///
+    /// Each relationship is returned once, even if it is asserted
+    /// by several axioms, which may differ in their annotations, or
+    /// in whether they use an inverse property.
+    pub fn properties_of(
+        &self,
+        subject: &Individual<A>,
+    ) -> impl Iterator<Item = (ObjectPropertyExpression<A>, Individual<A>)> {
+        self.0
+            .get(subject)
+            .into_iter()
+            .flat_map(|axioms| axioms.iter())
+            .filter_map(|aa| Self::aa_to_assertion(aa.borrow()))
+            .map(|opa| (opa.ope, opa.to))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+    }
+
+    fn aa_to_assertion(ax: &AnnotatedAxiom<A>) -> Option<ObjectPropertyAssertion<A>> {
+        match &ax.axiom {
+            Axiom::ObjectPropertyAssertion(opa) => Some(opa.normalize()),
+            _ => None,
+        }
+    }
+}
+
+impl<A: ForIRI, AA: ForIndex<A>> OntologyIndex<A, AA> for PropertyAssertionMappedIndex<A, AA> {
+    fn index_insert(&mut self, ax: AA) -> bool {
+        if let Some(opa) = Self::aa_to_assertion(ax.borrow()) {
+            self.0.entry(opa.from).or_default().insert(ax)
+        } else {
+            false
+        }
+    }
+
+    fn index_remove(&mut self, ax: &AnnotatedAxiom<A>) -> bool {
+        let opa = match Self::aa_to_assertion(ax) {
+            Some(opa) => opa,
+            None => return false,
+        };
+
+        let axioms = match self.0.get_mut(&opa.from) {
+            Some(axioms) => axioms,
+            None => return false,
+        };
+
+        let rtn = axioms.remove(ax);
+        if axioms.is_empty() {
+            self.0.remove(&opa.from);
+        }
+        rtn
+    }
+}
+
+impl<A: ForIRI, AA: ForIndex<A>, B: ForIRI, BB: ForIndex<B>> ReindexInto<B, BB>
+    for PropertyAssertionMappedIndex<A, AA>
+{
+    type Output = PropertyAssertionMappedIndex<B, BB>;
+
+    fn empty_index(&self) -> PropertyAssertionMappedIndex<B, BB> {
+        PropertyAssertionMappedIndex::new()
+    }
+}
+
+impl PropertyAssertionMappedIndex<RcStr, RcAnnotatedAxiom> {
+    pub fn new_rc() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PropertyAssertionMappedIndex;
+    use crate::model::*;
+    use crate::ontology::{indexed::TwoIndexedOntology, set::SetIndex};
+
+    #[test]
+    fn test_properties_of() {
+        let b = Build::new_rc();
+        let mut o = TwoIndexedOntology::new(
+            SetIndex::new_rc(),
+            PropertyAssertionMappedIndex::new_rc(),
+            Default::default(),
+        );
+        let p: ObjectPropertyExpression<_> = b.object_property("http://www.example.com/p").into();
+        let q: ObjectPropertyExpression<_> = b.object_property("http://www.example.com/q").into();
+        let i = |s: &str| -> Individual<_> {
+            b.named_individual(format!("http://www.example.com/{}", s))
+                .into()
+        };
+        let opa =
+            |ope: &ObjectPropertyExpression<_>, from: &str, to: &str| ObjectPropertyAssertion {
+                ope: ope.clone(),
+                from: i(from),
+                to: i(to),
+            };
+
+        o.insert(opa(&q, "a", "b"));
+        o.insert(opa(&p, "a", "c"));
+        o.insert(opa(&p, "a", "b"));
+        // The same as p from a to d
+        o.insert(opa(&p.inverse(), "d", "a"));
+        o.insert(opa(&p, "b", "a"));
+
+        let (ib, ic, id) = (i("b"), i("c"), i("d"));
+        assert_eq!(
+            o.j().properties_of(&i("a")).collect::<Vec<_>>(),
+            vec![
+                (p.clone(), ib.clone()),
+                (p.clone(), ic),
+                (p.clone(), id.clone()),
+                (q.clone(), ib)
+            ]
+        );
+        assert_eq!(o.j().properties_of(&i("d")).count(), 0);
+
+        // Both forms of the same assertion must be removed
+        let pd = (p.clone(), id.clone());
+        o.insert(opa(&p, "a", "d"));
+        o.remove(&opa(&p.inverse(), "d", "a").into());
+        assert!(o.j().properties_of(&i("a")).any(|n| n == pd));
+        o.remove(&opa(&p, "a", "d").into());
+        assert!(!o.j().properties_of(&i("a")).any(|n| n == pd));
+
+        o.remove(&opa(&p, "b", "a").into());
+        assert_eq!(o.j().properties_of(&i("b")).count(), 0);
+    }
+
+    #[test]
+    fn test_duplicate_insert() {
+        let b = Build::new_rc();
+        let mut o = TwoIndexedOntology::new(
+            SetIndex::new_rc(),
+            PropertyAssertionMappedIndex::new_rc(),
+            Default::default(),
+        );
+        let opa: AnnotatedAxiom<_> = ObjectPropertyAssertion {
+            ope: b.object_property("http://www.example.com/p").into(),
+            from: b.named_individual("http://www.example.com/a").into(),
+            to: b.named_individual("http://www.example.com/b").into(),
+        }
+        .into();
+        let a: Individual<_> = b.named_individual("http://www.example.com/a").into();
+
+        assert!(o.insert(opa.clone()));
+        assert!(!o.insert(opa.clone()));
+        assert!(o.remove(&opa));
+        assert_eq!(o.i().len(), 0);
+        assert_eq!(o.j().properties_of(&a).count(), 0);
+    }
+
+    #[test]
+    fn test_remove_absent_inverse_form() {
+        let b = Build::new_rc();
+        let mut o = TwoIndexedOntology::new(
+            SetIndex::new_rc(),
+            PropertyAssertionMappedIndex::new_rc(),
+            Default::default(),
+        );
+        let p: ObjectPropertyExpression<_> = b.object_property("http://www.example.com/p").into();
+        let a: Individual<_> = b.named_individual("http://www.example.com/a").into();
+        let c: Individual<_> = b.named_individual("http://www.example.com/c").into();
+
+        o.insert(ObjectPropertyAssertion {
+            ope: p.clone(),
+            from: a.clone(),
+            to: c.clone(),
+        });
+        // The same relationship, but not the axiom which was inserted
+        assert!(!o.remove(
+            &ObjectPropertyAssertion {
+                ope: p.inverse(),
+                from: c.clone(),
+                to: a.clone(),
+            }
+            .into()
+        ));
+        assert_eq!(o.i().len(), 1);
+        assert_eq!(o.j().properties_of(&a).collect::<Vec<_>>(), vec![(p, c)]);
+    }
+}
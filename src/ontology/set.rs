@@ -1,14 +1,26 @@
 //! Rapid, simple, in-memory `Ontology` and `OntologyIndex`
-use std::{collections::HashSet, iter::FromIterator, rc::Rc};
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap, BTreeSet, HashMap, HashSet},
+    hash::{Hash, Hasher},
+    iter::FromIterator,
+    rc::Rc,
+};
 
+use super::hierarchy::HierarchyView;
 use super::indexed::ForIndex;
-use super::indexed::{OneIndexedOntology, OntologyIndex};
+use super::indexed::{OneIndexedOntology, OntologyIndex, ReindexInto};
 use crate::model::*;
+use crate::visitor::{entity::IRIExtract, Visit, VisitMut, Walk, WalkMut};
+use crate::vocab::{is_builtin_datatype, is_nothing, is_thing, AnnotationBuiltIn, WithIRI};
 use std::marker::PhantomData;
 
 /// An Ontology backed by a set. This should be the fastest and least
 /// overhead implementation of an ontology. It provides rapid testing
 /// of whether an equivalent axiom exists, and is iterable.
+///
+/// Its axioms must share its backing store: use `Build::new_rc`
+/// with `SetOntology::new_rc`, and `Build::new_arc` with
+/// `SetOntology::new_arc`. Mixing them is a compile error.
 #[derive(Debug, Eq, PartialEq)]
 pub struct SetOntology<A: ForIRI>(
     OneIndexedOntology<A, AnnotatedAxiom<A>, SetIndex<A, AnnotatedAxiom<A>>>,
@@ -26,12 +38,24 @@ impl<A: ForIRI> Default for SetOntology<A> {
     }
 }
 
+/// A `SetOntology` of `Rc` IRIs.
+pub type RcSetOntology = SetOntology<RcStr>;
+
+/// A `SetOntology` of `Arc` IRIs.
+pub type ArcSetOntology = SetOntology<ArcStr>;
+
 impl SetOntology<RcStr> {
     pub fn new_rc() -> SetOntology<RcStr> {
         SetOntology::new()
     }
 }
 
+impl SetOntology<ArcStr> {
+    pub fn new_arc() -> SetOntology<ArcStr> {
+        SetOntology::new()
+    }
+}
+
 impl<A: ForIRI> SetOntology<A> {
     /// Create a new ontology.
     ///
@@ -55,491 +79,5791 @@ impl<A: ForIRI> SetOntology<A> {
     pub fn iter(&self) -> SetIter<'_, A> {
         SetIter(self.0.i().0.iter())
     }
-}
 
-impl<A: ForIRI> Ontology<A> for SetOntology<A> {
-    fn id(&self) -> &OntologyID<A> {
-        self.0.id()
+    /// Return owned copies of the axioms for which `f` returns true.
+    ///
+    /// Unlike the result of `iter`, this does not borrow the
+    /// ontology, so can be moved to another thread. The axioms are
+    /// in no particular order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.declare(b.class("http://www.example.com/a"));
+    /// o.declare(b.object_property("http://www.example.com/r"));
+    ///
+    /// let classes = o.collect_axioms(|aa| aa.kind() == AxiomKind::DeclareClass);
+    /// assert_eq!(classes, vec![DeclareClass(b.class("http://www.example.com/a")).into()]);
+    /// ```
+    pub fn collect_axioms<F>(&self, mut f: F) -> Vec<AnnotatedAxiom<A>>
+    where
+        F: FnMut(&AnnotatedAxiom<A>) -> bool,
+    {
+        self.iter().filter(|aa| f(aa)).cloned().collect()
     }
 
-    fn mut_id(&mut self) -> &mut OntologyID<A> {
-        self.0.mut_id()
+    /// Returns the number of axioms in the ontology.
+    pub fn len(&self) -> usize {
+        self.0.i().len()
     }
 
-    fn doc_iri(&self) -> &Option<IRI<A>> {
-        self.0.doc_iri()
+    /// Returns true if the ontology contains no axioms.
+    ///
+    /// The `OntologyID` is not an axiom, so an ontology with only
+    /// an IRI is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.i().is_empty()
     }
 
-    fn mut_doc_iri(&mut self) -> &mut Option<IRI<A>> {
-        self.0.mut_doc_iri()
+    /// Return the number of successful changes made to this ontology.
+    ///
+    /// See `OneIndexedOntology::revision`.
+    pub fn revision(&self) -> u64 {
+        self.0.revision()
     }
-}
-
-/// An Interator for `SetOntology`
-pub struct SetIter<'a, A: ForIRI>(std::collections::hash_set::Iter<'a, AnnotatedAxiom<A>>);
 
-impl<'a, A: ForIRI> Iterator for SetIter<'a, A> {
-    type Item = &'a AnnotatedAxiom<A>;
-    fn next(&mut self) -> Option<Self::Item> {
-        self.0.next()
+    /// Return all datatypes used in the ontology.
+    ///
+    /// This includes datatypes in data ranges at any depth,
+    /// including those of datatype restrictions, and the datatypes
+    /// of typed literals.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.insert(DataPropertyRange {
+    ///     dp: b.data_property("http://www.example.com/dp"),
+    ///     dr: b.datatype("http://www.w3.org/2001/XMLSchema#string").into(),
+    /// });
+    ///
+    /// assert!(o.datatypes_used().contains(&b.datatype("http://www.w3.org/2001/XMLSchema#string")));
+    /// ```
+    pub fn datatypes_used(&self) -> HashSet<Datatype<A>> {
+        let mut walk = Walk::new(DatatypeExtract(HashSet::new()));
+        walk.set_ontology(self);
+        walk.into_visit().0
     }
-}
 
-impl<'a, A: ForIRI> IntoIterator for &'a SetOntology<A> {
-    type Item = &'a AnnotatedAxiom<A>;
-    type IntoIter = SetIter<'a, A>;
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter()
+    /// Return the kinds of axiom which occur in this ontology.
+    ///
+    /// This can be compared with the kinds which a writer supports,
+    /// to find those which it cannot write.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.declare(b.class("http://www.example.com/a"));
+    /// o.declare(b.class("http://www.example.com/b"));
+    ///
+    /// assert_eq!(
+    ///     o.axiom_kinds_present().into_iter().collect::<Vec<_>>(),
+    ///     vec![AxiomKind::DeclareClass]
+    /// );
+    /// ```
+    pub fn axiom_kinds_present(&self) -> BTreeSet<AxiomKind> {
+        self.iter().map(|aa| aa.kind()).collect()
     }
-}
-
-/// An owning iterator over the annotated axioms of an `Ontology`.
-pub struct SetIntoIter<A: ForIRI>(std::vec::IntoIter<AnnotatedAxiom<A>>);
 
-impl<A: ForIRI> Iterator for SetIntoIter<A> {
-    type Item = AnnotatedAxiom<A>;
-    fn next(&mut self) -> Option<Self::Item> {
-        self.0.next()
+    /// Return the annotation properties used to annotate something in
+    /// this ontology.
+    ///
+    /// This includes the properties of `AnnotationAssertion` axioms,
+    /// axiom annotations and ontology annotations, but not those
+    /// which are only declared or used in axioms about annotation
+    /// properties.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.insert(b.annotation_assertion(
+    ///     "http://www.example.com/a",
+    ///     "http://www.w3.org/2000/01/rdf-schema#label",
+    ///     "a",
+    /// ));
+    ///
+    /// assert!(o.annotation_properties_used().contains(
+    ///     &b.annotation_property("http://www.w3.org/2000/01/rdf-schema#label")
+    /// ));
+    /// ```
+    pub fn annotation_properties_used(&self) -> HashSet<AnnotationProperty<A>> {
+        let mut walk = Walk::new(AnnotationPropertyExtract(HashSet::new()));
+        walk.set_ontology(self);
+        walk.into_visit().0
     }
-}
 
-impl<A: ForIRI> IntoIterator for SetOntology<A> {
-    type Item = AnnotatedAxiom<A>;
-    type IntoIter = SetIntoIter<A>;
-    fn into_iter(self) -> Self::IntoIter {
-        SetIntoIter(self.0.index().into_iter())
+    /// Return the axioms of the ontology grouped by the annotation
+    /// properties of their annotations.
+    ///
+    /// Only the annotations on each axiom are considered, so an
+    /// `AnnotationAssertion` without annotations is grouped with
+    /// the other unannotated axioms under the empty set.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// # use std::collections::BTreeSet;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.declare(b.class("http://www.example.com/a"));
+    ///
+    /// let groups = o.group_by_annotation_properties();
+    /// assert_eq!(groups[&BTreeSet::new()].len(), 1);
+    /// ```
+    pub fn group_by_annotation_properties(
+        &self,
+    ) -> HashMap<BTreeSet<AnnotationProperty<A>>, Vec<&AnnotatedAxiom<A>>> {
+        let mut groups: HashMap<_, Vec<_>> = HashMap::new();
+        for aa in self.iter() {
+            let aps = aa.ann.iter().map(|ann| ann.ap.clone()).collect();
+            groups.entry(aps).or_default().push(aa);
+        }
+        groups
     }
-}
 
-impl<A: ForIRI> MutableOntology<A> for SetOntology<A> {
-    /// Insert an axiom into the ontology.
+    /// Return a row for each `DataPropertyAssertion` in the ontology.
+    ///
+    /// This flattens the assertions into a tabular form, for instance
+    /// for export to CSV; the datatype of each row is that given by
+    /// [`Literal::canonical_datatype`].
     ///
     /// # Examples
     /// ```
     /// # use horned_owl::model::*;
     /// # use horned_owl::ontology::set::SetOntology;
-    /// let mut o = SetOntology::new();
     /// let b = Build::new_rc();
-    /// o.insert(DeclareClass(b.class("http://www.example.com/a")));
-    /// o.insert(DeclareObjectProperty(b.object_property("http://www.example.com/r")));
-    /// ```
+    /// let mut o = SetOntology::new_rc();
+    /// o.insert(DataPropertyAssertion {
+    ///     dp: b.data_property("http://www.example.com/age"),
+    ///     from: b.named_individual("http://www.example.com/i").into(),
+    ///     to: Literal::Simple { literal: "x".into() },
+    /// });
     ///
-    /// See `declare` for an easier way to declare named entities.
-    fn insert<AA>(&mut self, ax: AA) -> bool
-    where
-        AA: Into<AnnotatedAxiom<A>>,
-    {
-        self.0.insert(ax)
+    /// let rows: Vec<_> = o.data_rows().collect();
+    /// assert_eq!(rows[0].value, "x");
+    /// ```
+    pub fn data_rows(&self) -> impl Iterator<Item = DataRow<A>> + '_ {
+        self.iter().filter_map(|aa| match &aa.axiom {
+            Axiom::DataPropertyAssertion(DataPropertyAssertion { dp, from, to }) => Some(DataRow {
+                subject: from.clone(),
+                property: dp.0.clone(),
+                value: to.literal().clone(),
+                datatype: to.canonical_datatype(),
+                lang: match to {
+                    Literal::Language { lang, .. } => Some(lang.clone()),
+                    _ => None,
+                },
+            }),
+            _ => None,
+        })
     }
 
-    fn remove(&mut self, ax: &AnnotatedAxiom<A>) -> bool {
-        self.0.remove(ax)
+    /// Return the number of annotation literals in each language.
+    ///
+    /// Language tags are compared case insensitively, so are
+    /// lowercased; literals without a language tag are counted under
+    /// the empty string. As with
+    /// [`annotation_properties_used`](#method.annotation_properties_used),
+    /// this covers `AnnotationAssertion` axioms, axiom annotations and
+    /// ontology annotations.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.insert(b.annotation_assertion(
+    ///     "http://www.example.com/a",
+    ///     "http://www.w3.org/2000/01/rdf-schema#label",
+    ///     Literal::Language {
+    ///         literal: "a".to_string(),
+    ///         lang: "EN".to_string(),
+    ///     },
+    /// ));
+    ///
+    /// assert_eq!(o.language_tag_histogram()["en"], 1);
+    /// ```
+    pub fn language_tag_histogram(&self) -> BTreeMap<String, usize> {
+        let mut walk = Walk::new(LanguageTagCount(BTreeMap::new()));
+        walk.set_ontology(self);
+        walk.into_visit().0
     }
 
-    fn take(&mut self, ax: &AnnotatedAxiom<A>) -> Option<AnnotatedAxiom<A>> {
-        self.0.take(ax)
-    }
-}
+    /// Return the entities with more than one distinct `rdfs:label`
+    /// in the same language, with the language and the labels.
+    ///
+    /// Labels are grouped by subject IRI and lowercased language tag;
+    /// labels without a language tag are grouped under the empty
+    /// string. The result is sorted, as are the labels of each group.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// let label = |l: &str| Literal::Language {
+    ///     literal: l.to_string(),
+    ///     lang: "en".to_string(),
+    /// };
+    /// for l in ["Colour", "Color"] {
+    ///     o.insert(b.annotation_assertion(
+    ///         "http://www.example.com/a",
+    ///         "http://www.w3.org/2000/01/rdf-schema#label",
+    ///         label(l),
+    ///     ));
+    /// }
+    ///
+    /// assert_eq!(
+    ///     o.conflicting_labels(),
+    ///     vec![(
+    ///         b.iri("http://www.example.com/a"),
+    ///         "en".to_string(),
+    ///         vec![label("Color"), label("Colour")]
+    ///     )]
+    /// );
+    /// ```
+    pub fn conflicting_labels(&self) -> Vec<(IRI<A>, String, Vec<Literal<A>>)> {
+        let mut labels: BTreeMap<(IRI<A>, String), BTreeSet<Literal<A>>> = BTreeMap::new();
+        for aa in self.iter() {
+            if let Axiom::AnnotationAssertion(AnnotationAssertion {
+                subject: AnnotationSubject::IRI(iri),
+                ann:
+                    Annotation {
+                        ap,
+                        av: AnnotationValue::Literal(l),
+                    },
+            }) = &aa.axiom
+            {
+                if ap.0.as_ref() == AnnotationBuiltIn::LABEL.iri_str() {
+                    let lang = match l {
+                        Literal::Language { lang, .. } => lang.to_lowercase(),
+                        _ => String::new(),
+                    };
+                    labels
+                        .entry((iri.clone(), lang))
+                        .or_default()
+                        .insert(l.clone());
+                }
+            }
+        }
 
-impl<A: ForIRI> FromIterator<AnnotatedAxiom<A>> for SetOntology<A> {
-    fn from_iter<I: IntoIterator<Item = AnnotatedAxiom<A>>>(iter: I) -> Self {
-        SetOntology(OneIndexedOntology::new(SetIndex(
-            HashSet::from_iter(iter),
-            Default::default(),
-        )))
+        labels
+            .into_iter()
+            .filter(|(_, l)| l.len() > 1)
+            .map(|((iri, lang), l)| (iri, lang, l.into_iter().collect()))
+            .collect()
     }
-}
 
-impl<A: ForIRI, I> From<(OntologyID<A>, I)> for SetOntology<A>
-where
-    I: Iterator<Item = AnnotatedAxiom<A>>,
-{
-    fn from((mut oid, i): (OntologyID<A>, I)) -> SetOntology<A> {
-        let mut so: SetOntology<_> = i.collect();
-        std::mem::swap(so.mut_id(), &mut oid);
-        so
-    }
-}
+    /// Return the preferred `rdfs:label` of each IRI which has one.
+    ///
+    /// If `lang` is given, a label with that language tag, compared
+    /// case insensitively, is preferred; otherwise, or if there is
+    /// none, a label without a language tag is used, and failing
+    /// that a label in any language. Where several labels are
+    /// equally preferred, the lexically first is chosen, so the
+    /// result is deterministic. IRIs without a label are omitted.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.insert(b.annotation_assertion(
+    ///     "http://www.example.com/a",
+    ///     "http://www.w3.org/2000/01/rdf-schema#label",
+    ///     Literal::Simple { literal: "Colour".into() },
+    /// ));
+    ///
+    /// let labels = o.label_map(Some("en"));
+    /// assert_eq!(labels[&b.iri("http://www.example.com/a")], "Colour");
+    /// ```
+    pub fn label_map(&self, lang: Option<&str>) -> HashMap<IRI<A>, String> {
+        let rank = |l: &Literal<A>| match (l, lang) {
+            (Literal::Language { lang: l, .. }, Some(lang)) if l.eq_ignore_ascii_case(lang) => 0,
+            (Literal::Language { .. }, _) => 2,
+            _ => 1,
+        };
 
-/*
-impl<A: ForIRI, O, I> From<(O, I)> for SetOntology<A>
-where
-    I: Iterator<Item = AnnotatedAxiom<A>>,
-    O: Ontology<A>,
-{
-    fn from((mut o, i): (O, I)) -> SetOntology<A> {
-        (o.mut_id().clone(), i).into()
+        let mut best: HashMap<IRI<A>, (u8, &String)> = HashMap::new();
+        for aa in self.iter() {
+            if let Axiom::AnnotationAssertion(AnnotationAssertion {
+                subject: AnnotationSubject::IRI(iri),
+                ann:
+                    Annotation {
+                        ap,
+                        av: AnnotationValue::Literal(l),
+                    },
+            }) = &aa.axiom
+            {
+                if ap.0.as_ref() == AnnotationBuiltIn::LABEL.iri_str() {
+                    let candidate = (rank(l), l.literal());
+                    best.entry(iri.clone())
+                        .and_modify(|b| *b = (*b).min(candidate))
+                        .or_insert(candidate);
+                }
+            }
+        }
+
+        best.into_iter()
+            .map(|(iri, (_, label))| (iri, label.clone()))
+            .collect()
     }
-}
-*/
 
-/// An `OntologyIndex` implemented over an in-memory HashSet. When
-/// combined with an `IndexedOntology` this should be nearly as
-/// fastest as `SetOntology`.
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
-pub struct SetIndex<A: ForIRI, AA: ForIndex<A>>(HashSet<AA>, PhantomData<A>);
+    /// Return every class expression in the ontology, including
+    /// those nested within other class expressions.
+    ///
+    /// Axioms are visited in sorted order, and the class expressions
+    /// of each axiom in pre-order, so the order is deterministic. A
+    /// class expression is returned each time that it occurs.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.insert(SubClassOf {
+    ///     sub: b.class("http://www.example.com/a").into(),
+    ///     sup: ClassExpression::ObjectComplementOf(
+    ///         Box::new(b.class("http://www.example.com/b").into())
+    ///     ),
+    /// });
+    ///
+    /// assert_eq!(o.all_class_expressions().count(), 3);
+    /// ```
+    pub fn all_class_expressions(&self) -> impl Iterator<Item = ClassExpression<A>> {
+        let mut axioms: Vec<_> = self.iter().collect();
+        axioms.sort();
 
-impl<A: ForIRI, AA: ForIndex<A>> OntologyIndex<A, AA> for SetIndex<A, AA> {
-    fn index_insert(&mut self, ax: AA) -> bool {
-        self.0.insert(ax)
+        let mut walk = Walk::new(ClassExpressionExtract(vec![]));
+        for aa in axioms {
+            walk.annotated_axiom(aa);
+        }
+        walk.into_visit().0.into_iter()
     }
 
-    fn index_remove(&mut self, ax: &AnnotatedAxiom<A>) -> bool {
-        self.0.remove(ax)
+    /// Return the signature of the ontology, that is every named
+    /// entity used in any axiom, including axiom annotations.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.insert(SubClassOf {
+    ///     sub: b.class("http://www.example.com/a").into(),
+    ///     sup: b.class("http://www.example.com/b").into(),
+    /// });
+    ///
+    /// assert_eq!(o.signature().len(), 2);
+    /// ```
+    pub fn signature(&self) -> HashSet<NamedEntity<A>> {
+        signature_of(self)
     }
-}
 
-impl<A: ForIRI, AA: ForIndex<A>> SetIndex<A, AA> {
-    pub fn new() -> SetIndex<A, AA> {
-        SetIndex(Default::default(), Default::default())
+    /// Return the named entities in the signature of this ontology
+    /// which are not in the signature of `other`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.declare(b.class("http://www.example.com/a"));
+    /// o.declare(b.class("http://www.example.com/b"));
+    /// let mut other = SetOntology::new_rc();
+    /// other.declare(b.class("http://www.example.com/b"));
+    ///
+    /// assert_eq!(
+    ///     o.signature_difference(&other),
+    ///     vec![b.class("http://www.example.com/a").into()].into_iter().collect()
+    /// );
+    /// ```
+    pub fn signature_difference<O>(&self, other: &O) -> HashSet<NamedEntity<A>>
+    where
+        O: Ontology<A>,
+        for<'a> &'a O: IntoIterator<Item = &'a AnnotatedAxiom<A>>,
+    {
+        let theirs = signature_of(other);
+        self.signature()
+            .into_iter()
+            .filter(|e| !theirs.contains(e))
+            .collect()
     }
 
-    pub fn contains(&self, ax: &AnnotatedAxiom<A>) -> bool {
-        self.0.contains(ax)
-    }
-}
+    /// Return the number of axioms which use each named entity.
+    ///
+    /// An entity is counted once for each axiom in which it appears
+    /// at any depth; the declaration of an entity does not count as
+    /// a use, so declared but otherwise unused entities have a count
+    /// of zero.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.declare(b.class("http://www.example.com/a"));
+    ///
+    /// assert_eq!(o.usage_counts()[&b.class("http://www.example.com/a").into()], 0);
+    /// ```
+    pub fn usage_counts(&self) -> HashMap<NamedEntity<A>, usize> {
+        let mut counts = HashMap::new();
+        for aa in self.iter() {
+            let mut walk = Walk::new(NamedEntityExtract(HashSet::new()));
+            walk.annotated_axiom(aa);
+            let mut used = walk.into_visit().0;
 
-impl SetIndex<RcStr, Rc<AnnotatedAxiom<RcStr>>> {
-    pub fn new_rc() -> Self {
-        Self::new()
+            if let Some(e) = aa.axiom.as_entity() {
+                used.remove(&e);
+                counts.entry(e).or_insert(0);
+            }
+
+            for e in used {
+                *counts.entry(e).or_insert(0) += 1;
+            }
+        }
+        counts
     }
-}
 
-impl<A: ForIRI, AA: ForIndex<A>> IntoIterator for SetIndex<A, AA> {
-    type Item = AnnotatedAxiom<A>;
-    type IntoIter = std::vec::IntoIter<AnnotatedAxiom<A>>;
-    fn into_iter(self) -> Self::IntoIter {
-        #[allow(clippy::needless_collect)]
-        let v: Vec<AnnotatedAxiom<_>> = self.0.into_iter().map(|fi| fi.unwrap()).collect();
-        v.into_iter()
+    /// Return all IRIs which are used as more than one kind of entity.
+    ///
+    /// Some puns are legal in OWL2 DL (for instance, a class and an
+    /// individual) and some are not (for instance, a class and a
+    /// datatype); all are returned here.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.declare(b.class("http://www.example.com/a"));
+    /// o.declare(b.datatype("http://www.example.com/a"));
+    ///
+    /// assert!(o.punned_iris().contains(&b.iri("http://www.example.com/a")));
+    /// ```
+    pub fn punned_iris(&self) -> HashSet<IRI<A>> {
+        self.entity_kinds()
+            .into_iter()
+            .filter(|(_, k)| k.len() > 1)
+            .map(|(iri, _)| iri)
+            .collect()
     }
-}
 
-impl<'a, A: ForIRI, AA: ForIndex<A>> IntoIterator for &'a SetIndex<A, AA> {
-    type Item = &'a AnnotatedAxiom<A>;
-    type IntoIter = std::vec::IntoIter<&'a AnnotatedAxiom<A>>;
-    fn into_iter(self) -> Self::IntoIter {
-        #[allow(clippy::needless_collect)]
-        let v: Vec<&'a AnnotatedAxiom<A>> = self.0.iter().map(|fiac| fiac.borrow()).collect();
-        v.into_iter()
+    /// Return the kinds of entity as which each IRI in the ontology
+    /// is used.
+    fn entity_kinds(&self) -> HashMap<IRI<A>, HashSet<NamedEntityKind>> {
+        let mut walk = Walk::new(NamedEntityExtract(HashSet::new()));
+        walk.set_ontology(self);
+
+        let mut kinds: HashMap<IRI<A>, HashSet<NamedEntityKind>> = HashMap::new();
+        for e in walk.into_visit().0 {
+            let (iri, kind) = entity_iri_kind(e);
+            kinds.entry(iri).or_default().insert(kind);
+        }
+        kinds
     }
-}
 
-#[cfg(test)]
+    /// Return the entities which are declared more than once, in
+    /// sorted order.
+    ///
+    /// As identical axioms are stored once, this happens when the
+    /// same declaration occurs with different axiom annotations, or
+    /// with and without annotations.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// let a = b.class("http://www.example.com/a");
+    /// o.declare(a.clone());
+    /// o.insert(AnnotatedAxiom::new(
+    ///     DeclareClass(a.clone()),
+    ///     vec![Annotation {
+    ///         ap: b.annotation_property("http://www.w3.org/2000/01/rdf-schema#comment"),
+    ///         av: "A class".into(),
+    ///     }]
+    ///     .into_iter()
+    ///     .collect(),
+    /// ));
+    ///
+    /// assert_eq!(o.redundant_declarations(), vec![a.into()]);
+    /// ```
+    pub fn redundant_declarations(&self) -> Vec<NamedEntity<A>> {
+        let mut counts: HashMap<NamedEntity<A>, usize> = HashMap::new();
+        for e in self.iter().filter_map(|aa| aa.axiom.as_entity()) {
+            *counts.entry(e).or_default() += 1;
+        }
+
+        let mut redundant: Vec<_> = counts
+            .into_iter()
+            .filter(|(_, n)| *n > 1)
+            .map(|(e, _)| e)
+            .collect();
+        redundant.sort_by_cached_key(|e| entity_iri_kind(e.clone()));
+        redundant
+    }
+
+    /// Return the IRIs which are the subject of an
+    /// `AnnotationAssertion` but are not declared as any entity, in
+    /// sorted order.
+    ///
+    /// These are often annotations left behind when an entity is
+    /// deleted. Anonymous individuals cannot be declared, so are
+    /// never returned.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.insert(b.annotation_assertion(
+    ///     "http://www.example.com/deleted",
+    ///     "http://www.w3.org/2000/01/rdf-schema#label",
+    ///     "Deleted",
+    /// ));
+    ///
+    /// assert_eq!(
+    ///     o.dangling_annotation_subjects(),
+    ///     vec![b.iri("http://www.example.com/deleted")]
+    /// );
+    /// ```
+    pub fn dangling_annotation_subjects(&self) -> Vec<IRI<A>> {
+        let declared: HashSet<IRI<A>> = self
+            .iter()
+            .filter_map(|aa| aa.axiom.as_entity())
+            .map(|e| entity_iri_kind(e).0)
+            .collect();
+
+        let dangling: BTreeSet<IRI<A>> = self
+            .iter()
+            .filter_map(|aa| match &aa.axiom {
+                Axiom::AnnotationAssertion(AnnotationAssertion {
+                    subject: AnnotationSubject::IRI(iri),
+                    ..
+                }) if !declared.contains(iri) => Some(iri.clone()),
+                _ => None,
+            })
+            .collect();
+        dangling.into_iter().collect()
+    }
+
+    /// Return the entities which are marked as deprecated.
+    ///
+    /// An entity is deprecated if its IRI is the subject of an
+    /// `owl:deprecated` annotation assertion with the `xsd:boolean`
+    /// value true. All entities with that IRI are returned, so a
+    /// punned IRI may give more than one.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// let a = b.class("http://www.example.com/a");
+    /// o.declare(a.clone());
+    /// o.insert(b.annotation_assertion(
+    ///     "http://www.example.com/a",
+    ///     "http://www.w3.org/2002/07/owl#deprecated",
+    ///     Literal::Datatype {
+    ///         literal: "true".to_string(),
+    ///         datatype_iri: b.iri("http://www.w3.org/2001/XMLSchema#boolean"),
+    ///     },
+    /// ));
+    ///
+    /// assert_eq!(o.deprecated_entities(), vec![a.into()].into_iter().collect());
+    /// ```
+    pub fn deprecated_entities(&self) -> HashSet<NamedEntity<A>> {
+        let iris = self.deprecated_iris();
+        self.signature()
+            .into_iter()
+            .filter(|e| iris.contains(&entity_iri_kind(e.clone()).0))
+            .collect()
+    }
+
+    /// Return a copy of this ontology without its deprecated entities.
+    ///
+    /// Every axiom which uses a deprecated entity, as found by
+    /// [`deprecated_entities`](#method.deprecated_entities), is
+    /// removed, as is every annotation assertion about a deprecated
+    /// IRI.
+    pub fn without_deprecated(&self) -> SetOntology<A> {
+        let iris = self.deprecated_iris();
+        let deprecated = self.deprecated_entities();
+
+        let mut so: SetOntology<A> = self
+            .iter()
+            .filter(|aa| match &aa.axiom {
+                Axiom::AnnotationAssertion(AnnotationAssertion {
+                    subject: AnnotationSubject::IRI(iri),
+                    ..
+                }) if iris.contains(iri) => false,
+                _ => signature_of(std::iter::once(*aa)).is_disjoint(&deprecated),
+            })
+            .cloned()
+            .collect();
+
+        *so.mut_id() = self.id().clone();
+        *so.mut_doc_iri() = self.doc_iri().clone();
+        so
+    }
+
+    /// Return a copy of this ontology in which every n-ary class and
+    /// property axiom relates exactly two operands.
+    ///
+    /// Disjointness axioms are replaced by one axiom for each pair of
+    /// operands, and equivalence axioms by a chain of axioms relating
+    /// each operand to the next, which together have the same
+    /// meaning. Each generated axiom has the annotations of the axiom
+    /// it replaces. `DisjointUnion` axioms are not changed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.insert(EquivalentClasses(vec![
+    ///     b.class("http://www.example.com/a").into(),
+    ///     b.class("http://www.example.com/b").into(),
+    ///     b.class("http://www.example.com/c").into(),
+    /// ]));
+    ///
+    /// assert_eq!(o.binarize().len(), 2);
+    /// ```
+    pub fn binarize(&self) -> SetOntology<A> {
+        fn binary<T: Clone>(v: &[T], pairwise: bool) -> Vec<Vec<T>> {
+            if pairwise {
+                (0..v.len())
+                    .flat_map(|i| (i + 1..v.len()).map(move |j| vec![v[i].clone(), v[j].clone()]))
+                    .collect()
+            } else {
+                v.windows(2).map(|w| w.to_vec()).collect()
+            }
+        }
+
+        let mut so: SetOntology<A> = self
+            .iter()
+            .flat_map(|aa| {
+                let axioms: Vec<Axiom<A>> = match &aa.axiom {
+                    Axiom::EquivalentClasses(EquivalentClasses(v)) if v.len() > 2 => {
+                        binary(v, false)
+                            .into_iter()
+                            .map(|v| EquivalentClasses(v).into())
+                            .collect()
+                    }
+                    Axiom::DisjointClasses(DisjointClasses(v)) if v.len() > 2 => binary(v, true)
+                        .into_iter()
+                        .map(|v| DisjointClasses(v).into())
+                        .collect(),
+                    Axiom::EquivalentObjectProperties(EquivalentObjectProperties(v))
+                        if v.len() > 2 =>
+                    {
+                        binary(v, false)
+                            .into_iter()
+                            .map(|v| EquivalentObjectProperties(v).into())
+                            .collect()
+                    }
+                    Axiom::DisjointObjectProperties(DisjointObjectProperties(v)) if v.len() > 2 => {
+                        binary(v, true)
+                            .into_iter()
+                            .map(|v| DisjointObjectProperties(v).into())
+                            .collect()
+                    }
+                    Axiom::EquivalentDataProperties(EquivalentDataProperties(v)) if v.len() > 2 => {
+                        binary(v, false)
+                            .into_iter()
+                            .map(|v| EquivalentDataProperties(v).into())
+                            .collect()
+                    }
+                    Axiom::DisjointDataProperties(DisjointDataProperties(v)) if v.len() > 2 => {
+                        binary(v, true)
+                            .into_iter()
+                            .map(|v| DisjointDataProperties(v).into())
+                            .collect()
+                    }
+                    axiom => vec![axiom.clone()],
+                };
+                axioms
+                    .into_iter()
+                    .map(move |axiom| AnnotatedAxiom::new(axiom, aa.ann.clone()))
+            })
+            .collect();
+
+        *so.mut_id() = self.id().clone();
+        *so.mut_doc_iri() = self.doc_iri().clone();
+        so
+    }
+
+    fn deprecated_iris(&self) -> HashSet<IRI<A>> {
+        self.iter()
+            .filter_map(|aa| match &aa.axiom {
+                Axiom::AnnotationAssertion(AnnotationAssertion {
+                    subject: AnnotationSubject::IRI(iri),
+                    ann:
+                        Annotation {
+                            ap,
+                            av: AnnotationValue::Literal(l),
+                        },
+                }) if ap.0.as_ref() == AnnotationBuiltIn::DEPRECATED.iri_str()
+                    && l.as_bool() == Some(true) =>
+                {
+                    Some(iri.clone())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Return pairs of named classes which are asserted to be both
+    /// equivalent and disjoint.
+    ///
+    /// This is a syntactic check which does not need a reasoner; it
+    /// only finds classes which appear directly in the same
+    /// `EquivalentClasses` and `DisjointClasses` axioms. Each pair is
+    /// returned once, in sorted order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// let a = b.class("http://www.example.com/a");
+    /// let c = b.class("http://www.example.com/c");
+    /// o.insert(EquivalentClasses(vec![a.clone().into(), c.clone().into()]));
+    /// o.insert(DisjointClasses(vec![c.clone().into(), a.clone().into()]));
+    ///
+    /// assert_eq!(o.obvious_disjointness_conflicts(), vec![(a, c)]);
+    /// ```
+    pub fn obvious_disjointness_conflicts(&self) -> Vec<(Class<A>, Class<A>)> {
+        let mut equivalent = HashSet::new();
+        let mut disjoint = BTreeSet::new();
+        for aa in self.iter() {
+            match &aa.axiom {
+                Axiom::EquivalentClasses(EquivalentClasses(ces)) => {
+                    equivalent.extend(named_class_pairs(ces))
+                }
+                Axiom::DisjointClasses(DisjointClasses(ces)) => {
+                    disjoint.extend(named_class_pairs(ces))
+                }
+                _ => {}
+            }
+        }
+
+        disjoint
+            .into_iter()
+            .filter(|p| equivalent.contains(p))
+            .collect()
+    }
+
+    /// Return true if named classes `a` and `b` are asserted to be
+    /// disjoint.
+    ///
+    /// This is a cheap check which does not need a reasoner. `a` and
+    /// `b` are disjoint if they, or any of their asserted named
+    /// superclasses, appear together in a `DisjointClasses` axiom.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// let animal = b.class("http://www.example.com/animal");
+    /// let plant = b.class("http://www.example.com/plant");
+    /// let dog = b.class("http://www.example.com/dog");
+    /// o.insert(DisjointClasses(vec![animal.clone().into(), plant.clone().into()]));
+    /// o.insert(SubClassOf {
+    ///     sub: dog.clone().into(),
+    ///     sup: animal.into(),
+    /// });
+    ///
+    /// assert!(o.asserted_disjoint(&dog, &plant));
+    /// ```
+    pub fn asserted_disjoint(&self, a: &Class<A>, b: &Class<A>) -> bool {
+        let mut hierarchy = HierarchyView::new();
+        let mut disjoint = HashSet::new();
+        for aa in self.iter() {
+            match &aa.axiom {
+                Axiom::SubClassOf(SubClassOf {
+                    sub: ClassExpression::Class(sub),
+                    sup: ClassExpression::Class(sup),
+                }) => {
+                    hierarchy.insert(sub.clone().into(), sup.clone().into());
+                }
+                Axiom::DisjointClasses(DisjointClasses(ces)) => {
+                    disjoint.extend(named_class_pairs(ces))
+                }
+                _ => {}
+            }
+        }
+        let ancestors = |c: &Class<A>| {
+            let c: NamedEntity<A> = c.clone().into();
+            let mut anc = hierarchy.super_properties(&c);
+            anc.insert(c);
+            anc.into_iter().filter_map(|e| match e {
+                NamedEntity::Class(c) => Some(c),
+                _ => None,
+            })
+        };
+
+        let b_ancestors: Vec<_> = ancestors(b).collect();
+        ancestors(a).any(|x| {
+            b_ancestors.iter().any(|y| {
+                let pair = if x < *y {
+                    (x.clone(), y.clone())
+                } else {
+                    (y.clone(), x.clone())
+                };
+                disjoint.contains(&pair)
+            })
+        })
+    }
+
+    /// Return the syntactic reasons that this ontology is not OWL2
+    /// DL.
+    ///
+    /// This is not a full validator, but covers the common
+    /// violations: IRIs punned as both class and datatype, or as
+    /// more than one kind of property; transitive object properties
+    /// used in cardinality restrictions; and entities which are used
+    /// without being declared. Built-in vocabulary need not be
+    /// declared. Issues are returned in that order, and sorted
+    /// within each kind.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::{DlIssue, SetOntology};
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.declare(b.class("http://www.example.com/a"));
+    /// o.declare(b.datatype("http://www.example.com/a"));
+    ///
+    /// assert_eq!(
+    ///     o.dl_syntactic_issues(),
+    ///     vec![DlIssue::IllegalPun(b.iri("http://www.example.com/a"))]
+    /// );
+    /// ```
+    pub fn dl_syntactic_issues(&self) -> Vec<DlIssue<A>> {
+        let mut puns: Vec<_> = self
+            .entity_kinds()
+            .into_iter()
+            .filter(|(_, k)| {
+                let properties = [
+                    NamedEntityKind::ObjectProperty,
+                    NamedEntityKind::DataProperty,
+                    NamedEntityKind::AnnotationProperty,
+                ];
+                (k.contains(&NamedEntityKind::Class) && k.contains(&NamedEntityKind::Datatype))
+                    || properties.iter().filter(|p| k.contains(p)).count() > 1
+            })
+            .map(|(iri, _)| iri)
+            .collect();
+        puns.sort();
+
+        let transitive: HashSet<&ObjectProperty<A>> = self
+            .iter()
+            .filter_map(|aa| match &aa.axiom {
+                Axiom::TransitiveObjectProperty(TransitiveObjectProperty(ope)) => {
+                    Some(named_object_property(ope))
+                }
+                _ => None,
+            })
+            .collect();
+        let non_simple: BTreeSet<ObjectProperty<A>> = self
+            .all_class_expressions()
+            .filter_map(|ce| match ce {
+                ClassExpression::ObjectMinCardinality { ope, .. }
+                | ClassExpression::ObjectMaxCardinality { ope, .. }
+                | ClassExpression::ObjectExactCardinality { ope, .. } => {
+                    Some(named_object_property(&ope).clone())
+                }
+                _ => None,
+            })
+            .filter(|op| transitive.contains(op))
+            .collect();
+
+        let builtin: HashSet<&str> = crate::vocab::all_builtin_iris().into_iter().collect();
+        let declared: HashSet<NamedEntity<A>> =
+            self.iter().filter_map(|aa| aa.axiom.as_entity()).collect();
+        let mut undeclared: Vec<_> = self
+            .signature()
+            .into_iter()
+            .filter(|e| !declared.contains(e))
+            .filter(|e| match entity_iri_kind(e.clone()) {
+                (iri, NamedEntityKind::Datatype) if is_builtin_datatype(&iri) => false,
+                (iri, _) => !builtin.contains(iri.as_ref()),
+            })
+            .collect();
+        undeclared.sort_by_cached_key(|e| entity_iri_kind(e.clone()));
+
+        puns.into_iter()
+            .map(DlIssue::IllegalPun)
+            .chain(non_simple.into_iter().map(DlIssue::NonSimpleCardinality))
+            .chain(undeclared.into_iter().map(DlIssue::Undeclared))
+            .collect()
+    }
+
+    /// Return the axioms, in sorted order, which use a property as a
+    /// different kind of property to that which it is declared as.
+    ///
+    /// For example, an object restriction whose property IRI is only
+    /// declared as a data property is returned. Properties which are
+    /// not declared as any kind of property are not checked.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.declare(b.data_property("http://www.example.com/p"));
+    /// let sc: AnnotatedAxiom<_> = SubClassOf {
+    ///     sub: b.class("http://www.example.com/a").into(),
+    ///     sup: ClassExpression::ObjectSomeValuesFrom {
+    ///         ope: b.object_property("http://www.example.com/p").into(),
+    ///         bce: Box::new(b.class("http://www.example.com/b").into()),
+    ///     },
+    /// }
+    /// .into();
+    /// o.insert(sc.clone());
+    ///
+    /// assert_eq!(o.property_type_mismatches(), vec![sc]);
+    /// ```
+    pub fn property_type_mismatches(&self) -> Vec<AnnotatedAxiom<A>> {
+        let properties = [
+            NamedEntityKind::ObjectProperty,
+            NamedEntityKind::DataProperty,
+            NamedEntityKind::AnnotationProperty,
+        ];
+        let mut declared: HashMap<IRI<A>, HashSet<NamedEntityKind>> = HashMap::new();
+        for e in self.iter().filter_map(|aa| aa.axiom.as_entity()) {
+            let (iri, kind) = entity_iri_kind(e);
+            if properties.contains(&kind) {
+                declared.entry(iri).or_default().insert(kind);
+            }
+        }
+
+        let mut mismatches: Vec<_> = self
+            .iter()
+            .filter(|aa| {
+                signature_of(std::iter::once(*aa)).into_iter().any(|e| {
+                    let (iri, kind) = entity_iri_kind(e);
+                    properties.contains(&kind)
+                        && declared.get(&iri).is_some_and(|k| !k.contains(&kind))
+                })
+            })
+            .cloned()
+            .collect();
+        mismatches.sort();
+        mismatches
+    }
+
+    /// Return the asserted subclass relationships between named
+    /// classes, as `(sub, sup)` pairs.
+    pub(crate) fn named_subclass_edges(&self) -> BTreeSet<(Class<A>, Class<A>)> {
+        self.iter()
+            .filter_map(|aa| match &aa.axiom {
+                Axiom::SubClassOf(SubClassOf {
+                    sub: ClassExpression::Class(sub),
+                    sup: ClassExpression::Class(sup),
+                }) => Some((sub.clone(), sup.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Return every asserted subsumption between class expressions,
+    /// as sorted `(sub, sup)` pairs.
+    ///
+    /// Each `SubClassOf` axiom gives one pair, and each
+    /// `EquivalentClasses` axiom gives a pair in both directions
+    /// between each two of its class expressions. Annotations are
+    /// ignored, so a subsumption asserted more than once occurs once.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// let a: ClassExpression<_> = b.class("http://www.example.com/a").into();
+    /// let c: ClassExpression<_> = b.class("http://www.example.com/c").into();
+    /// o.insert(SubClassOf {
+    ///     sub: a.clone(),
+    ///     sup: c.clone(),
+    /// });
+    ///
+    /// assert_eq!(o.subsumption_pairs(), vec![(a, c)]);
+    /// ```
+    pub fn subsumption_pairs(&self) -> Vec<(ClassExpression<A>, ClassExpression<A>)> {
+        let mut pairs = BTreeSet::new();
+        for aa in self.iter() {
+            match &aa.axiom {
+                Axiom::SubClassOf(SubClassOf { sub, sup }) => {
+                    pairs.insert((sub.clone(), sup.clone()));
+                }
+                Axiom::EquivalentClasses(EquivalentClasses(v)) => {
+                    for (i, sub) in v.iter().enumerate() {
+                        for (j, sup) in v.iter().enumerate() {
+                            if i != j {
+                                pairs.insert((sub.clone(), sup.clone()));
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        pairs.into_iter().collect()
+    }
+
+    /// Return the asserted object and data sub-property hierarchy.
+    ///
+    /// This is built from `SubObjectPropertyOf` axioms between two
+    /// named object properties, and `SubDataPropertyOf` axioms.
+    /// Property chains and inverse properties are not simple
+    /// sub-property relationships, so are excluded.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.insert(SubDataPropertyOf {
+    ///     sub: b.data_property("http://www.example.com/a"),
+    ///     sup: b.data_property("http://www.example.com/b"),
+    /// });
+    ///
+    /// let h = o.sub_property_hierarchy();
+    /// assert!(h
+    ///     .direct_super_properties(&b.data_property("http://www.example.com/a").into())
+    ///     .contains(&b.data_property("http://www.example.com/b").into()));
+    /// ```
+    pub fn sub_property_hierarchy(&self) -> HierarchyView<A> {
+        let mut h = HierarchyView::new();
+        for aa in self.iter() {
+            match &aa.axiom {
+                Axiom::SubObjectPropertyOf(SubObjectPropertyOf {
+                    sub:
+                        SubObjectPropertyExpression::ObjectPropertyExpression(
+                            ObjectPropertyExpression::ObjectProperty(sub),
+                        ),
+                    sup: ObjectPropertyExpression::ObjectProperty(sup),
+                }) => {
+                    h.insert(sub.clone().into(), sup.clone().into());
+                }
+                Axiom::SubDataPropertyOf(SubDataPropertyOf { sub, sup }) => {
+                    h.insert(sub.clone().into(), sup.clone().into());
+                }
+                _ => {}
+            }
+        }
+        h
+    }
+
+    /// Return the asserted class hierarchy as a graph.
+    ///
+    /// Every named class in the ontology is a node, and there is an
+    /// edge from each class to each of its asserted named
+    /// superclasses. The returned map gives the node of each class.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.insert(SubClassOf {
+    ///     sub: b.class("http://www.example.com/a").into(),
+    ///     sup: b.class("http://www.example.com/b").into(),
+    /// });
+    ///
+    /// let (g, nodes) = o.hierarchy_graph();
+    /// let a = nodes[&b.class("http://www.example.com/a")];
+    /// let sup = nodes[&b.class("http://www.example.com/b")];
+    /// assert!(g.contains_edge(a, sup));
+    /// ```
+    #[cfg(feature = "graph")]
+    pub fn hierarchy_graph(
+        &self,
+    ) -> (
+        petgraph::Graph<Class<A>, ()>,
+        HashMap<Class<A>, petgraph::graph::NodeIndex>,
+    ) {
+        let mut walk = Walk::new(NamedEntityExtract(HashSet::new()));
+        walk.set_ontology(self);
+        let mut classes: Vec<_> = walk
+            .into_visit()
+            .0
+            .into_iter()
+            .filter_map(|e| match e {
+                NamedEntity::Class(c) => Some(c),
+                _ => None,
+            })
+            .collect();
+        classes.sort();
+
+        let mut graph = petgraph::Graph::new();
+        let nodes: HashMap<_, _> = classes
+            .into_iter()
+            .map(|c| (c.clone(), graph.add_node(c)))
+            .collect();
+
+        for (sub, sup) in self.named_subclass_edges() {
+            graph.add_edge(nodes[&sub], nodes[&sup], ());
+        }
+
+        (graph, nodes)
+    }
+
+    /// Return the cycles in the asserted named class hierarchy.
+    ///
+    /// Each cycle is a strongly connected component of more than one
+    /// class in the graph of `SubClassOf` axioms between named
+    /// classes; all of the classes in a component are implied to be
+    /// equivalent. Classes are sorted within each component, and
+    /// components by their first class.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// let a = b.class("http://www.example.com/a");
+    /// let c = b.class("http://www.example.com/c");
+    /// o.insert(SubClassOf { sub: a.clone().into(), sup: c.clone().into() });
+    /// o.insert(SubClassOf { sub: c.clone().into(), sup: a.clone().into() });
+    ///
+    /// assert_eq!(o.subclass_cycles(), vec![vec![a, c]]);
+    /// ```
+    pub fn subclass_cycles(&self) -> Vec<Vec<Class<A>>> {
+        let edges = self.named_subclass_edges();
+        let classes: Vec<_> = edges
+            .iter()
+            .flat_map(|(sub, sup)| vec![sub, sup])
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .cloned()
+            .collect();
+        let position: HashMap<_, _> = classes.iter().enumerate().map(|(i, c)| (c, i)).collect();
+
+        let mut adjacent = vec![vec![]; classes.len()];
+        for (sub, sup) in &edges {
+            adjacent[position[sub]].push(position[sup]);
+        }
+
+        let mut cycles: Vec<Vec<Class<A>>> = strongly_connected(&adjacent)
+            .into_iter()
+            .filter(|scc| scc.len() > 1)
+            .map(|mut scc| {
+                scc.sort_unstable();
+                scc.into_iter().map(|i| classes[i].clone()).collect()
+            })
+            .collect();
+        cycles.sort();
+        cycles
+    }
+
+    /// Return the root classes of the ontology: those with no
+    /// asserted named superclass other than `owl:Thing`.
+    ///
+    /// `owl:Thing` is the implicit root of every hierarchy, and
+    /// `owl:Nothing` the implicit leaf, so neither is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// let a = b.class("http://www.example.com/a");
+    /// o.insert(SubClassOf {
+    ///     sub: b.class("http://www.example.com/b").into(),
+    ///     sup: a.clone().into(),
+    /// });
+    ///
+    /// assert_eq!(o.root_classes(), vec![a].into_iter().collect());
+    /// ```
+    pub fn root_classes(&self) -> HashSet<Class<A>> {
+        let with_super: HashSet<Class<A>> = self
+            .named_subclass_edges()
+            .into_iter()
+            .filter(|(sub, sup)| sub != sup && !is_thing(&sup.0))
+            .map(|(sub, _)| sub)
+            .collect();
+
+        self.hierarchy_classes()
+            .filter(|c| !with_super.contains(c))
+            .collect()
+    }
+
+    /// Return the leaf classes of the ontology: those with no
+    /// asserted named subclass other than `owl:Nothing`.
+    ///
+    /// As with [`root_classes`](#method.root_classes), neither
+    /// `owl:Thing` nor `owl:Nothing` is returned.
+    pub fn leaf_classes(&self) -> HashSet<Class<A>> {
+        let with_sub: HashSet<Class<A>> = self
+            .named_subclass_edges()
+            .into_iter()
+            .filter(|(sub, sup)| sub != sup && !is_nothing(&sub.0))
+            .map(|(_, sup)| sup)
+            .collect();
+
+        self.hierarchy_classes()
+            .filter(|c| !with_sub.contains(c))
+            .collect()
+    }
+
+    /// Return the most specific classes, in sorted order, which are
+    /// asserted ancestors of both `a` and `b`.
+    ///
+    /// Ancestors are found from `SubClassOf` axioms between named
+    /// classes, and each class counts as its own ancestor, so if `a`
+    /// is a subclass of `b` the result is `b`. A shared ancestor is
+    /// returned unless it is above another shared ancestor, so with
+    /// multiple inheritance there can be several. Classes in a cycle
+    /// are all returned.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// let sub = |sub: &str, sup: &str| SubClassOf {
+    ///     sub: b.class(sub).into(),
+    ///     sup: b.class(sup).into(),
+    /// };
+    /// o.insert(sub("http://www.example.com/a", "http://www.example.com/p"));
+    /// o.insert(sub("http://www.example.com/c", "http://www.example.com/p"));
+    /// o.insert(sub("http://www.example.com/p", "http://www.example.com/top"));
+    ///
+    /// assert_eq!(
+    ///     o.common_ancestors(
+    ///         &b.class("http://www.example.com/a"),
+    ///         &b.class("http://www.example.com/c")
+    ///     ),
+    ///     vec![b.class("http://www.example.com/p")]
+    /// );
+    /// ```
+    pub fn common_ancestors(&self, a: &Class<A>, b: &Class<A>) -> Vec<Class<A>> {
+        let mut hierarchy = HierarchyView::new();
+        for (sub, sup) in self.named_subclass_edges() {
+            hierarchy.insert(sub.into(), sup.into());
+        }
+        let ancestors = |c: &Class<A>| {
+            let c: NamedEntity<A> = c.clone().into();
+            let mut anc = hierarchy.super_properties(&c);
+            anc.insert(c);
+            anc
+        };
+
+        let a_ancestors = ancestors(a);
+        let shared: HashSet<_> = ancestors(b)
+            .into_iter()
+            .filter(|c| a_ancestors.contains(c))
+            .collect();
+
+        let mut common: Vec<_> = shared
+            .iter()
+            .filter(|c| {
+                !shared.iter().any(|other| {
+                    other != *c
+                        && hierarchy.super_properties(other).contains(c)
+                        && !hierarchy.super_properties(c).contains(other)
+                })
+            })
+            .filter_map(|c| match c {
+                NamedEntity::Class(c) => Some(c.clone()),
+                _ => None,
+            })
+            .collect();
+        common.sort();
+        common
+    }
+
+    /// Return the classes in the signature of the ontology, other
+    /// than `owl:Thing` and `owl:Nothing`.
+    fn hierarchy_classes(&self) -> impl Iterator<Item = Class<A>> {
+        self.signature().into_iter().filter_map(|e| match e {
+            NamedEntity::Class(c) if !is_thing(&c.0) && !is_nothing(&c.0) => Some(c),
+            _ => None,
+        })
+    }
+
+    /// Return the axioms of the ontology in an order suitable for
+    /// serialisation, with entities declared before they are used.
+    ///
+    /// Imports and ontology annotations come first, then
+    /// declarations, then logical axioms and finally annotation
+    /// axioms. Within the logical axioms, a `SubClassOf` axiom with a
+    /// named subclass comes after those for its superclasses;
+    /// cycles in the class hierarchy are broken at their least
+    /// class. Otherwise axioms are in their natural order, so the
+    /// result is deterministic.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// let sc: AnnotatedAxiom<_> = SubClassOf {
+    ///     sub: b.class("http://www.example.com/a").into(),
+    ///     sup: b.class("http://www.example.com/b").into(),
+    /// }.into();
+    /// o.insert(sc.clone());
+    /// o.declare(b.class("http://www.example.com/a"));
+    ///
+    /// assert_eq!(o.topological_axiom_order()[1], &sc);
+    /// ```
+    pub fn topological_axiom_order(&self) -> Vec<&AnnotatedAxiom<A>> {
+        let edges = self.named_subclass_edges();
+        let mut supers: HashMap<&Class<A>, usize> = HashMap::new();
+        let mut subs: HashMap<&Class<A>, Vec<&Class<A>>> = HashMap::new();
+        let mut remaining = BTreeSet::new();
+        for (sub, sup) in edges.iter().filter(|(sub, sup)| sub != sup) {
+            *supers.entry(sub).or_insert(0) += 1;
+            subs.entry(sup).or_default().push(sub);
+            remaining.insert(sub);
+            remaining.insert(sup);
+        }
+
+        // Rank the classes so that superclasses come first
+        let mut rank: HashMap<&Class<A>, usize> = HashMap::new();
+        let mut ready: BTreeSet<_> = remaining
+            .iter()
+            .filter(|c| !supers.contains_key(*c))
+            .cloned()
+            .collect();
+        while let Some(next) = ready
+            .iter()
+            .next()
+            .or_else(|| remaining.iter().next())
+            .cloned()
+        {
+            ready.remove(next);
+            remaining.remove(next);
+            rank.insert(next, rank.len());
+            for sub in subs.get(next).into_iter().flatten() {
+                let n = supers.get_mut(sub).unwrap();
+                *n -= 1;
+                if *n == 0 && remaining.contains(sub) {
+                    ready.insert(sub);
+                }
+            }
+        }
+
+        let group = |ax: &Axiom<A>| match ax {
+            Axiom::Import(_) | Axiom::OntologyAnnotation(_) => 0,
+            _ if ax.as_entity().is_some() => 1,
+            Axiom::AnnotationAssertion(_)
+            | Axiom::SubAnnotationPropertyOf(_)
+            | Axiom::AnnotationPropertyDomain(_)
+            | Axiom::AnnotationPropertyRange(_) => 3,
+            _ => 2,
+        };
+        let class_rank = |ax: &Axiom<A>| match ax {
+            Axiom::SubClassOf(SubClassOf {
+                sub: ClassExpression::Class(c),
+                ..
+            }) => rank.get(c).copied(),
+            _ => None,
+        };
+
+        let mut axioms: Vec<_> = self.iter().collect();
+        axioms.sort_by_cached_key(|aa| {
+            (
+                group(&aa.axiom),
+                class_rank(&aa.axiom).unwrap_or(usize::MAX),
+                *aa,
+            )
+        });
+        axioms
+    }
+
+    /// Return the classes which are defined, that is which occur as
+    /// a named class in an `EquivalentClasses` axiom.
+    ///
+    /// A class equivalent to another named class is defined, as is
+    /// one equivalent to a complex class expression.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// let a = b.class("http://www.example.com/a");
+    /// o.insert(EquivalentClasses(vec![
+    ///     a.clone().into(),
+    ///     ClassExpression::ObjectComplementOf(Box::new(b.class("http://www.example.com/b").into())),
+    /// ]));
+    ///
+    /// assert_eq!(o.defined_classes(), vec![a].into_iter().collect());
+    /// ```
+    pub fn defined_classes(&self) -> HashSet<Class<A>> {
+        self.iter()
+            .filter_map(|aa| match &aa.axiom {
+                Axiom::EquivalentClasses(EquivalentClasses(ces)) if ces.len() > 1 => Some(ces),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|ce| match ce {
+                ClassExpression::Class(c) => Some(c.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Return the classes which are primitive, that is which are the
+    /// subclass in a `SubClassOf` axiom, but are not
+    /// [defined](#method.defined_classes).
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// let a = b.class("http://www.example.com/a");
+    /// o.insert(SubClassOf {
+    ///     sub: a.clone().into(),
+    ///     sup: b.class("http://www.example.com/b").into(),
+    /// });
+    ///
+    /// assert_eq!(o.primitive_classes(), vec![a].into_iter().collect());
+    /// ```
+    pub fn primitive_classes(&self) -> HashSet<Class<A>> {
+        let defined = self.defined_classes();
+        self.iter()
+            .filter_map(|aa| match &aa.axiom {
+                Axiom::SubClassOf(SubClassOf {
+                    sub: ClassExpression::Class(c),
+                    ..
+                }) if !defined.contains(c) => Some(c.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Return a single class expression which describes `c`, for
+    /// display.
+    ///
+    /// If `c` is equivalent to exactly one complex class expression,
+    /// that expression is returned. Otherwise, the superclasses of `c`
+    /// from `SubClassOf` axioms are returned as an
+    /// `ObjectIntersectionOf` in their natural order, or alone if
+    /// there is only one. Equivalences to named classes are ignored.
+    /// `None` is returned if `c` has no superclasses.
+    ///
+    /// This is a heuristic: the result is implied by the ontology,
+    /// but a class with superclasses is not equivalent to their
+    /// intersection.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// let a = b.class("http://www.example.com/a");
+    /// o.insert(SubClassOf {
+    ///     sub: a.clone().into(),
+    ///     sup: b.class("http://www.example.com/b").into(),
+    /// });
+    ///
+    /// assert_eq!(o.definition(&a), Some(b.class("http://www.example.com/b").into()));
+    /// ```
+    pub fn definition(&self, c: &Class<A>) -> Option<ClassExpression<A>> {
+        let named = ClassExpression::Class(c.clone());
+        let mut equivalents = self
+            .iter()
+            .filter_map(|aa| match &aa.axiom {
+                Axiom::EquivalentClasses(EquivalentClasses(ces)) if ces.contains(&named) => {
+                    Some(ces)
+                }
+                _ => None,
+            })
+            .flatten()
+            .filter(|ce| !matches!(ce, ClassExpression::Class(_)));
+
+        if let (Some(ce), None) = (equivalents.next(), equivalents.next()) {
+            return Some(ce.clone());
+        }
+
+        let mut supers: Vec<_> = self
+            .iter()
+            .filter_map(|aa| match &aa.axiom {
+                Axiom::SubClassOf(SubClassOf { sub, sup }) if sub == &named => Some(sup.clone()),
+                _ => None,
+            })
+            .collect();
+        supers.sort();
+
+        match supers.len() {
+            0 => None,
+            1 => supers.pop(),
+            _ => Some(ClassExpression::ObjectIntersectionOf(supers)),
+        }
+    }
+
+    /// Return every named entity used, directly or transitively, in
+    /// the definition of `c`.
+    ///
+    /// The definition of a class is taken to be the other class
+    /// expressions of the `EquivalentClasses` axioms which contain it,
+    /// and the superclasses of the `SubClassOf` axioms of which it is
+    /// the subclass. The definitions of the named classes found there
+    /// are followed in turn. Other axioms, such as those about the
+    /// properties used, are not. `c` itself is only included if its
+    /// definition is cyclic.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// let a = b.class("http://www.example.com/a");
+    /// o.insert(SubClassOf {
+    ///     sub: a.clone().into(),
+    ///     sup: b.class("http://www.example.com/b").into(),
+    /// });
+    ///
+    /// assert_eq!(
+    ///     o.definition_dependencies(&a),
+    ///     vec![b.class("http://www.example.com/b").into()].into_iter().collect()
+    /// );
+    /// ```
+    pub fn definition_dependencies(&self, c: &Class<A>) -> HashSet<NamedEntity<A>> {
+        let mut walk = Walk::new(NamedEntityExtract(HashSet::new()));
+        let mut done = HashSet::new();
+        let mut todo = vec![c.clone()];
+
+        while let Some(c) = todo.pop() {
+            if !done.insert(c.clone()) {
+                continue;
+            }
+            let named = ClassExpression::Class(c);
+            for aa in self.iter() {
+                match &aa.axiom {
+                    Axiom::EquivalentClasses(EquivalentClasses(ces)) if ces.contains(&named) => {
+                        for ce in ces.iter().filter(|ce| *ce != &named) {
+                            walk.class_expression(ce);
+                        }
+                    }
+                    Axiom::SubClassOf(SubClassOf { sub, sup }) if sub == &named => {
+                        walk.class_expression(sup);
+                    }
+                    _ => {}
+                }
+            }
+            todo.extend(walk.as_mut_visit().0.iter().filter_map(|e| match e {
+                NamedEntity::Class(c) if !done.contains(c) => Some(c.clone()),
+                _ => None,
+            }));
+        }
+
+        walk.into_visit().0
+    }
+
+    /// Return the groups of named entities which are connected by
+    /// appearing together in axioms.
+    ///
+    /// Two entities are in the same component if some chain of
+    /// axioms links them, each axiom using the entity before and
+    /// after it. Annotations on axioms are not considered, as the
+    /// same annotation property often appears throughout an
+    /// ontology. Components are returned in no particular order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.insert(SubClassOf {
+    ///     sub: b.class("http://www.example.com/a").into(),
+    ///     sup: b.class("http://www.example.com/b").into(),
+    /// });
+    /// o.declare(b.class("http://www.example.com/c"));
+    ///
+    /// assert_eq!(o.entity_components().len(), 2);
+    /// ```
+    pub fn entity_components(&self) -> Vec<HashSet<NamedEntity<A>>> {
+        let mut index: HashMap<NamedEntity<A>, usize> = HashMap::new();
+        let mut parent: Vec<usize> = vec![];
+
+        for aa in self.iter() {
+            let mut walk = Walk::new(NamedEntityExtract(HashSet::new()));
+            walk.axiom(&aa.axiom);
+
+            let mut first = None;
+            for e in walk.into_visit().0 {
+                let i = *index.entry(e).or_insert_with(|| {
+                    parent.push(parent.len());
+                    parent.len() - 1
+                });
+                match first {
+                    None => first = Some(i),
+                    Some(f) => {
+                        let (rf, ri) = (find_root(&mut parent, f), find_root(&mut parent, i));
+                        parent[ri] = rf;
+                    }
+                }
+            }
+        }
+
+        let mut components: HashMap<usize, HashSet<NamedEntity<A>>> = HashMap::new();
+        for (e, i) in index {
+            let root = find_root(&mut parent, i);
+            components.entry(root).or_default().insert(e);
+        }
+        components.into_values().collect()
+    }
+
+    /// Return the axioms within `hops` steps of the entities in
+    /// `seed`.
+    ///
+    /// The axioms which use an entity in `seed`, or which are
+    /// annotation assertions about its IRI, are selected first. The
+    /// entities used by the selected axioms are then added to the
+    /// seed, and the selection repeated, `hops` times. As with
+    /// [`entity_components`](#method.entity_components), annotations
+    /// on axioms are not considered.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.insert(SubClassOf {
+    ///     sub: b.class("http://www.example.com/a").into(),
+    ///     sup: b.class("http://www.example.com/b").into(),
+    /// });
+    /// o.insert(SubClassOf {
+    ///     sub: b.class("http://www.example.com/b").into(),
+    ///     sup: b.class("http://www.example.com/c").into(),
+    /// });
+    /// let seed = vec![b.class("http://www.example.com/a").into()]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// assert_eq!(o.neighborhood(&seed, 0).len(), 1);
+    /// assert_eq!(o.neighborhood(&seed, 1).len(), 2);
+    /// ```
+    pub fn neighborhood(&self, seed: &HashSet<NamedEntity<A>>, hops: usize) -> SetOntology<A> {
+        let uses: Vec<_> = self
+            .iter()
+            .map(|aa| {
+                let mut walk = Walk::new(NamedEntityExtract(HashSet::new()));
+                walk.axiom(&aa.axiom);
+                (aa, walk.into_visit().0)
+            })
+            .collect();
+
+        let mut signature = seed.clone();
+        let mut hop = 0;
+        loop {
+            let iris: HashSet<IRI<A>> = signature
+                .iter()
+                .map(|e| entity_iri_kind(e.clone()).0)
+                .collect();
+            let selected: Vec<_> = uses
+                .iter()
+                .filter(|(aa, used)| match &aa.axiom {
+                    Axiom::AnnotationAssertion(AnnotationAssertion {
+                        subject: AnnotationSubject::IRI(iri),
+                        ..
+                    }) if iris.contains(iri) => true,
+                    _ => !used.is_disjoint(&signature),
+                })
+                .collect();
+
+            let expanded: HashSet<_> = signature
+                .iter()
+                .chain(selected.iter().flat_map(|(_, used)| used.iter()))
+                .cloned()
+                .collect();
+
+            if hop == hops || expanded.len() == signature.len() {
+                let mut so: SetOntology<A> =
+                    selected.into_iter().map(|(aa, _)| (*aa).clone()).collect();
+                *so.mut_id() = self.id().clone();
+                *so.mut_doc_iri() = self.doc_iri().clone();
+                return so;
+            }
+            signature = expanded;
+            hop += 1;
+        }
+    }
+
+    /// Replace each group of equivalent named classes with a single
+    /// representative, returning a map from each replaced class to
+    /// its representative.
+    ///
+    /// Named classes which occur together in `EquivalentClasses`
+    /// axioms, directly or through a chain of such axioms, form a
+    /// group; the least class of each, by IRI, is its
+    /// representative. Every other use of the classes is rewritten
+    /// to the representative, and axioms which become identical are
+    /// merged. Annotation assertions about the replaced classes are
+    /// not changed, as their subject is an IRI.
+    ///
+    /// If `retain` is true, the `EquivalentClasses` axioms are kept
+    /// unchanged, so that the replaced classes are still defined.
+    /// Otherwise they are rewritten too, and dropped if they then
+    /// relate fewer than two class expressions.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// let a = b.class("http://www.example.com/a");
+    /// let c = b.class("http://www.example.com/c");
+    /// o.insert(EquivalentClasses(vec![c.clone().into(), a.clone().into()]));
+    /// o.declare(c.clone());
+    ///
+    /// let map = o.collapse_equivalences(false);
+    /// assert_eq!(map[&c], a);
+    /// assert_eq!(o.iter().collect::<Vec<_>>(), vec![&DeclareClass(a).into()]);
+    /// ```
+    pub fn collapse_equivalences(&mut self, retain: bool) -> HashMap<Class<A>, Class<A>> {
+        let mut index: HashMap<Class<A>, usize> = HashMap::new();
+        let mut classes: Vec<Class<A>> = vec![];
+        let mut parent: Vec<usize> = vec![];
+
+        for aa in self.iter() {
+            if let Axiom::EquivalentClasses(EquivalentClasses(ces)) = &aa.axiom {
+                let mut first = None;
+                for c in ces.iter().filter_map(|ce| match ce {
+                    ClassExpression::Class(c) => Some(c),
+                    _ => None,
+                }) {
+                    let i = *index.entry(c.clone()).or_insert_with(|| {
+                        parent.push(parent.len());
+                        classes.push(c.clone());
+                        parent.len() - 1
+                    });
+                    match first {
+                        None => first = Some(i),
+                        Some(f) => {
+                            let (rf, ri) = (find_root(&mut parent, f), find_root(&mut parent, i));
+                            parent[ri] = rf;
+                        }
+                    }
+                }
+            }
+        }
+
+        let roots: Vec<usize> = (0..classes.len())
+            .map(|i| find_root(&mut parent, i))
+            .collect();
+        let mut representative: HashMap<usize, &Class<A>> = HashMap::new();
+        for (c, root) in classes.iter().zip(&roots) {
+            let r = representative.entry(*root).or_insert(c);
+            if c < *r {
+                *r = c;
+            }
+        }
+        let map: HashMap<Class<A>, Class<A>> = classes
+            .iter()
+            .zip(&roots)
+            .filter(|(c, root)| representative[*root] != *c)
+            .map(|(c, root)| (c.clone(), representative[root].clone()))
+            .collect();
+
+        if map.is_empty() {
+            return map;
+        }
+
+        let mut walk = WalkMut::new(RenameClass(&map));
+        let changed: Vec<_> = self
+            .iter()
+            .filter(|aa| !(retain && matches!(aa.axiom, Axiom::EquivalentClasses(_))))
+            .filter_map(|aa| {
+                let mut new = aa.clone();
+                walk.annotated_axiom(&mut new);
+                if let Axiom::EquivalentClasses(EquivalentClasses(ces)) = &mut new.axiom {
+                    let mut seen = vec![];
+                    ces.retain(|ce| {
+                        let unseen = !seen.contains(ce);
+                        seen.push(ce.clone());
+                        unseen
+                    });
+                    if ces.len() < 2 {
+                        return Some((aa.clone(), None));
+                    }
+                }
+                if &new == aa {
+                    None
+                } else {
+                    Some((aa.clone(), Some(new)))
+                }
+            })
+            .collect();
+
+        for (old, new) in changed {
+            self.remove(&old);
+            if let Some(new) = new {
+                self.insert(new);
+            }
+        }
+        map
+    }
+
+    /// Return the `SubClassOf` axioms whose subclass and superclass
+    /// are the same class expression.
+    ///
+    /// Such axioms are trivially true, and usually accidental.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// let sc: AnnotatedAxiom<_> = SubClassOf {
+    ///     sub: b.class("http://www.example.com/a").into(),
+    ///     sup: b.class("http://www.example.com/a").into(),
+    /// }
+    /// .into();
+    /// o.insert(sc.clone());
+    ///
+    /// assert_eq!(o.reflexive_subclass_axioms().collect::<Vec<_>>(), vec![&sc]);
+    /// ```
+    pub fn reflexive_subclass_axioms(&self) -> impl Iterator<Item = &AnnotatedAxiom<A>> {
+        self.iter().filter(|aa| match &aa.axiom {
+            Axiom::SubClassOf(SubClassOf { sub, sup }) => sub == sup,
+            _ => false,
+        })
+    }
+
+    /// Remove the axioms returned by
+    /// [`reflexive_subclass_axioms`](#method.reflexive_subclass_axioms),
+    /// returning the number of axioms removed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.insert(SubClassOf {
+    ///     sub: b.class("http://www.example.com/a").into(),
+    ///     sup: b.class("http://www.example.com/a").into(),
+    /// });
+    ///
+    /// assert_eq!(o.remove_reflexive_subclass_axioms(), 1);
+    /// assert!(o.is_empty());
+    /// ```
+    pub fn remove_reflexive_subclass_axioms(&mut self) -> usize {
+        let reflexive: Vec<AnnotatedAxiom<A>> = self.reflexive_subclass_axioms().cloned().collect();
+        for aa in &reflexive {
+            self.remove(aa);
+        }
+        reflexive.len()
+    }
+
+    /// Remove `SubClassOf` axioms between named classes which follow
+    /// from the others by transitivity, returning the number of
+    /// axioms removed.
+    ///
+    /// An axiom `A ⊑ C` is redundant if `C` can be reached from `A`
+    /// through the other subclass axioms between named classes. Edges
+    /// are removed one at a time, in sorted order, so that the
+    /// transitive closure of the remaining axioms is unchanged, even
+    /// in the presence of cycles. An axiom with annotations is only
+    /// removed if `remove_annotated` is true, as its annotations would
+    /// otherwise be lost.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// let sub = |sub: &str, sup: &str| SubClassOf {
+    ///     sub: b.class(sub).into(),
+    ///     sup: b.class(sup).into(),
+    /// };
+    /// o.insert(sub("http://www.example.com/a", "http://www.example.com/b"));
+    /// o.insert(sub("http://www.example.com/b", "http://www.example.com/c"));
+    /// o.insert(sub("http://www.example.com/a", "http://www.example.com/c"));
+    ///
+    /// assert_eq!(o.remove_redundant_subclass_axioms(false), 1);
+    /// assert_eq!(o.len(), 2);
+    /// ```
+    pub fn remove_redundant_subclass_axioms(&mut self, remove_annotated: bool) -> usize {
+        // The axioms for each edge, in sorted order of edge
+        let mut edges = BTreeMap::<_, Vec<AnnotatedAxiom<A>>>::new();
+        for aa in self.iter() {
+            if let Axiom::SubClassOf(SubClassOf {
+                sub: ClassExpression::Class(sub),
+                sup: ClassExpression::Class(sup),
+            }) = &aa.axiom
+            {
+                if sub != sup {
+                    edges
+                        .entry((sub.clone(), sup.clone()))
+                        .or_default()
+                        .push(aa.clone());
+                }
+            }
+        }
+
+        let mut sups: HashMap<Class<A>, HashSet<Class<A>>> = HashMap::new();
+        for (sub, sup) in edges.keys() {
+            sups.entry(sub.clone()).or_default().insert(sup.clone());
+        }
+
+        let mut removed = 0;
+        for ((sub, sup), axioms) in edges {
+            if !remove_annotated && axioms.iter().any(|aa| !aa.ann.is_empty()) {
+                continue;
+            }
+
+            sups.get_mut(&sub).unwrap().remove(&sup);
+            if reachable(&sups, &sub, &sup) {
+                for aa in axioms {
+                    self.remove(&aa);
+                    removed += 1;
+                }
+            } else {
+                sups.get_mut(&sub).unwrap().insert(sup);
+            }
+        }
+        removed
+    }
+
+    /// Merge `AnnotationAssertion` axioms which differ only in their
+    /// own annotations, returning the number of axioms removed.
+    ///
+    /// A set cannot contain exactly equal axioms, but the same
+    /// assertion can occur more than once with different axiom
+    /// annotations, for example after merging ontologies. These are
+    /// replaced by a single assertion with all of their annotations.
+    /// Assertions with different values, including literals which
+    /// differ only in language or datatype, are kept.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// let label = b.annotation_assertion(
+    ///     "http://www.example.com/a",
+    ///     "http://www.w3.org/2000/01/rdf-schema#label",
+    ///     "A",
+    /// );
+    /// let mut annotated = label.clone();
+    /// annotated.ann.insert(Annotation {
+    ///     ap: b.annotation_property("http://www.example.com/source"),
+    ///     av: "import".into(),
+    /// });
+    /// o.insert(label);
+    /// o.insert(annotated);
+    ///
+    /// assert_eq!(o.dedup_annotations(), 1);
+    /// assert_eq!(o.len(), 1);
+    /// ```
+    pub fn dedup_annotations(&mut self) -> usize {
+        let mut assertions: HashMap<Axiom<A>, Vec<AnnotatedAxiom<A>>> = HashMap::new();
+        for aa in self.iter() {
+            if let Axiom::AnnotationAssertion(_) = aa.axiom {
+                assertions
+                    .entry(aa.axiom.clone())
+                    .or_default()
+                    .push(aa.clone());
+            }
+        }
+
+        let mut removed = 0;
+        for (axiom, duplicates) in assertions {
+            if duplicates.len() < 2 {
+                continue;
+            }
+
+            let mut ann = BTreeSet::new();
+            for aa in &duplicates {
+                self.remove(aa);
+                ann.extend(aa.ann.iter().cloned());
+            }
+            self.insert(AnnotatedAxiom::new(axiom, ann));
+            removed += duplicates.len() - 1;
+        }
+        removed
+    }
+
+    /// Replace the `OntologyID` of this ontology.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.set_id(OntologyID {
+    ///     iri: Some(b.iri("http://www.example.com/o")),
+    ///     viri: None,
+    /// });
+    ///
+    /// assert_eq!(o.id().iri, Some(b.iri("http://www.example.com/o")));
+    /// ```
+    pub fn set_id(&mut self, id: OntologyID<A>) {
+        *self.mut_id() = id;
+    }
+
+    /// Insert every axiom of `other`, and give the result the
+    /// `OntologyID` `id`, returning the number of axioms added.
+    ///
+    /// Neither the existing ID nor that of `other` is kept, unless
+    /// passed as `id`, so that the caller decides which, if either,
+    /// the merged ontology should have.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.declare(b.class("http://www.example.com/a"));
+    /// let mut other = SetOntology::new_rc();
+    /// other.declare(b.class("http://www.example.com/b"));
+    ///
+    /// let id = OntologyID {
+    ///     iri: Some(b.iri("http://www.example.com/merged")),
+    ///     viri: None,
+    /// };
+    /// assert_eq!(o.merge(other, id.clone()), 1);
+    /// assert_eq!(o.id(), &id);
+    /// ```
+    pub fn merge<O>(&mut self, other: O, id: OntologyID<A>) -> usize
+    where
+        O: IntoIterator<Item = AnnotatedAxiom<A>>,
+    {
+        let added = other
+            .into_iter()
+            .filter(|aa| self.insert(aa.clone()))
+            .count();
+        self.set_id(id);
+        added
+    }
+
+    /// Insert every axiom of `other`, annotating each with its
+    /// source.
+    ///
+    /// Each axiom gains an annotation with property `ap` and value
+    /// `source_iri`. Where an axiom with the same annotations,
+    /// ignoring those with property `ap`, is already present, the
+    /// source is added to its annotations rather than inserting a
+    /// second copy; so an axiom found in several sources records
+    /// them all.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let ap = b.annotation_property("http://www.example.com/importedFrom");
+    /// let mut source = SetOntology::new_rc();
+    /// source.declare(b.class("http://www.example.com/a"));
+    ///
+    /// let mut o = SetOntology::new_rc();
+    /// o.merge_with_provenance(source.clone(), &b.iri("http://www.example.com/s1"), &ap);
+    /// o.merge_with_provenance(source, &b.iri("http://www.example.com/s2"), &ap);
+    ///
+    /// assert_eq!(o.len(), 1);
+    /// assert_eq!(o.iter().next().unwrap().ann.len(), 2);
+    /// ```
+    pub fn merge_with_provenance<O>(
+        &mut self,
+        other: O,
+        source_iri: &IRI<A>,
+        ap: &AnnotationProperty<A>,
+    ) where
+        O: IntoIterator<Item = AnnotatedAxiom<A>>,
+    {
+        let key = |aa: &AnnotatedAxiom<A>| {
+            let ann: BTreeSet<_> = aa.ann.iter().filter(|a| &a.ap != ap).cloned().collect();
+            (aa.axiom.clone(), ann)
+        };
+        let provenance = Annotation {
+            ap: ap.clone(),
+            av: source_iri.clone().into(),
+        };
+
+        let mut existing: HashMap<_, AnnotatedAxiom<A>> =
+            self.iter().map(|aa| (key(aa), aa.clone())).collect();
+
+        for mut aa in other {
+            let k = key(&aa);
+            if let Some(mut present) = existing.remove(&k) {
+                self.remove(&present);
+                present.ann.insert(provenance.clone());
+                self.insert(present.clone());
+                existing.insert(k, present);
+            } else {
+                aa.ann.insert(provenance.clone());
+                self.insert(aa.clone());
+                existing.insert(k, aa);
+            }
+        }
+    }
+
+    /// Return the `owl:versionInfo` of the ontology, from its
+    /// ontology annotations.
+    ///
+    /// If there is more than one, the least is returned, so that the
+    /// result is deterministic.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.insert(OntologyAnnotation(Annotation {
+    ///     ap: b.annotation_property("http://www.w3.org/2002/07/owl#versionInfo"),
+    ///     av: "1.0".into(),
+    /// }));
+    ///
+    /// assert_eq!(o.version_info(), Some("1.0"));
+    /// assert_eq!(o.header_comment(), None);
+    /// ```
+    pub fn version_info(&self) -> Option<&str> {
+        self.ontology_annotation_literal(AnnotationBuiltIn::VERSIONINFO.iri_str())
+    }
+
+    /// Return the `rdfs:comment` of the ontology, from its ontology
+    /// annotations.
+    ///
+    /// If there is more than one, the least is returned, so that the
+    /// result is deterministic.
+    pub fn header_comment(&self) -> Option<&str> {
+        self.ontology_annotation_literal(AnnotationBuiltIn::COMMENT.iri_str())
+    }
+
+    /// Return the `rdfs:isDefinedBy` of the ontology, from its
+    /// ontology annotations.
+    ///
+    /// Only IRI values are considered. If there is more than one, the
+    /// least is returned, so that the result is deterministic.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.insert(OntologyAnnotation(Annotation {
+    ///     ap: b.annotation_property("http://www.w3.org/2000/01/rdf-schema#isDefinedBy"),
+    ///     av: b.iri("http://www.example.com/spec").into(),
+    /// }));
+    ///
+    /// assert_eq!(o.defined_by(), Some(&b.iri("http://www.example.com/spec")));
+    /// assert_eq!(o.see_also().count(), 0);
+    /// ```
+    pub fn defined_by(&self) -> Option<&IRI<A>> {
+        self.ontology_annotation_values(AnnotationBuiltIn::ISDEFINEDBY.iri_str())
+            .filter_map(|av| match av {
+                AnnotationValue::IRI(iri) => Some(iri),
+                _ => None,
+            })
+            .min()
+    }
+
+    /// Return the values of all `rdfs:seeAlso` annotations of the
+    /// ontology, from its ontology annotations.
+    pub fn see_also(&self) -> impl Iterator<Item = &AnnotationValue<A>> {
+        self.ontology_annotation_values(AnnotationBuiltIn::SEEALSO.iri_str())
+    }
+
+    fn ontology_annotation_values<'a>(
+        &'a self,
+        ap: &'a str,
+    ) -> impl Iterator<Item = &'a AnnotationValue<A>> {
+        self.ontology_annotations()
+            .filter(move |ann| ann.ap.0.as_ref() == ap)
+            .map(|ann| &ann.av)
+    }
+
+    fn ontology_annotation_literal(&self, ap: &str) -> Option<&str> {
+        self.ontology_annotations()
+            .filter_map(|ann| match ann {
+                Annotation {
+                    ap: prop,
+                    av: AnnotationValue::Literal(l),
+                } if prop.0.as_ref() == ap => Some(l.literal().as_str()),
+                _ => None,
+            })
+            .min()
+    }
+
+    /// Return the annotations of the ontology itself.
+    ///
+    /// These are stored as `OntologyAnnotation` axioms, which the
+    /// parsers create from annotations in the ontology header, and
+    /// the writers render there; they are distinct from
+    /// `AnnotationAssertion` axioms about the ontology IRI.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// let ann = Annotation {
+    ///     ap: b.annotation_property("http://www.w3.org/2000/01/rdf-schema#label"),
+    ///     av: "An ontology".into(),
+    /// };
+    /// o.insert(OntologyAnnotation(ann.clone()));
+    ///
+    /// assert_eq!(o.ontology_annotations().collect::<Vec<_>>(), vec![&ann]);
+    /// ```
+    pub fn ontology_annotations(&self) -> impl Iterator<Item = &Annotation<A>> {
+        self.iter().filter_map(|aa| match &aa.axiom {
+            Axiom::OntologyAnnotation(OntologyAnnotation(ann)) => Some(ann),
+            _ => None,
+        })
+    }
+
+    /// Rewrite the properties of annotations according to `map`,
+    /// returning the number of axioms changed.
+    ///
+    /// Annotations in `AnnotationAssertion` axioms, axiom annotations
+    /// and ontology annotations are rewritten; other uses of the
+    /// annotation properties, such as declarations, are not.
+    /// Axioms which become identical after rewriting are merged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::collections::HashMap;
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.insert(b.annotation_assertion(
+    ///     "http://www.example.com/a",
+    ///     "http://purl.org/dc/elements/1.1/creator",
+    ///     "Phillip",
+    /// ));
+    ///
+    /// let mut map = HashMap::new();
+    /// map.insert(
+    ///     b.iri("http://purl.org/dc/elements/1.1/creator"),
+    ///     b.iri("http://purl.org/dc/terms/creator"),
+    /// );
+    ///
+    /// assert_eq!(o.canonicalize_annotation_properties(&map), 1);
+    /// assert!(o.iter().any(|aa| aa.axiom == b.annotation_assertion(
+    ///     "http://www.example.com/a",
+    ///     "http://purl.org/dc/terms/creator",
+    ///     "Phillip",
+    /// ).axiom));
+    /// ```
+    pub fn canonicalize_annotation_properties(&mut self, map: &HashMap<IRI<A>, IRI<A>>) -> usize {
+        let mut walk = WalkMut::new(RenameAnnotationProperty(map));
+
+        let changed: Vec<_> = self
+            .iter()
+            .filter_map(|aa| {
+                let mut new = aa.clone();
+                walk.annotated_axiom(&mut new);
+                if &new == aa {
+                    None
+                } else {
+                    Some((aa.clone(), new))
+                }
+            })
+            .collect();
+
+        for (old, new) in &changed {
+            self.remove(old);
+            self.insert(new.clone());
+        }
+        changed.len()
+    }
+
+    /// Replace every literal in the ontology for which `f` returns a
+    /// new literal, returning the number of axioms changed.
+    ///
+    /// `f` is called for literals at any depth, including those in
+    /// annotations; returning `None` leaves the literal as it is.
+    /// Axioms which become identical after rewriting are merged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.insert(b.annotation_assertion(
+    ///     "http://www.example.com/a",
+    ///     "http://www.w3.org/2000/01/rdf-schema#label",
+    ///     Literal::Simple { literal: "a label ".to_string() },
+    /// ));
+    ///
+    /// let n = o.map_literals(|l| match l {
+    ///     Literal::Simple { literal } if literal.trim() != literal => Some(Literal::Simple {
+    ///         literal: literal.trim().to_string(),
+    ///     }),
+    ///     _ => None,
+    /// });
+    /// assert_eq!(n, 1);
+    /// ```
+    pub fn map_literals<F>(&mut self, f: F) -> usize
+    where
+        F: FnMut(&Literal<A>) -> Option<Literal<A>>,
+    {
+        let mut walk = WalkMut::new(MapLiterals(f));
+
+        let changed: Vec<_> = self
+            .iter()
+            .filter_map(|aa| {
+                let mut new = aa.clone();
+                walk.annotated_axiom(&mut new);
+                if &new == aa {
+                    None
+                } else {
+                    Some((aa.clone(), new))
+                }
+            })
+            .collect();
+
+        for (old, new) in &changed {
+            self.remove(old);
+            self.insert(new.clone());
+        }
+        changed.len()
+    }
+
+    /// Move the ontology from `old_base` to `new_base`, returning the
+    /// number of IRIs changed.
+    ///
+    /// Every IRI which starts with `old_base`, including those in the
+    /// `OntologyID`, has that prefix replaced with `new_base`; IRIs
+    /// in other namespaces, such as those of imported ontologies,
+    /// are left untouched. As the ontology IRI often lacks the final
+    /// `#` or `/` of its namespace, it is also rebased if `old_base`
+    /// is the ontology IRI followed by one of these. Axioms which
+    /// become identical after rewriting are merged. New IRIs are
+    /// interned by `b`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.mut_id().iri = Some(b.iri("http://www.example.com/v1"));
+    /// o.declare(b.class("http://www.example.com/v1#a"));
+    ///
+    /// assert_eq!(
+    ///     o.rebase(&b, "http://www.example.com/v1#", "http://www.example.com/v2#"),
+    ///     2
+    /// );
+    /// assert_eq!(o.id().iri, Some(b.iri("http://www.example.com/v2")));
+    /// ```
+    pub fn rebase(&mut self, b: &Build<A>, old_base: &str, new_base: &str) -> usize {
+        let mut walk = WalkMut::new(Rebase {
+            b,
+            old_base,
+            new_base,
+            count: 0,
+        });
+
+        let mut id = self.id().clone();
+        if let Some(iri) = &mut id.iri {
+            let namespace = |base: &str| {
+                base.strip_suffix('#')
+                    .or_else(|| base.strip_suffix('/'))
+                    .map(str::to_string)
+            };
+            match (namespace(old_base), namespace(new_base)) {
+                (Some(old), Some(new)) if **iri == *old => {
+                    *iri = walk.as_mut_visit().b.iri(new);
+                    walk.as_mut_visit().count += 1;
+                }
+                _ => walk.iri(iri),
+            }
+        }
+        if let Some(viri) = &mut id.viri {
+            walk.iri(viri);
+        }
+        *self.mut_id() = id;
+
+        let changed: Vec<_> = self
+            .iter()
+            .filter_map(|aa| {
+                let mut new = aa.clone();
+                walk.annotated_axiom(&mut new);
+                if &new == aa {
+                    None
+                } else {
+                    Some((aa.clone(), new))
+                }
+            })
+            .collect();
+
+        for (old, new) in changed {
+            self.remove(&old);
+            self.insert(new);
+        }
+        walk.into_visit().count
+    }
+
+    /// Return a copy of this ontology with every IRI interned by `b`.
+    ///
+    /// Ontologies which have been built with different `Build`
+    /// instances do not share storage for identical IRIs; after
+    /// reinterning with the same `Build` they do.
+    pub fn reintern(&self, b: &Build<A>) -> SetOntology<A> {
+        let mut walk = WalkMut::new(Reintern(b));
+
+        let mut id = self.id().clone();
+        walk.ontology_id(&mut id);
+
+        let mut so: SetOntology<A> = self
+            .iter()
+            .map(|aa| {
+                let mut aa = aa.clone();
+                walk.annotated_axiom(&mut aa);
+                aa
+            })
+            .collect();
+
+        *so.mut_id() = id;
+        *so.mut_doc_iri() = self.doc_iri().as_ref().map(|iri| b.iri(&**iri));
+        so
+    }
+
+    /// Return every IRI in the ontology which is not an absolute IRI.
+    ///
+    /// `Build` does not check the IRIs that it creates, so relative
+    /// IRIs which were never resolved against a base can end up in
+    /// an ontology; this finds them before serialization. Each IRI
+    /// is returned once, in sorted order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.declare(b.class("http://www.example.com/a"));
+    /// o.declare(b.class("b"));
+    ///
+    /// assert_eq!(o.validate_iris(), vec![b.iri("b")]);
+    /// ```
+    pub fn validate_iris(&self) -> Vec<IRI<A>> {
+        let mut walk = Walk::new(IRIExtract::default());
+        walk.set_ontology(self);
+
+        let invalid: BTreeSet<_> = walk
+            .into_visit()
+            .into_vec()
+            .into_iter()
+            .filter(|iri| oxiri::Iri::parse(&**iri).is_err())
+            .collect();
+
+        invalid.into_iter().collect()
+    }
+
+    /// Return true if both ontologies contain the same axioms,
+    /// ignoring the annotations on those axioms and the
+    /// `OntologyID`.
+    ///
+    /// `AnnotationAssertion` axioms are axioms in their own right and
+    /// are compared.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.declare(b.class("http://www.example.com/a"));
+    ///
+    /// let mut p = SetOntology::new_rc();
+    /// p.mut_id().iri = Some(b.iri("http://www.example.com/p"));
+    /// p.declare(b.class("http://www.example.com/a"));
+    ///
+    /// assert!(o.logical_eq(&p));
+    /// ```
+    pub fn logical_eq(&self, other: &SetOntology<A>) -> bool {
+        let axioms: HashSet<&Axiom<A>> = self.iter().map(|aa| &aa.axiom).collect();
+        let other_axioms: HashSet<&Axiom<A>> = other.iter().map(|aa| &aa.axiom).collect();
+
+        axioms == other_axioms
+    }
+
+    /// Return the Jaccard similarity of the axioms of this ontology
+    /// and `other`: the number of axioms in both, divided by the
+    /// number in either.
+    ///
+    /// If `ignore_annotations` is true, the annotations on axioms are
+    /// not compared, as with [`logical_eq`](#method.logical_eq). Two
+    /// empty ontologies have a similarity of 1.0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.declare(b.class("http://www.example.com/a"));
+    /// o.declare(b.class("http://www.example.com/b"));
+    ///
+    /// let mut p = SetOntology::new_rc();
+    /// p.declare(b.class("http://www.example.com/a"));
+    ///
+    /// assert_eq!(o.jaccard_similarity(&p, false), 0.5);
+    /// ```
+    pub fn jaccard_similarity<O>(&self, other: &O, ignore_annotations: bool) -> f64
+    where
+        O: Ontology<A>,
+        for<'a> &'a O: IntoIterator<Item = &'a AnnotatedAxiom<A>>,
+    {
+        fn jaccard<T: Eq + Hash>(a: HashSet<T>, b: HashSet<T>) -> f64 {
+            let union = a.union(&b).count();
+            if union == 0 {
+                1.0
+            } else {
+                a.intersection(&b).count() as f64 / union as f64
+            }
+        }
+
+        if ignore_annotations {
+            jaccard(
+                self.iter().map(|aa| &aa.axiom).collect(),
+                other.into_iter().map(|aa| &aa.axiom).collect(),
+            )
+        } else {
+            jaccard(self.iter().collect(), other.into_iter().collect())
+        }
+    }
+
+    /// Return true if the ontologies are equal up to the renaming of
+    /// anonymous individuals.
+    ///
+    /// The identifiers of anonymous individuals, and so of blank
+    /// nodes, are not preserved by serialization, so this is the
+    /// equality needed when checking a round trip. The `OntologyID`
+    /// is compared, but the document IRI is not.
+    ///
+    /// Anonymous individuals are given canonical names by iterative
+    /// refinement: each is labelled by a hash of the axioms in which
+    /// it occurs, with other anonymous individuals replaced by their
+    /// labels from the previous round, until the labels stop
+    /// changing. Individuals with the same final label are then
+    /// numbered in an arbitrary order. This never returns true for
+    /// ontologies which are not isomorphic; for highly symmetric
+    /// structures where refinement cannot distinguish individuals
+    /// which are not interchangeable, it may return false for
+    /// ontologies which are.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.insert(ClassAssertion {
+    ///     ce: b.class("http://www.example.com/a").into(),
+    ///     i: b.anon("anon1").into(),
+    /// });
+    ///
+    /// let mut p = SetOntology::new_rc();
+    /// p.insert(ClassAssertion {
+    ///     ce: b.class("http://www.example.com/a").into(),
+    ///     i: b.anon("bn42").into(),
+    /// });
+    ///
+    /// assert_ne!(o, p);
+    /// assert!(o.iso_eq(&p));
+    /// ```
+    pub fn iso_eq(&self, other: &SetOntology<A>) -> bool {
+        self.id() == other.id()
+            && self.len() == other.len()
+            && self.canonical_anonymous() == other.canonical_anonymous()
+    }
+
+    /// Return the axioms of the ontology, with anonymous individuals
+    /// given canonical names. See `iso_eq`.
+    fn canonical_anonymous(&self) -> HashSet<AnnotatedAxiom<A>> {
+        let axioms: Vec<_> = self.iter().collect();
+        let mut occurs: HashMap<AnonymousIndividual<A>, Vec<usize>> = HashMap::new();
+        for (i, aa) in axioms.iter().enumerate() {
+            let mut walk = Walk::new(AnonymousExtract(HashSet::new()));
+            walk.annotated_axiom(aa);
+            for anon in walk.into_visit().0 {
+                occurs.entry(anon).or_default().push(i);
+            }
+        }
+
+        let anon = |s: String| AnonymousIndividual(A::from(s));
+        let this = anon("_:this".to_string());
+        let mut labels: HashMap<_, _> = occurs
+            .keys()
+            .map(|a| (a.clone(), anon("_:".to_string())))
+            .collect();
+        let mut distinct = 1;
+
+        for _ in 0..occurs.len() {
+            let mut next = HashMap::new();
+            for (a, axs) in &occurs {
+                let mut walk = WalkMut::new(RenameAnonymous {
+                    labels: &labels,
+                    this: Some((a, &this)),
+                });
+                let mut hashes: Vec<_> = axs
+                    .iter()
+                    .map(|i| {
+                        let mut aa = axioms[*i].clone();
+                        walk.annotated_axiom(&mut aa);
+                        hash_of(&aa)
+                    })
+                    .collect();
+                hashes.sort_unstable();
+                hashes.push(hash_of(&labels[a]));
+                next.insert(a.clone(), anon(format!("_:{:016x}", hash_of(&hashes))));
+            }
+
+            labels = next;
+            let n = labels.values().collect::<HashSet<_>>().len();
+            if n == distinct {
+                break;
+            }
+            distinct = n;
+        }
+
+        let mut sorted: Vec<_> = labels.into_iter().collect();
+        sorted.sort_by(|(a, la), (b, lb)| la.cmp(lb).then(a.cmp(b)));
+        let mut canonical = HashMap::new();
+        let mut count = 0;
+        for (i, (a, l)) in sorted.iter().enumerate() {
+            if i > 0 && sorted[i - 1].1 != *l {
+                count = 0;
+            }
+            canonical.insert(a.clone(), anon(format!("{}_{}", l.0.borrow(), count)));
+            count += 1;
+        }
+
+        let mut walk = WalkMut::new(RenameAnonymous {
+            labels: &canonical,
+            this: None,
+        });
+        axioms
+            .into_iter()
+            .map(|aa| {
+                let mut aa = aa.clone();
+                walk.annotated_axiom(&mut aa);
+                aa
+            })
+            .collect()
+    }
+
+    /// Find annotations with a literal value containing `needle`.
+    ///
+    /// Both the annotations of each axiom and the annotation of an
+    /// `AnnotationAssertion` are searched; each match is returned
+    /// with the axiom that it belongs to. This is a linear scan of
+    /// the ontology.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.insert(AnnotationAssertion {
+    ///     subject: b.iri("http://www.example.com/a").into(),
+    ///     ann: Annotation {
+    ///         ap: b.annotation_property("http://www.w3.org/2000/01/rdf-schema#label"),
+    ///         av: Literal::Simple { literal: "Apple".into() }.into(),
+    ///     },
+    /// });
+    ///
+    /// assert_eq!(o.search_annotations("APP", true).count(), 1);
+    /// assert_eq!(o.search_annotations("APP", false).count(), 0);
+    /// ```
+    pub fn search_annotations(
+        &self,
+        needle: &str,
+        case_insensitive: bool,
+    ) -> impl Iterator<Item = (&AnnotatedAxiom<A>, &Annotation<A>)> {
+        let needle = if case_insensitive {
+            needle.to_lowercase()
+        } else {
+            needle.to_string()
+        };
+
+        self.iter()
+            .flat_map(|aa| {
+                let asserted = match &aa.axiom {
+                    Axiom::AnnotationAssertion(AnnotationAssertion { ann, .. }) => Some(ann),
+                    _ => None,
+                };
+                aa.ann.iter().chain(asserted).map(move |ann| (aa, ann))
+            })
+            .filter(move |(_, ann)| match &ann.av {
+                AnnotationValue::Literal(l) if case_insensitive => {
+                    l.literal().to_lowercase().contains(&needle)
+                }
+                AnnotationValue::Literal(l) => l.literal().contains(&needle),
+                _ => false,
+            })
+    }
+
+    /// Return the greatest nesting depth of any class expression in
+    /// this ontology, or 0 if there are none.
+    ///
+    /// See `ClassExpression::depth`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.insert(SubClassOf {
+    ///     sub: b.class("http://www.example.com/a").into(),
+    ///     sup: ClassExpression::ObjectComplementOf(Box::new(
+    ///         b.class("http://www.example.com/b").into(),
+    ///     )),
+    /// });
+    ///
+    /// assert_eq!(o.max_expression_depth(), 2);
+    /// ```
+    pub fn max_expression_depth(&self) -> usize {
+        let mut walk = Walk::new(MaxExpressionDepth(0));
+        for aa in self.iter() {
+            walk.annotated_axiom(aa);
+        }
+        walk.into_visit().0
+    }
+
+    /// Return the property and filler of each object restriction in
+    /// this ontology.
+    ///
+    /// This covers `ObjectSomeValuesFrom`, `ObjectAllValuesFrom` and
+    /// the object cardinality restrictions, including those nested
+    /// within other class expressions. Each pair is returned once.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let b = Build::new_rc();
+    /// let mut o = SetOntology::new_rc();
+    /// o.insert(SubClassOf {
+    ///     sub: b.class("http://www.example.com/a").into(),
+    ///     sup: ClassExpression::ObjectSomeValuesFrom {
+    ///         ope: b.object_property("http://www.example.com/p").into(),
+    ///         bce: Box::new(b.class("http://www.example.com/b").into()),
+    ///     },
+    /// });
+    ///
+    /// assert_eq!(o.restriction_fillers().count(), 1);
+    /// ```
+    pub fn restriction_fillers(
+        &self,
+    ) -> impl Iterator<Item = (ObjectPropertyExpression<A>, ClassExpression<A>)> {
+        let mut walk = Walk::new(RestrictionFillers(HashSet::new()));
+        for aa in self.iter() {
+            walk.annotated_axiom(aa);
+        }
+        walk.into_visit().0.into_iter()
+    }
+}
+
+/// A syntactic reason that an ontology is not OWL2 DL.
+///
+/// See [`SetOntology::dl_syntactic_issues`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DlIssue<A> {
+    /// An IRI used as both a class and a datatype, or as more than
+    /// one kind of property.
+    IllegalPun(IRI<A>),
+    /// A transitive object property used in a cardinality
+    /// restriction.
+    NonSimpleCardinality(ObjectProperty<A>),
+    /// An entity which is used but not declared.
+    Undeclared(NamedEntity<A>),
+}
+
+/// A `DataPropertyAssertion` as a row of a table.
+///
+/// See [`SetOntology::data_rows`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DataRow<A> {
+    /// The individual with the value.
+    pub subject: Individual<A>,
+    /// The IRI of the data property.
+    pub property: IRI<A>,
+    /// The lexical form of the value.
+    pub value: String,
+    /// The datatype of the value.
+    pub datatype: IRI<A>,
+    /// The language tag of the value, if it has one.
+    pub lang: Option<String>,
+}
+
+impl<A: ForIRI> Ontology<A> for SetOntology<A> {
+    fn id(&self) -> &OntologyID<A> {
+        self.0.id()
+    }
+
+    fn mut_id(&mut self) -> &mut OntologyID<A> {
+        self.0.mut_id()
+    }
+
+    fn doc_iri(&self) -> &Option<IRI<A>> {
+        self.0.doc_iri()
+    }
+
+    fn mut_doc_iri(&mut self) -> &mut Option<IRI<A>> {
+        self.0.mut_doc_iri()
+    }
+
+    fn len(&self) -> usize {
+        SetOntology::len(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        SetOntology::is_empty(self)
+    }
+}
+
+struct ClassExpressionExtract<A>(Vec<ClassExpression<A>>);
+
+impl<A: ForIRI> Visit<A> for ClassExpressionExtract<A> {
+    fn visit_class_expression(&mut self, ce: &ClassExpression<A>) {
+        self.0.push(ce.clone());
+    }
+}
+
+struct DatatypeExtract<A>(HashSet<Datatype<A>>);
+
+impl<A: ForIRI> Visit<A> for DatatypeExtract<A> {
+    fn visit_datatype(&mut self, dt: &Datatype<A>) {
+        self.0.insert(dt.clone());
+    }
+
+    fn visit_literal(&mut self, l: &Literal<A>) {
+        if let Literal::Datatype { datatype_iri, .. } = l {
+            self.0.insert(datatype_iri.clone().into());
+        }
+    }
+}
+
+struct AnnotationPropertyExtract<A>(HashSet<AnnotationProperty<A>>);
+
+impl<A: ForIRI> Visit<A> for AnnotationPropertyExtract<A> {
+    fn visit_annotation(&mut self, ann: &Annotation<A>) {
+        self.0.insert(ann.ap.clone());
+    }
+}
+
+struct LanguageTagCount(BTreeMap<String, usize>);
+
+impl<A: ForIRI> Visit<A> for LanguageTagCount {
+    fn visit_annotation(&mut self, ann: &Annotation<A>) {
+        if let AnnotationValue::Literal(l) = &ann.av {
+            let lang = match l {
+                Literal::Language { lang, .. } => lang.to_lowercase(),
+                _ => String::new(),
+            };
+            *self.0.entry(lang).or_default() += 1;
+        }
+    }
+}
+
+struct MaxExpressionDepth(usize);
+
+impl<A: ForIRI> Visit<A> for MaxExpressionDepth {
+    fn visit_class_expression(&mut self, ce: &ClassExpression<A>) {
+        self.0 = self.0.max(ce.depth());
+    }
+}
+
+struct RestrictionFillers<A>(HashSet<(ObjectPropertyExpression<A>, ClassExpression<A>)>);
+
+impl<A: ForIRI> Visit<A> for RestrictionFillers<A> {
+    fn visit_class_expression(&mut self, ce: &ClassExpression<A>) {
+        match ce {
+            ClassExpression::ObjectSomeValuesFrom { ope, bce }
+            | ClassExpression::ObjectAllValuesFrom { ope, bce }
+            | ClassExpression::ObjectMinCardinality { ope, bce, .. }
+            | ClassExpression::ObjectMaxCardinality { ope, bce, .. }
+            | ClassExpression::ObjectExactCardinality { ope, bce, .. } => {
+                self.0.insert((ope.clone(), (**bce).clone()));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn entity_iri_kind<A: ForIRI>(e: NamedEntity<A>) -> (IRI<A>, NamedEntityKind) {
+    match e {
+        NamedEntity::Class(e) => (e.0, NamedEntityKind::Class),
+        NamedEntity::Datatype(e) => (e.0, NamedEntityKind::Datatype),
+        NamedEntity::ObjectProperty(e) => (e.0, NamedEntityKind::ObjectProperty),
+        NamedEntity::DataProperty(e) => (e.0, NamedEntityKind::DataProperty),
+        NamedEntity::AnnotationProperty(e) => (e.0, NamedEntityKind::AnnotationProperty),
+        NamedEntity::NamedIndividual(e) => (e.0, NamedEntityKind::NamedIndividual),
+    }
+}
+
+fn named_object_property<A: ForIRI>(ope: &ObjectPropertyExpression<A>) -> &ObjectProperty<A> {
+    match ope {
+        ObjectPropertyExpression::ObjectProperty(op) => op,
+        ObjectPropertyExpression::InverseObjectProperty(op) => op,
+    }
+}
+
+fn named_class_pairs<A: ForIRI>(ces: &[ClassExpression<A>]) -> Vec<(Class<A>, Class<A>)> {
+    let mut classes: Vec<_> = ces
+        .iter()
+        .filter_map(|ce| match ce {
+            ClassExpression::Class(c) => Some(c.clone()),
+            _ => None,
+        })
+        .collect();
+    classes.sort();
+    classes.dedup();
+
+    let mut pairs = vec![];
+    for (i, a) in classes.iter().enumerate() {
+        for b in &classes[i + 1..] {
+            pairs.push((a.clone(), b.clone()));
+        }
+    }
+    pairs
+}
+
+/// Return the strongly connected components of a graph, given as
+/// the adjacent nodes of each node.
+///
+/// This is Tarjan's algorithm, with an explicit stack so that long
+/// chains do not overflow.
+fn strongly_connected(adjacent: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let unvisited = usize::MAX;
+    let mut index = vec![unvisited; adjacent.len()];
+    let mut lowlink = vec![0; adjacent.len()];
+    let mut on_stack = vec![false; adjacent.len()];
+    let mut stack = vec![];
+    let mut next = 0;
+    let mut components = vec![];
+
+    for root in 0..adjacent.len() {
+        if index[root] != unvisited {
+            continue;
+        }
+
+        index[root] = next;
+        lowlink[root] = next;
+        next += 1;
+        stack.push(root);
+        on_stack[root] = true;
+        let mut calls = vec![(root, 0)];
+
+        while let Some((v, i)) = calls.last_mut() {
+            let v = *v;
+            if let Some(&w) = adjacent[v].get(*i) {
+                *i += 1;
+                if index[w] == unvisited {
+                    index[w] = next;
+                    lowlink[w] = next;
+                    next += 1;
+                    stack.push(w);
+                    on_stack[w] = true;
+                    calls.push((w, 0));
+                } else if on_stack[w] {
+                    lowlink[v] = lowlink[v].min(index[w]);
+                }
+            } else {
+                calls.pop();
+                if let Some((u, _)) = calls.last() {
+                    lowlink[*u] = lowlink[*u].min(lowlink[v]);
+                }
+
+                if lowlink[v] == index[v] {
+                    let mut component = vec![];
+                    while let Some(w) = stack.pop() {
+                        on_stack[w] = false;
+                        component.push(w);
+                        if w == v {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+    }
+
+    components
+}
+
+struct AnonymousExtract<A>(HashSet<AnonymousIndividual<A>>);
+
+impl<A: ForIRI> Visit<A> for AnonymousExtract<A> {
+    fn visit_anonymous_individual(&mut self, e: &AnonymousIndividual<A>) {
+        self.0.insert(e.clone());
+    }
+}
+
+/// Rename anonymous individuals according to `labels`, except for
+/// `this.0` which is renamed to `this.1`.
+struct RenameAnonymous<'a, A> {
+    labels: &'a HashMap<AnonymousIndividual<A>, AnonymousIndividual<A>>,
+    this: Option<(&'a AnonymousIndividual<A>, &'a AnonymousIndividual<A>)>,
+}
+
+impl<'a, A: ForIRI> VisitMut<A> for RenameAnonymous<'a, A> {
+    fn visit_anonymous_individual(&mut self, e: &mut AnonymousIndividual<A>) {
+        match self.this {
+            Some((this, label)) if this == e => *e = label.clone(),
+            _ => {
+                if let Some(label) = self.labels.get(e) {
+                    *e = label.clone()
+                }
+            }
+        }
+    }
+}
+
+fn hash_of<T: Hash>(t: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    t.hash(&mut hasher);
+    hasher.finish()
+}
+
+struct RenameAnnotationProperty<'a, A>(&'a HashMap<IRI<A>, IRI<A>>);
+
+impl<'a, A: ForIRI> VisitMut<A> for RenameAnnotationProperty<'a, A> {
+    fn visit_annotation(&mut self, ann: &mut Annotation<A>) {
+        if let Some(iri) = self.0.get(&ann.ap.0) {
+            ann.ap = iri.clone().into();
+        }
+    }
+}
+
+struct RenameClass<'a, A>(&'a HashMap<Class<A>, Class<A>>);
+
+impl<'a, A: ForIRI> VisitMut<A> for RenameClass<'a, A> {
+    fn visit_class(&mut self, c: &mut Class<A>) {
+        if let Some(r) = self.0.get(c) {
+            *c = r.clone();
+        }
+    }
+}
+
+struct MapLiterals<F>(F);
+
+impl<A: ForIRI, F> VisitMut<A> for MapLiterals<F>
+where
+    F: FnMut(&Literal<A>) -> Option<Literal<A>>,
+{
+    fn visit_literal(&mut self, l: &mut Literal<A>) {
+        if let Some(new) = (self.0)(l) {
+            *l = new;
+        }
+    }
+}
+
+struct Rebase<'a, A: ForIRI> {
+    b: &'a Build<A>,
+    old_base: &'a str,
+    new_base: &'a str,
+    count: usize,
+}
+
+impl<'a, A: ForIRI> VisitMut<A> for Rebase<'a, A> {
+    fn visit_iri(&mut self, iri: &mut IRI<A>) {
+        if let Some(rest) = iri.strip_prefix(self.old_base) {
+            *iri = self.b.iri(format!("{}{}", self.new_base, rest));
+            self.count += 1;
+        }
+    }
+}
+
+/// Return true if `to` can be reached from `from` through `sups`,
+/// which maps each class to its direct superclasses.
+fn reachable<A: ForIRI>(
+    sups: &HashMap<Class<A>, HashSet<Class<A>>>,
+    from: &Class<A>,
+    to: &Class<A>,
+) -> bool {
+    let mut seen = HashSet::new();
+    let mut stack = vec![from];
+    while let Some(c) = stack.pop() {
+        if c == to {
+            return true;
+        }
+        if seen.insert(c) {
+            stack.extend(sups.get(c).into_iter().flatten());
+        }
+    }
+    false
+}
+
+/// Find the root of `i` in a union-find forest, compressing the
+/// path as we go.
+fn find_root(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+pub(crate) struct Reintern<'a, A: ForIRI>(pub(crate) &'a Build<A>);
+
+impl<'a, A: ForIRI> VisitMut<A> for Reintern<'a, A> {
+    fn visit_iri(&mut self, iri: &mut IRI<A>) {
+        *iri = self.0.iri(&**iri);
+    }
+
+    fn visit_anonymous_individual(&mut self, anon: &mut AnonymousIndividual<A>) {
+        *anon = self.0.anon(&**anon);
+    }
+}
+
+/// An Interator for `SetOntology`
+pub struct SetIter<'a, A: ForIRI>(std::collections::hash_set::Iter<'a, AnnotatedAxiom<A>>);
+
+impl<'a, A: ForIRI> Iterator for SetIter<'a, A> {
+    type Item = &'a AnnotatedAxiom<A>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+impl<'a, A: ForIRI> IntoIterator for &'a SetOntology<A> {
+    type Item = &'a AnnotatedAxiom<A>;
+    type IntoIter = SetIter<'a, A>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An owning iterator over the annotated axioms of an `Ontology`.
+pub struct SetIntoIter<A: ForIRI>(std::vec::IntoIter<AnnotatedAxiom<A>>);
+
+impl<A: ForIRI> Iterator for SetIntoIter<A> {
+    type Item = AnnotatedAxiom<A>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+impl<A: ForIRI> IntoIterator for SetOntology<A> {
+    type Item = AnnotatedAxiom<A>;
+    type IntoIter = SetIntoIter<A>;
+    fn into_iter(self) -> Self::IntoIter {
+        SetIntoIter(self.0.index().into_iter())
+    }
+}
+
+impl<A: ForIRI> MutableOntology<A> for SetOntology<A> {
+    /// Insert an axiom into the ontology.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::set::SetOntology;
+    /// let mut o = SetOntology::new();
+    /// let b = Build::new_rc();
+    /// o.insert(DeclareClass(b.class("http://www.example.com/a")));
+    /// o.insert(DeclareObjectProperty(b.object_property("http://www.example.com/r")));
+    /// ```
+    ///
+    /// See `declare` for an easier way to declare named entities.
+    fn insert<AA>(&mut self, ax: AA) -> bool
+    where
+        AA: Into<AnnotatedAxiom<A>>,
+    {
+        self.0.insert(ax)
+    }
+
+    fn remove(&mut self, ax: &AnnotatedAxiom<A>) -> bool {
+        self.0.remove(ax)
+    }
+
+    fn take(&mut self, ax: &AnnotatedAxiom<A>) -> Option<AnnotatedAxiom<A>> {
+        self.0.take(ax)
+    }
+}
+
+impl<A: ForIRI> FromIterator<AnnotatedAxiom<A>> for SetOntology<A> {
+    fn from_iter<I: IntoIterator<Item = AnnotatedAxiom<A>>>(iter: I) -> Self {
+        SetOntology(OneIndexedOntology::new(SetIndex(
+            HashSet::from_iter(iter),
+            Default::default(),
+        )))
+    }
+}
+
+impl<A: ForIRI, I> From<(OntologyID<A>, I)> for SetOntology<A>
+where
+    I: Iterator<Item = AnnotatedAxiom<A>>,
+{
+    fn from((mut oid, i): (OntologyID<A>, I)) -> SetOntology<A> {
+        let mut so: SetOntology<_> = i.collect();
+        std::mem::swap(so.mut_id(), &mut oid);
+        so
+    }
+}
+
+/*
+impl<A: ForIRI, O, I> From<(O, I)> for SetOntology<A>
+where
+    I: Iterator<Item = AnnotatedAxiom<A>>,
+    O: Ontology<A>,
+{
+    fn from((mut o, i): (O, I)) -> SetOntology<A> {
+        (o.mut_id().clone(), i).into()
+    }
+}
+*/
+
+/// An `OntologyIndex` implemented over an in-memory HashSet. When
+/// combined with an `IndexedOntology` this should be nearly as
+/// fastest as `SetOntology`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SetIndex<A: ForIRI, AA: ForIndex<A>>(HashSet<AA>, PhantomData<A>);
+
+/// A `SetIndex` sharing axioms with `Rc`.
+pub type RcSetIndex = SetIndex<RcStr, RcAnnotatedAxiom>;
+
+/// A `SetIndex` sharing axioms with `Arc`.
+pub type ArcSetIndex = SetIndex<ArcStr, ArcAnnotatedAxiom>;
+
+impl<A: ForIRI, AA: ForIndex<A>> OntologyIndex<A, AA> for SetIndex<A, AA> {
+    fn index_insert(&mut self, ax: AA) -> bool {
+        self.0.insert(ax)
+    }
+
+    fn index_remove(&mut self, ax: &AnnotatedAxiom<A>) -> bool {
+        self.0.remove(ax)
+    }
+}
+
+impl<A: ForIRI, AA: ForIndex<A>, B: ForIRI, BB: ForIndex<B>> ReindexInto<B, BB>
+    for SetIndex<A, AA>
+{
+    type Output = SetIndex<B, BB>;
+
+    fn empty_index(&self) -> SetIndex<B, BB> {
+        SetIndex::new()
+    }
+}
+
+impl<A: ForIRI, AA: ForIndex<A>> SetIndex<A, AA> {
+    pub fn new() -> SetIndex<A, AA> {
+        SetIndex(Default::default(), Default::default())
+    }
+
+    pub fn contains(&self, ax: &AnnotatedAxiom<A>) -> bool {
+        self.0.contains(ax)
+    }
+
+    /// Returns the stored value equal to `ax`, if any.
+    pub fn get(&self, ax: &AnnotatedAxiom<A>) -> Option<&AA> {
+        self.0.get(ax)
+    }
+
+    /// Returns the number of axioms in the index.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if the index contains no axioms.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl SetIndex<RcStr, Rc<AnnotatedAxiom<RcStr>>> {
+    pub fn new_rc() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: ForIRI, AA: ForIndex<A>> IntoIterator for SetIndex<A, AA> {
+    type Item = AnnotatedAxiom<A>;
+    type IntoIter = std::vec::IntoIter<AnnotatedAxiom<A>>;
+    fn into_iter(self) -> Self::IntoIter {
+        #[allow(clippy::needless_collect)]
+        let v: Vec<AnnotatedAxiom<_>> = self.0.into_iter().map(|fi| fi.unwrap()).collect();
+        v.into_iter()
+    }
+}
+
+impl<'a, A: ForIRI, AA: ForIndex<A>> IntoIterator for &'a SetIndex<A, AA> {
+    type Item = &'a AnnotatedAxiom<A>;
+    type IntoIter = std::vec::IntoIter<&'a AnnotatedAxiom<A>>;
+    fn into_iter(self) -> Self::IntoIter {
+        #[allow(clippy::needless_collect)]
+        let v: Vec<&'a AnnotatedAxiom<A>> = self.0.iter().map(|fiac| fiac.borrow()).collect();
+        v.into_iter()
+    }
+}
+
+type AxiomFilter<A> = Box<dyn Fn(&AnnotatedAxiom<A>) -> bool>;
+
+/// A `SetIndex` which only retains the axioms matching a predicate.
+///
+/// Other axioms are not retained, and `index_insert` returns false
+/// for them; if no other index of an ontology retains them, they
+/// are dropped. This allows different indexes of an ontology to hold
+/// different parts of it, for example only the TBox.
+pub struct FilteredSetIndex<A: ForIRI, AA: ForIndex<A>> {
+    index: SetIndex<A, AA>,
+    filter: AxiomFilter<A>,
+}
+
+impl<A: ForIRI, AA: ForIndex<A>> FilteredSetIndex<A, AA> {
+    /// Create a new index, which retains the axioms for which
+    /// `filter` returns true.
+    ///
+    /// # Examples
+    /// ```
+    /// # use horned_owl::model::*;
+    /// # use horned_owl::ontology::indexed::OneIndexedOntology;
+    /// # use horned_owl::ontology::set::FilteredSetIndex;
+    /// let b = Build::new_rc();
+    /// let mut o = OneIndexedOntology::new_rc(FilteredSetIndex::new(|aa: &AnnotatedAxiom<_>| {
+    ///     aa.kind() == AxiomKind::DeclareClass
+    /// }));
+    ///
+    /// assert!(o.insert(DeclareClass(b.class("http://www.example.com/a"))));
+    /// assert!(!o.insert(DeclareDatatype(b.datatype("http://www.example.com/d"))));
+    /// assert_eq!(o.i().len(), 1);
+    /// ```
+    pub fn new<F>(filter: F) -> FilteredSetIndex<A, AA>
+    where
+        F: Fn(&AnnotatedAxiom<A>) -> bool + 'static,
+    {
+        FilteredSetIndex {
+            index: SetIndex::new(),
+            filter: Box::new(filter),
+        }
+    }
+
+    pub fn contains(&self, ax: &AnnotatedAxiom<A>) -> bool {
+        self.index.contains(ax)
+    }
+
+    /// Returns the number of axioms in the index.
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Returns true if the index contains no axioms.
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+}
+
+impl<A: ForIRI, AA: ForIndex<A>> std::fmt::Debug for FilteredSetIndex<A, AA> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FilteredSetIndex")
+            .field("index", &self.index)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A: ForIRI, AA: ForIndex<A>> OntologyIndex<A, AA> for FilteredSetIndex<A, AA> {
+    fn index_insert(&mut self, ax: AA) -> bool {
+        (self.filter)(ax.borrow()) && self.index.index_insert(ax)
+    }
+
+    fn index_remove(&mut self, ax: &AnnotatedAxiom<A>) -> bool {
+        self.index.index_remove(ax)
+    }
+}
+
+impl<'a, A: ForIRI, AA: ForIndex<A>> IntoIterator for &'a FilteredSetIndex<A, AA> {
+    type Item = &'a AnnotatedAxiom<A>;
+    type IntoIter = std::vec::IntoIter<&'a AnnotatedAxiom<A>>;
+    fn into_iter(self) -> Self::IntoIter {
+        (&self.index).into_iter()
+    }
+}
+
+#[cfg(test)]
 mod test {
     use super::{SetIndex, SetOntology};
     use crate::{model::*, ontology::indexed::OneIndexedOntology};
 
     #[test]
-    fn test_ontology_cons() {
-        let _ = SetOntology::new_rc();
-        assert!(true);
+    fn test_ontology_cons() {
+        let _ = SetOntology::new_rc();
+        assert!(true);
+    }
+
+    #[test]
+    fn test_ontology_id() {
+        let mut so = SetOntology::new_rc();
+        let b = Build::new_rc();
+        let mut oid = OntologyID {
+            iri: Some(b.iri("http://www.example.com/iri")),
+            viri: Some(b.iri("http://www.example.com/viri")),
+        };
+
+        std::mem::swap(so.mut_id(), &mut oid);
+
+        assert_eq!(so.id().iri, Some(b.iri("http://www.example.com/iri")));
+        assert_eq!(so.id().viri, Some(b.iri("http://www.example.com/viri")))
+    }
+
+    #[test]
+    fn test_ontology_clone() {
+        let mut so = SetOntology::new_rc();
+        let b = Build::new_rc();
+        let mut oid = OntologyID {
+            iri: Some(b.iri("http://www.example.com/iri")),
+            viri: Some(b.iri("http://www.example.com/viri")),
+        };
+
+        std::mem::swap(so.mut_id(), &mut oid);
+
+        let cso = so.clone();
+
+        assert_eq!(cso.id().iri, Some(b.iri("http://www.example.com/iri")));
+        assert_eq!(cso.id().viri, Some(b.iri("http://www.example.com/viri")))
+    }
+
+    #[test]
+    fn test_ontology_revision() {
+        let mut so = SetOntology::new_rc();
+        let b = Build::new_rc();
+        assert_eq!(so.revision(), 0);
+
+        so.declare(b.class("http://www.example.com/a"));
+        so.declare(b.class("http://www.example.com/b"));
+        assert_eq!(so.revision(), 2);
+
+        so.declare(b.class("http://www.example.com/a"));
+        assert_eq!(so.revision(), 2);
+
+        so.remove(&DeclareClass(b.class("http://www.example.com/a")).into());
+        assert_eq!(so.revision(), 3);
+    }
+
+    #[test]
+    fn test_ontology_len() {
+        let mut so = SetOntology::new_rc();
+        let b = Build::new_rc();
+        so.mut_id().iri = Some(b.iri("http://www.example.com/iri"));
+        assert_eq!(so.len(), 0);
+        assert!(so.is_empty());
+
+        so.declare(b.class("http://www.example.com/a"));
+        so.declare(b.class("http://www.example.com/a"));
+        so.declare(b.class("http://www.example.com/b"));
+        assert_eq!(so.len(), 2);
+        assert!(!so.is_empty());
+    }
+
+    #[test]
+    fn test_ontology_iter_empty() {
+        // Empty ontologies should stop iteration right away
+        let mut it = SetOntology::new_rc().into_iter();
+        assert_eq!(it.next(), None);
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn test_ontology_into_iter() {
+        // Setup
+        let build = Build::new_rc();
+        let mut o = SetOntology::new();
+        let decl1 = DeclareClass(build.class("http://www.example.com#a"));
+        let decl2 = DeclareClass(build.class("http://www.example.com#b"));
+        let decl3 = DeclareClass(build.class("http://www.example.com#c"));
+        let disj1 = DisjointClasses(vec![
+            ClassExpression::Class(build.class("http://www.example.com#a")),
+            ClassExpression::Class(build.class("http://www.example.com#b")),
+        ]);
+        let disj2 = DisjointClasses(vec![
+            ClassExpression::Class(build.class("http://www.example.com#b")),
+            ClassExpression::Class(build.class("http://www.example.com#c")),
+        ]);
+        o.insert(disj1.clone());
+        o.insert(disj2.clone());
+        o.insert(decl1.clone());
+        o.insert(decl2.clone());
+        o.insert(decl3.clone());
+
+        // Iteration is based on ascending order of axiom kinds.
+        let mut v: Vec<_> = o.into_iter().collect();
+        v.sort();
+        let mut it = v.into_iter();
+        assert_eq!(
+            it.next(),
+            Some(AnnotatedAxiom::from(Axiom::DeclareClass(decl1)))
+        );
+        assert_eq!(
+            it.next(),
+            Some(AnnotatedAxiom::from(Axiom::DeclareClass(decl2)))
+        );
+        assert_eq!(
+            it.next(),
+            Some(AnnotatedAxiom::from(Axiom::DeclareClass(decl3)))
+        );
+        assert_eq!(
+            it.next(),
+            Some(AnnotatedAxiom::from(Axiom::DisjointClasses(disj1)))
+        );
+        assert_eq!(
+            it.next(),
+            Some(AnnotatedAxiom::from(Axiom::DisjointClasses(disj2)))
+        );
+        assert_eq!(it.next(), None);
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn test_ontology_into_iter_empty() {
+        // Empty ontologies should stop iteration right away
+        let o = SetOntology::new_rc();
+        let mut it = (&o).into_iter();
+        assert_eq!(it.next(), None);
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn test_ontology_iter() {
+        // Setup
+        let build = Build::new_rc();
+        let mut o = SetOntology::new();
+        let decl1 = DeclareClass(build.class("http://www.example.com#a"));
+        let decl2 = DeclareClass(build.class("http://www.example.com#b"));
+        let decl3 = DeclareClass(build.class("http://www.example.com#c"));
+        let disj1 = DisjointClasses(vec![
+            ClassExpression::Class(build.class("http://www.example.com#a")),
+            ClassExpression::Class(build.class("http://www.example.com#b")),
+        ]);
+        let disj2 = DisjointClasses(vec![
+            ClassExpression::Class(build.class("http://www.example.com#b")),
+            ClassExpression::Class(build.class("http://www.example.com#c")),
+        ]);
+        o.insert(disj1.clone());
+        o.insert(disj2.clone());
+        o.insert(decl1.clone());
+        o.insert(decl2.clone());
+        o.insert(decl3.clone());
+
+        // Iteration is set based so undefined in order. So, sort first.
+        let mut v: Vec<_> = (&o).into_iter().collect();
+        v.sort();
+        let mut it = v.into_iter();
+        assert_eq!(
+            it.next(),
+            Some(&AnnotatedAxiom::from(Axiom::DeclareClass(decl1)))
+        );
+        assert_eq!(
+            it.next(),
+            Some(&AnnotatedAxiom::from(Axiom::DeclareClass(decl2)))
+        );
+        assert_eq!(
+            it.next(),
+            Some(&AnnotatedAxiom::from(Axiom::DeclareClass(decl3)))
+        );
+        assert_eq!(
+            it.next(),
+            Some(&AnnotatedAxiom::from(Axiom::DisjointClasses(disj1)))
+        );
+        assert_eq!(
+            it.next(),
+            Some(&AnnotatedAxiom::from(Axiom::DisjointClasses(disj2)))
+        );
+        assert_eq!(it.next(), None);
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn test_ontology_into() {
+        // Setup
+        let build = Build::new_rc();
+        let mut o = SetOntology::new();
+        let decl1 = DeclareClass(build.class("http://www.example.com#a"));
+        let decl2 = DeclareClass(build.class("http://www.example.com#b"));
+        let decl3 = DeclareClass(build.class("http://www.example.com#c"));
+
+        o.insert(decl1.clone());
+        o.insert(decl2.clone());
+        o.insert(decl3.clone());
+
+        let newo: SetOntology<_> = (o.mut_id().clone(), o.into_iter()).into();
+
+        // Iteration is set based so undefined in order. So, sort first.
+        let mut v: Vec<_> = (&newo).into_iter().collect();
+        v.sort();
+        let mut it = v.into_iter();
+        assert_eq!(
+            it.next(),
+            Some(&AnnotatedAxiom::from(Axiom::DeclareClass(decl1)))
+        );
+        assert_eq!(
+            it.next(),
+            Some(&AnnotatedAxiom::from(Axiom::DeclareClass(decl2)))
+        );
+        assert_eq!(
+            it.next(),
+            Some(&AnnotatedAxiom::from(Axiom::DeclareClass(decl3)))
+        );
+        assert_eq!(it.next(), None);
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn test_index_cons() {
+        let _ = SetIndex::new_rc();
+        assert!(true);
+    }
+
+    #[test]
+    fn test_index_iter_empty() {
+        // Empty ontologies should stop iteration right away
+        let mut it = SetIndex::new_rc().into_iter();
+        assert_eq!(it.next(), None);
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn test_index_into_iter() {
+        // Setup
+        let build = Build::new_rc();
+        let mut o = OneIndexedOntology::new(SetIndex::new_rc());
+        let decl1 = DeclareClass(build.class("http://www.example.com#a"));
+        let decl2 = DeclareClass(build.class("http://www.example.com#b"));
+        let decl3 = DeclareClass(build.class("http://www.example.com#c"));
+        let disj1 = DisjointClasses(vec![
+            ClassExpression::Class(build.class("http://www.example.com#a")),
+            ClassExpression::Class(build.class("http://www.example.com#b")),
+        ]);
+        let disj2 = DisjointClasses(vec![
+            ClassExpression::Class(build.class("http://www.example.com#b")),
+            ClassExpression::Class(build.class("http://www.example.com#c")),
+        ]);
+        o.insert(disj1.clone());
+        o.insert(disj2.clone());
+        o.insert(decl1.clone());
+        o.insert(decl2.clone());
+        o.insert(decl3.clone());
+
+        // Iteration is based on ascending order of axiom kinds.
+        let mut v: Vec<_> = o.index().into_iter().collect();
+        v.sort();
+        let mut it = v.into_iter();
+        assert_eq!(
+            it.next(),
+            Some(AnnotatedAxiom::from(Axiom::DeclareClass(decl1)))
+        );
+        assert_eq!(
+            it.next(),
+            Some(AnnotatedAxiom::from(Axiom::DeclareClass(decl2)))
+        );
+        assert_eq!(
+            it.next(),
+            Some(AnnotatedAxiom::from(Axiom::DeclareClass(decl3)))
+        );
+        assert_eq!(
+            it.next(),
+            Some(AnnotatedAxiom::from(Axiom::DisjointClasses(disj1)))
+        );
+        assert_eq!(
+            it.next(),
+            Some(AnnotatedAxiom::from(Axiom::DisjointClasses(disj2)))
+        );
+        assert_eq!(it.next(), None);
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn test_index_into_iter_empty() {
+        // Empty ontologies should stop iteration right away
+        let o = OneIndexedOntology::new(SetIndex::new_rc());
+        let mut it = o.i().into_iter();
+        assert_eq!(it.next(), None);
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn test_index_iter() {
+        // Setup
+        let build = Build::new_rc();
+        let mut o = OneIndexedOntology::new(SetIndex::new_rc());
+        let decl1 = DeclareClass(build.class("http://www.example.com#a"));
+        let decl2 = DeclareClass(build.class("http://www.example.com#b"));
+        let decl3 = DeclareClass(build.class("http://www.example.com#c"));
+        let disj1 = DisjointClasses(vec![
+            ClassExpression::Class(build.class("http://www.example.com#a")),
+            ClassExpression::Class(build.class("http://www.example.com#b")),
+        ]);
+        let disj2 = DisjointClasses(vec![
+            ClassExpression::Class(build.class("http://www.example.com#b")),
+            ClassExpression::Class(build.class("http://www.example.com#c")),
+        ]);
+        o.insert(disj1.clone());
+        o.insert(disj2.clone());
+        o.insert(decl1.clone());
+        o.insert(decl2.clone());
+        o.insert(decl3.clone());
+
+        // Iteration is set based so undefined in order. So, sort first.
+        let mut v: Vec<_> = o.i().into_iter().collect();
+        v.sort();
+        let mut it = v.into_iter();
+        assert_eq!(
+            it.next(),
+            Some(&AnnotatedAxiom::from(Axiom::DeclareClass(decl1)))
+        );
+        assert_eq!(
+            it.next(),
+            Some(&AnnotatedAxiom::from(Axiom::DeclareClass(decl2)))
+        );
+        assert_eq!(
+            it.next(),
+            Some(&AnnotatedAxiom::from(Axiom::DeclareClass(decl3)))
+        );
+        assert_eq!(
+            it.next(),
+            Some(&AnnotatedAxiom::from(Axiom::DisjointClasses(disj1)))
+        );
+        assert_eq!(
+            it.next(),
+            Some(&AnnotatedAxiom::from(Axiom::DisjointClasses(disj2)))
+        );
+        assert_eq!(it.next(), None);
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn test_annotation_order() {
+        let mut o = SetOntology::new_rc();
+        let b = Build::new_rc();
+
+        let ann1 = Annotation {
+            ap: b.annotation_property("http://www.example.com/ap1"),
+            av: b.iri("http://www.example.com/av1").into(),
+        };
+        let ann2 = Annotation {
+            ap: b.annotation_property("http://www.example.com/ap2"),
+            av: b.iri("http://www.example.com/av2").into(),
+        };
+        let decl = DeclareClass(b.class("http://www.example.com#a"));
+
+        assert!(o.insert(AnnotatedAxiom::new(
+            decl.clone(),
+            vec![ann1.clone(), ann2.clone()].into_iter().collect()
+        )));
+        assert!(!o.insert(AnnotatedAxiom::new(
+            decl,
+            vec![ann2, ann1].into_iter().collect()
+        )));
+
+        assert_eq!(o.iter().count(), 1);
+    }
+
+    #[test]
+    fn test_datatypes_used() {
+        let mut o = SetOntology::new_rc();
+        let b = Build::new_rc();
+        let integer = b.datatype("http://www.w3.org/2001/XMLSchema#integer");
+        let int = b.datatype("http://www.w3.org/2001/XMLSchema#int");
+
+        o.insert(DataPropertyRange {
+            dp: b.data_property("http://www.example.com/dp"),
+            dr: DataRange::DataComplementOf(Box::new(DataRange::DatatypeRestriction(
+                integer.clone(),
+                vec![FacetRestriction {
+                    f: Facet::MinInclusive,
+                    l: Literal::Datatype {
+                        literal: "5".into(),
+                        datatype_iri: int.clone().into(),
+                    },
+                }],
+            ))),
+        });
+
+        let used = o.datatypes_used();
+        assert_eq!(used.len(), 2);
+        assert!(used.contains(&integer));
+        assert!(used.contains(&int));
+    }
+
+    #[test]
+    fn test_annotation_properties_used() {
+        let mut o = SetOntology::new_rc();
+        let b = Build::new_rc();
+        let label = b.annotation_property("http://www.w3.org/2000/01/rdf-schema#label");
+        let comment = b.annotation_property("http://www.w3.org/2000/01/rdf-schema#comment");
+        let unused = b.annotation_property("http://www.example.com/unused");
+
+        o.insert(b.annotation_assertion(
+            "http://www.example.com/a",
+            "http://www.w3.org/2000/01/rdf-schema#label",
+            "a",
+        ));
+        o.insert(DeclareAnnotationProperty(unused.clone()));
+        o.insert(AnnotatedAxiom::new(
+            DeclareClass(b.class("http://www.example.com/a")),
+            vec![Annotation {
+                ap: comment.clone(),
+                av: "a class".into(),
+            }]
+            .into_iter()
+            .collect(),
+        ));
+
+        let used = o.annotation_properties_used();
+        assert_eq!(used.len(), 2);
+        assert!(used.contains(&label));
+        assert!(used.contains(&comment));
+        assert!(!used.contains(&unused));
+    }
+
+    #[test]
+    fn test_usage_counts() {
+        let mut o = SetOntology::new_rc();
+        let b = Build::new_rc();
+        let a = b.class("http://www.example.com#a");
+        let r = b.object_property("http://www.example.com#r");
+
+        o.declare(a.clone());
+        o.declare(b.class("http://www.example.com#orphan"));
+        o.insert(SubClassOf {
+            sub: b.class("http://www.example.com#b").into(),
+            sup: a.clone().into(),
+        });
+        o.insert(SubClassOf {
+            sub: b.class("http://www.example.com#c").into(),
+            sup: ClassExpression::ObjectSomeValuesFrom {
+                ope: r.clone().into(),
+                bce: Box::new(a.clone().into()),
+            },
+        });
+
+        let counts = o.usage_counts();
+        assert_eq!(counts[&a.into()], 2);
+        assert_eq!(counts[&r.into()], 1);
+        assert_eq!(counts[&b.class("http://www.example.com#orphan").into()], 0);
+    }
+
+    #[test]
+    fn test_all_class_expressions() {
+        let mut o = SetOntology::new_rc();
+        let b = Build::new_rc();
+        let r = b.object_property("http://www.example.com#r");
+        let some = |c: &str| ClassExpression::ObjectSomeValuesFrom {
+            ope: r.clone().into(),
+            bce: Box::new(b.class(c).into()),
+        };
+
+        let inter = ClassExpression::ObjectIntersectionOf(vec![
+            some("http://www.example.com#b"),
+            some("http://www.example.com#c"),
+        ]);
+        o.insert(EquivalentClasses(vec![
+            b.class("http://www.example.com#a").into(),
+            inter.clone(),
+        ]));
+
+        let ces: Vec<_> = o.all_class_expressions().collect();
+        assert_eq!(ces.len(), 6);
+        assert_eq!(ces[0], b.class("http://www.example.com#a").into());
+        assert_eq!(ces[1], inter);
+        assert_eq!(ces[2], some("http://www.example.com#b"));
+        assert_eq!(ces[3], b.class("http://www.example.com#b").into());
+        assert_eq!(
+            ces.iter()
+                .filter(|ce| matches!(ce, ClassExpression::ObjectSomeValuesFrom { .. }))
+                .count(),
+            2
+        );
+    }
+
+    #[cfg(feature = "graph")]
+    #[test]
+    fn test_hierarchy_graph() {
+        let mut o = SetOntology::new_rc();
+        let b = Build::new_rc();
+        let c = |s: &str| b.class(format!("http://www.example.com#{}", s));
+        let sub = |a: &str, b: &str| SubClassOf {
+            sub: c(a).into(),
+            sup: c(b).into(),
+        };
+
+        o.insert(sub("b", "a"));
+        o.insert(sub("c", "a"));
+        o.insert(sub("d", "b"));
+        o.insert(sub("d", "c"));
+        o.insert(SubClassOf {
+            sub: c("e").into(),
+            sup: ClassExpression::ObjectComplementOf(Box::new(c("a").into())),
+        });
+
+        let (g, nodes) = o.hierarchy_graph();
+        assert_eq!(g.node_count(), 5);
+        assert_eq!(g.edge_count(), 4);
+        assert_eq!(g.neighbors(nodes[&c("d")]).count(), 2);
+        assert!(g.contains_edge(nodes[&c("d")], nodes[&c("c")]));
+        assert!(!petgraph::algo::is_cyclic_directed(&g));
+    }
+
+    #[test]
+    fn test_sub_property_hierarchy() {
+        let mut o = SetOntology::new_rc();
+        let b = Build::new_rc();
+        let op = |s: &str| b.object_property(format!("http://www.example.com/{}", s));
+        let ne = |s: &str| -> NamedEntity<_> { op(s).into() };
+
+        // r < q < p, and s < p
+        o.insert(SubObjectPropertyOf {
+            sub: ObjectPropertyExpression::from(op("q")).into(),
+            sup: op("p").into(),
+        });
+        o.insert(SubObjectPropertyOf {
+            sub: ObjectPropertyExpression::from(op("r")).into(),
+            sup: op("q").into(),
+        });
+        o.insert(SubObjectPropertyOf {
+            sub: ObjectPropertyExpression::from(op("s")).into(),
+            sup: op("p").into(),
+        });
+        // Neither a chain nor an inverse is a simple edge
+        o.insert(SubObjectPropertyOf {
+            sub: SubObjectPropertyExpression::ObjectPropertyChain(vec![
+                op("r").into(),
+                op("s").into(),
+            ]),
+            sup: op("t").into(),
+        });
+        o.insert(SubObjectPropertyOf {
+            sub: ObjectPropertyExpression::InverseObjectProperty(op("u")).into(),
+            sup: op("p").into(),
+        });
+
+        let h = o.sub_property_hierarchy();
+        assert_eq!(h.len(), 3);
+        assert_eq!(
+            h.direct_sub_properties(&ne("p")),
+            vec![ne("q"), ne("s")].into_iter().collect()
+        );
+        assert_eq!(
+            h.sub_properties(&ne("p")),
+            vec![ne("q"), ne("r"), ne("s")].into_iter().collect()
+        );
+        assert_eq!(
+            h.direct_super_properties(&ne("r")),
+            vec![ne("q")].into_iter().collect()
+        );
+        assert_eq!(
+            h.super_properties(&ne("r")),
+            vec![ne("q"), ne("p")].into_iter().collect()
+        );
+        assert!(h.sub_properties(&ne("t")).is_empty());
+    }
+
+    #[test]
+    fn test_subclass_cycles() {
+        let mut o = SetOntology::new_rc();
+        let b = Build::new_rc();
+        let c = |s: &str| b.class(format!("http://www.example.com#{}", s));
+        let sub = |a: &str, b: &str| SubClassOf {
+            sub: c(a).into(),
+            sup: c(b).into(),
+        };
+
+        o.insert(sub("x", "a"));
+        o.insert(sub("a", "b"));
+        o.insert(sub("b", "c"));
+        o.insert(sub("c", "a"));
+        o.insert(sub("c", "d"));
+        o.insert(sub("e", "e"));
+        assert_eq!(o.subclass_cycles(), vec![vec![c("a"), c("b"), c("c")]]);
+
+        o.insert(sub("f", "g"));
+        o.insert(sub("g", "f"));
+        assert_eq!(
+            o.subclass_cycles(),
+            vec![vec![c("a"), c("b"), c("c")], vec![c("f"), c("g")]]
+        );
+    }
+
+    #[test]
+    fn test_defined_and_primitive_classes() {
+        let mut o = SetOntology::new_rc();
+        let b = Build::new_rc();
+        let c = |s: &str| b.class(format!("http://www.example.com#{}", s));
+        let some = |s: &str| ClassExpression::ObjectSomeValuesFrom {
+            ope: b.object_property("http://www.example.com#p").into(),
+            bce: Box::new(c(s).into()),
+        };
+
+        // a is defined, with a subclass axiom as well
+        o.insert(EquivalentClasses(vec![c("a").into(), some("x")]));
+        o.insert(SubClassOf {
+            sub: c("a").into(),
+            sup: c("x").into(),
+        });
+        // b is primitive
+        o.insert(SubClassOf {
+            sub: c("b").into(),
+            sup: some("x"),
+        });
+        // d is defined by another named class
+        o.insert(EquivalentClasses(vec![c("d").into(), c("e").into()]));
+
+        assert_eq!(
+            o.defined_classes(),
+            vec![c("a"), c("d"), c("e")].into_iter().collect()
+        );
+        assert_eq!(o.primitive_classes(), vec![c("b")].into_iter().collect());
+    }
+
+    #[test]
+    fn test_take_all() {
+        let mut o = SetOntology::new_rc();
+        let b = Build::new_rc();
+        let decl = |s: &str| -> AnnotatedAxiom<_> {
+            DeclareClass(b.class(format!("http://www.example.com#{}", s))).into()
+        };
+        o.insert(decl("a"));
+        o.insert(decl("b"));
+        o.insert(decl("c"));
+
+        let taken = o.take_all(&[decl("c"), decl("x"), decl("a"), decl("a")]);
+        assert_eq!(taken, vec![decl("c"), decl("a")]);
+        assert_eq!(o.iter().collect::<Vec<_>>(), vec![&decl("b")]);
+    }
+
+    #[test]
+    fn test_redundant_declarations() {
+        let mut o = SetOntology::new_rc();
+        let b = Build::new_rc();
+        let a = b.class("http://www.example.com#a");
+        let comment = |s: &str| {
+            vec![Annotation {
+                ap: b.annotation_property("http://www.w3.org/2000/01/rdf-schema#comment"),
+                av: s.into(),
+            }]
+            .into_iter()
+            .collect()
+        };
+
+        o.declare(a.clone());
+        o.declare(b.class("http://www.example.com#b"));
+        o.declare(b.object_property("http://www.example.com#a"));
+        assert!(o.redundant_declarations().is_empty());
+
+        o.insert(AnnotatedAxiom::new(DeclareClass(a.clone()), comment("A")));
+        assert_eq!(o.redundant_declarations(), vec![a.clone().into()]);
+
+        // Still only reported once
+        o.insert(AnnotatedAxiom::new(DeclareClass(a.clone()), comment("B")));
+        assert_eq!(o.redundant_declarations(), vec![a.into()]);
+    }
+
+    #[test]
+    fn test_entity_components() {
+        use std::collections::HashSet;
+
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        let c = |s: &str| b.class(format!("http://www.example.com/{}", s));
+
+        o.insert(SubClassOf {
+            sub: c("a").into(),
+            sup: c("b").into(),
+        });
+        o.insert(SubClassOf {
+            sub: c("b").into(),
+            sup: ClassExpression::ObjectSomeValuesFrom {
+                ope: b.object_property("http://www.example.com/r").into(),
+                bce: Box::new(c("c").into()),
+            },
+        });
+        o.insert(DisjointClasses(vec![c("x").into(), c("y").into()]));
+        o.declare(c("y"));
+
+        let components = o.entity_components();
+        assert_eq!(components.len(), 2);
+
+        let abc: HashSet<NamedEntity<_>> = vec![
+            c("a").into(),
+            c("b").into(),
+            c("c").into(),
+            b.object_property("http://www.example.com/r").into(),
+        ]
+        .into_iter()
+        .collect();
+        let xy: HashSet<NamedEntity<_>> = vec![c("x").into(), c("y").into()].into_iter().collect();
+        assert!(components.contains(&abc));
+        assert!(components.contains(&xy));
+    }
+
+    #[test]
+    fn test_merge_with_provenance() {
+        let b = Build::new_rc();
+        let ap = b.annotation_property("http://www.example.com/importedFrom");
+        let s1 = b.iri("http://www.example.com/s1");
+        let s2 = b.iri("http://www.example.com/s2");
+        let sc = |sub: &str, sup: &str| SubClassOf {
+            sub: b.class(format!("http://www.example.com/{}", sub)).into(),
+            sup: b.class(format!("http://www.example.com/{}", sup)).into(),
+        };
+
+        let mut first = SetOntology::new_rc();
+        first.insert(sc("a", "b"));
+        first.insert(sc("b", "c"));
+        let mut second = SetOntology::new_rc();
+        second.insert(sc("b", "c"));
+        second.insert(sc("c", "d"));
+
+        let mut o = SetOntology::new_rc();
+        o.merge_with_provenance(first, &s1, &ap);
+        o.merge_with_provenance(second, &s2, &ap);
+        assert_eq!(o.len(), 3);
+
+        let sources = |ax: Axiom<_>| -> Vec<AnnotationValue<_>> {
+            let aa = o.iter().find(|aa| aa.axiom == ax).unwrap();
+            aa.ann.iter().map(|a| a.av.clone()).collect()
+        };
+        assert_eq!(sources(sc("a", "b").into()), vec![s1.clone().into()]);
+        assert_eq!(
+            sources(sc("b", "c").into()),
+            vec![s1.into(), s2.clone().into()]
+        );
+        assert_eq!(sources(sc("c", "d").into()), vec![s2.into()]);
+    }
+
+    #[test]
+    fn test_topological_axiom_order() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        let c = |s: &str| b.class(format!("http://www.example.com/{}", s));
+        let sc = |sub: &str, sup: &str| -> AnnotatedAxiom<_> {
+            SubClassOf {
+                sub: c(sub).into(),
+                sup: c(sup).into(),
+            }
+            .into()
+        };
+
+        // a < b < c, with a cycle between x and y below a
+        o.insert(sc("a", "b"));
+        o.insert(sc("b", "c"));
+        o.insert(sc("x", "a"));
+        o.insert(sc("x", "y"));
+        o.insert(sc("y", "x"));
+        o.insert(b.annotation_assertion(
+            "http://www.example.com/a",
+            "http://www.w3.org/2000/01/rdf-schema#label",
+            "A",
+        ));
+        for n in &["a", "b", "c"] {
+            o.declare(c(n));
+        }
+
+        let order = o.topological_axiom_order();
+        assert_eq!(order.len(), o.len());
+        assert!(order[..3].iter().all(|aa| aa.axiom.as_entity().is_some()));
+        assert_eq!(
+            order[3..7].to_vec(),
+            vec![&sc("b", "c"), &sc("a", "b"), &sc("x", "a"), &sc("x", "y"),]
+        );
+        assert_eq!(order[7], &sc("y", "x"));
+        assert!(matches!(order[8].axiom, Axiom::AnnotationAssertion(_)));
+
+        assert_eq!(order, o.clone().topological_axiom_order());
+    }
+
+    #[test]
+    fn test_definition() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        let c = |s: &str| b.class(format!("http://www.example.com/{}", s));
+        let some_r = ClassExpression::ObjectSomeValuesFrom {
+            ope: b.object_property("http://www.example.com/r").into(),
+            bce: Box::new(c("x").into()),
+        };
+
+        // defined is equivalent to a named class and a complex one
+        o.insert(EquivalentClasses(vec![
+            c("defined").into(),
+            c("same").into(),
+            some_r.clone(),
+        ]));
+        o.insert(SubClassOf {
+            sub: c("defined").into(),
+            sup: c("y").into(),
+        });
+
+        o.insert(SubClassOf {
+            sub: c("primitive").into(),
+            sup: some_r.clone(),
+        });
+        o.insert(SubClassOf {
+            sub: c("primitive").into(),
+            sup: c("y").into(),
+        });
+
+        assert_eq!(o.definition(&c("defined")), Some(some_r.clone()));
+
+        let mut supers = vec![some_r, c("y").into()];
+        supers.sort();
+        assert_eq!(
+            o.definition(&c("primitive")),
+            Some(ClassExpression::ObjectIntersectionOf(supers))
+        );
+        assert_eq!(o.definition(&c("y")), None);
+    }
+
+    #[test]
+    fn test_rebase() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        o.mut_id().iri = Some(b.iri("http://www.example.com/v1/"));
+        o.mut_id().viri = Some(b.iri("http://www.example.com/v1/1.0"));
+        o.insert(Import(b.iri("http://www.example.org/other")));
+        o.insert(SubClassOf {
+            sub: b.class("http://www.example.com/v1/a").into(),
+            sup: b.class("http://www.example.org/other/b").into(),
+        });
+        o.insert(b.annotation_assertion(
+            "http://www.example.com/v1/a",
+            "http://www.w3.org/2000/01/rdf-schema#label",
+            "A",
+        ));
+
+        assert_eq!(
+            o.rebase(
+                &b,
+                "http://www.example.com/v1/",
+                "http://www.example.com/v2/"
+            ),
+            4
+        );
+
+        assert_eq!(o.id().iri, Some(b.iri("http://www.example.com/v2/")));
+        // The rebased IRIs are interned by `b`
+        assert!(std::ptr::eq(
+            o.id().iri.as_ref().unwrap().as_ref(),
+            b.iri("http://www.example.com/v2/").as_ref()
+        ));
+        assert_eq!(o.id().viri, Some(b.iri("http://www.example.com/v2/1.0")));
+
+        let mut expected = SetOntology::new_rc();
+        expected.insert(Import(b.iri("http://www.example.org/other")));
+        expected.insert(SubClassOf {
+            sub: b.class("http://www.example.com/v2/a").into(),
+            sup: b.class("http://www.example.org/other/b").into(),
+        });
+        expected.insert(b.annotation_assertion(
+            "http://www.example.com/v2/a",
+            "http://www.w3.org/2000/01/rdf-schema#label",
+            "A",
+        ));
+        *expected.mut_id() = o.id().clone();
+        assert_eq!(o, expected);
+    }
+
+    #[test]
+    fn test_dangling_annotation_subjects() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        let label = |s: &str| {
+            b.annotation_assertion(
+                format!("http://www.example.com/{}", s),
+                "http://www.w3.org/2000/01/rdf-schema#label",
+                s,
+            )
+        };
+
+        o.declare(b.class("http://www.example.com/a"));
+        o.insert(label("a"));
+        assert!(o.dangling_annotation_subjects().is_empty());
+
+        o.insert(label("deleted"));
+        o.insert(AnnotationAssertion {
+            subject: b.anon("anon").into(),
+            ann: Annotation {
+                ap: b.annotation_property("http://www.w3.org/2000/01/rdf-schema#label"),
+                av: "anon".into(),
+            },
+        });
+        assert_eq!(
+            o.dangling_annotation_subjects(),
+            vec![b.iri("http://www.example.com/deleted")]
+        );
+    }
+
+    #[test]
+    fn test_aliases() {
+        use super::{ArcSetOntology, RcSetIndex, RcSetOntology};
+        use crate::ontology::indexed::OntologyIndex;
+
+        let b = Build::new_rc();
+        let mut o: RcSetOntology = SetOntology::new_rc();
+        o.declare(b.class("http://www.example.com/a"));
+
+        let mut si = RcSetIndex::new();
+        si.index_insert(o.iter().next().unwrap().clone().into());
+        assert_eq!(SetOntology::from_index(Default::default(), si), o);
+
+        let mut a: ArcSetOntology = SetOntology::new_arc();
+        a.declare(Build::new_arc().class("http://www.example.com/a"));
+        assert_eq!(a.len(), o.len());
+    }
+
+    #[test]
+    fn test_signature_difference() {
+        use crate::ontology::axiom_mapped::AxiomMappedOntology;
+
+        let b = Build::new_rc();
+        let c =
+            |s: &str| -> NamedEntity<_> { b.class(format!("http://www.example.com/{}", s)).into() };
+
+        let mut o = SetOntology::new_rc();
+        o.declare(c("shared"));
+        o.declare(c("mine"));
+        let mut other = AxiomMappedOntology::new_rc();
+        other.insert(SubClassOf {
+            sub: b.class("http://www.example.com/theirs").into(),
+            sup: b.class("http://www.example.com/shared").into(),
+        });
+
+        assert_eq!(
+            o.signature_difference(&other),
+            vec![c("mine")].into_iter().collect()
+        );
+
+        let other: SetOntology<_> = other.into();
+        assert_eq!(
+            other.signature_difference(&o),
+            vec![c("theirs")].into_iter().collect()
+        );
+    }
+
+    #[test]
+    fn test_filtered_set_index() {
+        use super::FilteredSetIndex;
+        use crate::ontology::indexed::TwoIndexedOntology;
+
+        let b = Build::new_rc();
+        let tbox = |aa: &AnnotatedAxiom<_>| {
+            matches!(
+                aa.axiom,
+                Axiom::SubClassOf(_) | Axiom::EquivalentClasses(_) | Axiom::DisjointClasses(_)
+            )
+        };
+        let sc: AnnotatedAxiom<_> = SubClassOf {
+            sub: b.class("http://www.example.com/a").into(),
+            sup: b.class("http://www.example.com/b").into(),
+        }
+        .into();
+        let ca: AnnotatedAxiom<_> = ClassAssertion {
+            ce: b.class("http://www.example.com/a").into(),
+            i: b.named_individual("http://www.example.com/i").into(),
+        }
+        .into();
+
+        let mut o = OneIndexedOntology::new_rc(FilteredSetIndex::new(tbox));
+        assert!(o.insert(sc.clone()));
+        assert!(!o.insert(ca.clone()));
+        assert!(o.i().contains(&sc));
+        assert!(!o.i().contains(&ca));
+
+        let mut o = TwoIndexedOntology::new(
+            SetIndex::new_rc(),
+            FilteredSetIndex::new(tbox),
+            Default::default(),
+        );
+        assert!(o.insert(sc.clone()));
+        assert!(o.insert(ca.clone()));
+        assert_eq!(o.i().len(), 2);
+        assert_eq!(o.j().into_iter().collect::<Vec<_>>(), vec![&sc]);
+
+        assert!(o.remove(&sc));
+        assert!(o.j().is_empty());
+    }
+
+    #[test]
+    fn test_collapse_equivalences() {
+        let b = Build::new_rc();
+        let c = |s: &str| b.class(format!("http://www.example.com/{}", s));
+        let sc = |sub: &str, sup: &str| -> AnnotatedAxiom<_> {
+            SubClassOf {
+                sub: c(sub).into(),
+                sup: c(sup).into(),
+            }
+            .into()
+        };
+        let eq1: AnnotatedAxiom<_> = EquivalentClasses(vec![c("b").into(), c("a").into()]).into();
+        let eq2: AnnotatedAxiom<_> = EquivalentClasses(vec![c("c").into(), c("b").into()]).into();
+
+        let mut o = SetOntology::new_rc();
+        o.insert(eq1.clone());
+        o.insert(eq2.clone());
+        o.insert(sc("b", "x"));
+        o.insert(sc("c", "x"));
+        o.insert(sc("y", "c"));
+        let original = o.clone();
+
+        let map = o.collapse_equivalences(true);
+        assert_eq!(map.len(), 2);
+        assert_eq!(map[&c("b")], c("a"));
+        assert_eq!(map[&c("c")], c("a"));
+
+        let mut expected = SetOntology::new_rc();
+        expected.insert(eq1);
+        expected.insert(eq2);
+        expected.insert(sc("a", "x"));
+        expected.insert(sc("y", "a"));
+        assert_eq!(o, expected);
+
+        let mut o = original;
+        assert_eq!(o.collapse_equivalences(false), map);
+        expected.remove(&EquivalentClasses(vec![c("b").into(), c("a").into()]).into());
+        expected.remove(&EquivalentClasses(vec![c("c").into(), c("b").into()]).into());
+        assert_eq!(o, expected);
     }
 
     #[test]
-    fn test_ontology_id() {
-        let mut so = SetOntology::new_rc();
+    fn test_dl_syntactic_issues() {
+        use super::DlIssue;
+
         let b = Build::new_rc();
-        let mut oid = OntologyID {
-            iri: Some(b.iri("http://www.example.com/iri")),
-            viri: Some(b.iri("http://www.example.com/viri")),
+        let mut o = SetOntology::new_rc();
+        let a = b.class("http://www.example.com/a");
+        let c = b.class("http://www.example.com/c");
+        let p = b.object_property("http://www.example.com/p");
+        o.declare(a.clone());
+        o.declare(p.clone());
+        o.insert(TransitiveObjectProperty(p.clone().into()));
+        o.insert(SubClassOf {
+            sub: a.clone().into(),
+            sup: ClassExpression::ObjectMaxCardinality {
+                n: 1,
+                ope: ObjectPropertyExpression::InverseObjectProperty(p.clone()),
+                bce: Box::new(c.clone().into()),
+            },
+        });
+        o.insert(SubClassOf {
+            sub: a.into(),
+            sup: b.class("http://www.w3.org/2002/07/owl#Thing").into(),
+        });
+
+        assert_eq!(
+            o.dl_syntactic_issues(),
+            vec![
+                DlIssue::NonSimpleCardinality(p),
+                DlIssue::Undeclared(c.into())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dl_syntactic_issues_builtin_datatype() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        let dp = b.data_property("http://www.example.com/dp");
+        o.declare(dp.clone());
+        o.insert(DataPropertyRange {
+            dp,
+            dr: b.datatype("http://www.w3.org/2001/XMLSchema#string").into(),
+        });
+
+        assert!(o.dl_syntactic_issues().is_empty());
+    }
+
+    #[test]
+    fn test_defined_by_see_also() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        let ann = |ap: &str, av: AnnotationValue<_>| {
+            OntologyAnnotation(Annotation {
+                ap: b.annotation_property(ap),
+                av,
+            })
         };
+        o.insert(ann(
+            "http://www.w3.org/2000/01/rdf-schema#isDefinedBy",
+            b.iri("http://www.example.com/spec").into(),
+        ));
+        o.insert(ann(
+            "http://www.w3.org/2000/01/rdf-schema#seeAlso",
+            b.iri("http://www.example.com/other").into(),
+        ));
+        o.insert(ann(
+            "http://www.w3.org/2000/01/rdf-schema#seeAlso",
+            "The other ontology".into(),
+        ));
 
-        std::mem::swap(so.mut_id(), &mut oid);
+        assert_eq!(o.defined_by(), Some(&b.iri("http://www.example.com/spec")));
 
-        assert_eq!(so.id().iri, Some(b.iri("http://www.example.com/iri")));
-        assert_eq!(so.id().viri, Some(b.iri("http://www.example.com/viri")))
+        let mut see_also: Vec<_> = o.see_also().cloned().collect();
+        see_also.sort();
+        assert_eq!(
+            see_also,
+            vec![
+                "The other ontology".into(),
+                b.iri("http://www.example.com/other").into()
+            ]
+        );
     }
 
     #[test]
-    fn test_ontology_clone() {
-        let mut so = SetOntology::new_rc();
+    fn test_root_leaf_classes() {
+        use std::collections::HashSet;
+
         let b = Build::new_rc();
-        let mut oid = OntologyID {
-            iri: Some(b.iri("http://www.example.com/iri")),
-            viri: Some(b.iri("http://www.example.com/viri")),
+        let mut o = SetOntology::new_rc();
+        let class = |s: &str| b.class(format!("http://www.example.com/{}", s));
+        let sub = |sub: &str, sup: &str| SubClassOf {
+            sub: class(sub).into(),
+            sup: class(sup).into(),
         };
+        let thing = b.class("http://www.w3.org/2002/07/owl#Thing");
+        let nothing = b.class("http://www.w3.org/2002/07/owl#Nothing");
 
-        std::mem::swap(so.mut_id(), &mut oid);
+        //   Thing
+        //   /   \
+        //  a     d
+        //  |
+        //  b
+        //  |
+        //  c
+        //  |
+        // Nothing
+        o.insert(sub("b", "a"));
+        o.insert(sub("c", "b"));
+        o.insert(SubClassOf {
+            sub: class("a").into(),
+            sup: thing.into(),
+        });
+        o.insert(SubClassOf {
+            sub: nothing.into(),
+            sup: class("c").into(),
+        });
+        o.declare(class("d"));
 
-        let cso = so.clone();
+        let classes = |v: &[&str]| v.iter().map(|s| class(s)).collect::<HashSet<_>>();
+        assert_eq!(o.root_classes(), classes(&["a", "d"]));
+        assert_eq!(o.leaf_classes(), classes(&["c", "d"]));
+    }
 
-        assert_eq!(cso.id().iri, Some(b.iri("http://www.example.com/iri")));
-        assert_eq!(cso.id().viri, Some(b.iri("http://www.example.com/viri")))
+    #[test]
+    fn test_language_tag_histogram() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        let label = |s: &str, l: Literal<_>| {
+            b.annotation_assertion(
+                format!("http://www.example.com/{}", s),
+                "http://www.w3.org/2000/01/rdf-schema#label",
+                l,
+            )
+        };
+        let lang = |literal: &str, lang: &str| Literal::Language {
+            literal: literal.to_string(),
+            lang: lang.to_string(),
+        };
+
+        o.insert(label("a", lang("Cat", "en")));
+        o.insert(label("a", lang("Chat", "fr")));
+        o.insert(label("b", lang("Dog", "EN")));
+        o.insert(label(
+            "c",
+            Literal::Simple {
+                literal: "Fish".to_string(),
+            },
+        ));
+        o.insert(OntologyAnnotation(Annotation {
+            ap: b.annotation_property("http://www.w3.org/2000/01/rdf-schema#comment"),
+            av: lang("Animaux", "fr").into(),
+        }));
+        o.insert(AnnotatedAxiom::new(
+            DeclareClass(b.class("http://www.example.com/c")),
+            vec![Annotation {
+                ap: b.annotation_property("http://www.w3.org/2000/01/rdf-schema#comment"),
+                av: b.iri("http://www.example.com/not-a-literal").into(),
+            }]
+            .into_iter()
+            .collect(),
+        ));
+
+        let h = o.language_tag_histogram();
+        assert_eq!(
+            h.into_iter().collect::<Vec<_>>(),
+            vec![
+                ("".to_string(), 1),
+                ("en".to_string(), 2),
+                ("fr".to_string(), 2)
+            ]
+        );
     }
 
     #[test]
-    fn test_ontology_iter_empty() {
-        // Empty ontologies should stop iteration right away
-        let mut it = SetOntology::new_rc().into_iter();
-        assert_eq!(it.next(), None);
-        assert_eq!(it.next(), None);
+    fn test_map_literals() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        let label = |l: &str| {
+            b.annotation_assertion(
+                "http://www.example.com/a",
+                "http://www.w3.org/2000/01/rdf-schema#label",
+                Literal::Simple {
+                    literal: l.to_string(),
+                },
+            )
+        };
+        o.insert(label("A label  "));
+        o.insert(label("A label"));
+        o.insert(label("Another label"));
+
+        let trim = |l: &Literal<_>| match l {
+            Literal::Simple { literal } if literal.trim() != literal => Some(Literal::Simple {
+                literal: literal.trim().to_string(),
+            }),
+            _ => None,
+        };
+        assert_eq!(o.map_literals(trim), 1);
+
+        // The trimmed label coincides with an existing one
+        assert_eq!(o.len(), 2);
+        assert!(o.iter().any(|aa| aa == &label("A label")));
+        assert!(!o.iter().any(|aa| aa == &label("A label  ")));
+
+        assert_eq!(o.map_literals(trim), 0);
     }
 
     #[test]
-    fn test_ontology_into_iter() {
-        // Setup
-        let build = Build::new_rc();
-        let mut o = SetOntology::new();
-        let decl1 = DeclareClass(build.class("http://www.example.com#a"));
-        let decl2 = DeclareClass(build.class("http://www.example.com#b"));
-        let decl3 = DeclareClass(build.class("http://www.example.com#c"));
-        let disj1 = DisjointClasses(vec![
-            ClassExpression::Class(build.class("http://www.example.com#a")),
-            ClassExpression::Class(build.class("http://www.example.com#b")),
-        ]);
-        let disj2 = DisjointClasses(vec![
-            ClassExpression::Class(build.class("http://www.example.com#b")),
-            ClassExpression::Class(build.class("http://www.example.com#c")),
-        ]);
-        o.insert(disj1.clone());
-        o.insert(disj2.clone());
-        o.insert(decl1.clone());
-        o.insert(decl2.clone());
-        o.insert(decl3.clone());
+    fn test_conflicting_labels() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        let label = |s: &str, literal: &str, lang: &str| {
+            b.annotation_assertion(
+                format!("http://www.example.com/{}", s),
+                "http://www.w3.org/2000/01/rdf-schema#label",
+                Literal::Language {
+                    literal: literal.to_string(),
+                    lang: lang.to_string(),
+                },
+            )
+        };
+
+        o.insert(label("a", "Cat", "en"));
+        o.insert(label("a", "Kitty", "EN"));
+        o.insert(label("b", "Dog", "en"));
+        o.insert(label("b", "Chien", "fr"));
+
+        let conflicts = o.conflicting_labels();
+        assert_eq!(conflicts.len(), 1);
+        let (iri, lang, labels) = &conflicts[0];
+        assert_eq!(iri, &b.iri("http://www.example.com/a"));
+        assert_eq!(lang, "en");
+        assert_eq!(labels.len(), 2);
+    }
+
+    #[test]
+    fn test_deprecated() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        o.mut_id().iri = Some(b.iri("http://www.example.com/o"));
+        let a = b.class("http://www.example.com/a");
+        let c = b.class("http://www.example.com/c");
+        let deprecated = |s: &str, value: &str| {
+            b.annotation_assertion(
+                s,
+                "http://www.w3.org/2002/07/owl#deprecated",
+                Literal::Datatype {
+                    literal: value.to_string(),
+                    datatype_iri: b.iri("http://www.w3.org/2001/XMLSchema#boolean"),
+                },
+            )
+        };
+
+        o.declare(a.clone());
+        o.declare(c.clone());
+        o.insert(deprecated("http://www.example.com/a", "true"));
+        o.insert(deprecated("http://www.example.com/c", "false"));
+        o.insert(b.annotation_assertion(
+            "http://www.example.com/a",
+            "http://www.w3.org/2000/01/rdf-schema#label",
+            "A",
+        ));
+        o.insert(SubClassOf {
+            sub: a.clone().into(),
+            sup: c.clone().into(),
+        });
+
+        assert_eq!(
+            o.deprecated_entities(),
+            vec![a.into()].into_iter().collect()
+        );
+
+        let without = o.without_deprecated();
+        assert_eq!(without.id(), o.id());
+        let mut axioms: Vec<_> = without.iter().cloned().collect();
+        axioms.sort();
+        assert_eq!(
+            axioms,
+            vec![
+                DeclareClass(c).into(),
+                deprecated("http://www.example.com/c", "false")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collect_axioms() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        let sub = |sub: &str, sup: &str| -> AnnotatedAxiom<_> {
+            SubClassOf {
+                sub: b.class(sub).into(),
+                sup: b.class(sup).into(),
+            }
+            .into()
+        };
+        o.insert(sub("http://www.example.com/b", "http://www.example.com/a"));
+        o.insert(sub("http://www.example.com/c", "http://www.example.com/a"));
+        o.declare(b.class("http://www.example.com/a"));
+
+        let mut subs = o.collect_axioms(|aa| matches!(aa.axiom, Axiom::SubClassOf(_)));
+        subs.sort();
+        assert_eq!(
+            subs,
+            vec![
+                sub("http://www.example.com/b", "http://www.example.com/a"),
+                sub("http://www.example.com/c", "http://www.example.com/a")
+            ]
+        );
+
+        // The result is owned, so outlives the ontology
+        drop(o);
+        assert_eq!(subs.len(), 2);
+    }
+
+    #[test]
+    fn test_remove_redundant_subclass_axioms() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        let sub = |sub: &str, sup: &str| -> AnnotatedAxiom<_> {
+            SubClassOf {
+                sub: b.class(format!("http://www.example.com/{}", sub)).into(),
+                sup: b.class(format!("http://www.example.com/{}", sup)).into(),
+            }
+            .into()
+        };
+        let annotated = |aa: AnnotatedAxiom<_>| {
+            AnnotatedAxiom::new(
+                aa.axiom,
+                vec![Annotation {
+                    ap: b.annotation_property("http://www.w3.org/2000/01/rdf-schema#comment"),
+                    av: "Asserted".into(),
+                }]
+                .into_iter()
+                .collect(),
+            )
+        };
+
+        o.insert(sub("a", "b"));
+        o.insert(sub("b", "c"));
+        o.insert(sub("a", "c"));
+        // A cycle, of which no edge is redundant
+        o.insert(sub("x", "y"));
+        o.insert(sub("y", "x"));
+        // An annotated redundant edge
+        o.insert(sub("c", "d"));
+        o.insert(annotated(sub("b", "d")));
+
+        assert_eq!(o.remove_redundant_subclass_axioms(false), 1);
+        assert!(!o.iter().any(|aa| aa == &sub("a", "c")));
+        assert!(o.iter().any(|aa| aa == &sub("a", "b")));
+        assert!(o.iter().any(|aa| aa == &sub("b", "c")));
+        assert_eq!(o.len(), 6);
+
+        assert_eq!(o.remove_redundant_subclass_axioms(true), 1);
+        assert!(!o.iter().any(|aa| aa == &annotated(sub("b", "d"))));
+        assert_eq!(o.len(), 5);
+    }
+
+    #[test]
+    fn test_property_type_mismatches() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        o.declare(b.data_property("http://www.example.com/age"));
+        o.declare(b.object_property("http://www.example.com/knows"));
+
+        let wrong: AnnotatedAxiom<_> = SubClassOf {
+            sub: b.class("http://www.example.com/a").into(),
+            sup: ClassExpression::ObjectMinCardinality {
+                n: 1,
+                ope: b.object_property("http://www.example.com/age").into(),
+                bce: Box::new(b.class("http://www.example.com/b").into()),
+            },
+        }
+        .into();
+        o.insert(wrong.clone());
+
+        let wrong_data: AnnotatedAxiom<_> = DataPropertyDomain {
+            dp: b.data_property("http://www.example.com/knows"),
+            ce: b.class("http://www.example.com/a").into(),
+        }
+        .into();
+        o.insert(wrong_data.clone());
+
+        // Correct usage, and usage of undeclared properties
+        o.insert(SubClassOf {
+            sub: b.class("http://www.example.com/a").into(),
+            sup: ClassExpression::ObjectSomeValuesFrom {
+                ope: b.object_property("http://www.example.com/knows").into(),
+                bce: Box::new(b.class("http://www.example.com/b").into()),
+            },
+        });
+        o.insert(DataPropertyDomain {
+            dp: b.data_property("http://www.example.com/height"),
+            ce: b.class("http://www.example.com/a").into(),
+        });
+
+        let mut expected = vec![wrong, wrong_data];
+        expected.sort();
+        assert_eq!(o.property_type_mismatches(), expected);
+    }
+
+    #[test]
+    fn test_neighborhood() {
+        use std::collections::HashSet;
+
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        let sc = |sub: &str, sup: &str| -> AnnotatedAxiom<_> {
+            SubClassOf {
+                sub: b.class(sub).into(),
+                sup: b.class(sup).into(),
+            }
+            .into()
+        };
+        let ab = sc("http://www.example.com/a", "http://www.example.com/b");
+        let bc = sc("http://www.example.com/b", "http://www.example.com/c");
+        let cd = sc("http://www.example.com/c", "http://www.example.com/d");
+        let label: AnnotatedAxiom<_> = AnnotationAssertion {
+            subject: b.iri("http://www.example.com/a").into(),
+            ann: Annotation {
+                ap: b.annotation_property("http://www.w3.org/2000/01/rdf-schema#label"),
+                av: Literal::Simple {
+                    literal: "a".to_string(),
+                }
+                .into(),
+            },
+        }
+        .into();
+        for ax in [&ab, &bc, &cd, &label] {
+            o.insert(ax.clone());
+        }
+
+        let seed: HashSet<NamedEntity<_>> = vec![b.class("http://www.example.com/a").into()]
+            .into_iter()
+            .collect();
+
+        let zero: HashSet<_> = o.neighborhood(&seed, 0).into_iter().collect();
+        assert_eq!(zero, vec![ab.clone(), label.clone()].into_iter().collect());
+
+        let one: HashSet<_> = o.neighborhood(&seed, 1).into_iter().collect();
+        assert_eq!(
+            one,
+            vec![ab.clone(), bc.clone(), label.clone()]
+                .into_iter()
+                .collect()
+        );
+
+        assert_eq!(o.neighborhood(&seed, 10).len(), 4);
+        assert!(o.neighborhood(&HashSet::new(), 1).is_empty());
+    }
+
+    #[test]
+    fn test_reflexive_subclass_axioms() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        let some = ClassExpression::ObjectSomeValuesFrom {
+            ope: b.object_property("http://www.example.com/r").into(),
+            bce: Box::new(b.class("http://www.example.com/a").into()),
+        };
+        let named: AnnotatedAxiom<_> = SubClassOf {
+            sub: b.class("http://www.example.com/a").into(),
+            sup: b.class("http://www.example.com/a").into(),
+        }
+        .into();
+        let complex: AnnotatedAxiom<_> = SubClassOf {
+            sub: some.clone(),
+            sup: some,
+        }
+        .into();
+        o.insert(named.clone());
+        o.insert(complex.clone());
+        o.insert(SubClassOf {
+            sub: b.class("http://www.example.com/a").into(),
+            sup: b.class("http://www.example.com/b").into(),
+        });
+
+        let mut reflexive: Vec<_> = o.reflexive_subclass_axioms().cloned().collect();
+        reflexive.sort();
+        let mut expected = vec![named, complex];
+        expected.sort();
+        assert_eq!(reflexive, expected);
+
+        assert_eq!(o.remove_reflexive_subclass_axioms(), 2);
+        assert_eq!(o.len(), 1);
+        assert_eq!(o.reflexive_subclass_axioms().count(), 0);
+    }
+
+    #[test]
+    fn test_jaccard_similarity() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        o.declare(b.class("http://www.example.com/a"));
+        o.declare(b.class("http://www.example.com/b"));
+
+        let mut disjoint = SetOntology::new_rc();
+        disjoint.declare(b.class("http://www.example.com/c"));
+
+        assert_eq!(o.jaccard_similarity(&o, false), 1.0);
+        assert_eq!(o.jaccard_similarity(&disjoint, false), 0.0);
+        assert_eq!(
+            SetOntology::new_rc().jaccard_similarity(&SetOntology::new_rc(), false),
+            1.0
+        );
+
+        let mut annotated = SetOntology::new_rc();
+        annotated.declare(b.class("http://www.example.com/a"));
+        annotated.insert(AnnotatedAxiom::new(
+            DeclareClass(b.class("http://www.example.com/b")),
+            vec![Annotation {
+                ap: b.annotation_property("http://www.w3.org/2000/01/rdf-schema#comment"),
+                av: Literal::Simple {
+                    literal: "b".to_string(),
+                }
+                .into(),
+            }]
+            .into_iter()
+            .collect(),
+        ));
+
+        assert_eq!(o.jaccard_similarity(&annotated, false), 1.0 / 3.0);
+        assert_eq!(o.jaccard_similarity(&annotated, true), 1.0);
+    }
+
+    #[test]
+    fn test_binarize() {
+        use std::collections::HashSet;
+
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        let a: ClassExpression<_> = b.class("http://www.example.com/a").into();
+        let c: ClassExpression<_> = b.class("http://www.example.com/c").into();
+        let d: ClassExpression<_> = b.class("http://www.example.com/d").into();
+        let ann: std::collections::BTreeSet<_> = vec![Annotation {
+            ap: b.annotation_property("http://www.w3.org/2000/01/rdf-schema#comment"),
+            av: Literal::Simple {
+                literal: "disjoint".to_string(),
+            }
+            .into(),
+        }]
+        .into_iter()
+        .collect();
+        o.insert(AnnotatedAxiom::new(
+            DisjointClasses(vec![a.clone(), c.clone(), d.clone()]),
+            ann.clone(),
+        ));
+        let binary: AnnotatedAxiom<_> = EquivalentObjectProperties(vec![
+            b.object_property("http://www.example.com/r").into(),
+            b.object_property("http://www.example.com/s").into(),
+        ])
+        .into();
+        o.insert(binary.clone());
+
+        let bin = o.binarize();
+        let axioms: HashSet<_> = bin.iter().cloned().collect();
+        let expected: HashSet<_> = vec![
+            AnnotatedAxiom::new(DisjointClasses(vec![a.clone(), c.clone()]), ann.clone()),
+            AnnotatedAxiom::new(DisjointClasses(vec![a, d.clone()]), ann.clone()),
+            AnnotatedAxiom::new(DisjointClasses(vec![c, d]), ann),
+            binary,
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(axioms, expected);
+    }
+
+    #[test]
+    fn test_subsumption_pairs() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        let a: ClassExpression<_> = b.class("http://www.example.com/a").into();
+        let c: ClassExpression<_> = b.class("http://www.example.com/c").into();
+        let some = ClassExpression::ObjectSomeValuesFrom {
+            ope: b.object_property("http://www.example.com/r").into(),
+            bce: Box::new(a.clone()),
+        };
+        o.insert(EquivalentClasses(vec![a.clone(), c.clone()]));
+        o.insert(SubClassOf {
+            sub: some.clone(),
+            sup: c.clone(),
+        });
+        // Already implied by the equivalence
+        o.insert(SubClassOf {
+            sub: a.clone(),
+            sup: c.clone(),
+        });
+        o.declare(b.class("http://www.example.com/a"));
+
+        let mut expected = vec![(a.clone(), c.clone()), (c.clone(), a), (some, c)];
+        expected.sort();
+        assert_eq!(o.subsumption_pairs(), expected);
+    }
+
+    #[test]
+    fn test_axiom_kinds_present() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        assert!(o.axiom_kinds_present().is_empty());
+
+        o.declare(b.class("http://www.example.com/a"));
+        o.declare(b.object_property("http://www.example.com/r"));
+        o.insert(SubClassOf {
+            sub: b.class("http://www.example.com/a").into(),
+            sup: b.class("http://www.example.com/b").into(),
+        });
+        o.insert(SubClassOf {
+            sub: b.class("http://www.example.com/b").into(),
+            sup: b.class("http://www.example.com/c").into(),
+        });
+        o.insert(b.annotation_assertion(
+            "http://www.example.com/a",
+            "http://www.w3.org/2000/01/rdf-schema#label",
+            "a",
+        ));
+
+        assert_eq!(
+            o.axiom_kinds_present().into_iter().collect::<Vec<_>>(),
+            vec![
+                AxiomKind::DeclareClass,
+                AxiomKind::DeclareObjectProperty,
+                AxiomKind::SubClassOf,
+                AxiomKind::AnnotationAssertion,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_common_ancestors() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        let c = |s: &str| b.class(format!("http://www.example.com/{}", s));
+        let mut sub = |sub: &str, sup: &str| {
+            o.insert(SubClassOf {
+                sub: c(sub).into(),
+                sup: c(sup).into(),
+            });
+        };
+        // A diamond below top, with x and y both below left and right
+        sub("left", "top");
+        sub("right", "top");
+        sub("x", "left");
+        sub("x", "right");
+        sub("y", "left");
+        sub("y", "right");
+        sub("z", "left");
+        sub("w", "x");
+
+        assert_eq!(
+            o.common_ancestors(&c("x"), &c("y")),
+            vec![c("left"), c("right")]
+        );
+        assert_eq!(o.common_ancestors(&c("x"), &c("z")), vec![c("left")]);
+        assert_eq!(o.common_ancestors(&c("left"), &c("right")), vec![c("top")]);
+        assert_eq!(o.common_ancestors(&c("w"), &c("x")), vec![c("x")]);
+        assert_eq!(o.common_ancestors(&c("x"), &c("x")), vec![c("x")]);
+        assert!(o.common_ancestors(&c("x"), &c("unrelated")).is_empty());
+    }
+
+    #[test]
+    fn test_merge() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        o.set_id(OntologyID {
+            iri: Some(b.iri("http://www.example.com/o")),
+            viri: Some(b.iri("http://www.example.com/o/1")),
+        });
+        o.declare(b.class("http://www.example.com/a"));
+
+        let mut other = SetOntology::new_rc();
+        other.set_id(OntologyID {
+            iri: Some(b.iri("http://www.example.com/other")),
+            viri: Some(b.iri("http://www.example.com/other/2")),
+        });
+        other.declare(b.class("http://www.example.com/a"));
+        other.declare(b.class("http://www.example.com/b"));
+
+        let id = OntologyID {
+            iri: Some(b.iri("http://www.example.com/merged")),
+            viri: None,
+        };
+        assert_eq!(o.merge(other, id.clone()), 1);
+        assert_eq!(o.id(), &id);
+        assert_eq!(o.len(), 2);
+    }
+
+    #[test]
+    fn test_max_expression_depth() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        assert_eq!(o.max_expression_depth(), 0);
+
+        o.declare(b.class("http://www.example.com/a"));
+        assert_eq!(o.max_expression_depth(), 0);
+
+        let p: ObjectPropertyExpression<_> = b.object_property("http://www.example.com/p").into();
+        let nested = ClassExpression::ObjectSomeValuesFrom {
+            ope: p.clone(),
+            bce: Box::new(ClassExpression::ObjectUnionOf(vec![
+                b.class("http://www.example.com/b").into(),
+                ClassExpression::ObjectAllValuesFrom {
+                    ope: p,
+                    bce: Box::new(b.class("http://www.example.com/c").into()),
+                },
+            ])),
+        };
+        assert_eq!(nested.size(), 5);
+        assert_eq!(nested.depth(), 4);
+
+        o.insert(EquivalentClasses(vec![
+            b.class("http://www.example.com/a").into(),
+            nested,
+        ]));
+        assert_eq!(o.max_expression_depth(), 4);
+    }
+
+    #[test]
+    fn test_definition_dependencies() {
+        use std::collections::HashSet;
+
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        let c = |s: &str| b.class(format!("http://www.example.com/{}", s));
+        let r = b.object_property("http://www.example.com/r");
+
+        // a is defined in terms of b, which is defined in terms of c
+        o.insert(EquivalentClasses(vec![
+            c("a").into(),
+            ClassExpression::ObjectSomeValuesFrom {
+                ope: r.clone().into(),
+                bce: Box::new(c("b").into()),
+            },
+        ]));
+        o.insert(SubClassOf {
+            sub: c("b").into(),
+            sup: c("c").into(),
+        });
+
+        // Neither is followed, as they are not part of a definition
+        o.insert(SubClassOf {
+            sub: c("d").into(),
+            sup: c("a").into(),
+        });
+        o.insert(ObjectPropertyDomain {
+            ope: r.clone().into(),
+            ce: c("e").into(),
+        });
+
+        let expected: HashSet<NamedEntity<_>> = vec![r.into(), c("b").into(), c("c").into()]
+            .into_iter()
+            .collect();
+        assert_eq!(o.definition_dependencies(&c("a")), expected);
+        assert!(o.definition_dependencies(&c("c")).is_empty());
+
+        // A cyclic definition includes the class itself
+        o.insert(SubClassOf {
+            sub: c("c").into(),
+            sup: c("a").into(),
+        });
+        assert!(o.definition_dependencies(&c("a")).contains(&c("a").into()));
+    }
+
+    #[test]
+    fn test_group_by_annotation_properties() {
+        use std::collections::BTreeSet;
+
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        let source = b.annotation_property("http://purl.org/dc/terms/source");
+        let comment = b.annotation_property("http://www.w3.org/2000/01/rdf-schema#comment");
+        let annotated = |c: &str, aps: &[&AnnotationProperty<_>]| AnnotatedAxiom {
+            axiom: DeclareClass(b.class(c)).into(),
+            ann: aps
+                .iter()
+                .map(|ap| Annotation {
+                    ap: (*ap).clone(),
+                    av: "x".into(),
+                })
+                .collect(),
+        };
+
+        o.insert(annotated("http://www.example.com/a", &[]));
+        o.insert(annotated("http://www.example.com/b", &[]));
+        o.insert(annotated("http://www.example.com/c", &[&source]));
+        o.insert(annotated("http://www.example.com/d", &[&source, &comment]));
+        o.insert(annotated("http://www.example.com/e", &[&comment, &source]));
+
+        let groups = o.group_by_annotation_properties();
+        let group = |aps: &[&AnnotationProperty<_>]| {
+            let key: BTreeSet<_> = aps.iter().map(|ap| (*ap).clone()).collect();
+            groups.get(&key).map_or(0, |v| v.len())
+        };
+        assert_eq!(groups.len(), 3);
+        assert_eq!(group(&[]), 2);
+        assert_eq!(group(&[&source]), 1);
+        assert_eq!(group(&[&comment, &source]), 2);
+        assert_eq!(group(&[&comment]), 0);
+    }
+
+    #[test]
+    fn test_data_rows() {
+        use super::DataRow;
+
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        let i: Individual<_> = b.named_individual("http://www.example.com/i").into();
+        o.insert(DataPropertyAssertion {
+            dp: b.data_property("http://www.example.com/age"),
+            from: i.clone(),
+            to: Literal::Datatype {
+                literal: "42".into(),
+                datatype_iri: b.iri("http://www.w3.org/2001/XMLSchema#integer"),
+            },
+        });
+        o.insert(DataPropertyAssertion {
+            dp: b.data_property("http://www.example.com/name"),
+            from: i.clone(),
+            to: Literal::Language {
+                literal: "Bob".into(),
+                lang: "en".into(),
+            },
+        });
+        o.insert(ObjectPropertyAssertion {
+            ope: b.object_property("http://www.example.com/knows").into(),
+            from: i.clone(),
+            to: i.clone(),
+        });
+
+        let mut rows: Vec<_> = o.data_rows().collect();
+        rows.sort_by(|a, b| a.value.cmp(&b.value));
+
+        assert_eq!(
+            rows,
+            vec![
+                DataRow {
+                    subject: i.clone(),
+                    property: b.iri("http://www.example.com/age"),
+                    value: "42".into(),
+                    datatype: b.iri("http://www.w3.org/2001/XMLSchema#integer"),
+                    lang: None,
+                },
+                DataRow {
+                    subject: i,
+                    property: b.iri("http://www.example.com/name"),
+                    value: "Bob".into(),
+                    datatype: b.iri("http://www.w3.org/1999/02/22-rdf-syntax-ns#langString"),
+                    lang: Some("en".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_restriction_fillers() {
+        use std::collections::HashSet;
+
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        let p: ObjectPropertyExpression<_> = b.object_property("http://www.example.com/p").into();
+        let q: ObjectPropertyExpression<_> = b.object_property("http://www.example.com/q").into();
+        let c: ClassExpression<_> = b.class("http://www.example.com/c").into();
+        let inner = ClassExpression::ObjectMinCardinality {
+            n: 2,
+            ope: q.clone(),
+            bce: Box::new(c.clone()),
+        };
+        let outer = ClassExpression::ObjectAllValuesFrom {
+            ope: p.clone(),
+            bce: Box::new(inner.clone()),
+        };
+        o.insert(SubClassOf {
+            sub: b.class("http://www.example.com/a").into(),
+            sup: outer.clone(),
+        });
+        o.insert(EquivalentClasses(vec![
+            b.class("http://www.example.com/b").into(),
+            outer,
+        ]));
+
+        let fillers: HashSet<_> = o.restriction_fillers().collect();
+        assert_eq!(fillers, vec![(p, inner), (q, c)].into_iter().collect());
+    }
+
+    #[test]
+    fn test_asserted_disjoint() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        let class = |s: &str| b.class(format!("http://www.example.com/{}", s));
+        let sub = |a: &str, c: &str| SubClassOf {
+            sub: class(a).into(),
+            sup: class(c).into(),
+        };
+        o.insert(DisjointClasses(vec![
+            class("animal").into(),
+            class("plant").into(),
+            class("fungus").into(),
+        ]));
+        o.insert(sub("dog", "mammal"));
+        o.insert(sub("mammal", "animal"));
+        o.insert(sub("oak", "plant"));
+        o.insert(sub("cat", "mammal"));
+
+        // Direct
+        assert!(o.asserted_disjoint(&class("plant"), &class("animal")));
+        assert!(o.asserted_disjoint(&class("animal"), &class("fungus")));
+        // Inherited on one or both sides
+        assert!(o.asserted_disjoint(&class("dog"), &class("plant")));
+        assert!(o.asserted_disjoint(&class("dog"), &class("oak")));
+        assert!(o.asserted_disjoint(&class("fungus"), &class("oak")));
+
+        assert!(!o.asserted_disjoint(&class("dog"), &class("cat")));
+        assert!(!o.asserted_disjoint(&class("dog"), &class("animal")));
+        assert!(!o.asserted_disjoint(&class("dog"), &class("stone")));
+    }
+
+    #[test]
+    fn test_label_map() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        let label = |s: &str, l: Literal<_>| {
+            b.annotation_assertion(
+                format!("http://www.example.com/{}", s),
+                "http://www.w3.org/2000/01/rdf-schema#label",
+                l,
+            )
+        };
+        let tagged = |literal: &str, lang: &str| Literal::Language {
+            literal: literal.into(),
+            lang: lang.into(),
+        };
+        o.insert(label("a", tagged("Colour", "en")));
+        o.insert(label("a", tagged("Couleur", "fr")));
+        o.insert(label(
+            "b",
+            Literal::Simple {
+                literal: "Shape".into(),
+            },
+        ));
+        o.insert(label("b", tagged("Form", "de")));
+        o.insert(label("c", tagged("Size", "en")));
+        o.declare(b.class("http://www.example.com/d"));
+
+        let iri = |s: &str| b.iri(format!("http://www.example.com/{}", s));
+
+        let fr = o.label_map(Some("fr"));
+        assert_eq!(fr.len(), 3);
+        assert_eq!(fr[&iri("a")], "Couleur");
+        assert_eq!(fr[&iri("b")], "Shape");
+        assert_eq!(fr[&iri("c")], "Size");
+
+        let en = o.label_map(Some("EN"));
+        assert_eq!(en[&iri("a")], "Colour");
+
+        let none = o.label_map(None);
+        assert_eq!(none[&iri("a")], "Colour");
+        assert_eq!(none[&iri("b")], "Shape");
+        assert!(!none.contains_key(&iri("d")));
+    }
 
-        // Iteration is based on ascending order of axiom kinds.
-        let mut v: Vec<_> = o.into_iter().collect();
-        v.sort();
-        let mut it = v.into_iter();
-        assert_eq!(
-            it.next(),
-            Some(AnnotatedAxiom::from(Axiom::DeclareClass(decl1)))
-        );
-        assert_eq!(
-            it.next(),
-            Some(AnnotatedAxiom::from(Axiom::DeclareClass(decl2)))
-        );
-        assert_eq!(
-            it.next(),
-            Some(AnnotatedAxiom::from(Axiom::DeclareClass(decl3)))
-        );
-        assert_eq!(
-            it.next(),
-            Some(AnnotatedAxiom::from(Axiom::DisjointClasses(disj1)))
-        );
-        assert_eq!(
-            it.next(),
-            Some(AnnotatedAxiom::from(Axiom::DisjointClasses(disj2)))
-        );
-        assert_eq!(it.next(), None);
-        assert_eq!(it.next(), None);
+    #[test]
+    fn test_dedup_annotations() {
+        let mut o = SetOntology::new_rc();
+        let b = Build::new_rc();
+        let label = |l: Literal<_>| {
+            b.annotation_assertion(
+                "http://www.example.com#a",
+                "http://www.w3.org/2000/01/rdf-schema#label",
+                l,
+            )
+        };
+        let with_source = |mut aa: AnnotatedAxiom<_>, s: &str| {
+            aa.ann.insert(Annotation {
+                ap: b.annotation_property("http://www.example.com#source"),
+                av: s.into(),
+            });
+            aa
+        };
+        let en = label(Literal::Language {
+            literal: "colour".into(),
+            lang: "en".into(),
+        });
+        let us = label(Literal::Language {
+            literal: "colour".into(),
+            lang: "en-US".into(),
+        });
+
+        o.insert(en.clone());
+        o.insert(with_source(en.clone(), "x"));
+        o.insert(with_source(en.clone(), "y"));
+        o.insert(us.clone());
+        o.declare(b.class("http://www.example.com#a"));
+
+        assert_eq!(o.dedup_annotations(), 2);
+        assert_eq!(o.len(), 3);
+        let merged = with_source(with_source(en, "x"), "y");
+        assert!(o.iter().any(|aa| aa == &us));
+        assert!(o.iter().any(|aa| aa == &merged));
+
+        assert_eq!(o.dedup_annotations(), 0);
     }
 
     #[test]
-    fn test_ontology_into_iter_empty() {
-        // Empty ontologies should stop iteration right away
-        let o = SetOntology::new_rc();
-        let mut it = (&o).into_iter();
-        assert_eq!(it.next(), None);
-        assert_eq!(it.next(), None);
+    fn test_version_info_and_header_comment() {
+        let mut o = SetOntology::new_rc();
+        let b = Build::new_rc();
+        let ontology_annotation = |ap: &str, av: &str| {
+            OntologyAnnotation(Annotation {
+                ap: b.annotation_property(ap),
+                av: av.into(),
+            })
+        };
+
+        assert_eq!(o.version_info(), None);
+        assert_eq!(o.header_comment(), None);
+
+        o.insert(ontology_annotation(
+            "http://www.w3.org/2002/07/owl#versionInfo",
+            "2.1",
+        ));
+        o.insert(ontology_annotation(
+            "http://www.w3.org/2000/01/rdf-schema#comment",
+            "An example ontology",
+        ));
+        // Not an ontology annotation
+        o.insert(b.annotation_assertion(
+            "http://www.example.com/a",
+            "http://www.w3.org/2000/01/rdf-schema#comment",
+            "A class",
+        ));
+
+        assert_eq!(o.version_info(), Some("2.1"));
+        assert_eq!(o.header_comment(), Some("An example ontology"));
     }
 
     #[test]
-    fn test_ontology_iter() {
-        // Setup
-        let build = Build::new_rc();
-        let mut o = SetOntology::new();
-        let decl1 = DeclareClass(build.class("http://www.example.com#a"));
-        let decl2 = DeclareClass(build.class("http://www.example.com#b"));
-        let decl3 = DeclareClass(build.class("http://www.example.com#c"));
-        let disj1 = DisjointClasses(vec![
-            ClassExpression::Class(build.class("http://www.example.com#a")),
-            ClassExpression::Class(build.class("http://www.example.com#b")),
-        ]);
-        let disj2 = DisjointClasses(vec![
-            ClassExpression::Class(build.class("http://www.example.com#b")),
-            ClassExpression::Class(build.class("http://www.example.com#c")),
-        ]);
-        o.insert(disj1.clone());
-        o.insert(disj2.clone());
-        o.insert(decl1.clone());
-        o.insert(decl2.clone());
-        o.insert(decl3.clone());
+    fn test_canonicalize_annotation_properties() {
+        let mut o = SetOntology::new_rc();
+        let b = Build::new_rc();
+        let dc = "http://purl.org/dc/elements/1.1/creator";
+        let dcterms = "http://purl.org/dc/terms/creator";
+        let local = "http://www.example.com/creator";
 
-        // Iteration is set based so undefined in order. So, sort first.
-        let mut v: Vec<_> = (&o).into_iter().collect();
-        v.sort();
-        let mut it = v.into_iter();
-        assert_eq!(
-            it.next(),
-            Some(&AnnotatedAxiom::from(Axiom::DeclareClass(decl1)))
-        );
-        assert_eq!(
-            it.next(),
-            Some(&AnnotatedAxiom::from(Axiom::DeclareClass(decl2)))
-        );
-        assert_eq!(
-            it.next(),
-            Some(&AnnotatedAxiom::from(Axiom::DeclareClass(decl3)))
-        );
-        assert_eq!(
-            it.next(),
-            Some(&AnnotatedAxiom::from(Axiom::DisjointClasses(disj1)))
-        );
+        o.insert(b.annotation_assertion("http://www.example.com/a", dc, "Phillip"));
+        o.insert(b.annotation_assertion("http://www.example.com/a", local, "Phillip"));
+        o.insert(b.annotation_assertion("http://www.example.com/a", dcterms, "Phillip"));
+        o.insert(AnnotatedAxiom::new(
+            DeclareClass(b.class("http://www.example.com/a")),
+            vec![Annotation {
+                ap: b.annotation_property(local),
+                av: "Jennifer".into(),
+            }]
+            .into_iter()
+            .collect(),
+        ));
+        o.insert(DeclareAnnotationProperty(b.annotation_property(dc)));
+
+        let mut map = std::collections::HashMap::new();
+        map.insert(b.iri(dc), b.iri(dcterms));
+        map.insert(b.iri(local), b.iri(dcterms));
+
+        assert_eq!(o.canonicalize_annotation_properties(&map), 3);
+
+        // The three assertions are now one
+        assert_eq!(o.len(), 3);
         assert_eq!(
-            it.next(),
-            Some(&AnnotatedAxiom::from(Axiom::DisjointClasses(disj2)))
+            o.annotation_properties_used(),
+            vec![b.annotation_property(dcterms)].into_iter().collect()
         );
-        assert_eq!(it.next(), None);
-        assert_eq!(it.next(), None);
+        assert!(o
+            .iter()
+            .any(|aa| aa.axiom == DeclareAnnotationProperty(b.annotation_property(dc)).into()));
+
+        assert_eq!(o.canonicalize_annotation_properties(&map), 0);
     }
 
     #[test]
-    fn test_ontology_into() {
-        // Setup
-        let build = Build::new_rc();
-        let mut o = SetOntology::new();
-        let decl1 = DeclareClass(build.class("http://www.example.com#a"));
-        let decl2 = DeclareClass(build.class("http://www.example.com#b"));
-        let decl3 = DeclareClass(build.class("http://www.example.com#c"));
+    fn test_iso_eq() {
+        let b = Build::new_rc();
+        let knows = b.object_property("http://www.example.com#knows");
+        let ont = |x: &str, y: &str, z: &str| {
+            let mut o = SetOntology::new_rc();
+            o.mut_id().iri = Some(b.iri("http://www.example.com/o"));
+            let assert = |s: &str, t: &str| ObjectPropertyAssertion {
+                ope: knows.clone().into(),
+                from: b.anon(s).into(),
+                to: b.anon(t).into(),
+            };
+            o.insert(assert(x, y));
+            o.insert(assert(y, z));
+            o.insert(ClassAssertion {
+                ce: b.class("http://www.example.com#a").into(),
+                i: b.anon(z).into(),
+            });
+            o.insert(ClassAssertion {
+                ce: b.class("http://www.example.com#a").into(),
+                i: b.named_individual("http://www.example.com#i").into(),
+            });
+            o
+        };
 
-        o.insert(decl1.clone());
-        o.insert(decl2.clone());
-        o.insert(decl3.clone());
+        let o = ont("a1", "a2", "a3");
+        let p = ont("bn9", "bn1", "bn5");
+        assert_ne!(o, p);
+        assert!(o.iso_eq(&p));
+        assert!(p.iso_eq(&o));
 
-        let newo: SetOntology<_> = (o.mut_id().clone(), o.into_iter()).into();
+        // The same individuals in a different structure
+        let q = ont("bn9", "bn5", "bn1");
+        assert!(o.iso_eq(&q));
+        let r = ont("a1", "a3", "a3");
+        assert!(!o.iso_eq(&r));
 
-        // Iteration is set based so undefined in order. So, sort first.
-        let mut v: Vec<_> = (&newo).into_iter().collect();
-        v.sort();
-        let mut it = v.into_iter();
-        assert_eq!(
-            it.next(),
-            Some(&AnnotatedAxiom::from(Axiom::DeclareClass(decl1)))
-        );
-        assert_eq!(
-            it.next(),
-            Some(&AnnotatedAxiom::from(Axiom::DeclareClass(decl2)))
-        );
-        assert_eq!(
-            it.next(),
-            Some(&AnnotatedAxiom::from(Axiom::DeclareClass(decl3)))
-        );
-        assert_eq!(it.next(), None);
-        assert_eq!(it.next(), None);
+        let mut s = ont("a1", "a2", "a3");
+        s.insert(ObjectPropertyAssertion {
+            ope: knows.into(),
+            from: b.anon("a3").into(),
+            to: b.anon("a1").into(),
+        });
+        assert!(!o.iso_eq(&s));
     }
 
     #[test]
-    fn test_index_cons() {
-        let _ = SetIndex::new_rc();
-        assert!(true);
+    fn test_punned_iris() {
+        let mut o = SetOntology::new_rc();
+        let b = Build::new_rc();
+
+        o.declare(b.class("http://www.example.com#a"));
+        o.insert(ClassAssertion {
+            ce: b.class("http://www.example.com#b").into(),
+            i: b.named_individual("http://www.example.com#a").into(),
+        });
+        o.declare(b.class("http://www.example.com#c"));
+        o.declare(b.datatype("http://www.example.com#c"));
+
+        let puns = o.punned_iris();
+        assert_eq!(puns.len(), 2);
+        assert!(puns.contains(&b.iri("http://www.example.com#a")));
+        assert!(puns.contains(&b.iri("http://www.example.com#c")));
     }
 
     #[test]
-    fn test_index_iter_empty() {
-        // Empty ontologies should stop iteration right away
-        let mut it = SetIndex::new_rc().into_iter();
-        assert_eq!(it.next(), None);
-        assert_eq!(it.next(), None);
+    fn test_logical_eq() {
+        let b = Build::new_rc();
+        let sub: Axiom<_> = SubClassOf {
+            sub: b.class("http://www.example.com#a").into(),
+            sup: b.class("http://www.example.com#b").into(),
+        }
+        .into();
+        let ann = Annotation {
+            ap: b.annotation_property("http://www.example.com#p"),
+            av: b.iri("http://www.example.com#c").into(),
+        };
+
+        let mut o = SetOntology::new_rc();
+        o.declare(b.class("http://www.example.com#a"));
+        o.insert(sub.clone());
+
+        let mut p = SetOntology::new_rc();
+        p.mut_id().iri = Some(b.iri("http://www.example.com/p"));
+        p.insert(AnnotatedAxiom::new(
+            DeclareClass(b.class("http://www.example.com#a")),
+            vec![ann.clone()].into_iter().collect(),
+        ));
+        p.insert(AnnotatedAxiom::new(sub, vec![ann].into_iter().collect()));
+
+        assert_ne!(o, p);
+        assert!(o.logical_eq(&p));
+        assert!(p.logical_eq(&o));
+
+        p.declare(b.class("http://www.example.com#b"));
+        assert!(!o.logical_eq(&p));
     }
 
     #[test]
-    fn test_index_into_iter() {
-        // Setup
-        let build = Build::new_rc();
-        let mut o = OneIndexedOntology::new(SetIndex::new_rc());
-        let decl1 = DeclareClass(build.class("http://www.example.com#a"));
-        let decl2 = DeclareClass(build.class("http://www.example.com#b"));
-        let decl3 = DeclareClass(build.class("http://www.example.com#c"));
-        let disj1 = DisjointClasses(vec![
-            ClassExpression::Class(build.class("http://www.example.com#a")),
-            ClassExpression::Class(build.class("http://www.example.com#b")),
-        ]);
-        let disj2 = DisjointClasses(vec![
-            ClassExpression::Class(build.class("http://www.example.com#b")),
-            ClassExpression::Class(build.class("http://www.example.com#c")),
-        ]);
-        o.insert(disj1.clone());
-        o.insert(disj2.clone());
-        o.insert(decl1.clone());
-        o.insert(decl2.clone());
-        o.insert(decl3.clone());
+    fn test_validate_iris() {
+        let mut o = SetOntology::new_rc();
+        let b = Build::new_rc();
+        o.mut_id().iri = Some(b.iri("http://www.example.com/o"));
+        o.declare(b.class("http://www.example.com#a"));
+        assert!(o.validate_iris().is_empty());
 
-        // Iteration is based on ascending order of axiom kinds.
-        let mut v: Vec<_> = o.index().into_iter().collect();
-        v.sort();
-        let mut it = v.into_iter();
-        assert_eq!(
-            it.next(),
-            Some(AnnotatedAxiom::from(Axiom::DeclareClass(decl1)))
-        );
-        assert_eq!(
-            it.next(),
-            Some(AnnotatedAxiom::from(Axiom::DeclareClass(decl2)))
-        );
-        assert_eq!(
-            it.next(),
-            Some(AnnotatedAxiom::from(Axiom::DeclareClass(decl3)))
-        );
-        assert_eq!(
-            it.next(),
-            Some(AnnotatedAxiom::from(Axiom::DisjointClasses(disj1)))
-        );
-        assert_eq!(
-            it.next(),
-            Some(AnnotatedAxiom::from(Axiom::DisjointClasses(disj2)))
-        );
-        assert_eq!(it.next(), None);
-        assert_eq!(it.next(), None);
+        o.insert(SubClassOf {
+            sub: b.class("http://www.example.com#a").into(),
+            sup: b.class("#b").into(),
+        });
+        o.declare(b.class("#b"));
+        o.declare(b.object_property("c d"));
+
+        assert_eq!(o.validate_iris(), vec![b.iri("#b"), b.iri("c d")]);
     }
 
     #[test]
-    fn test_index_into_iter_empty() {
-        // Empty ontologies should stop iteration right away
-        let o = OneIndexedOntology::new(SetIndex::new_rc());
-        let mut it = o.i().into_iter();
-        assert_eq!(it.next(), None);
-        assert_eq!(it.next(), None);
+    fn test_obvious_disjointness_conflicts() {
+        let mut o = SetOntology::new_rc();
+        let b = Build::new_rc();
+        let a = b.class("http://www.example.com#a");
+        let c = b.class("http://www.example.com#c");
+        let d = b.class("http://www.example.com#d");
+
+        o.insert(EquivalentClasses(vec![
+            a.clone().into(),
+            c.clone().into(),
+            d.clone().into(),
+        ]));
+        o.insert(DisjointClasses(vec![
+            d.clone().into(),
+            ClassExpression::ObjectComplementOf(Box::new(a.clone().into())),
+            a.clone().into(),
+        ]));
+        o.insert(DisjointClasses(vec![
+            c.clone().into(),
+            b.class("http://www.example.com#e").into(),
+        ]));
+
+        assert_eq!(o.obvious_disjointness_conflicts(), vec![(a, d)]);
     }
 
     #[test]
-    fn test_index_iter() {
-        // Setup
-        let build = Build::new_rc();
-        let mut o = OneIndexedOntology::new(SetIndex::new_rc());
-        let decl1 = DeclareClass(build.class("http://www.example.com#a"));
-        let decl2 = DeclareClass(build.class("http://www.example.com#b"));
-        let decl3 = DeclareClass(build.class("http://www.example.com#c"));
-        let disj1 = DisjointClasses(vec![
-            ClassExpression::Class(build.class("http://www.example.com#a")),
-            ClassExpression::Class(build.class("http://www.example.com#b")),
-        ]);
-        let disj2 = DisjointClasses(vec![
-            ClassExpression::Class(build.class("http://www.example.com#b")),
-            ClassExpression::Class(build.class("http://www.example.com#c")),
-        ]);
-        o.insert(disj1.clone());
-        o.insert(disj2.clone());
-        o.insert(decl1.clone());
-        o.insert(decl2.clone());
-        o.insert(decl3.clone());
+    fn test_reintern() {
+        let b1 = Build::new_rc();
+        let b2 = Build::new_rc();
+        let mut o1 = SetOntology::new_rc();
+        let mut o2 = SetOntology::new_rc();
+        o1.declare(b1.class("http://www.example.com#a"));
+        o2.insert(SubClassOf {
+            sub: b2.class("http://www.example.com#a").into(),
+            sup: b2.class("http://www.example.com#b").into(),
+        });
 
-        // Iteration is set based so undefined in order. So, sort first.
-        let mut v: Vec<_> = o.i().into_iter().collect();
-        v.sort();
-        let mut it = v.into_iter();
-        assert_eq!(
-            it.next(),
-            Some(&AnnotatedAxiom::from(Axiom::DeclareClass(decl1)))
-        );
-        assert_eq!(
-            it.next(),
-            Some(&AnnotatedAxiom::from(Axiom::DeclareClass(decl2)))
-        );
-        assert_eq!(
-            it.next(),
-            Some(&AnnotatedAxiom::from(Axiom::DeclareClass(decl3)))
-        );
-        assert_eq!(
-            it.next(),
-            Some(&AnnotatedAxiom::from(Axiom::DisjointClasses(disj1)))
-        );
-        assert_eq!(
-            it.next(),
-            Some(&AnnotatedAxiom::from(Axiom::DisjointClasses(disj2)))
-        );
-        assert_eq!(it.next(), None);
-        assert_eq!(it.next(), None);
+        let class_iri = |o: &SetOntology<RcStr>| -> IRI<RcStr> {
+            let mut walk = crate::visitor::Walk::new(crate::visitor::entity::IRIExtract::default());
+            walk.set_ontology(o);
+            walk.into_visit()
+                .into_vec()
+                .into_iter()
+                .find(|i| i.as_ref() == "http://www.example.com#a")
+                .unwrap()
+        };
+
+        assert!(!std::ptr::eq(
+            class_iri(&o1).as_ref(),
+            class_iri(&o2).as_ref()
+        ));
+
+        let b = Build::new_rc();
+        let r1 = o1.reintern(&b);
+        let r2 = o2.reintern(&b);
+        assert_eq!(r1, o1);
+        assert_eq!(r2, o2);
+        assert!(std::ptr::eq(
+            class_iri(&r1).as_ref(),
+            class_iri(&r2).as_ref()
+        ));
+    }
+
+    #[test]
+    fn test_search_annotations() {
+        let mut o = SetOntology::new_rc();
+        let b = Build::new_rc();
+        let label = b.annotation_property("http://www.w3.org/2000/01/rdf-schema#label");
+
+        o.insert(AnnotationAssertion {
+            subject: b.iri("http://www.example.com#a").into(),
+            ann: Annotation {
+                ap: label.clone(),
+                av: Literal::Language {
+                    literal: "Äpfel und Birnen".into(),
+                    lang: "de".into(),
+                }
+                .into(),
+            },
+        });
+        o.insert(AnnotatedAxiom {
+            axiom: DeclareClass(b.class("http://www.example.com#b")).into(),
+            ann: vec![Annotation {
+                ap: label,
+                av: Literal::Simple {
+                    literal: "pears".into(),
+                }
+                .into(),
+            }]
+            .into_iter()
+            .collect(),
+        });
+
+        let found: Vec<_> = o.search_annotations("äPFEL", true).collect();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0.kind(), AxiomKind::AnnotationAssertion);
+
+        assert_eq!(o.search_annotations("äPFEL", false).count(), 0);
+        assert_eq!(o.search_annotations("Äpfel", false).count(), 1);
+
+        let found: Vec<_> = o.search_annotations("PEAR", true).collect();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0.kind(), AxiomKind::DeclareClass);
     }
 }
@@ -0,0 +1,155 @@
+//! An index that provides rapid look up of the axioms about a
+//! subject
+
+//! # Overview
+//!
+//! The `SubjectMappedIndex` indexes every axiom under its subject,
+//! as given by `Axiom::subject_iri`, so that all of the axioms about
+//! an entity, as shown by a frame-based editor, can be found without
+//! a scan. Axioms with no single subject, such as `DisjointClasses`
+//! or a general class inclusion axiom, are held together in a
+//! separate bucket.
+use crate::model::*;
+
+use super::indexed::ForIndex;
+use super::indexed::OntologyIndex;
+use super::indexed::ReindexInto;
+
+use std::collections::{BTreeSet, HashMap};
+
+#[derive(Debug, Default)]
+pub struct SubjectMappedIndex<A, AA> {
+    subjects: HashMap<IRI<A>, BTreeSet<AA>>,
+    without_subject: BTreeSet<AA>,
+}
+
+impl<A: ForIRI, AA: ForIndex<A>> SubjectMappedIndex<A, AA> {
+    pub fn new() -> SubjectMappedIndex<A, AA> {
+        SubjectMappedIndex {
+            subjects: HashMap::new(),
+            without_subject: BTreeSet::new(),
+        }
+    }
+
+    /// Return all axioms with `iri` as their subject, in sorted
+    /// order.
+    pub fn axioms_for(&self, iri: &IRI<A>) -> impl Iterator<Item = &AnnotatedAxiom<A>> {
+        self.subjects
+            .get(iri)
+            .into_iter()
+            .flat_map(|s| s.iter())
+            .map(|aa| aa.borrow())
+    }
+
+    /// Return all axioms which have no single subject, in sorted
+    /// order.
+    pub fn without_subject(&self) -> impl Iterator<Item = &AnnotatedAxiom<A>> {
+        self.without_subject.iter().map(|aa| aa.borrow())
+    }
+
+    /// Return every IRI which is the subject of at least one axiom.
+    pub fn subjects(&self) -> impl Iterator<Item = &IRI<A>> {
+        self.subjects.keys()
+    }
+}
+
+impl<A: ForIRI, AA: ForIndex<A>> OntologyIndex<A, AA> for SubjectMappedIndex<A, AA> {
+    fn index_insert(&mut self, ax: AA) -> bool {
+        match ax.borrow().axiom.subject_iri() {
+            Some(iri) => self.subjects.entry(iri.clone()).or_default().insert(ax),
+            None => self.without_subject.insert(ax),
+        }
+    }
+
+    fn index_remove(&mut self, ax: &AnnotatedAxiom<A>) -> bool {
+        let iri = match ax.axiom.subject_iri() {
+            Some(iri) => iri,
+            None => return self.without_subject.remove(ax),
+        };
+
+        let axioms = match self.subjects.get_mut(iri) {
+            Some(axioms) => axioms,
+            None => return false,
+        };
+
+        let rtn = axioms.remove(ax);
+        if axioms.is_empty() {
+            self.subjects.remove(iri);
+        }
+        rtn
+    }
+}
+
+impl<A: ForIRI, AA: ForIndex<A>, B: ForIRI, BB: ForIndex<B>> ReindexInto<B, BB>
+    for SubjectMappedIndex<A, AA>
+{
+    type Output = SubjectMappedIndex<B, BB>;
+
+    fn empty_index(&self) -> SubjectMappedIndex<B, BB> {
+        SubjectMappedIndex::new()
+    }
+}
+
+impl SubjectMappedIndex<RcStr, RcAnnotatedAxiom> {
+    pub fn new_rc() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SubjectMappedIndex;
+    use crate::model::*;
+    use crate::ontology::{indexed::TwoIndexedOntology, set::SetIndex};
+
+    #[test]
+    fn test_axioms_for() {
+        let b = Build::new_rc();
+        let mut o = TwoIndexedOntology::new(
+            SetIndex::new_rc(),
+            SubjectMappedIndex::new_rc(),
+            Default::default(),
+        );
+        let a = b.class("http://www.example.com/a");
+        let c = b.class("http://www.example.com/c");
+
+        let decl: AnnotatedAxiom<_> = DeclareClass(a.clone()).into();
+        let sc: AnnotatedAxiom<_> = SubClassOf {
+            sub: a.clone().into(),
+            sup: c.clone().into(),
+        }
+        .into();
+        let label: AnnotatedAxiom<_> = AnnotationAssertion {
+            subject: a.0.clone().into(),
+            ann: Annotation {
+                ap: b.annotation_property("http://www.w3.org/2000/01/rdf-schema#label"),
+                av: "a".into(),
+            },
+        }
+        .into();
+        let disjoint: AnnotatedAxiom<_> =
+            DisjointClasses(vec![a.clone().into(), c.clone().into()]).into();
+
+        o.insert(decl.clone());
+        o.insert(sc.clone());
+        o.insert(label.clone());
+        o.insert(disjoint.clone());
+        o.insert(DeclareClass(c.clone()));
+
+        let mut expected = vec![&decl, &sc, &label];
+        expected.sort();
+        assert_eq!(o.j().axioms_for(&a.0).collect::<Vec<_>>(), expected);
+        assert_eq!(o.j().axioms_for(&c.0).count(), 1);
+        assert_eq!(o.j().without_subject().collect::<Vec<_>>(), vec![&disjoint]);
+        assert_eq!(o.j().subjects().count(), 2);
+
+        o.remove(&sc);
+        o.remove(&disjoint);
+        assert_eq!(o.j().axioms_for(&a.0).count(), 2);
+        assert_eq!(o.j().without_subject().count(), 0);
+
+        o.remove(&DeclareClass(c.clone()).into());
+        assert_eq!(o.j().axioms_for(&c.0).count(), 0);
+        assert_eq!(o.j().subjects().collect::<Vec<_>>(), vec![&a.0]);
+    }
+}
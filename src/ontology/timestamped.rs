@@ -0,0 +1,146 @@
+//! A `ForIndex` type which records when an axiom was inserted
+
+//! # Overview
+//!
+//! Indexed ontologies are generic over the type `AA` in which they
+//! store axioms, which can be any type implementing
+//! [`ForIndex`](../indexed/trait.ForIndex.html). Usually this is
+//! `Rc<AnnotatedAxiom>` or `Arc<AnnotatedAxiom>`, but any wrapper
+//! can be used, which allows extra data to be stored alongside each
+//! axiom. The wrapper must be created from an `AnnotatedAxiom`, and
+//! must borrow, compare and hash as the axiom it wraps, so that
+//! indexes can find it by the axiom alone.
+//!
+//! `TimestampedAxiom` is such a wrapper, recording the time at which
+//! each axiom was inserted. The wrapper is created once for each
+//! insertion, then cloned into each index, so all indexes share the
+//! same timestamp.
+//!
+//! # Examples
+//! ```
+//! # use horned_owl::model::*;
+//! # use horned_owl::ontology::indexed::OneIndexedOntology;
+//! # use horned_owl::ontology::set::SetIndex;
+//! # use horned_owl::ontology::timestamped::TimestampedAxiom;
+//! # use std::time::Instant;
+//! let b = Build::new_rc();
+//! let mut o: OneIndexedOntology<_, TimestampedAxiom<_>, _> =
+//!     OneIndexedOntology::new(SetIndex::new());
+//!
+//! let before = Instant::now();
+//! o.declare(b.class("http://www.example.com/a"));
+//!
+//! let decl = DeclareClass(b.class("http://www.example.com/a")).into();
+//! assert!(o.i().get(&decl).unwrap().inserted() >= before);
+//! ```
+use std::{
+    borrow::Borrow,
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+    sync::Arc,
+    time::Instant,
+};
+
+use super::indexed::RefCounted;
+use crate::model::*;
+
+/// An `AnnotatedAxiom` and the time at which it was created.
+///
+/// Equality, ordering and hashing consider only the axiom.
+#[derive(Clone, Debug)]
+pub struct TimestampedAxiom<A> {
+    axiom: Arc<AnnotatedAxiom<A>>,
+    inserted: Instant,
+}
+
+impl<A: ForIRI> TimestampedAxiom<A> {
+    /// Return the time at which this axiom was inserted.
+    pub fn inserted(&self) -> Instant {
+        self.inserted
+    }
+}
+
+impl<A: ForIRI> From<AnnotatedAxiom<A>> for TimestampedAxiom<A> {
+    fn from(aa: AnnotatedAxiom<A>) -> TimestampedAxiom<A> {
+        TimestampedAxiom {
+            axiom: Arc::new(aa),
+            inserted: Instant::now(),
+        }
+    }
+}
+
+impl<A: ForIRI> Borrow<AnnotatedAxiom<A>> for TimestampedAxiom<A> {
+    fn borrow(&self) -> &AnnotatedAxiom<A> {
+        &self.axiom
+    }
+}
+
+impl<A: ForIRI> RefCounted for TimestampedAxiom<A> {
+    fn strong_count(&self) -> usize {
+        self.axiom.strong_count()
+    }
+}
+
+impl<A: ForIRI> PartialEq for TimestampedAxiom<A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.axiom == other.axiom
+    }
+}
+
+impl<A: ForIRI> Eq for TimestampedAxiom<A> {}
+
+impl<A: ForIRI> PartialOrd for TimestampedAxiom<A> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<A: ForIRI> Ord for TimestampedAxiom<A> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.axiom.cmp(&other.axiom)
+    }
+}
+
+impl<A: ForIRI> Hash for TimestampedAxiom<A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.axiom.hash(state)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TimestampedAxiom;
+    use crate::model::*;
+    use crate::ontology::axiom_mapped::AxiomMappedIndex;
+    use crate::ontology::indexed::{RefCounted, TwoIndexedOntology};
+    use crate::ontology::set::SetIndex;
+    use std::time::Instant;
+
+    #[test]
+    fn test_timestamp() {
+        let b = Build::new_rc();
+        let mut o: TwoIndexedOntology<_, TimestampedAxiom<_>, _, _> =
+            TwoIndexedOntology::new(SetIndex::new(), AxiomMappedIndex::new(), Default::default());
+
+        let start = Instant::now();
+        o.declare(b.class("http://www.example.com/a"));
+        let middle = Instant::now();
+        o.declare(b.class("http://www.example.com/b"));
+
+        let a = DeclareClass(b.class("http://www.example.com/a")).into();
+        let b_decl = DeclareClass(b.class("http://www.example.com/b")).into();
+        let ta = o.i().get(&a).unwrap();
+        let tb = o.i().get(&b_decl).unwrap();
+
+        assert!(start <= ta.inserted() && ta.inserted() <= middle);
+        assert!(middle <= tb.inserted());
+
+        // Both indexes share one wrapper
+        assert_eq!(ta.strong_count(), 2);
+
+        // Reinserting does not change the timestamp
+        let t = ta.inserted();
+        assert!(!o.insert(a.clone()));
+        assert_eq!(o.i().get(&a).unwrap().inserted(), t);
+    }
+}
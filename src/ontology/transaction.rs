@@ -0,0 +1,182 @@
+//! Reversible batches of changes to a `MutableOntology`
+
+//! # Overview
+//!
+//! A `Transaction` wraps a mutable reference to any
+//! [`MutableOntology`](../../model/trait.MutableOntology.html), and
+//! records each insertion or removal made through it. Unless
+//! `commit` is called, these changes are reversed when the
+//! `Transaction` is dropped, so that an editor can make a batch of
+//! changes and abandon them on error.
+//!
+//! Only changes which actually alter the ontology are recorded, so
+//! rolling back restores it exactly. Changes made to the ontology
+//! other than through the `Transaction` are not recorded, and the
+//! borrow prevents them while it is live.
+use std::ops::Deref;
+
+use super::event_log::Change;
+use crate::model::*;
+
+/// A guard which reverses the changes made through it when dropped,
+/// unless they are committed.
+///
+/// # Examples
+/// ```
+/// # use horned_owl::model::*;
+/// # use horned_owl::ontology::set::SetOntology;
+/// # use horned_owl::ontology::transaction::Transaction;
+/// let b = Build::new_rc();
+/// let mut o = SetOntology::new_rc();
+/// {
+///     let mut t = Transaction::new(&mut o);
+///     t.insert(DeclareClass(b.class("http://www.example.com/a")));
+///     assert_eq!(t.len(), 1);
+/// }
+/// assert!(o.is_empty());
+///
+/// let mut t = Transaction::new(&mut o);
+/// t.insert(DeclareClass(b.class("http://www.example.com/a")));
+/// t.commit();
+/// assert_eq!(o.len(), 1);
+/// ```
+#[derive(Debug)]
+pub struct Transaction<'a, A: ForIRI, O: MutableOntology<A>> {
+    ontology: &'a mut O,
+    changes: Vec<Change<A>>,
+    committed: bool,
+}
+
+impl<'a, A: ForIRI, O: MutableOntology<A>> Transaction<'a, A, O> {
+    pub fn new(ontology: &'a mut O) -> Transaction<'a, A, O> {
+        Transaction {
+            ontology,
+            changes: vec![],
+            committed: false,
+        }
+    }
+
+    /// Keep the changes made through this `Transaction`.
+    pub fn commit(mut self) {
+        self.committed = true;
+    }
+
+    /// Reverse the changes made through this `Transaction`.
+    ///
+    /// This is the same as dropping it, but explicit.
+    pub fn rollback(self) {}
+
+    /// Return the number of changes recorded.
+    pub fn change_count(&self) -> usize {
+        self.changes.len()
+    }
+}
+
+impl<'a, A: ForIRI, O: MutableOntology<A>> Deref for Transaction<'a, A, O> {
+    type Target = O;
+
+    fn deref(&self) -> &O {
+        self.ontology
+    }
+}
+
+impl<'a, A: ForIRI, O: MutableOntology<A>> MutableOntology<A> for Transaction<'a, A, O> {
+    fn insert<AA>(&mut self, ax: AA) -> bool
+    where
+        AA: Into<AnnotatedAxiom<A>>,
+    {
+        let ax = ax.into();
+        let inserted = self.ontology.insert(ax.clone());
+        if inserted {
+            self.changes.push(Change::Insert(ax));
+        }
+        inserted
+    }
+
+    fn take(&mut self, ax: &AnnotatedAxiom<A>) -> Option<AnnotatedAxiom<A>> {
+        let taken = self.ontology.take(ax);
+        if let Some(ax) = &taken {
+            self.changes.push(Change::Remove(ax.clone()));
+        }
+        taken
+    }
+}
+
+impl<'a, A: ForIRI, O: MutableOntology<A>> Drop for Transaction<'a, A, O> {
+    fn drop(&mut self) {
+        if self.committed {
+            return;
+        }
+
+        while let Some(change) = self.changes.pop() {
+            match change {
+                Change::Insert(ax) => {
+                    self.ontology.remove(&ax);
+                }
+                Change::Remove(ax) => {
+                    self.ontology.insert(ax);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ontology::set::SetOntology;
+
+    #[test]
+    fn test_rollback_on_drop() {
+        let b = Build::new_rc();
+        let decl = |s: &str| -> AnnotatedAxiom<_> {
+            DeclareClass(b.class(format!("http://www.example.com/{}", s))).into()
+        };
+
+        let mut o = SetOntology::new_rc();
+        o.insert(decl("a"));
+        o.insert(decl("b"));
+        let original = o.clone();
+
+        {
+            let mut t = Transaction::new(&mut o);
+            assert!(t.insert(decl("c")));
+            // Already present, so not recorded
+            assert!(!t.insert(decl("a")));
+            assert!(t.remove(&decl("a")));
+            // Absent, so not recorded
+            assert!(!t.remove(&decl("x")));
+            // Removed and then inserted again
+            assert!(t.remove(&decl("b")));
+            assert!(t.insert(decl("b")));
+            assert!(t.insert(decl("d")));
+
+            assert_eq!(t.change_count(), 5);
+            assert_eq!(t.len(), 3);
+        }
+
+        assert_eq!(o, original);
+    }
+
+    #[test]
+    fn test_commit() {
+        let b = Build::new_rc();
+        let mut o = SetOntology::new_rc();
+        o.insert(DeclareClass(b.class("http://www.example.com/a")));
+
+        let mut t = Transaction::new(&mut o);
+        t.insert(DeclareClass(b.class("http://www.example.com/b")));
+        t.remove(&DeclareClass(b.class("http://www.example.com/a")).into());
+        t.commit();
+
+        assert_eq!(
+            o.iter().collect::<Vec<_>>(),
+            vec![&DeclareClass(b.class("http://www.example.com/b")).into()]
+        );
+
+        let mut t = Transaction::new(&mut o);
+        t.insert(DeclareClass(b.class("http://www.example.com/c")));
+        t.rollback();
+        assert_eq!(o.len(), 1);
+    }
+}
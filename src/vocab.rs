@@ -60,6 +60,7 @@ where
 
 #[derive(Debug, Eq, PartialEq)]
 pub enum Namespace {
+    DC,
     OWL,
     RDF,
     RDFS,
@@ -68,6 +69,7 @@ pub enum Namespace {
 
 lazy_meta! {
     Namespace, IRIString, METANS;
+    DC, to_meta("http://purl.org/dc/elements/1.1/");
     OWL, to_meta("http://www.w3.org/2002/07/owl#");
     RDF, to_meta("http://www.w3.org/1999/02/22-rdf-syntax-ns#");
     RDFS, to_meta("http://www.w3.org/2000/01/rdf-schema#");
@@ -127,9 +129,52 @@ lazy_meta! {
     SubPropertyOf, extend(RDFS, "subPropertyOf");
 }
 
+/// The Dublin Core Metadata Element Set, Version 1.1
+///
+/// These are the legacy `dc:` elements, distinct from those of the
+/// `http://purl.org/dc/terms/` namespace.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum DC {
+    Contributor,
+    Coverage,
+    Creator,
+    Date,
+    Description,
+    Format,
+    Identifier,
+    Language,
+    Publisher,
+    Relation,
+    Rights,
+    Source,
+    Subject,
+    Title,
+    Type,
+}
+
+lazy_meta! {
+    DC, IRIString, METADC;
+    Contributor, extend(DC, "contributor");
+    Coverage, extend(DC, "coverage");
+    Creator, extend(DC, "creator");
+    Date, extend(DC, "date");
+    Description, extend(DC, "description");
+    Format, extend(DC, "format");
+    Identifier, extend(DC, "identifier");
+    Language, extend(DC, "language");
+    Publisher, extend(DC, "publisher");
+    Relation, extend(DC, "relation");
+    Rights, extend(DC, "rights");
+    Source, extend(DC, "source");
+    Subject, extend(DC, "subject");
+    Title, extend(DC, "title");
+    Type, extend(DC, "type");
+}
+
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum OWL {
     AllDifferent,
+    AllDisjointClasses,
     AllDisjointProperties,
     AllValuesFrom,
     AnnotatedProperty,
@@ -152,6 +197,7 @@ pub enum OWL {
     EquivalentProperty,
     FunctionalProperty,
     HasKey,
+    HasSelf,
     HasValue,
     Imports,
     IntersectionOf,
@@ -197,6 +243,7 @@ lazy_meta! {
     OWL, IRIString, METAOWL;
 
     AllDifferent, extend(OWL, "AllDifferent");
+    AllDisjointClasses, extend(OWL, "AllDisjointClasses");
     AllDisjointProperties, extend(OWL, "AllDisjointProperties");
     AllValuesFrom, extend(OWL, "allValuesFrom");
     AnnotatedProperty, extend(OWL, "annotatedProperty");
@@ -224,6 +271,7 @@ lazy_meta! {
     InverseOf, extend(OWL, "inverseOf");
     IrreflexiveProperty, extend(OWL, "IrreflexiveProperty");
     HasKey, extend(OWL, "hasKey");
+    HasSelf, extend(OWL, "hasSelf");
     HasValue, extend(OWL, "hasValue");
     MaxCardinality, extend(OWL, "maxCardinality");
     MaxQualifiedCardinality, extend(OWL, "maxQualifiedCardinality");
@@ -293,6 +341,20 @@ fn meta_testing() {
     );
 }
 
+#[test]
+fn dc_testing() {
+    assert_eq!("http://purl.org/dc/elements/1.1/", DC.iri_s());
+    assert_eq!(
+        "http://purl.org/dc/elements/1.1/title",
+        self::DC::Title.iri_s()
+    );
+    assert_eq!(
+        self::DC::var_s("http://purl.org/dc/elements/1.1/creator"),
+        Some(self::DC::Creator)
+    );
+    assert_eq!(self::DC::var_s("http://purl.org/dc/terms/creator"), None);
+}
+
 pub fn entity_for_iri<A: ForIRI, S: Borrow<str>>(
     type_iri: S,
     entity_iri: S,
@@ -346,11 +408,19 @@ pub fn test_entity_for_iri() {
 
 pub enum OWL2Datatype {
     RDFSLiteral,
+    RDFPlainLiteral,
+    RDFXMLLiteral,
+    OWLReal,
+    OWLRational,
 }
 
 lazy_meta! {
     OWL2Datatype, IRIString, METAOWL2DATATYPE;
-    RDFSLiteral, extend(RDFS, "Literal")
+    RDFSLiteral, extend(RDFS, "Literal");
+    RDFPlainLiteral, extend(RDF, "PlainLiteral");
+    RDFXMLLiteral, extend(RDF, "XMLLiteral");
+    OWLReal, extend(OWL, "real");
+    OWLRational, extend(OWL, "rational")
 }
 
 pub enum AnnotationBuiltIn {
@@ -439,6 +509,7 @@ fn facet_meta() {
 }
 
 pub enum XSD {
+    Boolean,
     NonNegativeInteger,
 }
 
@@ -447,13 +518,38 @@ pub fn is_xsd_datatype<A:AsRef<str>>(iri:A) -> bool {
     iri.as_ref().starts_with("http://www.w3.org/2001/XMLSchema")
 }
 
+/// Return true if `iri` is a datatype of the OWL 2 datatype map.
+///
+/// This is every `xsd:` datatype, together with `rdf:PlainLiteral`,
+/// `rdf:XMLLiteral`, `rdfs:Literal`, `owl:real` and `owl:rational`.
+pub fn is_builtin_datatype<A: AsRef<str>>(iri: A) -> bool {
+    is_xsd_datatype(iri.as_ref())
+        || OWL2Datatype::all()
+            .iter()
+            .any(|dt| dt.iri_str() == iri.as_ref())
+}
+
+#[test]
+fn builtin_datatype() {
+    for iri in &[
+        "http://www.w3.org/2001/XMLSchema#string",
+        "http://www.w3.org/2002/07/owl#rational",
+        "http://www.w3.org/1999/02/22-rdf-syntax-ns#PlainLiteral",
+    ] {
+        assert!(is_builtin_datatype(iri));
+    }
+    assert!(!is_builtin_datatype("http://www.w3.org/2002/07/owl#Thing"));
+}
+
 
 lazy_meta! {
     XSD, IRIString, METAXSD;
+    Boolean, extend(XSD, "boolean");
     NonNegativeInteger, extend(XSD, "nonNegativeInteger")
 }
 
 pub enum Vocab {
+    DC(DC),
     Facet(Facet),
     RDF(RDF),
     RDFS(RDFS),
@@ -465,6 +561,7 @@ pub enum Vocab {
 impl<'a> Meta<&'a IRIString> for Vocab {
     fn meta(&self) -> &'a IRIString {
         match self {
+            Self::DC(dc) => dc.meta(),
             Self::Facet(facet) => facet.meta(),
             Self::RDF(rdf) => rdf.meta(),
             Self::RDFS(rdfs) => rdfs.meta(),
@@ -491,6 +588,12 @@ impl From<&Facet> for Vocab {
     }
 }
 
+impl From<DC> for Vocab {
+    fn from(dc: DC) -> Self {
+        Self::DC(dc)
+    }
+}
+
 impl From<RDF> for Vocab {
     fn from(rdf: RDF) -> Self {
         Self::RDF(rdf)
@@ -514,3 +617,37 @@ impl From<XSD> for Vocab {
         Self::XSD(xsd)
     }
 }
+
+/// Return the IRI of every term in the `OWL`, `RDF`, `RDFS`, `XSD`,
+/// `OWL2Datatype`, `Facet` and `AnnotationBuiltIn` vocabularies.
+///
+/// Only some `xsd:` datatypes have a term; use `is_builtin_datatype`
+/// to check for a datatype.
+///
+/// Some terms occur in more than one vocabulary; each IRI is
+/// returned once, in the order first seen.
+pub fn all_builtin_iris() -> Vec<&'static str> {
+    let mut seen = std::collections::HashSet::new();
+    OWL::all()
+        .iter()
+        .map(WithIRI::iri_str)
+        .chain(RDF::all().iter().map(WithIRI::iri_str))
+        .chain(RDFS::all().iter().map(WithIRI::iri_str))
+        .chain(XSD::all().iter().map(WithIRI::iri_str))
+        .chain(OWL2Datatype::all().iter().map(WithIRI::iri_str))
+        .chain(Facet::all().iter().map(WithIRI::iri_str))
+        .chain(AnnotationBuiltIn::all().iter().map(WithIRI::iri_str))
+        .filter(|iri| seen.insert(*iri))
+        .collect()
+}
+
+#[test]
+fn test_all_builtin_iris() {
+    let iris = all_builtin_iris();
+    assert!(iris.contains(&"http://www.w3.org/2002/07/owl#Class"));
+    assert!(iris.contains(&"http://www.w3.org/2000/01/rdf-schema#label"));
+    assert!(iris.contains(&"http://www.w3.org/2001/XMLSchema#minLength"));
+
+    let unique: std::collections::HashSet<_> = iris.iter().collect();
+    assert_eq!(unique.len(), iris.len());
+}